@@ -0,0 +1,95 @@
+use crate::errors::Result;
+use crate::types::NetAddress;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How a poll ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PollOutcome {
+    /// The peer was reached and its addresses were taken
+    Accepted,
+    /// The peer was reached but failed version checks
+    Rejected,
+    /// The peer could not be reached
+    Failed,
+}
+
+/// One line of the poll audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollRecord {
+    /// Polled peer, as `ip:port`
+    pub peer: String,
+    /// Unix seconds the poll started
+    pub timestamp: u64,
+    pub outcome: PollOutcome,
+    pub protocol_version: Option<u32>,
+    pub user_agent: Option<String>,
+    pub addresses_received: usize,
+    /// Received addresses that were new to the address manager
+    pub addresses_added: usize,
+    /// Why the peer was rejected or could not be reached
+    pub reason: Option<String>,
+}
+
+impl PollRecord {
+    /// Start a record for a poll of `address`, assumed to fail until filled in
+    pub fn new(address: &NetAddress) -> Self {
+        Self {
+            peer: format!("{}:{}", address.ip, address.port),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            outcome: PollOutcome::Failed,
+            protocol_version: None,
+            user_agent: None,
+            addresses_received: 0,
+            addresses_added: 0,
+            reason: None,
+        }
+    }
+}
+
+/// Newline-delimited JSON record of every poll, kept apart from the
+/// operational log as a forensic trail
+pub struct AuditLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Open the audit log for appending, creating it if needed
+    pub fn open(path: &str) -> Result<Self> {
+        let path = PathBuf::from(path);
+        if let Some(parent_dir) = path.parent()
+            && !parent_dir.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent_dir)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append a poll record
+    pub fn record(&self, record: &PollRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = self.file.lock().unwrap();
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,175 @@
+use crate::errors::{KaseederError, Result};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+
+const BANS_FILENAME: &str = "bans.json";
+
+/// A banned IP and when the ban expires, as stored in `bans.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanEntry {
+    pub ip: IpAddr,
+    /// Unix timestamp in seconds after which the ban is lifted
+    pub expires_at: u64,
+}
+
+/// Banned peer IPs, persisted separately from the peer database so operators
+/// can inspect and edit them without touching `peers.json`
+pub struct BanList {
+    bans: DashMap<IpAddr, SystemTime>,
+    bans_file: PathBuf,
+}
+
+impl BanList {
+    /// Load the ban list from `bans.json` in the application directory,
+    /// dropping bans that expired while the seeder was down
+    pub fn load(app_dir: &str) -> Result<Self> {
+        let bans_file = Path::new(app_dir).join(BANS_FILENAME);
+        let ban_list = Self {
+            bans: DashMap::new(),
+            bans_file,
+        };
+
+        if ban_list.bans_file.exists() {
+            let content = std::fs::read_to_string(&ban_list.bans_file)?;
+            let entries: Vec<BanEntry> = serde_json::from_str(&content)?;
+            let now = SystemTime::now();
+            for entry in entries {
+                let expires_at = UNIX_EPOCH + Duration::from_secs(entry.expires_at);
+                if expires_at > now {
+                    ban_list.bans.insert(entry.ip, expires_at);
+                }
+            }
+            info!("{} bans loaded", ban_list.bans.len());
+        }
+
+        Ok(ban_list)
+    }
+
+    /// Ban an IP for the given duration and save the list
+    pub fn ban(&self, ip: IpAddr, duration: Duration) -> Result<()> {
+        self.bans.insert(ip, SystemTime::now() + duration);
+        info!("Banned {} for {:?}", ip, duration);
+        self.save()
+    }
+
+    /// Lift the ban on an IP, returning whether it was banned
+    pub fn unban(&self, ip: &IpAddr) -> Result<bool> {
+        if self.bans.remove(ip).is_none() {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Check whether an IP is currently banned. Expired bans are dropped.
+    pub fn is_banned(&self, ip: &IpAddr) -> bool {
+        let expires_at = match self.bans.get(ip) {
+            Some(expires_at) => *expires_at,
+            None => return false,
+        };
+
+        let now = SystemTime::now();
+        if expires_at > now {
+            return true;
+        }
+
+        if self
+            .bans
+            .remove_if(ip, |_, expires_at| *expires_at <= now)
+            .is_some()
+            && let Err(e) = self.save()
+        {
+            error!("Failed to save bans: {}", e);
+        }
+        false
+    }
+
+    /// Active bans, ordered by IP
+    pub fn entries(&self) -> Vec<BanEntry> {
+        let now = SystemTime::now();
+        let mut entries: Vec<BanEntry> = self
+            .bans
+            .iter()
+            .filter(|entry| *entry.value() > now)
+            .map(|entry| BanEntry {
+                ip: *entry.key(),
+                expires_at: entry
+                    .value()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.ip);
+        entries
+    }
+
+    /// Lift all bans, returning how many were active
+    pub fn clear(&self) -> Result<usize> {
+        let cleared = self.entries().len();
+        self.bans.clear();
+        self.save()?;
+        Ok(cleared)
+    }
+
+    /// Write the active bans to `bans.json`
+    fn save(&self) -> Result<()> {
+        if let Some(parent_dir) = self.bans_file.parent() {
+            std::fs::create_dir_all(parent_dir)?;
+        }
+
+        let serialized = serde_json::to_string_pretty(&self.entries()).map_err(|e| {
+            KaseederError::Serialization(format!("Failed to serialize bans: {}", e))
+        })?;
+
+        let tmp_file = self.bans_file.with_extension("json.new");
+        std::fs::write(&tmp_file, serialized)?;
+        std::fs::rename(&tmp_file, &self.bans_file)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bans_persist_across_restart_and_expire() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let long_ban: IpAddr = "45.0.0.1".parse().unwrap();
+        let short_ban: IpAddr = "2001:4860::1".parse().unwrap();
+
+        {
+            let bans = BanList::load(&app_dir).unwrap();
+            bans.ban(long_ban, Duration::from_secs(3600)).unwrap();
+            bans.ban(short_ban, Duration::from_secs(2)).unwrap();
+            assert!(bans.is_banned(&long_ban));
+            assert!(bans.is_banned(&short_ban));
+        }
+        assert!(temp_dir.path().join(BANS_FILENAME).exists());
+
+        // Simulated restart: bans are read back from bans.json
+        let bans = BanList::load(&app_dir).unwrap();
+        assert!(bans.is_banned(&long_ban));
+        assert!(bans.is_banned(&short_ban));
+        assert!(!bans.is_banned(&"45.0.0.2".parse().unwrap()));
+
+        std::thread::sleep(Duration::from_millis(2100));
+        assert!(!bans.is_banned(&short_ban));
+        assert_eq!(bans.entries().len(), 1);
+
+        // The expired ban is gone from disk too
+        let bans = BanList::load(&app_dir).unwrap();
+        assert_eq!(bans.entries()[0].ip, long_ban);
+
+        assert_eq!(bans.clear().unwrap(), 1);
+        assert!(BanList::load(&app_dir).unwrap().entries().is_empty());
+    }
+}
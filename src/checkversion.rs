@@ -1,16 +1,27 @@
 use crate::errors::{KaseederError, Result};
-use tracing::warn;
+use semver::Version;
+use tracing::{debug, warn};
 
 /// Version checker
 pub struct VersionChecker;
 
 impl VersionChecker {
-    /// Check if user agent version meets minimum requirements
-    pub fn check_version(min_version: &str, peer_version: &str) -> Result<()> {
-        if min_version.is_empty() || peer_version.is_empty() {
+    /// Check if user agent version meets minimum requirements. The peer's
+    /// kaspad version is taken from its user agent, e.g. `0.12.6` from
+    /// `/kaspad:0.12.6/kaspa-seeder:1.0.0/`; agents without one are accepted.
+    pub fn check_version(min_version: &str, user_agent: &str) -> Result<()> {
+        if min_version.is_empty() || user_agent.is_empty() {
             return Ok(());
         }
 
+        let Some(peer_version) = Self::kaspad_version(user_agent) else {
+            debug!(
+                "No kaspad version in user agent {}, accepting peer",
+                user_agent
+            );
+            return Ok(());
+        };
+
         match Self::compare_semantic_versions(min_version, peer_version) {
             Ok(ordering) => {
                 if ordering == std::cmp::Ordering::Greater {
@@ -28,39 +39,50 @@ impl VersionChecker {
         }
     }
 
-    /// Compare semantic versions
-    fn compare_semantic_versions(version1: &str, version2: &str) -> Result<std::cmp::Ordering> {
-        let v1_parts: Vec<u32> = version1
-            .split('.')
-            .filter_map(|part| part.parse().ok())
-            .collect();
-
-        let v2_parts: Vec<u32> = version2
-            .split('.')
-            .filter_map(|part| part.parse().ok())
-            .collect();
-
-        if v1_parts.is_empty() || v2_parts.is_empty() {
-            return Err(KaseederError::Validation(
-                "Invalid version format".to_string(),
-            ));
+    /// Extract the kaspad version from a user agent. Agents are `/`-separated
+    /// `name:version` segments, e.g. `/kaspad:0.12.6/kaspa-seeder:1.0.0/`;
+    /// a bare version like `0.12.6` is returned as is.
+    pub fn kaspad_version(user_agent: &str) -> Option<&str> {
+        let user_agent = user_agent.trim();
+        if !user_agent.contains('/') && !user_agent.contains(':') {
+            return Some(user_agent).filter(|version| !version.is_empty());
         }
 
-        // Compare version numbers
-        let max_len = std::cmp::max(v1_parts.len(), v2_parts.len());
+        user_agent
+            .split('/')
+            .filter_map(|segment| segment.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("kaspad"))
+            .map(|(_, version)| version.trim())
+            .filter(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+    }
 
-        for i in 0..max_len {
-            let v1_part = v1_parts.get(i).copied().unwrap_or(0);
-            let v2_part = v2_parts.get(i).copied().unwrap_or(0);
+    /// Parse a version leniently: a short core like `0.12` reads as
+    /// `0.12.0` and a trailing comment like `(custom)` is ignored
+    fn parse_version(version: &str) -> Option<Version> {
+        let version = version.split('(').next()?.trim();
+        if let Ok(version) = Version::parse(version) {
+            return Some(version);
+        }
 
-            match v1_part.cmp(&v2_part) {
-                std::cmp::Ordering::Equal => continue,
-                other => return Ok(other),
-            }
+        // Pad a short core, keeping any pre-release or build suffix
+        let (core, suffix) = version.split_at(version.find(['-', '+']).unwrap_or(version.len()));
+        let dots = core.matches('.').count();
+        if dots >= 2 {
+            return None;
         }
+        Version::parse(&format!("{}{}{}", core, ".0".repeat(2 - dots), suffix)).ok()
+    }
 
-        // All parts are equal
-        Ok(std::cmp::Ordering::Equal)
+    /// Compare semantic versions by precedence: pre-releases order before
+    /// their release (`0.12.0-rc1 < 0.12.0`) and build metadata is ignored
+    fn compare_semantic_versions(version1: &str, version2: &str) -> Result<std::cmp::Ordering> {
+        match (Self::parse_version(version1), Self::parse_version(version2)) {
+            (Some(v1), Some(v2)) => Ok(v1.cmp_precedence(&v2)),
+            _ => Err(KaseederError::Validation(format!(
+                "Invalid version format: {} or {}",
+                version1, version2
+            ))),
+        }
     }
 
     /// Check if protocol version meets minimum requirements
@@ -118,4 +140,68 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), std::cmp::Ordering::Greater);
     }
+
+    #[test]
+    fn test_kaspad_version_is_parsed_from_user_agent() {
+        assert_eq!(
+            VersionChecker::kaspad_version("/kaspad:0.12.6/kaspa-seeder:1.0.0/"),
+            Some("0.12.6")
+        );
+        assert_eq!(
+            VersionChecker::kaspad_version("/kaspa-seeder:1.0.0/kaspad:0.13.4/"),
+            Some("0.13.4")
+        );
+        assert_eq!(
+            VersionChecker::kaspad_version("/kaspad:0.14.1-dev/kaspad:0.14.1/"),
+            Some("0.14.1-dev")
+        );
+        assert_eq!(VersionChecker::kaspad_version("0.12.6"), Some("0.12.6"));
+        assert_eq!(VersionChecker::kaspad_version("/kaspa-seeder:1.0.0/"), None);
+        assert_eq!(VersionChecker::kaspad_version("/kaspad:/"), None);
+        assert_eq!(VersionChecker::kaspad_version("/kaspad/"), None);
+    }
+
+    #[test]
+    fn test_min_version_applies_to_realistic_user_agents() {
+        assert!(
+            VersionChecker::check_version("0.12.0", "/kaspad:0.12.6/kaspa-seeder:1.0.0/").is_ok()
+        );
+        assert!(VersionChecker::check_version("0.12.0", "/kaspad:0.11.17/").is_err());
+        // The seeder's own version does not count as the node version
+        assert!(
+            VersionChecker::check_version("0.12.0", "/kaspad:0.11.2/kaspa-seeder:1.0.0/").is_err()
+        );
+        // Pre-release suffixes compare by their numeric part
+        assert!(VersionChecker::check_version("0.14.0", "/kaspad:0.14.1-dev/").is_ok());
+        assert!(VersionChecker::check_version("0.14.2", "/kaspad:0.14.1(custom)/").is_err());
+        // Agents without a kaspad token are accepted
+        assert!(VersionChecker::check_version("0.12.0", "/kaspa-seeder:1.0.0/").is_ok());
+        assert!(VersionChecker::check_version("0.12.0", "/some-client/").is_ok());
+    }
+
+    #[test]
+    fn test_prerelease_precedence() {
+        use std::cmp::Ordering;
+
+        let cmp = VersionChecker::compare_semantic_versions;
+        assert_eq!(cmp("0.12.0-rc1", "0.12.0").unwrap(), Ordering::Less);
+        assert_eq!(cmp("0.12.0-rc1", "0.12.0-rc2").unwrap(), Ordering::Less);
+        assert_eq!(
+            cmp("0.12.0-alpha", "0.12.0-alpha.1").unwrap(),
+            Ordering::Less
+        );
+        assert_eq!(cmp("0.12.0-rc.2", "0.12.0-rc.10").unwrap(), Ordering::Less);
+        assert_eq!(cmp("0.11.9", "0.12.0-rc1").unwrap(), Ordering::Less);
+        // Build metadata does not affect precedence
+        assert_eq!(cmp("1.2.3+build.5", "1.2.3").unwrap(), Ordering::Equal);
+        assert_eq!(cmp("0.12", "0.12.0").unwrap(), Ordering::Equal);
+        assert!(cmp("next", "0.12.0").is_err());
+
+        // A release candidate does not satisfy the release it precedes
+        assert!(VersionChecker::check_version("0.12.0", "/kaspad:0.12.0-rc1/").is_err());
+        assert!(VersionChecker::check_version("0.12.0-rc1", "/kaspad:0.12.0/").is_ok());
+        assert!(VersionChecker::check_version("0.12.0", "/kaspad:0.12.0+a1b2c3/").is_ok());
+        // Unparseable versions are still accepted
+        assert!(VersionChecker::check_version("0.12.0", "/kaspad:0.x/").is_ok());
+    }
 }
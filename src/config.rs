@@ -1,10 +1,55 @@
+use crate::constants::{
+    DEFAULT_ADDRESS_CHANNEL_CAPACITY, DEFAULT_ADDRESS_REQUEST_INTERVAL, DEFAULT_BAN_AFTER_FAILURES,
+    DEFAULT_BAN_DURATION, DEFAULT_CLOCK_SKEW_THRESHOLD, DEFAULT_DNS_CACHE_TTL,
+    DEFAULT_DNS_RATE_LIMIT, DEFAULT_DNS_RECORDS_PER_RESPONSE, DEFAULT_DNS_RESEED_INTERVAL,
+    DEFAULT_DUMP_ADDRESS_INTERVAL, DEFAULT_EDNS_UDP_PAYLOAD_SIZE, DEFAULT_HANDSHAKE_TIMEOUT,
+    DEFAULT_MAX_ADDRESSES_PER_PEER, DEFAULT_MAX_CONCURRENT_POLLS, DEFAULT_MAX_CONSECUTIVE_FAILURES,
+    DEFAULT_MAX_PORTS_PER_IP, DEFAULT_MAX_STORED_ADDRESSES, DEFAULT_MAX_TCP_DNS_CONNECTIONS,
+    DEFAULT_PROTOCOL_VERSIONS, DEFAULT_PRUNE_ADDRESS_INTERVAL, DEFAULT_PRUNE_EXPIRE_TIMEOUT,
+    DEFAULT_SOA_EXPIRE, DEFAULT_SOA_MINIMUM, DEFAULT_SOA_REFRESH, DEFAULT_SOA_RETRY,
+    DEFAULT_STALE_FALLBACK_CUTOFF, DEFAULT_STALE_GOOD_TIMEOUT, DEFAULT_TCP_DNS_IDLE_TIMEOUT,
+    FALLBACK_TESTNET_SUFFIX, MAX_ADDRESS_CHANNEL_CAPACITY, MAX_ADDRESSES, MAX_ADDRESSES_PER_BATCH,
+    MAX_DNS_CACHE_TTL, MAX_DNS_RECORDS_PER_RESPONSE, MAX_DUMP_ADDRESS_INTERVAL,
+    MAX_EDNS_UDP_PAYLOAD_SIZE, MAX_POLL_CONCURRENCY, MAX_PRUNE_ADDRESS_INTERVAL,
+    MAX_PRUNE_EXPIRE_TIMEOUT, MAX_STALE_GOOD_TIMEOUT, MAX_UDP_RESPONSE_SIZE, NETWORK_DEFAULT_PORTS,
+    POLL_BATCH_PER_THREAD, is_supported_testnet_suffix, is_valid_max_addresses,
+};
+use crate::dns::NameserverEntry;
+use crate::dns_seed_discovery::{DEFAULT_DISCOVERY_METHODS, DiscoveryMethod};
 use crate::errors::{KaseederError, Result};
+use crate::kaspa_protocol::expected_network_name;
 use crate::logging::LoggingConfig;
+use crate::manager::TierWeights;
+use crate::netadapter::{RetryPolicies, parse_subnetwork_id};
+use crate::proxy::Socks5Proxy;
+use crate::store::StorageBackend;
+use crate::types::NetAddress;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::net::{IpAddr, SocketAddr};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
 use tracing::{info, warn};
+use trust_dns_proto::rr::RecordType;
+
+/// Configuration shared with running services, updated in place on reload
+pub type SharedConfig = Arc<RwLock<Config>>;
+
+/// Copy of the current shared configuration
+pub fn config_snapshot(config: &SharedConfig) -> Arc<Config> {
+    Arc::new(config.read().unwrap_or_else(|e| e.into_inner()).clone())
+}
+
+/// Outcome of applying a reloaded configuration to a running seeder
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConfigReload {
+    /// Changed fields that took effect immediately
+    pub applied: Vec<&'static str>,
+    /// Changed fields that only take effect after a restart
+    pub restart_required: Vec<&'static str>,
+}
 
 /// Network parameters enum
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +59,30 @@ pub enum NetworkParams {
 }
 
 impl NetworkParams {
+    /// Parameters of mainnet or the given testnet, with the default port
+    /// taken from [`NETWORK_DEFAULT_PORTS`]
+    pub fn for_network(testnet: bool, suffix: u16) -> Self {
+        let table_port = |suffix: Option<u16>| {
+            NETWORK_DEFAULT_PORTS
+                .iter()
+                .find(|(network, _)| *network == suffix)
+                .map(|(_, port)| *port)
+        };
+
+        if testnet {
+            NetworkParams::Testnet {
+                suffix,
+                default_port: table_port(Some(suffix))
+                    .or_else(|| table_port(Some(FALLBACK_TESTNET_SUFFIX)))
+                    .expect("fallback testnet has a default port"),
+            }
+        } else {
+            NetworkParams::Mainnet {
+                default_port: table_port(None).expect("mainnet has a default port"),
+            }
+        }
+    }
+
     pub fn default_port(&self) -> u16 {
         match self {
             NetworkParams::Mainnet { default_port } => *default_port,
@@ -41,6 +110,75 @@ pub struct ConfigFile {
     pub nologfiles: Option<bool>,
     pub error_log_file: Option<String>,
     pub profile: Option<String>,
+    pub dns_ttl: Option<u32>,
+    pub enable_srv: Option<bool>,
+    pub max_dns_records: Option<usize>,
+    pub prefer_reachable_port: Option<bool>,
+    pub max_tcp_connections: Option<usize>,
+    pub tcp_idle_timeout_secs: Option<u64>,
+    pub sweep_interval_secs: Option<u64>,
+    pub sweep_max_per_minute: Option<u32>,
+    pub dns_rate_limit: Option<u32>,
+    pub penalize_malformed_addresses: Option<bool>,
+    pub soa_mailbox: Option<String>,
+    pub soa_refresh: Option<u32>,
+    pub soa_retry: Option<u32>,
+    pub soa_expire: Option<u32>,
+    pub soa_minimum: Option<u32>,
+    pub http_listen: Option<String>,
+    pub disabled_query_types: Option<Vec<String>>,
+    pub json_format: Option<bool>,
+    pub handshake_timeout_secs: Option<u64>,
+    pub alert_webhook_url: Option<String>,
+    pub nameservers: Option<Vec<String>>,
+    pub request_all_subnetworks: Option<bool>,
+    pub request_subnetwork_id: Option<String>,
+    pub address_request_interval_secs: Option<u64>,
+    pub geoip_database: Option<String>,
+    pub proxy: Option<String>,
+    pub discovery_methods: Option<Vec<String>>,
+    pub trusted_peers: Option<String>,
+    pub opt_out_marker: Option<String>,
+    pub stale_fallback_min_good: Option<usize>,
+    pub stale_fallback_cutoff_secs: Option<u64>,
+    pub expose_country_counts: Option<bool>,
+    pub aaaa_placeholder: Option<bool>,
+    pub dns_self_check_interval_secs: Option<u64>,
+    pub max_ports_per_ip: Option<usize>,
+    pub discovery_bias_report: Option<bool>,
+    pub crawl_graph_retention_secs: Option<u64>,
+    pub start_draining: Option<bool>,
+    pub verify_peer_network: Option<bool>,
+    pub stale_good_timeout_secs: Option<u64>,
+    pub prune_expire_timeout_secs: Option<u64>,
+    pub prune_interval_secs: Option<u64>,
+    pub dump_interval_secs: Option<u64>,
+    pub max_stored_addresses: Option<usize>,
+    pub address_channel_capacity: Option<usize>,
+    pub version_txt_label: Option<String>,
+    pub ban_after_failures: Option<u32>,
+    pub ban_duration_secs: Option<u64>,
+    pub quarantine_release_per_minute: Option<u32>,
+    pub max_consecutive_failures: Option<u32>,
+    pub storage_backend: Option<String>,
+    pub poll_audit_log: Option<String>,
+    pub edns_udp_payload_size: Option<u16>,
+    pub adapter_rebalancing: Option<bool>,
+    pub status_page: Option<bool>,
+    pub clock_skew_threshold_secs: Option<u64>,
+    pub expose_source_counts: Option<bool>,
+    pub max_concurrent_polls: Option<usize>,
+    pub batch_size: Option<usize>,
+    pub dns_reseed_interval_secs: Option<u64>,
+    pub disable_dns_seeding: Option<bool>,
+    pub record_classification_reasons: Option<bool>,
+    pub connect_retry_policies: Option<Vec<String>>,
+    pub admin_token: Option<String>,
+    pub serving_tier_weights: Option<Vec<String>>,
+    pub max_addresses_per_peer: Option<usize>,
+    pub dns_cache_ttl: Option<u64>,
+    pub protocol_versions: Option<Vec<u32>>,
+    pub accept_unroutable: Option<bool>,
     // Additional fields from Go version
     pub peers: Option<String>,          // Alias for known_peers
     pub default_seeder: Option<String>, // Alias for seeder
@@ -53,7 +191,7 @@ pub struct Config {
     pub host: String,
     /// DNS server nameserver
     pub nameserver: String,
-    /// DNS server listen address
+    /// DNS server listen addresses, comma-separated
     pub listen: String,
     /// gRPC server listen address
     pub grpc_listen: String,
@@ -81,6 +219,146 @@ pub struct Config {
     pub error_log_file: Option<String>,
     /// Performance analysis port
     pub profile: Option<String>,
+    /// TTL in seconds for A/AAAA records served by the DNS server
+    pub dns_ttl: u32,
+    /// Whether to answer SRV queries with per-peer ports
+    pub enable_srv: bool,
+    /// Maximum number of address records returned per DNS response
+    pub max_dns_records: usize,
+    /// Prefer the port a peer was reached on over ports advertised for it in gossip
+    pub prefer_reachable_port: bool,
+    /// Maximum concurrent DNS-over-TCP connections (0 disables TCP)
+    pub max_tcp_connections: usize,
+    /// Idle timeout in seconds for DNS-over-TCP connections
+    pub tcp_idle_timeout_secs: u64,
+    /// Window in seconds over which every known node is re-verified (0 disables the sweep)
+    pub sweep_interval_secs: u64,
+    /// Maximum nodes polled per minute by the reachability sweep
+    pub sweep_max_per_minute: u32,
+    /// Maximum DNS queries per second from a single source IP (0 disables limiting)
+    pub dns_rate_limit: u32,
+    /// Count malformed address entries from a peer as a failed attempt
+    pub penalize_malformed_addresses: bool,
+    /// Administrator mailbox published in the SOA record
+    pub soa_mailbox: Option<String>,
+    /// SOA refresh interval in seconds
+    pub soa_refresh: u32,
+    /// SOA retry interval in seconds
+    pub soa_retry: u32,
+    /// SOA expire time in seconds
+    pub soa_expire: u32,
+    /// SOA minimum (negative caching) TTL in seconds
+    pub soa_minimum: u32,
+    /// HTTP listen address for the JSON peer list (disabled when unset)
+    pub http_listen: Option<String>,
+    /// DNS query types answered with REFUSED (e.g. TXT, AAAA)
+    pub disabled_query_types: Vec<String>,
+    /// Emit console logs as newline-delimited JSON
+    pub json_format: bool,
+    /// Timeout in seconds for the P2P version handshake
+    pub handshake_timeout_secs: u64,
+    /// Webhook URL that receives a JSON POST when the health state changes
+    pub alert_webhook_url: Option<String>,
+    /// Authoritative nameservers for NS answers, as `name` or `name=ip,ip` to publish glue
+    pub nameservers: Option<Vec<String>>,
+    /// Ask peers for addresses on all subnetworks
+    pub request_all_subnetworks: bool,
+    /// Subnetwork ID (40 hex characters) to ask peers for addresses on
+    pub request_subnetwork_id: Option<String>,
+    /// Minimum seconds between address requests to the same peer (0 disables)
+    pub address_request_interval_secs: u64,
+    /// GeoIP CSV file mapping networks to country and region
+    pub geoip_database: Option<String>,
+    /// SOCKS5 proxy for outbound peer connections (socks5://host:port)
+    pub proxy: Option<String>,
+    /// Discovery methods tried against each DNS seed server, in order
+    pub discovery_methods: Vec<String>,
+    /// Trusted peer addresses used as trust anchors (comma-separated)
+    pub trusted_peers: Option<String>,
+    /// User agent marker peers use to opt out of being advertised
+    pub opt_out_marker: Option<String>,
+    /// Serve recently good peers when fewer good peers than this are available (0 disables)
+    pub stale_fallback_min_good: usize,
+    /// Only fall back to peers whose last success is within this many seconds
+    pub stale_fallback_cutoff_secs: u64,
+    /// Expose good peer counts per country over gRPC, TXT and the dashboard
+    pub expose_country_counts: bool,
+    /// Answer AAAA queries with the non-routable 100:: address when no IPv6 peer is available,
+    /// as the Go seeder does, instead of an empty answer
+    pub aaaa_placeholder: bool,
+    /// Seconds between self-queries comparing DNS answers with the address pool (0 disables)
+    pub dns_self_check_interval_secs: u64,
+    /// Maximum distinct ports stored per IP address (0 disables the cap)
+    pub max_ports_per_ip: usize,
+    /// Report reachable vs unreachable peers by GeoIP region and ASN to spot blind spots
+    pub discovery_bias_report: bool,
+    /// Record which peer reported which address, keeping edges this many seconds (0 disables)
+    pub crawl_graph_retention_secs: u64,
+    /// Start with the DNS server answering SERVFAIL until drain mode is left over gRPC
+    pub start_draining: bool,
+    /// Reject peers whose version message advertises a different network
+    pub verify_peer_network: bool,
+    /// Seconds a peer stays good after its last successful connection
+    pub stale_good_timeout_secs: u64,
+    /// Seconds without being seen in gossip before a peer is pruned
+    pub prune_expire_timeout_secs: u64,
+    /// Seconds between prunes of expired peers
+    pub prune_interval_secs: u64,
+    /// Seconds between saves of the peer list to disk
+    pub dump_interval_secs: u64,
+    /// Maximum nodes kept in the address book, 0 for no limit
+    pub max_stored_addresses: usize,
+    /// Address batches buffered per peer connection before its handler waits for room
+    pub address_channel_capacity: usize,
+    /// Label under the seeder host answering TXT queries with build info, e.g. `version`
+    pub version_txt_label: Option<String>,
+    /// Consecutive failed connections after which a peer is banned, 0 to never ban for failures
+    pub ban_after_failures: u32,
+    /// Seconds misbehaving peers are banned for, 0 to disable automatic bans
+    pub ban_duration_secs: u64,
+    /// Rate new gossiped addresses are released from quarantine to the crawler, 0 to crawl them right away
+    pub quarantine_release_per_minute: u32,
+    /// Consecutive failed connections after which a node is pruned, 0 for no limit
+    pub max_consecutive_failures: u32,
+    /// Peer store backend: json or sqlite
+    pub storage_backend: String,
+    /// Newline-delimited JSON file recording every poll outcome
+    pub poll_audit_log: Option<String>,
+    /// UDP payload size advertised in EDNS0 OPT records
+    pub edns_udp_payload_size: u16,
+    /// Route more polls through adapters with higher recent success rates
+    pub adapter_rebalancing: bool,
+    /// Serve a plain-text summary on /status of the profiling server
+    pub status_page: bool,
+    /// System clock jumps beyond this many seconds shift node timestamps (0 disables)
+    pub clock_skew_threshold_secs: u64,
+    /// Whether good peer counts per discovery source are exposed
+    pub expose_source_counts: bool,
+    /// Maximum number of peers polled at once
+    pub max_concurrent_polls: usize,
+    /// Addresses selected per crawl round, 0 for three per thread
+    pub batch_size: usize,
+    /// Seconds between DNS re-seeds while crawling, 0 to only seed when out of peers
+    pub dns_reseed_interval_secs: u64,
+    /// Whether DNS seeding is disabled, crawling only from configured peers
+    pub disable_dns_seeding: bool,
+    /// Whether the reason behind each peer's classification is recorded
+    pub record_classification_reasons: bool,
+    /// Retry policy overrides per connection error class, as `class=retries[:backoff_ms]`
+    pub connect_retry_policies: Vec<String>,
+    /// Token admin RPCs must present in the `x-admin-token` metadata; admin RPCs are disabled when unset
+    pub admin_token: Option<String>,
+    /// Share of DNS answer slots per serving tier, as `tier=weight`; empty samples by recency and reliability alone
+    pub serving_tier_weights: Vec<String>,
+    /// Addresses accepted from a single peer per poll; the rest are dropped
+    pub max_addresses_per_peer: usize,
+    /// Seconds the addresses selected for a query are reused for identical queries (0 disables)
+    pub dns_cache_ttl: u64,
+    /// Protocol versions offered in the peer handshake, tried in order until one is accepted
+    pub protocol_versions: Vec<u32>,
+    /// Whether private and other unroutable peer addresses are stored and
+    /// served. Switching it off drops the unroutable peers already stored.
+    pub accept_unroutable: bool,
     /// Logging configuration
     pub logging: LoggingConfig,
 
@@ -111,6 +389,78 @@ impl Config {
             nologfiles: false,
             error_log_file: Some("logs/kaseeder_error.log".to_string()),
             profile: None,
+            dns_ttl: 30,
+            enable_srv: false,
+            max_dns_records: DEFAULT_DNS_RECORDS_PER_RESPONSE,
+            prefer_reachable_port: true,
+            max_tcp_connections: DEFAULT_MAX_TCP_DNS_CONNECTIONS,
+            tcp_idle_timeout_secs: DEFAULT_TCP_DNS_IDLE_TIMEOUT.as_secs(),
+            sweep_interval_secs: 0,
+            sweep_max_per_minute: 60,
+            dns_rate_limit: DEFAULT_DNS_RATE_LIMIT,
+            penalize_malformed_addresses: true,
+            soa_mailbox: None,
+            soa_refresh: DEFAULT_SOA_REFRESH,
+            soa_retry: DEFAULT_SOA_RETRY,
+            soa_expire: DEFAULT_SOA_EXPIRE,
+            soa_minimum: DEFAULT_SOA_MINIMUM,
+            http_listen: None,
+            disabled_query_types: Vec::new(),
+            json_format: false,
+            handshake_timeout_secs: DEFAULT_HANDSHAKE_TIMEOUT.as_secs(),
+            alert_webhook_url: None,
+            nameservers: None,
+            request_all_subnetworks: false,
+            request_subnetwork_id: None,
+            address_request_interval_secs: DEFAULT_ADDRESS_REQUEST_INTERVAL.as_secs(),
+            geoip_database: None,
+            proxy: None,
+            discovery_methods: DEFAULT_DISCOVERY_METHODS
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+            trusted_peers: None,
+            opt_out_marker: None,
+            stale_fallback_min_good: 0,
+            stale_fallback_cutoff_secs: DEFAULT_STALE_FALLBACK_CUTOFF.as_secs(),
+            expose_country_counts: false,
+            aaaa_placeholder: false,
+            dns_self_check_interval_secs: 0,
+            max_ports_per_ip: DEFAULT_MAX_PORTS_PER_IP,
+            discovery_bias_report: false,
+            crawl_graph_retention_secs: 0,
+            start_draining: false,
+            verify_peer_network: true,
+            stale_good_timeout_secs: DEFAULT_STALE_GOOD_TIMEOUT.as_secs(),
+            prune_expire_timeout_secs: DEFAULT_PRUNE_EXPIRE_TIMEOUT.as_secs(),
+            prune_interval_secs: DEFAULT_PRUNE_ADDRESS_INTERVAL.as_secs(),
+            dump_interval_secs: DEFAULT_DUMP_ADDRESS_INTERVAL.as_secs(),
+            max_stored_addresses: DEFAULT_MAX_STORED_ADDRESSES,
+            address_channel_capacity: DEFAULT_ADDRESS_CHANNEL_CAPACITY,
+            version_txt_label: None,
+            ban_after_failures: DEFAULT_BAN_AFTER_FAILURES,
+            ban_duration_secs: DEFAULT_BAN_DURATION.as_secs(),
+            quarantine_release_per_minute: 0,
+            max_consecutive_failures: DEFAULT_MAX_CONSECUTIVE_FAILURES,
+            storage_backend: StorageBackend::default().to_string(),
+            poll_audit_log: None,
+            edns_udp_payload_size: DEFAULT_EDNS_UDP_PAYLOAD_SIZE,
+            adapter_rebalancing: false,
+            status_page: true,
+            clock_skew_threshold_secs: DEFAULT_CLOCK_SKEW_THRESHOLD.as_secs(),
+            expose_source_counts: false,
+            max_concurrent_polls: DEFAULT_MAX_CONCURRENT_POLLS,
+            batch_size: 0,
+            dns_reseed_interval_secs: DEFAULT_DNS_RESEED_INTERVAL.as_secs(),
+            disable_dns_seeding: false,
+            record_classification_reasons: false,
+            connect_retry_policies: Vec::new(),
+            admin_token: None,
+            serving_tier_weights: Vec::new(),
+            max_addresses_per_peer: DEFAULT_MAX_ADDRESSES_PER_PEER,
+            dns_cache_ttl: DEFAULT_DNS_CACHE_TTL.as_secs(),
+            protocol_versions: DEFAULT_PROTOCOL_VERSIONS.to_vec(),
+            accept_unroutable: false,
             logging: LoggingConfig::default(),
             monitoring: MonitoringConfig::default(),
             advanced_logging: AdvancedLoggingConfig::default(),
@@ -137,8 +487,8 @@ impl Config {
             });
         }
 
-        // Validate listen address
-        self.validate_socket_addr(&self.listen, "listen")?;
+        // Validate listen addresses
+        parse_listen_addrs(&self.listen)?;
 
         // Validate gRPC listen address
         self.validate_socket_addr(&self.grpc_listen, "grpc_listen")?;
@@ -154,15 +504,14 @@ impl Config {
 
         // Protocol version validation is implicit for u16 (0-65535)
 
-        // Validate testnet suffix (aligned with Go version: only support testnet-11)
-        if self.testnet && self.net_suffix != 0 {
-            if self.net_suffix != 11 {
-                return Err(KaseederError::InvalidConfigValue {
-                    field: "net_suffix".to_string(),
-                    value: self.net_suffix.to_string(),
-                    expected: "only testnet-11 (suffix 11) is supported".to_string(),
-                });
-            }
+        // Validate testnet suffix: 0 selects the default testnet, otherwise
+        // it must be a testnet with a known default port
+        if self.testnet && self.net_suffix != 0 && !is_supported_testnet_suffix(self.net_suffix) {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "net_suffix".to_string(),
+                value: self.net_suffix.to_string(),
+                expected: "10 (testnet-10) or 11 (testnet-11)".to_string(),
+            });
         }
 
         // Validate log level
@@ -171,14 +520,14 @@ impl Config {
         // Validate app directory
         self.validate_directory(&self.app_dir)?;
 
-        // Validate seeder address if provided
+        // Validate seeder addresses if provided
         if let Some(ref seeder) = self.seeder {
-            self.validate_address(seeder, "seeder")?;
+            self.validate_peer_list(seeder, "seeder")?;
         }
 
         // Validate known peers if provided
         if let Some(ref peers) = self.known_peers {
-            self.validate_peer_list(peers)?;
+            self.validate_peer_list(peers, "known_peers")?;
         }
 
         // Validate profile port if provided (aligned with Go version: 1024-65535)
@@ -186,6 +535,262 @@ impl Config {
             self.validate_profile_port(profile, "profile")?;
         }
 
+        // Validate DNS record TTL
+        if self.dns_ttl == 0 || self.dns_ttl > 86400 {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "dns_ttl".to_string(),
+                value: self.dns_ttl.to_string(),
+                expected: "1-86400 seconds".to_string(),
+            });
+        }
+
+        // Validate DNS records per response
+        if self.max_dns_records == 0 || self.max_dns_records > MAX_DNS_RECORDS_PER_RESPONSE {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "max_dns_records".to_string(),
+                value: self.max_dns_records.to_string(),
+                expected: format!("1-{}", MAX_DNS_RECORDS_PER_RESPONSE),
+            });
+        }
+
+        // Validate TCP DNS idle timeout
+        if self.max_tcp_connections > 0 && self.tcp_idle_timeout_secs == 0 {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "tcp_idle_timeout_secs".to_string(),
+                value: self.tcp_idle_timeout_secs.to_string(),
+                expected: "positive timeout in seconds".to_string(),
+            });
+        }
+
+        // Validate reachability sweep rate
+        if self.sweep_interval_secs > 0 && self.sweep_max_per_minute == 0 {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "sweep_max_per_minute".to_string(),
+                value: self.sweep_max_per_minute.to_string(),
+                expected: "positive number of nodes per minute".to_string(),
+            });
+        }
+
+        // Validate HTTP listen address if provided
+        if let Some(ref http_listen) = self.http_listen {
+            self.validate_socket_addr(http_listen, "http_listen")?;
+        }
+
+        // Validate disabled DNS query types
+        for query_type in &self.disabled_query_types {
+            if RecordType::from_str(&query_type.to_uppercase()).is_err() {
+                return Err(KaseederError::InvalidConfigValue {
+                    field: "disabled_query_types".to_string(),
+                    value: query_type.clone(),
+                    expected: "DNS record type name (e.g. TXT, AAAA)".to_string(),
+                });
+            }
+        }
+
+        // Validate P2P handshake timeout
+        if self.handshake_timeout_secs == 0 {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "handshake_timeout_secs".to_string(),
+                value: self.handshake_timeout_secs.to_string(),
+                expected: "positive timeout in seconds".to_string(),
+            });
+        }
+
+        // Validate alert webhook URL if provided
+        if let Some(ref alert_webhook_url) = self.alert_webhook_url
+            && !alert_webhook_url.starts_with("http://")
+            && !alert_webhook_url.starts_with("https://")
+        {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "alert_webhook_url".to_string(),
+                value: alert_webhook_url.clone(),
+                expected: "http:// or https:// URL".to_string(),
+            });
+        }
+
+        // Validate authoritative nameserver list if provided
+        if let Some(ref nameservers) = self.nameservers {
+            if nameservers.is_empty() {
+                return Err(KaseederError::InvalidConfigValue {
+                    field: "nameservers".to_string(),
+                    value: "[]".to_string(),
+                    expected: "at least one nameserver".to_string(),
+                });
+            }
+            for nameserver in nameservers {
+                if let Err(reason) = NameserverEntry::from_str(nameserver) {
+                    return Err(KaseederError::InvalidConfigValue {
+                        field: "nameservers".to_string(),
+                        value: nameserver.clone(),
+                        expected: format!("name or name=ip,ip ({})", reason),
+                    });
+                }
+            }
+        }
+
+        // Validate the subnetwork requested from peers if provided
+        if let Some(ref request_subnetwork_id) = self.request_subnetwork_id
+            && let Err(reason) = parse_subnetwork_id(request_subnetwork_id)
+        {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "request_subnetwork_id".to_string(),
+                value: request_subnetwork_id.clone(),
+                expected: reason,
+            });
+        }
+
+        // Validate SOCKS5 proxy URL if provided
+        if let Some(ref proxy) = self.proxy
+            && let Err(e) = Socks5Proxy::parse(proxy)
+        {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "proxy".to_string(),
+                value: proxy.clone(),
+                expected: e,
+            });
+        }
+
+        // Validate the discovery method chain
+        if self.discovery_methods.is_empty() {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "discovery_methods".to_string(),
+                value: "[]".to_string(),
+                expected: "at least one discovery method".to_string(),
+            });
+        }
+        for method in &self.discovery_methods {
+            if let Err(e) = DiscoveryMethod::from_str(method) {
+                return Err(KaseederError::InvalidConfigValue {
+                    field: "discovery_methods".to_string(),
+                    value: method.clone(),
+                    expected: e,
+                });
+            }
+        }
+
+        // Validate trusted peers if provided
+        if let Some(ref peers) = self.trusted_peers {
+            self.validate_peer_list(peers, "trusted_peers")?;
+        }
+
+        self.validate_address_manager_timeouts()?;
+
+        if self.stale_fallback_cutoff_secs == 0 {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "stale_fallback_cutoff_secs".to_string(),
+                value: self.stale_fallback_cutoff_secs.to_string(),
+                expected: "positive cutoff in seconds".to_string(),
+            });
+        }
+
+        if self.address_channel_capacity == 0
+            || self.address_channel_capacity > MAX_ADDRESS_CHANNEL_CAPACITY
+        {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "address_channel_capacity".to_string(),
+                value: self.address_channel_capacity.to_string(),
+                expected: format!("1-{}", MAX_ADDRESS_CHANNEL_CAPACITY),
+            });
+        }
+
+        if let Some(ref label) = self.version_txt_label
+            && (label.is_empty()
+                || label.len() > 63
+                || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+        {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "version_txt_label".to_string(),
+                value: label.clone(),
+                expected: "single DNS label of letters, digits and hyphens".to_string(),
+            });
+        }
+
+        if self.storage_backend.parse::<StorageBackend>().is_err() {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "storage_backend".to_string(),
+                value: self.storage_backend.clone(),
+                expected: "json or sqlite".to_string(),
+            });
+        }
+
+        if !(MAX_UDP_RESPONSE_SIZE as u16..=MAX_EDNS_UDP_PAYLOAD_SIZE)
+            .contains(&self.edns_udp_payload_size)
+        {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "edns_udp_payload_size".to_string(),
+                value: self.edns_udp_payload_size.to_string(),
+                expected: format!("{}-{}", MAX_UDP_RESPONSE_SIZE, MAX_EDNS_UDP_PAYLOAD_SIZE),
+            });
+        }
+
+        if self.max_concurrent_polls == 0 || self.max_concurrent_polls > MAX_POLL_CONCURRENCY {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "max_concurrent_polls".to_string(),
+                value: self.max_concurrent_polls.to_string(),
+                expected: format!("1-{}", MAX_POLL_CONCURRENCY),
+            });
+        }
+
+        if self.batch_size > MAX_ADDRESSES_PER_BATCH {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "batch_size".to_string(),
+                value: self.batch_size.to_string(),
+                expected: format!("0-{}", MAX_ADDRESSES_PER_BATCH),
+            });
+        }
+
+        if let Err(e) = RetryPolicies::parse(&self.connect_retry_policies) {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "connect_retry_policies".to_string(),
+                value: self.connect_retry_policies.join(","),
+                expected: e,
+            });
+        }
+
+        if self
+            .admin_token
+            .as_deref()
+            .is_some_and(|token| token.trim().is_empty())
+        {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "admin_token".to_string(),
+                value: String::new(),
+                expected: "a non-empty token, or no token to disable admin RPCs".to_string(),
+            });
+        }
+
+        if let Err(e) = TierWeights::parse(&self.serving_tier_weights) {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "serving_tier_weights".to_string(),
+                value: self.serving_tier_weights.join(","),
+                expected: e,
+            });
+        }
+
+        if !is_valid_max_addresses(self.max_addresses_per_peer) {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "max_addresses_per_peer".to_string(),
+                value: self.max_addresses_per_peer.to_string(),
+                expected: format!("1-{}", MAX_ADDRESSES),
+            });
+        }
+
+        if self.dns_cache_ttl > MAX_DNS_CACHE_TTL.as_secs() {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "dns_cache_ttl".to_string(),
+                value: self.dns_cache_ttl.to_string(),
+                expected: format!("0-{}", MAX_DNS_CACHE_TTL.as_secs()),
+            });
+        }
+
+        if self.protocol_versions.is_empty() || self.protocol_versions.contains(&0) {
+            return Err(KaseederError::InvalidConfigValue {
+                field: "protocol_versions".to_string(),
+                value: format!("{:?}", self.protocol_versions),
+                expected: "non-empty list of protocol versions above 0".to_string(),
+            });
+        }
+
         // Validate advanced logging configuration
         self.validate_advanced_logging()?;
 
@@ -331,11 +936,52 @@ impl Config {
     }
 
     /// Validate peer list format
-    fn validate_peer_list(&self, peers: &str) -> Result<()> {
+    fn validate_peer_list(&self, peers: &str, field: &str) -> Result<()> {
         for peer in peers.split(',') {
             let peer = peer.trim();
             if !peer.is_empty() {
-                self.validate_address(peer, "known_peers")?;
+                self.validate_address(peer, field)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the address manager's good, expiry, prune and save timings
+    fn validate_address_manager_timeouts(&self) -> Result<()> {
+        let ranges = [
+            (
+                "stale_good_timeout_secs",
+                self.stale_good_timeout_secs,
+                60,
+                MAX_STALE_GOOD_TIMEOUT.as_secs(),
+            ),
+            (
+                "prune_expire_timeout_secs",
+                self.prune_expire_timeout_secs,
+                self.stale_good_timeout_secs,
+                MAX_PRUNE_EXPIRE_TIMEOUT.as_secs(),
+            ),
+            (
+                "prune_interval_secs",
+                self.prune_interval_secs,
+                1,
+                MAX_PRUNE_ADDRESS_INTERVAL.as_secs(),
+            ),
+            (
+                "dump_interval_secs",
+                self.dump_interval_secs,
+                10,
+                MAX_DUMP_ADDRESS_INTERVAL.as_secs(),
+            ),
+        ];
+
+        for (field, value, min, max) in ranges {
+            if !(min..=max).contains(&value) {
+                return Err(KaseederError::InvalidConfigValue {
+                    field: field.to_string(),
+                    value: value.to_string(),
+                    expected: format!("{}-{} seconds", min, max),
+                });
             }
         }
         Ok(())
@@ -516,6 +1162,213 @@ impl Config {
         if let Some(profile) = config_file.profile {
             config.profile = Some(profile);
         }
+        if let Some(dns_ttl) = config_file.dns_ttl {
+            config.dns_ttl = dns_ttl;
+        }
+        if let Some(enable_srv) = config_file.enable_srv {
+            config.enable_srv = enable_srv;
+        }
+        if let Some(max_dns_records) = config_file.max_dns_records {
+            config.max_dns_records = max_dns_records;
+        }
+        if let Some(prefer_reachable_port) = config_file.prefer_reachable_port {
+            config.prefer_reachable_port = prefer_reachable_port;
+        }
+        if let Some(max_tcp_connections) = config_file.max_tcp_connections {
+            config.max_tcp_connections = max_tcp_connections;
+        }
+        if let Some(tcp_idle_timeout_secs) = config_file.tcp_idle_timeout_secs {
+            config.tcp_idle_timeout_secs = tcp_idle_timeout_secs;
+        }
+        if let Some(sweep_interval_secs) = config_file.sweep_interval_secs {
+            config.sweep_interval_secs = sweep_interval_secs;
+        }
+        if let Some(sweep_max_per_minute) = config_file.sweep_max_per_minute {
+            config.sweep_max_per_minute = sweep_max_per_minute;
+        }
+        if let Some(dns_rate_limit) = config_file.dns_rate_limit {
+            config.dns_rate_limit = dns_rate_limit;
+        }
+        if let Some(penalize_malformed_addresses) = config_file.penalize_malformed_addresses {
+            config.penalize_malformed_addresses = penalize_malformed_addresses;
+        }
+        if let Some(soa_mailbox) = config_file.soa_mailbox {
+            config.soa_mailbox = Some(soa_mailbox);
+        }
+        if let Some(soa_refresh) = config_file.soa_refresh {
+            config.soa_refresh = soa_refresh;
+        }
+        if let Some(soa_retry) = config_file.soa_retry {
+            config.soa_retry = soa_retry;
+        }
+        if let Some(soa_expire) = config_file.soa_expire {
+            config.soa_expire = soa_expire;
+        }
+        if let Some(soa_minimum) = config_file.soa_minimum {
+            config.soa_minimum = soa_minimum;
+        }
+        if let Some(http_listen) = config_file.http_listen {
+            config.http_listen = Some(http_listen);
+        }
+        if let Some(disabled_query_types) = config_file.disabled_query_types {
+            config.disabled_query_types = disabled_query_types;
+        }
+        if let Some(json_format) = config_file.json_format {
+            config.json_format = json_format;
+        }
+        if let Some(handshake_timeout_secs) = config_file.handshake_timeout_secs {
+            config.handshake_timeout_secs = handshake_timeout_secs;
+        }
+        if let Some(alert_webhook_url) = config_file.alert_webhook_url {
+            config.alert_webhook_url = Some(alert_webhook_url);
+        }
+        if let Some(nameservers) = config_file.nameservers {
+            config.nameservers = Some(nameservers);
+        }
+        if let Some(request_all_subnetworks) = config_file.request_all_subnetworks {
+            config.request_all_subnetworks = request_all_subnetworks;
+        }
+        if let Some(request_subnetwork_id) = config_file.request_subnetwork_id {
+            config.request_subnetwork_id = Some(request_subnetwork_id);
+        }
+        if let Some(address_request_interval_secs) = config_file.address_request_interval_secs {
+            config.address_request_interval_secs = address_request_interval_secs;
+        }
+        if let Some(geoip_database) = config_file.geoip_database {
+            config.geoip_database = Some(geoip_database);
+        }
+        if let Some(proxy) = config_file.proxy {
+            config.proxy = Some(proxy);
+        }
+        if let Some(discovery_methods) = config_file.discovery_methods {
+            config.discovery_methods = discovery_methods;
+        }
+        if let Some(trusted_peers) = config_file.trusted_peers {
+            config.trusted_peers = Some(trusted_peers);
+        }
+        if let Some(opt_out_marker) = config_file.opt_out_marker {
+            config.opt_out_marker = Some(opt_out_marker);
+        }
+        if let Some(stale_fallback_min_good) = config_file.stale_fallback_min_good {
+            config.stale_fallback_min_good = stale_fallback_min_good;
+        }
+        if let Some(stale_fallback_cutoff_secs) = config_file.stale_fallback_cutoff_secs {
+            config.stale_fallback_cutoff_secs = stale_fallback_cutoff_secs;
+        }
+        if let Some(expose_country_counts) = config_file.expose_country_counts {
+            config.expose_country_counts = expose_country_counts;
+        }
+        if let Some(aaaa_placeholder) = config_file.aaaa_placeholder {
+            config.aaaa_placeholder = aaaa_placeholder;
+        }
+        if let Some(dns_self_check_interval_secs) = config_file.dns_self_check_interval_secs {
+            config.dns_self_check_interval_secs = dns_self_check_interval_secs;
+        }
+        if let Some(max_ports_per_ip) = config_file.max_ports_per_ip {
+            config.max_ports_per_ip = max_ports_per_ip;
+        }
+        if let Some(discovery_bias_report) = config_file.discovery_bias_report {
+            config.discovery_bias_report = discovery_bias_report;
+        }
+        if let Some(crawl_graph_retention_secs) = config_file.crawl_graph_retention_secs {
+            config.crawl_graph_retention_secs = crawl_graph_retention_secs;
+        }
+        if let Some(start_draining) = config_file.start_draining {
+            config.start_draining = start_draining;
+        }
+        if let Some(verify_peer_network) = config_file.verify_peer_network {
+            config.verify_peer_network = verify_peer_network;
+        }
+        if let Some(stale_good_timeout_secs) = config_file.stale_good_timeout_secs {
+            config.stale_good_timeout_secs = stale_good_timeout_secs;
+        }
+        if let Some(prune_expire_timeout_secs) = config_file.prune_expire_timeout_secs {
+            config.prune_expire_timeout_secs = prune_expire_timeout_secs;
+        }
+        if let Some(prune_interval_secs) = config_file.prune_interval_secs {
+            config.prune_interval_secs = prune_interval_secs;
+        }
+        if let Some(dump_interval_secs) = config_file.dump_interval_secs {
+            config.dump_interval_secs = dump_interval_secs;
+        }
+        if let Some(max_stored_addresses) = config_file.max_stored_addresses {
+            config.max_stored_addresses = max_stored_addresses;
+        }
+        if let Some(address_channel_capacity) = config_file.address_channel_capacity {
+            config.address_channel_capacity = address_channel_capacity;
+        }
+        if let Some(version_txt_label) = config_file.version_txt_label {
+            config.version_txt_label = Some(version_txt_label);
+        }
+        if let Some(ban_after_failures) = config_file.ban_after_failures {
+            config.ban_after_failures = ban_after_failures;
+        }
+        if let Some(ban_duration_secs) = config_file.ban_duration_secs {
+            config.ban_duration_secs = ban_duration_secs;
+        }
+        if let Some(quarantine_release_per_minute) = config_file.quarantine_release_per_minute {
+            config.quarantine_release_per_minute = quarantine_release_per_minute;
+        }
+        if let Some(max_consecutive_failures) = config_file.max_consecutive_failures {
+            config.max_consecutive_failures = max_consecutive_failures;
+        }
+        if let Some(storage_backend) = config_file.storage_backend {
+            config.storage_backend = storage_backend;
+        }
+        if let Some(poll_audit_log) = config_file.poll_audit_log {
+            config.poll_audit_log = Some(poll_audit_log);
+        }
+        if let Some(edns_udp_payload_size) = config_file.edns_udp_payload_size {
+            config.edns_udp_payload_size = edns_udp_payload_size;
+        }
+        if let Some(adapter_rebalancing) = config_file.adapter_rebalancing {
+            config.adapter_rebalancing = adapter_rebalancing;
+        }
+        if let Some(status_page) = config_file.status_page {
+            config.status_page = status_page;
+        }
+        if let Some(clock_skew_threshold_secs) = config_file.clock_skew_threshold_secs {
+            config.clock_skew_threshold_secs = clock_skew_threshold_secs;
+        }
+        if let Some(expose_source_counts) = config_file.expose_source_counts {
+            config.expose_source_counts = expose_source_counts;
+        }
+        if let Some(max_concurrent_polls) = config_file.max_concurrent_polls {
+            config.max_concurrent_polls = max_concurrent_polls;
+        }
+        if let Some(batch_size) = config_file.batch_size {
+            config.batch_size = batch_size;
+        }
+        if let Some(dns_reseed_interval_secs) = config_file.dns_reseed_interval_secs {
+            config.dns_reseed_interval_secs = dns_reseed_interval_secs;
+        }
+        if let Some(disable_dns_seeding) = config_file.disable_dns_seeding {
+            config.disable_dns_seeding = disable_dns_seeding;
+        }
+        if let Some(record_classification_reasons) = config_file.record_classification_reasons {
+            config.record_classification_reasons = record_classification_reasons;
+        }
+        if let Some(connect_retry_policies) = config_file.connect_retry_policies {
+            config.connect_retry_policies = connect_retry_policies;
+        }
+        if let Some(admin_token) = config_file.admin_token {
+            config.admin_token = Some(admin_token);
+        }
+        if let Some(serving_tier_weights) = config_file.serving_tier_weights {
+            config.serving_tier_weights = serving_tier_weights;
+        }
+        if let Some(max_addresses_per_peer) = config_file.max_addresses_per_peer {
+            config.max_addresses_per_peer = max_addresses_per_peer;
+        }
+        if let Some(dns_cache_ttl) = config_file.dns_cache_ttl {
+            config.dns_cache_ttl = dns_cache_ttl;
+        }
+        if let Some(protocol_versions) = config_file.protocol_versions {
+            config.protocol_versions = protocol_versions;
+        }
+        if let Some(accept_unroutable) = config_file.accept_unroutable {
+            config.accept_unroutable = accept_unroutable;
+        }
 
         // Validate the final configuration
         config.validate()?;
@@ -584,6 +1437,224 @@ impl Config {
         if let Some(profile) = overrides.profile {
             self.profile = Some(profile);
         }
+        if let Some(dns_ttl) = overrides.dns_ttl {
+            self.dns_ttl = dns_ttl;
+        }
+        if let Some(enable_srv) = overrides.enable_srv {
+            self.enable_srv = enable_srv;
+        }
+        if let Some(max_dns_records) = overrides.max_dns_records {
+            self.max_dns_records = max_dns_records;
+        }
+        if let Some(prefer_reachable_port) = overrides.prefer_reachable_port {
+            self.prefer_reachable_port = prefer_reachable_port;
+        }
+        if let Some(max_tcp_connections) = overrides.max_tcp_connections {
+            self.max_tcp_connections = max_tcp_connections;
+        }
+        if let Some(tcp_idle_timeout_secs) = overrides.tcp_idle_timeout_secs {
+            self.tcp_idle_timeout_secs = tcp_idle_timeout_secs;
+        }
+        if let Some(sweep_interval_secs) = overrides.sweep_interval_secs {
+            self.sweep_interval_secs = sweep_interval_secs;
+        }
+        if let Some(sweep_max_per_minute) = overrides.sweep_max_per_minute {
+            self.sweep_max_per_minute = sweep_max_per_minute;
+        }
+        if let Some(dns_rate_limit) = overrides.dns_rate_limit {
+            self.dns_rate_limit = dns_rate_limit;
+        }
+        if let Some(penalize_malformed_addresses) = overrides.penalize_malformed_addresses {
+            self.penalize_malformed_addresses = penalize_malformed_addresses;
+        }
+        if let Some(soa_mailbox) = overrides.soa_mailbox {
+            self.soa_mailbox = Some(soa_mailbox);
+        }
+        if let Some(http_listen) = overrides.http_listen {
+            self.http_listen = Some(http_listen);
+        }
+        if let Some(disabled_query_types) = overrides.disabled_query_types {
+            self.disabled_query_types = disabled_query_types
+                .split(',')
+                .map(|query_type| query_type.trim().to_string())
+                .filter(|query_type| !query_type.is_empty())
+                .collect();
+        }
+        if let Some(json_format) = overrides.json_format {
+            self.json_format = json_format;
+        }
+        if let Some(handshake_timeout_secs) = overrides.handshake_timeout_secs {
+            self.handshake_timeout_secs = handshake_timeout_secs;
+        }
+        if let Some(alert_webhook_url) = overrides.alert_webhook_url {
+            self.alert_webhook_url = Some(alert_webhook_url);
+        }
+        if let Some(request_all_subnetworks) = overrides.request_all_subnetworks {
+            self.request_all_subnetworks = request_all_subnetworks;
+        }
+        if let Some(request_subnetwork_id) = overrides.request_subnetwork_id {
+            self.request_subnetwork_id = Some(request_subnetwork_id);
+        }
+        if let Some(address_request_interval_secs) = overrides.address_request_interval_secs {
+            self.address_request_interval_secs = address_request_interval_secs;
+        }
+        if let Some(geoip_database) = overrides.geoip_database {
+            self.geoip_database = Some(geoip_database);
+        }
+        if let Some(proxy) = overrides.proxy {
+            self.proxy = Some(proxy);
+        }
+        if let Some(discovery_methods) = overrides.discovery_methods {
+            self.discovery_methods = discovery_methods
+                .split(',')
+                .map(|method| method.trim().to_string())
+                .filter(|method| !method.is_empty())
+                .collect();
+        }
+        if let Some(trusted_peers) = overrides.trusted_peers {
+            self.trusted_peers = Some(trusted_peers);
+        }
+        if let Some(opt_out_marker) = overrides.opt_out_marker {
+            self.opt_out_marker = Some(opt_out_marker);
+        }
+        if let Some(stale_fallback_min_good) = overrides.stale_fallback_min_good {
+            self.stale_fallback_min_good = stale_fallback_min_good;
+        }
+        if let Some(stale_fallback_cutoff_secs) = overrides.stale_fallback_cutoff_secs {
+            self.stale_fallback_cutoff_secs = stale_fallback_cutoff_secs;
+        }
+        if let Some(expose_country_counts) = overrides.expose_country_counts {
+            self.expose_country_counts = expose_country_counts;
+        }
+        if let Some(aaaa_placeholder) = overrides.aaaa_placeholder {
+            self.aaaa_placeholder = aaaa_placeholder;
+        }
+        if let Some(dns_self_check_interval_secs) = overrides.dns_self_check_interval_secs {
+            self.dns_self_check_interval_secs = dns_self_check_interval_secs;
+        }
+        if let Some(max_ports_per_ip) = overrides.max_ports_per_ip {
+            self.max_ports_per_ip = max_ports_per_ip;
+        }
+        if let Some(discovery_bias_report) = overrides.discovery_bias_report {
+            self.discovery_bias_report = discovery_bias_report;
+        }
+        if let Some(crawl_graph_retention_secs) = overrides.crawl_graph_retention_secs {
+            self.crawl_graph_retention_secs = crawl_graph_retention_secs;
+        }
+        if let Some(start_draining) = overrides.start_draining {
+            self.start_draining = start_draining;
+        }
+        if let Some(verify_peer_network) = overrides.verify_peer_network {
+            self.verify_peer_network = verify_peer_network;
+        }
+        if let Some(stale_good_timeout_secs) = overrides.stale_good_timeout_secs {
+            self.stale_good_timeout_secs = stale_good_timeout_secs;
+        }
+        if let Some(prune_expire_timeout_secs) = overrides.prune_expire_timeout_secs {
+            self.prune_expire_timeout_secs = prune_expire_timeout_secs;
+        }
+        if let Some(prune_interval_secs) = overrides.prune_interval_secs {
+            self.prune_interval_secs = prune_interval_secs;
+        }
+        if let Some(dump_interval_secs) = overrides.dump_interval_secs {
+            self.dump_interval_secs = dump_interval_secs;
+        }
+        if let Some(max_stored_addresses) = overrides.max_stored_addresses {
+            self.max_stored_addresses = max_stored_addresses;
+        }
+        if let Some(address_channel_capacity) = overrides.address_channel_capacity {
+            self.address_channel_capacity = address_channel_capacity;
+        }
+        if let Some(version_txt_label) = overrides.version_txt_label {
+            self.version_txt_label = Some(version_txt_label);
+        }
+        if let Some(ban_after_failures) = overrides.ban_after_failures {
+            self.ban_after_failures = ban_after_failures;
+        }
+        if let Some(ban_duration_secs) = overrides.ban_duration_secs {
+            self.ban_duration_secs = ban_duration_secs;
+        }
+        if let Some(quarantine_release_per_minute) = overrides.quarantine_release_per_minute {
+            self.quarantine_release_per_minute = quarantine_release_per_minute;
+        }
+        if let Some(max_consecutive_failures) = overrides.max_consecutive_failures {
+            self.max_consecutive_failures = max_consecutive_failures;
+        }
+        if let Some(storage_backend) = overrides.storage_backend {
+            self.storage_backend = storage_backend;
+        }
+        if let Some(poll_audit_log) = overrides.poll_audit_log {
+            self.poll_audit_log = Some(poll_audit_log);
+        }
+        if let Some(edns_udp_payload_size) = overrides.edns_udp_payload_size {
+            self.edns_udp_payload_size = edns_udp_payload_size;
+        }
+        if let Some(adapter_rebalancing) = overrides.adapter_rebalancing {
+            self.adapter_rebalancing = adapter_rebalancing;
+        }
+        if let Some(status_page) = overrides.status_page {
+            self.status_page = status_page;
+        }
+        if let Some(clock_skew_threshold_secs) = overrides.clock_skew_threshold_secs {
+            self.clock_skew_threshold_secs = clock_skew_threshold_secs;
+        }
+        if let Some(expose_source_counts) = overrides.expose_source_counts {
+            self.expose_source_counts = expose_source_counts;
+        }
+        if let Some(max_concurrent_polls) = overrides.max_concurrent_polls {
+            self.max_concurrent_polls = max_concurrent_polls;
+        }
+        if let Some(batch_size) = overrides.batch_size {
+            self.batch_size = batch_size;
+        }
+        if let Some(dns_reseed_interval_secs) = overrides.dns_reseed_interval_secs {
+            self.dns_reseed_interval_secs = dns_reseed_interval_secs;
+        }
+        if let Some(disable_dns_seeding) = overrides.disable_dns_seeding {
+            self.disable_dns_seeding = disable_dns_seeding;
+        }
+        if let Some(record_classification_reasons) = overrides.record_classification_reasons {
+            self.record_classification_reasons = record_classification_reasons;
+        }
+        if let Some(connect_retry_policies) = overrides.connect_retry_policies {
+            self.connect_retry_policies = connect_retry_policies
+                .split(',')
+                .map(|policy| policy.trim().to_string())
+                .filter(|policy| !policy.is_empty())
+                .collect();
+        }
+        if let Some(serving_tier_weights) = overrides.serving_tier_weights {
+            self.serving_tier_weights = serving_tier_weights
+                .split(',')
+                .map(|weight| weight.trim().to_string())
+                .filter(|weight| !weight.is_empty())
+                .collect();
+        }
+        if let Some(max_addresses_per_peer) = overrides.max_addresses_per_peer {
+            self.max_addresses_per_peer = max_addresses_per_peer;
+        }
+        if let Some(dns_cache_ttl) = overrides.dns_cache_ttl {
+            self.dns_cache_ttl = dns_cache_ttl;
+        }
+        if let Some(protocol_versions) = overrides.protocol_versions {
+            self.protocol_versions = protocol_versions
+                .split(',')
+                .map(str::trim)
+                .filter(|version| !version.is_empty())
+                .map(|version| {
+                    version
+                        .parse()
+                        .map_err(|_| KaseederError::InvalidConfigValue {
+                            field: "protocol_versions".to_string(),
+                            value: version.to_string(),
+                            expected: "comma-separated protocol versions".to_string(),
+                        })
+                })
+                .collect::<Result<_>>()?;
+        }
+        if let Some(accept_unroutable) = overrides.accept_unroutable {
+            self.accept_unroutable = accept_unroutable;
+        }
 
         // Re-validate after applying overrides
         self.validate()?;
@@ -593,16 +1664,7 @@ impl Config {
 
     /// Get network parameters - aligned with Go version
     pub fn network_params(&self) -> NetworkParams {
-        if self.testnet {
-            NetworkParams::Testnet {
-                suffix: self.net_suffix,
-                default_port: if self.net_suffix == 11 { 16311 } else { 16211 }, // Aligned with Go version
-            }
-        } else {
-            NetworkParams::Mainnet {
-                default_port: 16111, // Default mainnet port
-            }
-        }
+        NetworkParams::for_network(self.testnet, self.net_suffix)
     }
 
     /// Get default port for the network
@@ -610,19 +1672,161 @@ impl Config {
         self.network_params().default_port()
     }
 
-    /// Get network name - aligned with Go version
-    pub fn network_name(&self) -> String {
-        if self.testnet {
-            if self.net_suffix == 11 {
-                "kaspa-testnet-11".to_string() // Aligned with Go version
-            } else {
-                "kaspa-testnet".to_string()
-            }
+    /// Disabled query types as DNS record types, skipping unknown names
+    pub fn disabled_record_types(&self) -> Vec<RecordType> {
+        self.disabled_query_types
+            .iter()
+            .filter_map(|query_type| query_type.to_uppercase().parse().ok())
+            .collect()
+    }
+
+    /// Apply the hot-reloadable fields of a reloaded configuration, those the
+    /// DNS server and crawler read from the shared configuration as they go.
+    /// Changes to every other field are read once at startup, such as listen
+    /// addresses, the network, storage, logging and the address manager's
+    /// options, so they are reported as needing a restart and left as they
+    /// are. Each field belongs to exactly one of the two lists.
+    pub fn apply_reload(&mut self, reloaded: Config) -> ConfigReload {
+        let mut outcome = ConfigReload::default();
+
+        macro_rules! hot {
+            ($($field:ident),*) => {$(
+                if self.$field != reloaded.$field {
+                    self.$field = reloaded.$field.clone();
+                    outcome.applied.push(stringify!($field));
+                }
+            )*};
+        }
+        macro_rules! restart {
+            ($($field:ident),*) => {$(
+                if self.$field != reloaded.$field {
+                    outcome.restart_required.push(stringify!($field));
+                }
+            )*};
+        }
+
+        hot!(
+            min_proto_ver,
+            min_ua_ver,
+            dns_ttl,
+            max_dns_records,
+            dns_rate_limit,
+            disabled_query_types,
+            aaaa_placeholder,
+            discovery_methods,
+            sweep_max_per_minute,
+            penalize_malformed_addresses,
+            ban_after_failures,
+            ban_duration_secs,
+            adapter_rebalancing,
+            batch_size,
+            dns_reseed_interval_secs,
+            disable_dns_seeding,
+            max_addresses_per_peer,
+            dns_cache_ttl,
+            accept_unroutable
+        );
+        restart!(
+            host,
+            nameserver,
+            listen,
+            grpc_listen,
+            http_listen,
+            profile,
+            app_dir,
+            seeder,
+            known_peers,
+            trusted_peers,
+            threads,
+            testnet,
+            net_suffix,
+            log_level,
+            nologfiles,
+            error_log_file,
+            json_format,
+            logging,
+            advanced_logging,
+            monitoring,
+            enable_srv,
+            soa_mailbox,
+            soa_refresh,
+            soa_retry,
+            soa_expire,
+            soa_minimum,
+            nameservers,
+            start_draining,
+            dns_self_check_interval_secs,
+            max_tcp_connections,
+            tcp_idle_timeout_secs,
+            handshake_timeout_secs,
+            address_request_interval_secs,
+            request_all_subnetworks,
+            request_subnetwork_id,
+            verify_peer_network,
+            address_channel_capacity,
+            version_txt_label,
+            sweep_interval_secs,
+            prefer_reachable_port,
+            max_ports_per_ip,
+            max_stored_addresses,
+            stale_good_timeout_secs,
+            prune_expire_timeout_secs,
+            prune_interval_secs,
+            dump_interval_secs,
+            stale_fallback_min_good,
+            stale_fallback_cutoff_secs,
+            opt_out_marker,
+            geoip_database,
+            discovery_bias_report,
+            crawl_graph_retention_secs,
+            alert_webhook_url,
+            quarantine_release_per_minute,
+            max_consecutive_failures,
+            storage_backend,
+            poll_audit_log,
+            edns_udp_payload_size,
+            status_page,
+            clock_skew_threshold_secs,
+            proxy,
+            expose_source_counts,
+            expose_country_counts,
+            max_concurrent_polls,
+            record_classification_reasons,
+            connect_retry_policies,
+            admin_token,
+            serving_tier_weights,
+            protocol_versions
+        );
+
+        outcome
+    }
+
+    /// Addresses the crawler selects per round, derived from the thread
+    /// count unless `batch_size` is set
+    pub fn poll_batch_size(&self) -> usize {
+        if self.batch_size > 0 {
+            self.batch_size
         } else {
-            "kaspa-mainnet".to_string()
+            self.threads as usize * POLL_BATCH_PER_THREAD
         }
     }
 
+    /// Seeder and known peer addresses merged into one deduplicated list,
+    /// resolving hostnames
+    pub fn bootstrap_peers(&self) -> Result<Vec<NetAddress>> {
+        let peers = [self.seeder.as_deref(), self.known_peers.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(",");
+        parse_peer_list(&peers, self.default_port())
+    }
+
+    /// Get network name, e.g. `kaspa-testnet-10`
+    pub fn network_name(&self) -> String {
+        expected_network_name(self.testnet, self.net_suffix)
+    }
+
     /// Save the configuration to a file
     pub fn save_to_file(&self, config_path: &str) -> Result<()> {
         let config_path = Path::new(config_path);
@@ -651,6 +1855,75 @@ impl Config {
             nologfiles: Some(self.nologfiles),
             error_log_file: self.error_log_file.clone(),
             profile: self.profile.clone(),
+            dns_ttl: Some(self.dns_ttl),
+            enable_srv: Some(self.enable_srv),
+            max_dns_records: Some(self.max_dns_records),
+            prefer_reachable_port: Some(self.prefer_reachable_port),
+            max_tcp_connections: Some(self.max_tcp_connections),
+            tcp_idle_timeout_secs: Some(self.tcp_idle_timeout_secs),
+            sweep_interval_secs: Some(self.sweep_interval_secs),
+            sweep_max_per_minute: Some(self.sweep_max_per_minute),
+            dns_rate_limit: Some(self.dns_rate_limit),
+            penalize_malformed_addresses: Some(self.penalize_malformed_addresses),
+            soa_mailbox: self.soa_mailbox.clone(),
+            soa_refresh: Some(self.soa_refresh),
+            soa_retry: Some(self.soa_retry),
+            soa_expire: Some(self.soa_expire),
+            soa_minimum: Some(self.soa_minimum),
+            http_listen: self.http_listen.clone(),
+            disabled_query_types: Some(self.disabled_query_types.clone()),
+            json_format: Some(self.json_format),
+            handshake_timeout_secs: Some(self.handshake_timeout_secs),
+            alert_webhook_url: self.alert_webhook_url.clone(),
+            nameservers: self.nameservers.clone(),
+            request_all_subnetworks: Some(self.request_all_subnetworks),
+            request_subnetwork_id: self.request_subnetwork_id.clone(),
+            address_request_interval_secs: Some(self.address_request_interval_secs),
+            geoip_database: self.geoip_database.clone(),
+            proxy: self.proxy.clone(),
+            discovery_methods: Some(self.discovery_methods.clone()),
+            trusted_peers: self.trusted_peers.clone(),
+            opt_out_marker: self.opt_out_marker.clone(),
+            stale_fallback_min_good: Some(self.stale_fallback_min_good),
+            stale_fallback_cutoff_secs: Some(self.stale_fallback_cutoff_secs),
+            expose_country_counts: Some(self.expose_country_counts),
+            aaaa_placeholder: Some(self.aaaa_placeholder),
+            dns_self_check_interval_secs: Some(self.dns_self_check_interval_secs),
+            max_ports_per_ip: Some(self.max_ports_per_ip),
+            discovery_bias_report: Some(self.discovery_bias_report),
+            crawl_graph_retention_secs: Some(self.crawl_graph_retention_secs),
+            start_draining: Some(self.start_draining),
+            verify_peer_network: Some(self.verify_peer_network),
+            stale_good_timeout_secs: Some(self.stale_good_timeout_secs),
+            prune_expire_timeout_secs: Some(self.prune_expire_timeout_secs),
+            prune_interval_secs: Some(self.prune_interval_secs),
+            dump_interval_secs: Some(self.dump_interval_secs),
+            max_stored_addresses: Some(self.max_stored_addresses),
+            address_channel_capacity: Some(self.address_channel_capacity),
+            version_txt_label: self.version_txt_label.clone(),
+            ban_after_failures: Some(self.ban_after_failures),
+            ban_duration_secs: Some(self.ban_duration_secs),
+            quarantine_release_per_minute: Some(self.quarantine_release_per_minute),
+            max_consecutive_failures: Some(self.max_consecutive_failures),
+            storage_backend: Some(self.storage_backend.clone()),
+            poll_audit_log: self.poll_audit_log.clone(),
+            edns_udp_payload_size: Some(self.edns_udp_payload_size),
+            adapter_rebalancing: Some(self.adapter_rebalancing),
+            status_page: Some(self.status_page),
+            clock_skew_threshold_secs: Some(self.clock_skew_threshold_secs),
+            expose_source_counts: Some(self.expose_source_counts),
+            max_concurrent_polls: Some(self.max_concurrent_polls),
+            batch_size: Some(self.batch_size),
+            dns_reseed_interval_secs: Some(self.dns_reseed_interval_secs),
+            disable_dns_seeding: Some(self.disable_dns_seeding),
+            record_classification_reasons: Some(self.record_classification_reasons),
+            connect_retry_policies: Some(self.connect_retry_policies.clone()),
+            admin_token: self.admin_token.clone(),
+            serving_tier_weights: Some(self.serving_tier_weights.clone()),
+            max_addresses_per_peer: Some(self.max_addresses_per_peer),
+            dns_cache_ttl: Some(self.dns_cache_ttl),
+            protocol_versions: Some(self.protocol_versions.clone()),
+            accept_unroutable: Some(self.accept_unroutable),
             peers: None, // Don't save aliases
             default_seeder: None,
         };
@@ -665,6 +1938,17 @@ impl Config {
         Ok(())
     }
 
+    /// Every field of the configuration as TOML, with the admin token redacted
+    pub fn to_toml(&self) -> Result<String> {
+        let mut config = self.clone();
+        if config.admin_token.is_some() {
+            config.admin_token = Some("<redacted>".to_string());
+        }
+
+        toml::to_string_pretty(&config)
+            .map_err(|e| KaseederError::Serialization(format!("TOML serialization error: {}", e)))
+    }
+
     /// Create a default configuration file
     pub fn create_default_config(config_path: &str) -> Result<()> {
         let default_config = Self::new();
@@ -713,6 +1997,9 @@ impl Config {
         info!("  gRPC Listen: {}", self.grpc_listen);
         info!("  App Directory: {}", self.app_dir);
         info!("  Threads: {}", self.threads);
+        info!("  Max Concurrent Polls: {}", self.max_concurrent_polls);
+        info!("  Poll Batch Size: {}", self.poll_batch_size());
+        info!("  Max Addresses per Peer: {}", self.max_addresses_per_peer);
         if let Some(ref peers) = self.known_peers {
             info!("  Known Peers: {}", peers);
         }
@@ -728,6 +2015,158 @@ impl Config {
         if let Some(ref profile) = self.profile {
             info!("  Profile Port: {}", profile);
         }
+        info!("  DNS TTL: {}s", self.dns_ttl);
+        info!("  SRV Records: {}", self.enable_srv);
+        info!("  Max DNS Records: {}", self.max_dns_records);
+        info!("  Prefer Reachable Port: {}", self.prefer_reachable_port);
+        info!("  Max TCP DNS Connections: {}", self.max_tcp_connections);
+        info!("  TCP DNS Idle Timeout: {}s", self.tcp_idle_timeout_secs);
+        if self.sweep_interval_secs > 0 {
+            info!(
+                "  Reachability Sweep: every {}s, at most {}/min",
+                self.sweep_interval_secs, self.sweep_max_per_minute
+            );
+        }
+        info!("  DNS Rate Limit: {}/s per IP", self.dns_rate_limit);
+        info!(
+            "  Penalize Malformed Addresses: {}",
+            self.penalize_malformed_addresses
+        );
+        info!(
+            "  SOA: mailbox {}, refresh {}s, retry {}s, expire {}s, minimum {}s",
+            self.soa_mailbox.as_deref().unwrap_or("hostmaster.<host>"),
+            self.soa_refresh,
+            self.soa_retry,
+            self.soa_expire,
+            self.soa_minimum
+        );
+        if let Some(ref http_listen) = self.http_listen {
+            info!("  HTTP Listen: {}", http_listen);
+        }
+        if !self.disabled_query_types.is_empty() {
+            info!(
+                "  Disabled Query Types: {}",
+                self.disabled_query_types.join(", ")
+            );
+        }
+        info!("  JSON Logs: {}", self.json_format);
+        info!("  Handshake Timeout: {}s", self.handshake_timeout_secs);
+        if let Some(ref alert_webhook_url) = self.alert_webhook_url {
+            info!("  Alert Webhook: {}", alert_webhook_url);
+        }
+        if let Some(ref nameservers) = self.nameservers {
+            info!("  Nameservers: {}", nameservers.join(" "));
+        }
+        info!(
+            "  Request All Subnetworks: {}",
+            self.request_all_subnetworks
+        );
+        if let Some(ref request_subnetwork_id) = self.request_subnetwork_id {
+            info!("  Request Subnetwork ID: {}", request_subnetwork_id);
+        }
+        info!(
+            "  Address Request Interval: {}s",
+            self.address_request_interval_secs
+        );
+        if let Some(ref geoip_database) = self.geoip_database {
+            info!("  GeoIP Database: {}", geoip_database);
+        }
+        if let Some(ref proxy) = self.proxy {
+            info!("  Proxy: {}", proxy);
+        }
+        info!("  Discovery Methods: {}", self.discovery_methods.join(", "));
+        if self.disable_dns_seeding {
+            info!("  DNS Seeding: disabled");
+        } else if self.dns_reseed_interval_secs > 0 {
+            info!("  DNS Reseed Interval: {}s", self.dns_reseed_interval_secs);
+        }
+        if let Some(ref peers) = self.trusted_peers {
+            info!("  Trusted Peers: {}", peers);
+        }
+        if let Some(ref opt_out_marker) = self.opt_out_marker {
+            info!("  Opt-out Marker: {}", opt_out_marker);
+        }
+        if self.stale_fallback_min_good > 0 {
+            info!(
+                "  Stale Fallback: below {} good peers, cutoff {}s",
+                self.stale_fallback_min_good, self.stale_fallback_cutoff_secs
+            );
+        }
+        info!("  Expose Country Counts: {}", self.expose_country_counts);
+        info!("  Expose Source Counts: {}", self.expose_source_counts);
+        info!(
+            "  Record Classification Reasons: {}",
+            self.record_classification_reasons
+        );
+        info!(
+            "  Admin RPCs: {}",
+            if self.admin_token.is_some() {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        info!("  AAAA Placeholder: {}", self.aaaa_placeholder);
+        if self.dns_self_check_interval_secs > 0 {
+            info!(
+                "  DNS Self-check Interval: {}s",
+                self.dns_self_check_interval_secs
+            );
+        }
+        info!("  Max Ports per IP: {}", self.max_ports_per_ip);
+        if !self.serving_tier_weights.is_empty() {
+            info!(
+                "  Serving Tier Weights: {}",
+                self.serving_tier_weights.join(", ")
+            );
+        }
+        if !self.connect_retry_policies.is_empty() {
+            info!(
+                "  Connect Retry Policies: {}",
+                self.connect_retry_policies.join(", ")
+            );
+        }
+        info!("  Discovery Bias Report: {}", self.discovery_bias_report);
+        if self.crawl_graph_retention_secs > 0 {
+            info!(
+                "  Crawl Graph Retention: {}s",
+                self.crawl_graph_retention_secs
+            );
+        }
+        info!("  Max Stored Addresses: {}", self.max_stored_addresses);
+        info!(
+            "  Address Channel Capacity: {}",
+            self.address_channel_capacity
+        );
+        if let Some(ref version_txt_label) = self.version_txt_label {
+            info!("  Version TXT Label: {}", version_txt_label);
+        }
+        info!("  Ban After Failures: {}", self.ban_after_failures);
+        info!("  Ban Duration: {}s", self.ban_duration_secs);
+        info!(
+            "  Quarantine Release per Minute: {}",
+            self.quarantine_release_per_minute
+        );
+        info!(
+            "  Max Consecutive Failures: {}",
+            self.max_consecutive_failures
+        );
+        info!("  Storage Backend: {}", self.storage_backend);
+        if let Some(ref poll_audit_log) = self.poll_audit_log {
+            info!("  Poll Audit Log: {}", poll_audit_log);
+        }
+        info!("  EDNS UDP Payload Size: {}", self.edns_udp_payload_size);
+        info!("  Adapter Rebalancing: {}", self.adapter_rebalancing);
+        info!("  Status Page: {}", self.status_page);
+        info!(
+            "  Clock Skew Threshold: {}s",
+            self.clock_skew_threshold_secs
+        );
+        info!("  DNS Answer Cache TTL: {}s", self.dns_cache_ttl);
+        info!("  Protocol Versions: {:?}", self.protocol_versions);
+        if self.accept_unroutable {
+            info!("  Unroutable Addresses: accepted");
+        }
     }
 }
 
@@ -749,6 +2188,69 @@ pub struct CliOverrides {
     pub log_level: Option<String>,
     pub nologfiles: Option<bool>,
     pub profile: Option<String>,
+    pub dns_ttl: Option<u32>,
+    pub enable_srv: Option<bool>,
+    pub max_dns_records: Option<usize>,
+    pub prefer_reachable_port: Option<bool>,
+    pub max_tcp_connections: Option<usize>,
+    pub tcp_idle_timeout_secs: Option<u64>,
+    pub sweep_interval_secs: Option<u64>,
+    pub sweep_max_per_minute: Option<u32>,
+    pub dns_rate_limit: Option<u32>,
+    pub penalize_malformed_addresses: Option<bool>,
+    pub soa_mailbox: Option<String>,
+    pub http_listen: Option<String>,
+    pub disabled_query_types: Option<String>,
+    pub json_format: Option<bool>,
+    pub handshake_timeout_secs: Option<u64>,
+    pub alert_webhook_url: Option<String>,
+    pub request_all_subnetworks: Option<bool>,
+    pub request_subnetwork_id: Option<String>,
+    pub address_request_interval_secs: Option<u64>,
+    pub geoip_database: Option<String>,
+    pub proxy: Option<String>,
+    pub discovery_methods: Option<String>,
+    pub trusted_peers: Option<String>,
+    pub opt_out_marker: Option<String>,
+    pub stale_fallback_min_good: Option<usize>,
+    pub stale_fallback_cutoff_secs: Option<u64>,
+    pub expose_country_counts: Option<bool>,
+    pub aaaa_placeholder: Option<bool>,
+    pub dns_self_check_interval_secs: Option<u64>,
+    pub max_ports_per_ip: Option<usize>,
+    pub discovery_bias_report: Option<bool>,
+    pub crawl_graph_retention_secs: Option<u64>,
+    pub start_draining: Option<bool>,
+    pub verify_peer_network: Option<bool>,
+    pub stale_good_timeout_secs: Option<u64>,
+    pub prune_expire_timeout_secs: Option<u64>,
+    pub prune_interval_secs: Option<u64>,
+    pub dump_interval_secs: Option<u64>,
+    pub max_stored_addresses: Option<usize>,
+    pub address_channel_capacity: Option<usize>,
+    pub version_txt_label: Option<String>,
+    pub ban_after_failures: Option<u32>,
+    pub ban_duration_secs: Option<u64>,
+    pub quarantine_release_per_minute: Option<u32>,
+    pub max_consecutive_failures: Option<u32>,
+    pub storage_backend: Option<String>,
+    pub poll_audit_log: Option<String>,
+    pub edns_udp_payload_size: Option<u16>,
+    pub adapter_rebalancing: Option<bool>,
+    pub status_page: Option<bool>,
+    pub clock_skew_threshold_secs: Option<u64>,
+    pub expose_source_counts: Option<bool>,
+    pub max_concurrent_polls: Option<usize>,
+    pub batch_size: Option<usize>,
+    pub dns_reseed_interval_secs: Option<u64>,
+    pub disable_dns_seeding: Option<bool>,
+    pub record_classification_reasons: Option<bool>,
+    pub connect_retry_policies: Option<String>,
+    pub serving_tier_weights: Option<String>,
+    pub max_addresses_per_peer: Option<usize>,
+    pub dns_cache_ttl: Option<u64>,
+    pub protocol_versions: Option<String>,
+    pub accept_unroutable: Option<bool>,
 }
 
 impl Default for Config {
@@ -758,7 +2260,7 @@ impl Default for Config {
 }
 
 /// Advanced logging configuration with rotation support
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AdvancedLoggingConfig {
     /// Log rotation strategy: "daily", "hourly", "size", "hybrid"
     pub rotation_strategy: String,
@@ -809,7 +2311,7 @@ impl Default for AdvancedLoggingConfig {
 }
 
 /// Performance monitoring configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MonitoringConfig {
     /// Whether to enable performance monitoring
     pub enabled: bool,
@@ -847,6 +2349,87 @@ impl Default for MonitoringConfig {
     }
 }
 
+/// Parse a peer given as `ip`, `ip:port`, `[ipv6]:port` or `hostname:port`,
+/// using the default port when none is given. A hostname yields every address
+/// it resolves to.
+pub fn parse_peer(peer: &str, default_port: u16) -> Result<Vec<NetAddress>> {
+    let peer = peer.trim();
+    let invalid = |reason: &str| KaseederError::InvalidAddress(format!("{}: '{}'", reason, peer));
+
+    if let Ok(address) = peer.parse::<SocketAddr>() {
+        if address.port() == 0 {
+            return Err(invalid("Peer port must not be 0"));
+        }
+        return Ok(vec![NetAddress::new(address.ip(), address.port())]);
+    }
+    let bare_ip = peer
+        .strip_prefix('[')
+        .and_then(|ip| ip.strip_suffix(']'))
+        .unwrap_or(peer);
+    if let Ok(ip) = bare_ip.parse::<IpAddr>() {
+        return Ok(vec![NetAddress::new(ip, default_port)]);
+    }
+
+    let (host, port) = match peer.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) if port != 0 => (host, port),
+            _ => return Err(invalid("Invalid peer port")),
+        },
+        None => (peer, default_port),
+    };
+    let valid_hostname = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    if !valid_hostname {
+        return Err(invalid("Invalid peer address"));
+    }
+
+    let addresses: Vec<NetAddress> = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| invalid(&format!("Failed to resolve peer ({})", e)))?
+        .map(|address| NetAddress::new(address.ip(), address.port()))
+        .collect();
+    if addresses.is_empty() {
+        return Err(invalid("Peer hostname resolved to no addresses"));
+    }
+    Ok(addresses)
+}
+
+/// Parse the comma-separated DNS listen addresses, e.g.
+/// `0.0.0.0:5354,[::]:5354`, dropping duplicates
+pub fn parse_listen_addrs(listen: &str) -> Result<Vec<SocketAddr>> {
+    let mut addrs = Vec::new();
+    for entry in listen.split(',').map(str::trim) {
+        let addr: SocketAddr = entry
+            .parse()
+            .map_err(|_| KaseederError::InvalidConfigValue {
+                field: "listen".to_string(),
+                value: entry.to_string(),
+                expected: "comma-separated socket addresses (IP:port)".to_string(),
+            })?;
+        if !addrs.contains(&addr) {
+            addrs.push(addr);
+        }
+    }
+    Ok(addrs)
+}
+
+/// Parse a comma-separated peer list, dropping duplicates while keeping the
+/// order peers were listed in
+pub fn parse_peer_list(peers: &str, default_port: u16) -> Result<Vec<NetAddress>> {
+    let mut seen = HashSet::new();
+    let mut addresses = Vec::new();
+    for peer in peers.split(',').filter(|peer| !peer.trim().is_empty()) {
+        for address in parse_peer(peer, default_port)? {
+            if seen.insert(address.clone()) {
+                addresses.push(address);
+            }
+        }
+    }
+    Ok(addresses)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -874,6 +2457,66 @@ mod tests {
         testnet_config.net_suffix = 10;
         let testnet_params = testnet_config.network_params();
         assert_eq!(testnet_params.default_port(), 16211);
+
+        testnet_config.net_suffix = 11;
+        let testnet_params = testnet_config.network_params();
+        assert_eq!(testnet_params.default_port(), 16311);
+        assert_eq!(testnet_config.default_port(), 16311);
+        assert!(matches!(
+            testnet_params,
+            NetworkParams::Testnet { suffix: 11, .. }
+        ));
+    }
+
+    #[test]
+    fn test_network_default_ports_match_kaspad() {
+        for (testnet, suffix, port) in [
+            (false, 0, 16111),
+            (true, 10, 16211),
+            (true, 11, 16311),
+            // Unknown testnets use the testnet-10 port
+            (true, 12, 16211),
+        ] {
+            assert_eq!(
+                NetworkParams::for_network(testnet, suffix).default_port(),
+                port,
+                "testnet={} suffix={}",
+                testnet,
+                suffix
+            );
+        }
+
+        // One entry per network, and no two networks share a port
+        for (i, (network, port)) in NETWORK_DEFAULT_PORTS.iter().enumerate() {
+            for (other_network, other_port) in &NETWORK_DEFAULT_PORTS[i + 1..] {
+                assert_ne!(network, other_network);
+                assert_ne!(port, other_port);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_listen_addrs() {
+        let addrs = parse_listen_addrs("0.0.0.0:5354, [::]:5354,0.0.0.0:5354").unwrap();
+        assert_eq!(
+            addrs,
+            vec![
+                "0.0.0.0:5354".parse::<SocketAddr>().unwrap(),
+                "[::]:5354".parse().unwrap(),
+            ]
+        );
+
+        for invalid in ["", "0.0.0.0:5354,", "0.0.0.0:5354,localhost:53"] {
+            assert!(
+                parse_listen_addrs(invalid).is_err(),
+                "accepted {:?}",
+                invalid
+            );
+        }
+
+        let mut config = Config::new();
+        config.listen = "127.0.0.1:5354,[::1]:5354".to_string();
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -885,6 +2528,27 @@ mod tests {
         testnet_config.testnet = true;
         testnet_config.net_suffix = 11;
         assert_eq!(testnet_config.network_name(), "kaspa-testnet-11");
+
+        testnet_config.net_suffix = 10;
+        assert_eq!(testnet_config.network_name(), "kaspa-testnet-10");
+    }
+
+    #[test]
+    fn test_testnet_suffix_validation() {
+        let mut config = Config::new();
+        config.testnet = true;
+        for suffix in [0, 10, 11] {
+            config.net_suffix = suffix;
+            assert!(config.validate().is_ok(), "suffix {} rejected", suffix);
+        }
+
+        config.net_suffix = 12;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("testnet-10"), "unexpected error: {}", err);
+
+        // The suffix is ignored on mainnet
+        config.testnet = false;
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -919,6 +2583,55 @@ mod tests {
         let mut invalid_config = Config::new();
         invalid_config.log_level = "invalid-level".to_string();
         assert!(invalid_config.validate().is_err());
+
+        let mut invalid_config = Config::new();
+        invalid_config.dns_ttl = 0;
+        assert!(invalid_config.validate().is_err());
+        invalid_config.dns_ttl = 86401;
+        assert!(invalid_config.validate().is_err());
+
+        let mut invalid_config = Config::new();
+        invalid_config.max_dns_records = 0;
+        assert!(invalid_config.validate().is_err());
+        invalid_config.max_dns_records = 34;
+        assert!(invalid_config.validate().is_err());
+
+        let mut query_types_config = Config::new();
+        query_types_config.disabled_query_types = vec!["txt".to_string(), "AAAA".to_string()];
+        assert!(query_types_config.validate().is_ok());
+        query_types_config.disabled_query_types = vec!["BOGUS".to_string()];
+        assert!(query_types_config.validate().is_err());
+
+        let mut nameservers_config = Config::new();
+        nameservers_config.nameservers = Some(vec![
+            "ns1.seed.kaspa.org=203.0.113.1".to_string(),
+            "ns2.seed.kaspa.org".to_string(),
+        ]);
+        assert!(nameservers_config.validate().is_ok());
+        nameservers_config.nameservers = Some(Vec::new());
+        assert!(nameservers_config.validate().is_err());
+        nameservers_config.nameservers = Some(vec!["ns1.seed.kaspa.org=bogus".to_string()]);
+        assert!(nameservers_config.validate().is_err());
+
+        let mut invalid_config = Config::new();
+        invalid_config.max_concurrent_polls = 0;
+        assert!(invalid_config.validate().is_err());
+        invalid_config.max_concurrent_polls = MAX_POLL_CONCURRENCY + 1;
+        assert!(invalid_config.validate().is_err());
+
+        let mut invalid_config = Config::new();
+        invalid_config.batch_size = MAX_ADDRESSES_PER_BATCH + 1;
+        assert!(invalid_config.validate().is_err());
+    }
+
+    #[test]
+    fn test_poll_batch_size_defaults_to_thread_count() {
+        let mut config = Config::new();
+        assert_eq!(config.max_concurrent_polls, DEFAULT_MAX_CONCURRENT_POLLS);
+        assert_eq!(config.poll_batch_size(), 24);
+
+        config.batch_size = 200;
+        assert_eq!(config.poll_batch_size(), 200);
     }
 
     #[test]
@@ -995,4 +2708,231 @@ mod tests {
         assert!(config.validate_log_level("invalid").is_err());
         assert!(config.validate_log_level("").is_err());
     }
+
+    #[test]
+    fn test_peer_list_parsing() {
+        let peers = parse_peer_list(
+            " 45.0.0.1:16111, [2001:4860::1]:16211,45.0.0.2,[2001:4860::2],45.0.0.1:16111",
+            16111,
+        )
+        .unwrap();
+        assert_eq!(
+            peers,
+            vec![
+                NetAddress::new("45.0.0.1".parse().unwrap(), 16111),
+                NetAddress::new("2001:4860::1".parse().unwrap(), 16211),
+                NetAddress::new("45.0.0.2".parse().unwrap(), 16111),
+                NetAddress::new("2001:4860::2".parse().unwrap(), 16111),
+            ]
+        );
+
+        // Hostnames resolve to every address they point to
+        let localhost = parse_peer("localhost:16311", 16111).unwrap();
+        assert!(!localhost.is_empty());
+        assert!(
+            localhost
+                .iter()
+                .all(|peer| peer.ip.is_loopback() && peer.port == 16311)
+        );
+
+        for malformed in [
+            "45.0.0.1:",
+            "45.0.0.1:port",
+            "45.0.0.1:0",
+            "[2001:4860::1]:99999",
+            "bad_host:16111",
+            ":16111",
+        ] {
+            assert!(
+                matches!(
+                    parse_peer(malformed, 16111),
+                    Err(KaseederError::InvalidAddress(_))
+                ),
+                "{} should be rejected",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn test_bootstrap_peers_merge_seeder_and_known_peers() {
+        let mut config = Config::new();
+        assert!(config.bootstrap_peers().unwrap().is_empty());
+
+        config.seeder = Some("45.0.0.1,[2001:4860::1]:16111".to_string());
+        config.known_peers = Some("45.0.0.1:16111,45.0.0.2:16111".to_string());
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.bootstrap_peers().unwrap(),
+            vec![
+                NetAddress::new("45.0.0.1".parse().unwrap(), 16111),
+                NetAddress::new("2001:4860::1".parse().unwrap(), 16111),
+                NetAddress::new("45.0.0.2".parse().unwrap(), 16111),
+            ]
+        );
+
+        config.known_peers = Some("45.0.0.2:16111,not a peer".to_string());
+        assert!(matches!(
+            config.bootstrap_peers(),
+            Err(KaseederError::InvalidAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_reload_separates_hot_and_restart_fields() {
+        let mut running = Config::new();
+        let mut reloaded = Config::new();
+        reloaded.min_proto_ver = 7;
+        reloaded.dns_ttl = 60;
+        reloaded.disabled_query_types = vec!["txt".to_string()];
+        reloaded.listen = "0.0.0.0:53".to_string();
+        reloaded.threads = 16;
+
+        let outcome = running.apply_reload(reloaded);
+        assert_eq!(
+            outcome,
+            ConfigReload {
+                applied: vec!["min_proto_ver", "dns_ttl", "disabled_query_types"],
+                restart_required: vec!["listen", "threads"],
+            }
+        );
+        assert_eq!(running.min_proto_ver, 7);
+        assert_eq!(running.disabled_record_types(), vec![RecordType::TXT]);
+        // Restart-only fields keep their running values
+        assert_eq!(running.listen, Config::new().listen);
+        assert_eq!(running.threads, 8);
+
+        assert_eq!(
+            running.apply_reload(running.clone()),
+            ConfigReload::default()
+        );
+
+        // The address manager reads its reporting options once at startup
+        let mut reloaded = running.clone();
+        reloaded.expose_country_counts = true;
+        assert_eq!(
+            running.apply_reload(reloaded).restart_required,
+            vec!["expose_country_counts"]
+        );
+        assert!(!running.expose_country_counts);
+    }
+
+    /// Candidate values different from `value` and of the same JSON shape.
+    /// The type behind `null` is unknown, so it gets one of each.
+    fn changed_values(value: &serde_json::Value) -> Vec<serde_json::Value> {
+        use serde_json::{Value, json};
+        match value {
+            Value::Bool(b) => vec![json!(!b)],
+            Value::Number(n) => match n.as_u64() {
+                Some(n) => vec![json!(n.checked_sub(1).unwrap_or(1))],
+                None => vec![json!(n.as_f64().unwrap_or_default() + 1.0)],
+            },
+            Value::String(s) => vec![json!(format!("{}x", s))],
+            Value::Array(a) if !a.is_empty() => vec![json!([])],
+            Value::Array(_) => vec![json!(["x"]), json!([1])],
+            Value::Object(fields) => {
+                let Some((name, first)) = fields.iter().next() else {
+                    return Vec::new();
+                };
+                changed_values(first)
+                    .into_iter()
+                    .map(|changed| {
+                        let mut fields = fields.clone();
+                        fields.insert(name.clone(), changed);
+                        Value::Object(fields)
+                    })
+                    .collect()
+            }
+            Value::Null => vec![json!("x"), json!(1), json!(["x"]), json!(true)],
+        }
+    }
+
+    #[test]
+    fn test_apply_reload_classifies_every_field() {
+        let running = Config::new();
+        let serde_json::Value::Object(fields) = serde_json::to_value(&running).unwrap() else {
+            panic!("Config does not serialize to an object");
+        };
+
+        for (name, value) in &fields {
+            let reloaded = changed_values(value)
+                .into_iter()
+                .find_map(|changed| {
+                    let mut fields = fields.clone();
+                    fields.insert(name.clone(), changed);
+                    serde_json::from_value::<Config>(serde_json::Value::Object(fields)).ok()
+                })
+                .unwrap_or_else(|| panic!("no changed value for `{}`", name));
+
+            let outcome = running.clone().apply_reload(reloaded);
+            assert!(
+                outcome.applied.contains(&name.as_str())
+                    || outcome.restart_required.contains(&name.as_str()),
+                "`{}` is neither hot-reloadable nor marked as needing a restart",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_manager_timeout_validation() {
+        let mut config = Config::new();
+        assert!(config.validate().is_ok());
+
+        // A low-churn network may keep peers for weeks
+        config.prune_expire_timeout_secs = 30 * 86400;
+        assert!(config.validate().is_ok());
+
+        // Peers must not expire before they stop being good
+        config.prune_expire_timeout_secs = config.stale_good_timeout_secs - 1;
+        assert!(config.validate().is_err());
+
+        let mut config = Config::new();
+        config.prune_interval_secs = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = Config::new();
+        config.dump_interval_secs = MAX_DUMP_ADDRESS_INTERVAL.as_secs() + 1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_to_toml_includes_every_field() {
+        let mut config = Config::new();
+        config.threads = 3;
+        config.admin_token = Some("secret".to_string());
+
+        let toml_content = config.to_toml().unwrap();
+        assert!(!toml_content.contains("secret"));
+
+        // Parses back into the same configuration, so no field is dropped
+        let parsed: Config = toml::from_str(&toml_content).unwrap();
+        assert_eq!(parsed.threads, 3);
+        assert_eq!(parsed.admin_token.as_deref(), Some("<redacted>"));
+        assert_eq!(parsed.to_toml().unwrap(), toml_content);
+        // Fields display() leaves out are printed too
+        assert!(toml_content.contains("soa_refresh"));
+    }
+
+    #[test]
+    fn test_protocol_versions() {
+        let config = Config::new();
+        assert_eq!(config.protocol_versions, DEFAULT_PROTOCOL_VERSIONS);
+
+        let config = config
+            .with_cli_overrides(CliOverrides {
+                protocol_versions: Some("8, 7".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.protocol_versions, vec![8, 7]);
+
+        for invalid in ["", "7,x", "0"] {
+            let result = Config::new().with_cli_overrides(CliOverrides {
+                protocol_versions: Some(invalid.to_string()),
+                ..Default::default()
+            });
+            assert!(result.is_err(), "accepted {:?}", invalid);
+        }
+    }
 }
@@ -7,6 +7,23 @@ pub const DEFAULT_DNS_PORT: u16 = 5354;
 pub const DEFAULT_GRPC_PORT: u16 = 3737;
 pub const DEFAULT_PROFILE_PORT: u16 = 8080;
 
+// Default P2P ports, as used by kaspad, keyed by testnet suffix (mainnet has
+// none). This is the only place network ports are defined; testnets without
+// an entry use the testnet-10 port.
+pub const NETWORK_DEFAULT_PORTS: &[(Option<u16>, u16)] = &[
+    (None, 16111),     // mainnet
+    (Some(10), 16211), // testnet-10
+    (Some(11), 16311), // testnet-11
+];
+pub const FALLBACK_TESTNET_SUFFIX: u16 = 10;
+
+/// Whether `suffix` names a testnet listed in [`NETWORK_DEFAULT_PORTS`]
+pub fn is_supported_testnet_suffix(suffix: u16) -> bool {
+    NETWORK_DEFAULT_PORTS
+        .iter()
+        .any(|(network, _)| *network == Some(suffix))
+}
+
 // Port Ranges
 pub const MIN_PORT: u16 = 1024; // Avoid privileged ports
 pub const MAX_PORT: u16 = 65535;
@@ -22,27 +39,88 @@ pub const MAX_NETWORK_SUFFIX: u16 = 99;
 // Protocol Configuration
 pub const MIN_PROTOCOL_VERSION: u16 = 0;
 pub const MAX_PROTOCOL_VERSION: u16 = 65535;
+/// Protocol versions offered in the handshake, in order, until a peer accepts
+/// one. Version 7 is what active (Crescendo) nodes speak.
+pub const DEFAULT_PROTOCOL_VERSIONS: &[u32] = &[7];
 
 // Timeout Configuration
 pub const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
 pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(60);
 pub const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_secs(60);
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Upper bound on retries a connection error class may be configured with
+pub const MAX_CONNECT_RETRIES: u32 = 10;
+pub const DEFAULT_ADDRESS_REQUEST_INTERVAL: Duration = Duration::from_secs(60);
+pub const DEFAULT_ADDRESS_CHANNEL_CAPACITY: usize = 100;
+pub const MAX_ADDRESS_CHANNEL_CAPACITY: usize = 10_000;
+/// How long a received address batch waits for room in a full channel before it is dropped
+pub const ADDRESS_CHANNEL_SEND_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long each component gets to stop cleanly on shutdown before it is aborted
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 
 // Crawler Configuration
-pub const MAX_CONCURRENT_POLLS: usize = 100;
+pub const DEFAULT_MAX_CONCURRENT_POLLS: usize = 100;
+pub const MAX_POLL_CONCURRENCY: usize = 10_000;
+/// Addresses selected per crawl round for each thread when `batch_size` is 0
+pub const POLL_BATCH_PER_THREAD: usize = 3;
 pub const CRAWLER_SLEEP_INTERVAL: Duration = Duration::from_secs(10);
+pub const DEFAULT_DNS_RESEED_INTERVAL: Duration = Duration::from_secs(3600);
 pub const MAX_ADDRESSES_PER_BATCH: usize = 1000;
+/// Addresses accepted from one peer per poll, matching kaspad's limit
+pub const DEFAULT_MAX_ADDRESSES_PER_PEER: usize = 1000;
 
 // Address Manager Configuration
 pub const DEFAULT_MAX_ADDRESSES: usize = 2000;
+pub const DEFAULT_MAX_PORTS_PER_IP: usize = 8;
+pub const DEFAULT_MAX_STORED_ADDRESSES: usize = 100_000;
+pub const DEFAULT_BAN_AFTER_FAILURES: u32 = 10;
+pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(86400); // 24 hours
+pub const DEFAULT_MAX_CONSECUTIVE_FAILURES: u32 = 20;
+/// Consecutive failures after which a previously reached peer is no longer served as good
+pub const MAX_GOOD_CONSECUTIVE_FAILURES: u32 = 3;
 pub const MAX_ADDRESSES: usize = 10000;
 pub const PEER_CLEANUP_INTERVAL: Duration = Duration::from_secs(3600); // 1 hour
 pub const ADDRESS_EXPIRY_TIMEOUT: Duration = Duration::from_secs(86400); // 24 hours
+pub const DEFAULT_STALE_FALLBACK_CUTOFF: Duration = Duration::from_secs(8 * 3600); // 8 hours
+/// Wall-clock jumps beyond this are corrected in node timestamps
+pub const DEFAULT_CLOCK_SKEW_THRESHOLD: Duration = Duration::from_secs(60);
+pub const CLOCK_SKEW_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+pub const DEFAULT_STALE_GOOD_TIMEOUT: Duration = Duration::from_secs(3600); // 1 hour, same as Go version
+pub const DEFAULT_PRUNE_EXPIRE_TIMEOUT: Duration = Duration::from_secs(8 * 3600); // 8 hours, same as Go version
+pub const DEFAULT_PRUNE_ADDRESS_INTERVAL: Duration = Duration::from_secs(60); // same as Go version
+pub const DEFAULT_DUMP_ADDRESS_INTERVAL: Duration = Duration::from_secs(2 * 60); // same as Go version
+pub const MAX_STALE_GOOD_TIMEOUT: Duration = Duration::from_secs(7 * 86400); // 7 days
+pub const MAX_PRUNE_EXPIRE_TIMEOUT: Duration = Duration::from_secs(90 * 86400); // 90 days
+pub const MAX_PRUNE_ADDRESS_INTERVAL: Duration = Duration::from_secs(3600); // 1 hour
+pub const MAX_DUMP_ADDRESS_INTERVAL: Duration = Duration::from_secs(86400); // 24 hours
 
 // DNS Configuration
 pub const MAX_DNS_RECORDS: usize = 100;
 pub const DNS_TTL: u32 = 300; // 5 minutes
 pub const DNS_CACHE_SIZE: usize = 1000;
+pub const MAX_CONCURRENT_DNS_REQUESTS: usize = 256;
+pub const DEFAULT_DNS_RECORDS_PER_RESPONSE: usize = 8;
+pub const MAX_DNS_RECORDS_PER_RESPONSE: usize = 33;
+/// Successes in a row that put a peer in the reliable serving tier
+pub const RELIABLE_TIER_MIN_SUCCESSES: u32 = 3;
+/// A peer reached this recently, but not yet reliable, is in the fresh serving tier
+pub const FRESH_TIER_WINDOW: Duration = Duration::from_secs(3600);
+pub const MAX_UDP_RESPONSE_SIZE: usize = 512;
+pub const MAX_TCP_RESPONSE_SIZE: usize = u16::MAX as usize;
+/// UDP payload size advertised in EDNS0 OPT records (DNS flag day 2020)
+pub const DEFAULT_EDNS_UDP_PAYLOAD_SIZE: u16 = 1232;
+pub const MAX_EDNS_UDP_PAYLOAD_SIZE: u16 = 4096;
+pub const DEFAULT_MAX_TCP_DNS_CONNECTIONS: usize = 64;
+pub const DEFAULT_TCP_DNS_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_DNS_RATE_LIMIT: u32 = 20; // queries per second per source IP
+pub const DNS_RATE_LIMIT_PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+/// How long a selected answer is reused for identical queries
+pub const DEFAULT_DNS_CACHE_TTL: Duration = Duration::from_secs(3);
+pub const MAX_DNS_CACHE_TTL: Duration = Duration::from_secs(60);
+pub const DEFAULT_SOA_REFRESH: u32 = 604800; // 1 week
+pub const DEFAULT_SOA_RETRY: u32 = 86400; // 1 day
+pub const DEFAULT_SOA_EXPIRE: u32 = 2592000; // 30 days
+pub const DEFAULT_SOA_MINIMUM: u32 = 60; // Negative caching TTL
 
 // gRPC Configuration
 pub const MAX_GRPC_CONNECTIONS: usize = 100;
@@ -1,23 +1,81 @@
+use crate::audit::{AuditLog, PollOutcome, PollRecord};
 use crate::checkversion::VersionChecker;
-use crate::config::Config;
-use crate::constants::MAX_CONCURRENT_POLLS;
-use crate::dns_seed_discovery::DnsSeedDiscovery;
+use crate::config::{Config, SharedConfig, config_snapshot, parse_peer_list};
+use crate::dns_seed_discovery::{DiscoveryMethod, DnsSeedDiscovery};
 use crate::errors::{KaseederError, Result};
-use crate::manager::AddressManager;
-use crate::netadapter::DnsseedNetAdapter;
+use crate::kaspa_protocol::{check_peer_network, expected_network_name};
+use crate::manager::{AddressManager, PeerSource};
+use crate::netadapter::{
+    AddressRequestLimiter, ConnectionSettings, DnsseedNetAdapter, RetryPolicies,
+    format_subnetwork_id, parse_subnetwork_id,
+};
+use crate::proxy::Socks5Proxy;
 use crate::types::NetAddress;
+use dashmap::DashSet;
 use kaspa_consensus_core::config::Config as ConsensusConfig;
+use std::collections::VecDeque;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore, mpsc};
 use tracing::{debug, error, info, warn};
 
+/// How often the reachability sweep checks for nodes that are due
+const SWEEP_TICK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Weight of the latest poll in an adapter's moving success rate
+const ADAPTER_HEALTH_SMOOTHING: f64 = 0.1;
+/// Share of polls a failing adapter keeps, so it is noticed when it recovers
+const MIN_ADAPTER_WEIGHT: f64 = 0.05;
+
+/// Connection settings for adapters dialing peers with the given
+/// configuration
+pub fn connection_settings(config: &Config) -> Result<ConnectionSettings> {
+    Ok(ConnectionSettings {
+        handshake_timeout: Duration::from_secs(config.handshake_timeout_secs),
+        include_all_subnetworks: config.request_all_subnetworks,
+        subnetwork_id: config
+            .request_subnetwork_id
+            .as_deref()
+            .map(parse_subnetwork_id)
+            .transpose()
+            .map_err(KaseederError::Config)?,
+        address_requests: Arc::new(AddressRequestLimiter::new(Duration::from_secs(
+            config.address_request_interval_secs,
+        ))),
+        proxy: config
+            .proxy
+            .as_deref()
+            .map(Socks5Proxy::parse)
+            .transpose()
+            .map_err(KaseederError::Config)?
+            .map(Arc::new),
+        verify_network: config.verify_peer_network,
+        address_channel_capacity: config.address_channel_capacity,
+        retry_policies: RetryPolicies::parse(&config.connect_retry_policies)
+            .map_err(KaseederError::Config)?,
+        protocol_versions: config.protocol_versions.clone(),
+    })
+}
+
 /// Performance-optimized crawler manager
 pub struct Crawler {
     address_manager: Arc<AddressManager>,
     net_adapters: Vec<Arc<DnsseedNetAdapter>>,
-    config: Arc<Config>,
+    /// Per-adapter health used to rebalance polls when enabled
+    adapter_balancer: Arc<AdapterBalancer>,
+    /// Peers currently being polled, so no peer is polled twice at once
+    in_flight: Arc<InFlightPolls>,
+    /// Set while DNS seeders are being resolved, so overlapping bootstrap
+    /// triggers share one resolution
+    dns_seeding: Arc<SeedingGuard>,
+    /// Live configuration, updated in place by SIGHUP reloads
+    config: SharedConfig,
     quit_tx: mpsc::Sender<()>,
+    /// Taken by `start`, so only the first started crawler listens for quit
+    quit_rx: Arc<std::sync::Mutex<Option<mpsc::Receiver<()>>>>,
+    /// Per-poll audit trail, if enabled
+    audit_log: Option<Arc<AuditLog>>,
     // Concurrent control
     semaphore: Arc<Semaphore>,
     // Performance statistics
@@ -36,104 +94,204 @@ pub struct CrawlerPerformanceStats {
     pub memory_usage_bytes: u64,
 }
 
+impl CrawlerPerformanceStats {
+    /// Count one completed poll, successful or not, and fold its duration
+    /// into the running mean
+    pub fn record_poll(&mut self, duration: Duration, success: bool, addresses_found: usize) {
+        self.total_polls += 1;
+        if success {
+            self.successful_polls += 1;
+        } else {
+            self.failed_polls += 1;
+        }
+        self.total_addresses_found += addresses_found as u64;
+
+        let poll_time_ms = duration.as_secs_f64() * 1000.0;
+        self.average_poll_time_ms +=
+            (poll_time_ms - self.average_poll_time_ms) / self.total_polls as f64;
+    }
+}
+
 impl Crawler {
     /// Create a new crawler instance
     pub fn new(
         address_manager: Arc<AddressManager>,
         consensus_config: Arc<ConsensusConfig>,
-        config: Arc<Config>,
+        shared_config: SharedConfig,
     ) -> Result<Self> {
         let mut net_adapters = Vec::new();
+        let config = config_snapshot(&shared_config);
+
+        // All adapters share one address request limiter so the per-peer
+        // interval holds no matter which adapter polls the peer
+        let connection_settings = connection_settings(&config)?;
 
         // Create network adapter for each thread
         for _ in 0..config.threads {
-            let adapter = DnsseedNetAdapter::new(consensus_config.clone())?;
+            let adapter = DnsseedNetAdapter::with_settings(
+                consensus_config.clone(),
+                connection_settings.clone(),
+            )?;
             net_adapters.push(Arc::new(adapter));
         }
 
-        let (quit_tx, _quit_rx) = mpsc::channel(1);
+        let (quit_tx, quit_rx) = mpsc::channel(1);
+
+        let audit_log = match config.poll_audit_log {
+            Some(ref path) => {
+                let audit_log = AuditLog::open(path)?;
+                info!("Writing poll audit log to {}", audit_log.path().display());
+                Some(Arc::new(audit_log))
+            }
+            None => None,
+        };
 
         // Create semaphore to control concurrency
-        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_POLLS));
+        let semaphore = Arc::new(Semaphore::new(config.max_concurrent_polls));
 
         Ok(Self {
             address_manager,
+            adapter_balancer: Arc::new(AdapterBalancer::new(net_adapters.len())),
+            in_flight: Arc::new(InFlightPolls::default()),
+            dns_seeding: Arc::new(SeedingGuard::default()),
             net_adapters,
-            config,
+            config: shared_config,
             quit_tx,
+            quit_rx: Arc::new(std::sync::Mutex::new(Some(quit_rx))),
+            audit_log,
             semaphore,
             stats: Arc::new(Mutex::new(CrawlerPerformanceStats::default())),
         })
     }
 
-    /// Start crawler
+    /// Current configuration, reflecting any reload
+    fn config(&self) -> Arc<Config> {
+        config_snapshot(&self.config)
+    }
+
+    /// Start crawler. Runs until [`shutdown`](Self::shutdown) is called, then
+    /// waits for in-flight polls and closes the network adapters.
     pub async fn start(&mut self) -> Result<()> {
-        info!("Starting crawler with {} threads", self.config.threads);
+        let Some(mut quit_rx) = self
+            .quit_rx
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        else {
+            return Err(KaseederError::Service("crawler is already running".to_string()));
+        };
+        info!("Starting crawler with {} threads", self.config().threads);
 
-        // Initialize known peers
+        // Initialize known and trusted peers
         self.initialize_known_peers().await?;
+        self.initialize_trusted_peers()?;
+
+        // Start the periodic reachability sweep if enabled
+        let sweep_handle = (self.config().sweep_interval_secs > 0).then(|| {
+            let crawler = self.clone();
+            tokio::spawn(async move {
+                crawler.sweep_loop().await;
+            })
+        });
 
         // Start main crawl loop
-        self.creep_loop().await?;
+        let result = tokio::select! {
+            result = self.creep_loop() => result,
+            _ = quit_rx.recv() => {
+                info!("Crawler stopping");
+                Ok(())
+            }
+        };
 
-        Ok(())
+        if let Some(sweep_handle) = sweep_handle {
+            sweep_handle.abort();
+        }
+        self.drain().await;
+        result
+    }
+
+    /// Wait for in-flight polls to finish, then close every adapter so no
+    /// peer connection outlives the crawler
+    async fn drain(&self) {
+        let permits = u32::try_from(self.config().max_concurrent_polls).unwrap_or(u32::MAX);
+        if let Ok(permits) = self.semaphore.acquire_many(permits).await {
+            // Keep the permits so nothing polls while the adapters close
+            permits.forget();
+        }
+        self.semaphore.close();
+
+        for net_adapter in &self.net_adapters {
+            net_adapter.close().await;
+        }
+        info!("Crawler stopped");
     }
 
     /// Initialize known peers - aligned with Go version logic
     async fn initialize_known_peers(&self) -> Result<()> {
-        if let Some(ref known_peers) = self.config.known_peers {
-            info!("Processing {} known peers", known_peers.split(',').count());
-
-            let peers: Vec<NetAddress> = known_peers
-                .split(',')
-                .filter_map(|peer_str| {
-                    let parts: Vec<&str> = peer_str.split(':').collect();
-                    if parts.len() != 2 {
-                        warn!("Invalid peer address format: {}", peer_str);
-                        return None;
-                    }
+        // Seeder and known peers, deduplicated
+        let config = self.config();
+        let peers = config.bootstrap_peers()?;
 
-                    let ip = parts[0].parse().ok()?;
-                    let port = parts[1].parse().ok()?;
+        if !peers.is_empty() {
+            let added = self.address_manager.add_addresses_from(
+                peers.clone(),
+                PeerSource::Known,
+                config.accept_unroutable,
+            );
 
-                    Some(NetAddress::new(ip, port))
-                })
-                .collect();
+            info!("Adding {} known peers to address manager", peers.len());
 
-            if !peers.is_empty() {
-                let added = self.address_manager.add_addresses(
-                    peers.clone(),
-                    self.config.network_params().default_port(),
-                    false, // Do not accept unroutable addresses
-                );
+            // Mark known nodes as good (like Go version)
+            for peer in peers {
+                info!("Marking peer {}:{} as good", peer.ip, peer.port);
+                self.address_manager.attempt(&peer);
+                self.address_manager.good(&peer, None, None);
+            }
 
-                info!("Adding {} known peers to address manager", peers.len());
+            info!(
+                "Address manager now has {} total nodes",
+                self.address_manager.address_count()
+            );
+            info!("Added {} known peers", added);
+        }
 
-                // Mark known nodes as good (like Go version)
-                for peer in peers {
-                    info!("Marking peer {}:{} as good", peer.ip, peer.port);
-                    self.address_manager.attempt(&peer);
-                    self.address_manager.good(&peer, None, None);
-                }
+        Ok(())
+    }
 
-                info!(
-                    "Address manager now has {} total nodes",
-                    self.address_manager.address_count()
-                );
-                info!("Added {} known peers", added);
-            }
+    /// Add trusted peers. They are served before their first poll and the
+    /// addresses they report are crawled ahead of other gossip.
+    fn initialize_trusted_peers(&self) -> Result<()> {
+        let config = self.config();
+        if let Some(ref trusted_peers) = config.trusted_peers {
+            let peers = parse_peer_list(trusted_peers, config.default_port())?;
+            let added = self.address_manager.add_addresses_from(
+                peers.clone(),
+                PeerSource::Trusted,
+                config.accept_unroutable,
+            );
+            info!("Added {} trusted peers ({} new)", peers.len(), added);
         }
-
         Ok(())
     }
 
     /// Main crawl loop - aligned with Go version logic
     async fn creep_loop(&mut self) -> Result<()> {
         let mut batch_tasks = Vec::new();
+        let mut last_dns_seed = Instant::now();
+        let mut warned_no_seeding = false;
 
         loop {
+            let config = self.config();
+
+            // Top up from DNS periodically so a decaying peer set is refreshed
+            if Self::dns_reseed_due(&config, last_dns_seed, Instant::now()) {
+                info!("Refreshing peers from DNS seeders");
+                self.seed_from_dns().await?;
+                last_dns_seed = Instant::now();
+            }
+
             // Get addresses to poll like Go version
-            let peers = self.address_manager.addresses(self.config.threads);
+            let peers = self.address_manager.address_batch(config.poll_batch_size());
             info!(
                 "Main loop: Addresses() returned {} peers, total nodes: {}",
                 peers.len(),
@@ -142,11 +300,23 @@ impl Crawler {
 
             // More aggressive DNS seeding strategy (from previous commit)
             if peers.is_empty() {
-                if self.address_manager.address_count() < 1000 {
+                if config.disable_dns_seeding {
+                    if self.address_manager.address_count() == 0 && !warned_no_seeding {
+                        warn!(
+                            "Address store is empty and DNS seeding is disabled; configure known_peers or trusted_peers to crawl from"
+                        );
+                        warned_no_seeding = true;
+                    }
+                    info!("No addresses available - waiting 10 seconds before retry");
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                    continue;
+                } else if self.address_manager.address_count() < 1000 {
                     // Force DNS seeding to test our improvements (from previous commit)
                     info!("Forcing DNS seeding to discover more addresses (current: {})", self.address_manager.address_count());
                     self.seed_from_dns().await?;
-                    let peers_after_dns = self.address_manager.addresses(self.config.threads);
+                    last_dns_seed = Instant::now();
+                    let peers_after_dns =
+                        self.address_manager.address_batch(config.poll_batch_size());
                     info!(
                         "After DNS seeding: Addresses() returned {} peers",
                         peers_after_dns.len()
@@ -168,22 +338,51 @@ impl Crawler {
 
             // Process peers (like Go version)
             info!("Processing {} peers for polling", peers.len());
+            self.stats.lock().await.last_poll_batch_size = peers.len();
 
             // Process peers in parallel with optimized network adapter selection
             for (i, addr) in peers.iter().enumerate() {
+                let Some(claim) = self.in_flight.claim(addr) else {
+                    debug!("Skipping {}:{}, already being polled", addr.ip, addr.port);
+                    continue;
+                };
                 let permit = self.semaphore.clone().acquire_owned().await?;
-                // Use round-robin distribution for better load balancing
-                let net_adapter = self.net_adapters[i % self.net_adapters.len()].clone();
+                // Favor healthier adapters when rebalancing, otherwise
+                // distribute round-robin
+                let adapter_index = if config.adapter_rebalancing {
+                    self.adapter_balancer.pick().await
+                } else {
+                    i % self.net_adapters.len()
+                };
+                let net_adapter = self.net_adapters[adapter_index].clone();
+                let adapter_balancer = self.adapter_balancer.clone();
                 let address = addr.clone();
                 let address_manager = self.address_manager.clone();
-                let config = self.config.clone();
+                let config = config.clone();
+                let audit_log = self.audit_log.clone();
+                let stats = self.stats.clone();
 
                 let task = tokio::spawn(async move {
-                    let result =
-                        Self::poll_single_peer(net_adapter, address, address_manager, config).await;
+                    let result = Self::poll_single_peer(
+                        net_adapter,
+                        address,
+                        address_manager,
+                        config,
+                        audit_log,
+                        stats,
+                    )
+                    .await;
+                    // Peers rejected for their version were reached fine
+                    adapter_balancer
+                        .record(
+                            adapter_index,
+                            !matches!(result, Err(KaseederError::ConnectionFailed(_))),
+                        )
+                        .await;
 
                     // Automatically release semaphore permit
                     drop(permit);
+                    drop(claim);
                     result
                 });
 
@@ -207,16 +406,104 @@ impl Crawler {
         }
     }
 
+    /// Re-verify every known node, spread across the configured sweep window
+    async fn sweep_loop(&self) {
+        let window = Duration::from_secs(self.config().sweep_interval_secs);
+
+        loop {
+            let config = self.config();
+            let nodes = self
+                .address_manager
+                .get_all_nodes()
+                .into_iter()
+                .map(|node| node.address)
+                .collect();
+            let mut sweep = ReachabilitySweep::new(nodes, window, config.sweep_max_per_minute);
+            let started = Instant::now();
+
+            info!(
+                "Starting reachability sweep over {} nodes ({}s window)",
+                sweep.total(),
+                window.as_secs()
+            );
+
+            while !sweep.is_complete() && started.elapsed() < window {
+                tokio::time::sleep(SWEEP_TICK_INTERVAL).await;
+
+                for (i, address) in sweep.next_batch(started.elapsed()).into_iter().enumerate() {
+                    let Some(claim) = self.in_flight.claim(&address) else {
+                        continue;
+                    };
+                    let permit = match self.semaphore.clone().acquire_owned().await {
+                        Ok(permit) => permit,
+                        Err(e) => {
+                            error!("Reachability sweep stopped: {}", e);
+                            return;
+                        }
+                    };
+                    let net_adapter = self.net_adapters[i % self.net_adapters.len()].clone();
+                    let address_manager = self.address_manager.clone();
+                    let config = config.clone();
+                    let audit_log = self.audit_log.clone();
+                    let stats = self.stats.clone();
+
+                    tokio::spawn(async move {
+                        if let Err(e) = Self::poll_single_peer(
+                            net_adapter,
+                            address,
+                            address_manager,
+                            config,
+                            audit_log,
+                            stats,
+                        )
+                        .await
+                        {
+                            debug!("Sweep: {}", e);
+                        }
+                        drop(permit);
+                        drop(claim);
+                    });
+                }
+            }
+
+            info!(
+                "Reachability sweep finished: {}/{} nodes attempted",
+                sweep.total() - sweep.remaining(),
+                sweep.total()
+            );
+
+            if let Some(rest) = window.checked_sub(started.elapsed()) {
+                tokio::time::sleep(rest).await;
+            }
+        }
+    }
+
     /// Discover nodes from DNS seed servers - aligned with Go version dnsseed.SeedFromDNS
     async fn seed_from_dns(&self) -> Result<()> {
-        let network_params = self.config.network_params();
+        let Some(_seeding) = self.dns_seeding.try_begin() else {
+            debug!("DNS seeding already in progress, skipping");
+            return Ok(());
+        };
+
+        let config = self.config();
+        let network_params = config.network_params();
         let seed_servers = DnsSeedDiscovery::get_dns_seeders_from_network_params(&network_params);
+        let discovery_methods: Vec<DiscoveryMethod> = config
+            .discovery_methods
+            .iter()
+            .filter_map(|method| method.parse().ok())
+            .collect();
         let mut discovered_addresses = Vec::new();
 
         // Query each DNS seed server (like Go version)
         for seed_server in seed_servers {
-            match DnsSeedDiscovery::query_seed_server(&seed_server, network_params.default_port())
-                .await
+            match DnsSeedDiscovery::query_seed_server(
+                &seed_server,
+                network_params.default_port(),
+                &discovery_methods,
+                self.net_adapters.first().map(Arc::as_ref),
+            )
+            .await
             {
                 Ok(addresses) => {
                     if !addresses.is_empty() {
@@ -237,9 +524,9 @@ impl Crawler {
         // Add discovered addresses (like Go version)
         if !discovered_addresses.is_empty() {
             info!("DNS seeding found {} addresses", discovered_addresses.len());
-            self.address_manager.add_addresses(
+            self.address_manager.add_addresses_from(
                 discovered_addresses,
-                network_params.default_port(),
+                PeerSource::Seeder,
                 true, // Accept any addresses from DNS seeding
             );
         }
@@ -247,24 +534,95 @@ impl Crawler {
         Ok(())
     }
 
-    /// Poll a single node with intelligent connection tracking
+    /// Whether the periodic DNS re-seed is due
+    fn dns_reseed_due(config: &Config, last_seed: Instant, now: Instant) -> bool {
+        !config.disable_dns_seeding
+            && config.dns_reseed_interval_secs > 0
+            && now.saturating_duration_since(last_seed)
+                >= Duration::from_secs(config.dns_reseed_interval_secs)
+    }
+
+    /// Poll a single node, counting it in the performance statistics and
+    /// writing the outcome to the audit log if enabled
     async fn poll_single_peer(
         net_adapter: Arc<DnsseedNetAdapter>,
         address: NetAddress,
         address_manager: Arc<AddressManager>,
         config: Arc<Config>,
+        audit_log: Option<Arc<AuditLog>>,
+        stats: Arc<Mutex<CrawlerPerformanceStats>>,
+    ) -> Result<()> {
+        let mut record = PollRecord::new(&address);
+        let started = Instant::now();
+        let result =
+            Self::poll_peer(net_adapter, address, address_manager, config, &mut record).await;
+        stats.lock().await.record_poll(
+            started.elapsed(),
+            result.is_ok(),
+            record.addresses_received,
+        );
+
+        if let Some(audit_log) = audit_log {
+            match result {
+                Ok(()) => record.outcome = PollOutcome::Accepted,
+                Err(ref e) => {
+                    if matches!(e, KaseederError::Validation(_)) {
+                        record.outcome = PollOutcome::Rejected;
+                    }
+                    record.reason = Some(e.to_string());
+                }
+            }
+            if let Err(e) = audit_log.record(&record) {
+                error!("Failed to write poll audit record: {}", e);
+            }
+        }
+
+        result
+    }
+
+    /// Check the network a peer advertised at handshake is the one we crawl,
+    /// unless network verification is turned off
+    fn verify_peer_network(config: &Config, advertised: &str) -> Result<()> {
+        if !config.verify_peer_network {
+            return Ok(());
+        }
+        check_peer_network(
+            &expected_network_name(config.testnet, config.net_suffix),
+            advertised,
+        )
+    }
+
+    /// Truncate the addresses a peer sent to `max`, returning how many were dropped
+    fn cap_peer_addresses(addresses: &mut Vec<NetAddress>, max: usize) -> usize {
+        let dropped = addresses.len().saturating_sub(max);
+        addresses.truncate(max);
+        dropped
+    }
+
+    /// Poll a single node with intelligent connection tracking
+    async fn poll_peer(
+        net_adapter: Arc<DnsseedNetAdapter>,
+        address: NetAddress,
+        address_manager: Arc<AddressManager>,
+        config: Arc<Config>,
+        record: &mut PollRecord,
     ) -> Result<()> {
         // Mark attempt to connect
         address_manager.attempt(&address);
 
-        let peer_address = format!("{}:{}", address.ip, address.port);
+        let peer_address = address.dial_string();
         debug!("Polling peer {}", peer_address);
 
         // Connect to node and get addresses
         let connection_result = net_adapter.connect_and_get_addresses(&peer_address).await;
 
         match connection_result {
-            Ok((version_msg, addresses)) => {
+            Ok((version_msg, batch)) => {
+                let mut addresses = batch.addresses;
+                record.protocol_version = Some(version_msg.protocol_version);
+                record.user_agent = Some(version_msg.user_agent.clone());
+                record.addresses_received = addresses.len();
+
                 // Record successful connection
                 address_manager.record_connection_result(&address, true, None);
 
@@ -279,12 +637,28 @@ impl Crawler {
                         false,
                         Some(error_msg.clone()),
                     );
+                    Self::ban_peer(&address_manager, &address, &config, &error_msg);
                     return Err(KaseederError::Validation(format!(
                         "Peer {} protocol version validation failed: {}",
                         peer_address, e
                     )));
                 }
 
+                // Never serve a peer from another network
+                if let Err(e) = Self::verify_peer_network(&config, &version_msg.network) {
+                    let error_msg = format!("Network validation failed: {}", e);
+                    address_manager.record_connection_result(
+                        &address,
+                        false,
+                        Some(error_msg.clone()),
+                    );
+                    Self::ban_peer(&address_manager, &address, &config, &error_msg);
+                    return Err(KaseederError::Validation(format!(
+                        "Peer {} network validation failed: {}",
+                        peer_address, e
+                    )));
+                }
+
                 // Check user agent version
                 if let Some(ref min_ua_ver) = config.min_ua_ver {
                     if let Err(e) =
@@ -303,12 +677,48 @@ impl Crawler {
                     }
                 }
 
+                // Flag peers that sent malformed address entries
+                if batch.rejected > 0 {
+                    warn!(
+                        "Peer {} sent {} malformed addresses",
+                        peer_address, batch.rejected
+                    );
+                    address_manager.record_malformed_addresses(
+                        &address,
+                        batch.rejected,
+                        config.penalize_malformed_addresses,
+                    );
+                }
+
+                // Cap what a single peer can inject into the address book
+                let dropped =
+                    Self::cap_peer_addresses(&mut addresses, config.max_addresses_per_peer);
+                if dropped > 0 {
+                    warn!(
+                        "Peer {} sent {} addresses, dropped {} over the limit of {}",
+                        peer_address,
+                        addresses.len() + dropped,
+                        dropped,
+                        config.max_addresses_per_peer
+                    );
+                }
+
                 // Add received addresses
                 let added = address_manager.add_addresses(
                     addresses.clone(),
                     config.network_params().default_port(),
-                    false, // Do not accept unroutable addresses
+                    config.accept_unroutable,
                 );
+                record.addresses_added = added;
+
+                if let Some(crawl_graph) = address_manager.crawl_graph() {
+                    crawl_graph.record(&address, &addresses);
+                }
+
+                // Addresses reported by a trust anchor are checked first
+                if address_manager.is_trusted(&address) {
+                    address_manager.vouch_for(&addresses);
+                }
 
                 info!(
                     "✅ Peer {} ({}) sent {} addresses, {} new",
@@ -318,15 +728,35 @@ impl Crawler {
                     added
                 );
 
-                // Mark node as good
-                address_manager.good(&address, Some(&version_msg.user_agent), None);
+                // Mark node as good, recording the subnetwork it runs on
+                let subnetwork_id = version_msg
+                    .subnetwork_id
+                    .as_ref()
+                    .map(|id| format_subnetwork_id(&id.bytes));
+                address_manager.good(
+                    &address,
+                    Some(&version_msg.user_agent),
+                    subnetwork_id.as_deref(),
+                );
+                address_manager.set_services(&address, version_msg.services);
+                address_manager.set_protocol_version(&address, version_msg.protocol_version);
 
                 Ok(())
             }
             Err(e) => {
                 // Record failed connection with error details
                 let error_msg = e.to_string();
-                address_manager.record_connection_result(&address, false, Some(error_msg.clone()));
+                address_manager.failed(&address, Some(error_msg.clone()));
+
+                let failures = address_manager.consecutive_failures(&address);
+                if config.ban_after_failures > 0 && failures >= config.ban_after_failures {
+                    Self::ban_peer(
+                        &address_manager,
+                        &address,
+                        &config,
+                        &format!("{} consecutive failures", failures),
+                    );
+                }
 
                 // Classify error type for different handling
                 let classified_error = if error_msg.contains("Unimplemented") {
@@ -349,7 +779,33 @@ impl Crawler {
         }
     }
 
-    /// Shutdown crawler
+    /// Ban a misbehaving peer's IP for the configured duration. Peers the
+    /// operator added or trusts are left alone.
+    fn ban_peer(
+        address_manager: &AddressManager,
+        address: &NetAddress,
+        config: &Config,
+        reason: &str,
+    ) {
+        if config.ban_duration_secs == 0 {
+            return;
+        }
+        if address_manager
+            .get_node(address)
+            .is_some_and(|node| node.source >= PeerSource::Manual)
+        {
+            return;
+        }
+
+        warn!("Banning peer {}:{}: {}", address.ip, address.port, reason);
+        if let Err(e) =
+            address_manager.ban(address.ip, Duration::from_secs(config.ban_duration_secs))
+        {
+            error!("Failed to ban peer {}: {}", address.ip, e);
+        }
+    }
+
+    /// Ask the running crawler to stop; `start` returns once it has drained
     pub async fn shutdown(&self) {
         let _ = self.quit_tx.send(()).await;
     }
@@ -360,8 +816,13 @@ impl Clone for Crawler {
         Self {
             address_manager: self.address_manager.clone(),
             net_adapters: self.net_adapters.clone(),
+            adapter_balancer: self.adapter_balancer.clone(),
+            in_flight: self.in_flight.clone(),
+            dns_seeding: self.dns_seeding.clone(),
             config: self.config.clone(),
             quit_tx: self.quit_tx.clone(),
+            quit_rx: self.quit_rx.clone(),
+            audit_log: self.audit_log.clone(),
             semaphore: self.semaphore.clone(),
             stats: self.stats.clone(),
         }
@@ -369,6 +830,11 @@ impl Clone for Crawler {
 }
 
 impl Crawler {
+    /// Shared handle to the live performance statistics
+    pub fn shared_stats(&self) -> Arc<Mutex<CrawlerPerformanceStats>> {
+        self.stats.clone()
+    }
+
     /// Get performance statistics
     pub async fn get_performance_stats(&self) -> CrawlerPerformanceStats {
         let stats = self.stats.lock().await;
@@ -396,6 +862,211 @@ impl Crawler {
     }
 }
 
+/// Addresses with a poll in progress
+#[derive(Debug, Default)]
+pub struct InFlightPolls {
+    addresses: DashSet<String>,
+}
+
+impl InFlightPolls {
+    /// Mark a peer as being polled, or `None` if a poll is already running.
+    /// The peer is released when the returned claim is dropped.
+    pub fn claim(self: &Arc<Self>, address: &NetAddress) -> Option<InFlightClaim> {
+        let key = format!("{}:{}", address.ip, address.port);
+        self.addresses.insert(key.clone()).then(|| InFlightClaim {
+            polls: self.clone(),
+            key,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+}
+
+/// A peer's slot in [`InFlightPolls`], released on drop
+#[derive(Debug)]
+pub struct InFlightClaim {
+    polls: Arc<InFlightPolls>,
+    key: String,
+}
+
+impl Drop for InFlightClaim {
+    fn drop(&mut self) {
+        self.polls.addresses.remove(&self.key);
+    }
+}
+
+/// Allows a single DNS seeding run at a time
+#[derive(Debug, Default)]
+pub struct SeedingGuard {
+    running: AtomicBool,
+}
+
+impl SeedingGuard {
+    /// Start a seeding run, or `None` if one is already running. The run
+    /// ends when the returned handle is dropped.
+    pub fn try_begin(self: &Arc<Self>) -> Option<SeedingRun> {
+        self.running
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+            .then(|| SeedingRun {
+                guard: self.clone(),
+            })
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Acquire)
+    }
+}
+
+/// An active run held in [`SeedingGuard`], ended on drop
+#[derive(Debug)]
+pub struct SeedingRun {
+    guard: Arc<SeedingGuard>,
+}
+
+impl Drop for SeedingRun {
+    fn drop(&mut self) {
+        self.guard.running.store(false, Ordering::Release);
+    }
+}
+
+/// Moving success rate of one network adapter
+#[derive(Debug, Clone)]
+struct AdapterHealth {
+    success_rate: f64,
+    /// Smooth weighted round-robin credit
+    credit: f64,
+}
+
+/// Routes polls across network adapters in proportion to their recent
+/// success rates, using smooth weighted round-robin so the split is even
+/// over short runs. Adapters start out equally healthy.
+#[derive(Debug)]
+pub struct AdapterBalancer {
+    adapters: Mutex<Vec<AdapterHealth>>,
+}
+
+impl AdapterBalancer {
+    pub fn new(adapter_count: usize) -> Self {
+        Self {
+            adapters: Mutex::new(vec![
+                AdapterHealth {
+                    success_rate: 1.0,
+                    credit: 0.0,
+                };
+                adapter_count.max(1)
+            ]),
+        }
+    }
+
+    /// Index of the adapter that should carry the next poll
+    pub async fn pick(&self) -> usize {
+        let mut adapters = self.adapters.lock().await;
+        let mut total = 0.0;
+        for adapter in adapters.iter_mut() {
+            let weight = adapter.success_rate.max(MIN_ADAPTER_WEIGHT);
+            adapter.credit += weight;
+            total += weight;
+        }
+
+        let mut best = 0;
+        for (i, adapter) in adapters.iter().enumerate() {
+            if adapter.credit > adapters[best].credit {
+                best = i;
+            }
+        }
+        adapters[best].credit -= total;
+        best
+    }
+
+    /// Record whether a poll through an adapter got through
+    pub async fn record(&self, index: usize, success: bool) {
+        let mut adapters = self.adapters.lock().await;
+        if let Some(adapter) = adapters.get_mut(index) {
+            let outcome = if success { 1.0 } else { 0.0 };
+            adapter.success_rate += ADAPTER_HEALTH_SMOOTHING * (outcome - adapter.success_rate);
+        }
+    }
+
+    /// Recent success rate of each adapter
+    pub async fn success_rates(&self) -> Vec<f64> {
+        self.adapters
+            .lock()
+            .await
+            .iter()
+            .map(|adapter| adapter.success_rate)
+            .collect()
+    }
+}
+
+/// Schedule for re-verifying a snapshot of nodes evenly across a window
+#[derive(Debug)]
+pub struct ReachabilitySweep {
+    window: Duration,
+    max_per_minute: u32,
+    pending: VecDeque<NetAddress>,
+    total: usize,
+    last_elapsed: Duration,
+    /// Polls allowed by the rate limit but not yet used, capped at one minute's worth
+    allowance: f64,
+}
+
+impl ReachabilitySweep {
+    pub fn new(nodes: Vec<NetAddress>, window: Duration, max_per_minute: u32) -> Self {
+        Self {
+            window,
+            max_per_minute,
+            total: nodes.len(),
+            pending: nodes.into(),
+            last_elapsed: Duration::ZERO,
+            allowance: 0.0,
+        }
+    }
+
+    /// Nodes due for a poll `elapsed` into the window, capped by the rate
+    /// limit for the time since the previous batch
+    pub fn next_batch(&mut self, elapsed: Duration) -> Vec<NetAddress> {
+        let progress = if self.window.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f64() / self.window.as_secs_f64()).min(1.0)
+        };
+        let target = (self.total as f64 * progress).ceil() as usize;
+        let done = self.total - self.pending.len();
+
+        let since_last = elapsed.saturating_sub(self.last_elapsed);
+        self.last_elapsed = elapsed;
+        self.allowance = (self.allowance
+            + self.max_per_minute as f64 * since_last.as_secs_f64() / 60.0)
+            .min(self.max_per_minute as f64);
+
+        let count = target
+            .saturating_sub(done)
+            .min(self.allowance.floor() as usize)
+            .min(self.pending.len());
+        self.allowance -= count as f64;
+        self.pending.drain(..count).collect()
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
 /// Crawler statistics
 #[derive(Debug, Clone, Default)]
 pub struct CrawlerStats {
@@ -438,3 +1109,443 @@ impl CrawlerStats {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kaspa_protocol::create_consensus_config;
+    use std::collections::HashSet;
+    use std::net::{IpAddr, Ipv4Addr, TcpListener};
+    use tempfile::TempDir;
+
+    fn nodes(count: u32) -> Vec<NetAddress> {
+        (0..count)
+            .map(|i| NetAddress::new(IpAddr::V4(Ipv4Addr::from(0x2d00_0000 + i)), 16111))
+            .collect()
+    }
+
+    #[test]
+    fn test_sweep_attempts_all_nodes_within_window() {
+        let window = Duration::from_secs(3600);
+        let mut sweep = ReachabilitySweep::new(nodes(500), window, 60);
+
+        let mut attempted = HashSet::new();
+        let mut elapsed = Duration::ZERO;
+        while elapsed <= window {
+            for address in sweep.next_batch(elapsed) {
+                // Each node is attempted exactly once per sweep
+                assert!(attempted.insert(address));
+            }
+            elapsed += SWEEP_TICK_INTERVAL;
+        }
+
+        assert!(sweep.is_complete());
+        assert_eq!(attempted.len(), 500);
+    }
+
+    #[test]
+    fn test_sweep_is_spread_across_window() {
+        let window = Duration::from_secs(3600);
+        let mut sweep = ReachabilitySweep::new(nodes(360), window, 1000);
+
+        // A quarter of the way in, roughly a quarter of the nodes are due
+        let first = sweep.next_batch(window / 4);
+        assert_eq!(first.len(), 90);
+        assert_eq!(sweep.remaining(), 270);
+    }
+
+    #[test]
+    fn test_sweep_respects_rate_limit() {
+        let window = Duration::from_secs(600);
+        let mut sweep = ReachabilitySweep::new(nodes(1000), window, 10);
+
+        let mut attempted = 0;
+        let mut elapsed = Duration::ZERO;
+        while elapsed <= window {
+            attempted += sweep.next_batch(elapsed).len();
+            elapsed += SWEEP_TICK_INTERVAL;
+        }
+
+        // 10 per minute over 10 minutes
+        assert_eq!(attempted, 100);
+        assert_eq!(sweep.remaining(), 900);
+    }
+
+    #[tokio::test]
+    async fn test_each_poll_writes_an_audit_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let audit_path = temp_dir.path().join("audit").join("polls.jsonl");
+        let audit_log = Arc::new(AuditLog::open(audit_path.to_str().unwrap()).unwrap());
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        let net_adapter =
+            Arc::new(DnsseedNetAdapter::new(create_consensus_config(false, 0)).unwrap());
+        let config = Arc::new(Config::default());
+
+        // Listeners that never accept, so both polls fail. They stay bound
+        // for the whole test so no other socket can take their ports.
+        let listeners: Vec<TcpListener> = (0..2)
+            .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+            .collect();
+        let peers: Vec<NetAddress> = listeners
+            .iter()
+            .map(|listener| {
+                let port = listener.local_addr().unwrap().port();
+                NetAddress::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port)
+            })
+            .collect();
+
+        let stats = Arc::new(Mutex::new(CrawlerPerformanceStats::default()));
+        for peer in &peers {
+            let result = Crawler::poll_single_peer(
+                net_adapter.clone(),
+                peer.clone(),
+                address_manager.clone(),
+                config.clone(),
+                Some(audit_log.clone()),
+                stats.clone(),
+            )
+            .await;
+            assert!(result.is_err());
+        }
+
+        // Failed polls are counted too
+        let stats = stats.lock().await;
+        assert_eq!(stats.total_polls, 2);
+        assert_eq!(stats.failed_polls, 2);
+        assert_eq!(stats.successful_polls, 0);
+
+        let content = std::fs::read_to_string(&audit_path).unwrap();
+        let records: Vec<PollRecord> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), peers.len());
+        for (record, peer) in records.iter().zip(&peers) {
+            assert_eq!(record.peer, format!("127.0.0.1:{}", peer.port));
+            assert_eq!(record.outcome, PollOutcome::Failed);
+            assert!(record.timestamp > 0);
+            assert!(record.reason.is_some());
+            assert_eq!(record.protocol_version, None);
+            assert_eq!(record.addresses_received, 0);
+        }
+        assert!(content.contains("\"outcome\":\"failed\""));
+        drop(listeners);
+    }
+
+    #[tokio::test]
+    async fn test_polls_shift_toward_healthier_adapter() {
+        let balancer = AdapterBalancer::new(2);
+        let picks = async |count: usize| {
+            let mut picks = Vec::new();
+            for _ in 0..count {
+                picks.push(balancer.pick().await);
+            }
+            picks
+        };
+
+        // Equally healthy adapters split polls evenly
+        let first = picks(10).await;
+        assert_eq!(first.iter().filter(|&&i| i == 0).count(), 5);
+
+        // Adapter 1 keeps failing while adapter 0 gets through
+        let mut recent = [0; 2];
+        for round in 0..300 {
+            let index = balancer.pick().await;
+            balancer.record(index, index == 0).await;
+            if round >= 200 {
+                recent[index] += 1;
+            }
+        }
+        let rates = balancer.success_rates().await;
+        assert!(rates[0] > 0.99);
+        assert!(rates[1] < 0.1);
+        assert!(recent[0] >= 90, "{:?}", recent);
+        // The failing adapter still gets an occasional poll to detect recovery
+        assert!(recent[1] > 0, "{:?}", recent);
+
+        // Once it recovers it wins its share back
+        for _ in 0..200 {
+            let index = balancer.pick().await;
+            balancer.record(index, true).await;
+        }
+        let last = picks(10).await;
+        let recovered = last.iter().filter(|&&i| i == 1).count();
+        assert!(recovered >= 3, "{:?}", last);
+    }
+
+    #[test]
+    fn test_overlapping_batches_skip_peers_in_flight() {
+        let in_flight = Arc::new(InFlightPolls::default());
+        let peers = nodes(4);
+
+        // The first batch claims peers 0-2 and keeps polling them
+        let first: Vec<_> = peers[..3]
+            .iter()
+            .filter_map(|peer| in_flight.claim(peer))
+            .collect();
+        assert_eq!(first.len(), 3);
+
+        // The next batch overlaps it; only the new peer is dispatched
+        let second: Vec<_> = peers[1..]
+            .iter()
+            .filter_map(|peer| in_flight.claim(peer))
+            .collect();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].key, format!("{}:{}", peers[3].ip, peers[3].port));
+        assert_eq!(in_flight.len(), 4);
+
+        // Finished polls free their peers for later batches
+        drop(first);
+        assert_eq!(in_flight.len(), 1);
+        assert!(in_flight.claim(&peers[0]).is_some());
+        assert!(in_flight.claim(&peers[3]).is_none());
+        drop(second);
+        assert!(in_flight.is_empty());
+    }
+
+    #[test]
+    fn test_dns_reseed_follows_interval_and_disable_flag() {
+        let mut config = Config::new();
+        let last_seed = Instant::now();
+        let interval = Duration::from_secs(config.dns_reseed_interval_secs);
+
+        assert!(!Crawler::dns_reseed_due(&config, last_seed, last_seed));
+        let later = last_seed + interval;
+        assert!(Crawler::dns_reseed_due(&config, last_seed, later));
+
+        config.disable_dns_seeding = true;
+        assert!(!Crawler::dns_reseed_due(&config, last_seed, later));
+
+        config.disable_dns_seeding = false;
+        config.dns_reseed_interval_secs = 0;
+        assert!(!Crawler::dns_reseed_due(&config, last_seed, later));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_bootstraps_share_one_resolution() {
+        let guard = Arc::new(SeedingGuard::default());
+        let cycles = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let guard = guard.clone();
+                let cycles = cycles.clone();
+                tokio::spawn(async move {
+                    if let Some(_run) = guard.try_begin() {
+                        cycles.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                    }
+                })
+            })
+            .collect();
+        futures::future::join_all(tasks).await;
+
+        assert_eq!(cycles.load(Ordering::SeqCst), 1);
+        assert!(!guard.is_running());
+
+        // Once the run finishes the next trigger resolves again
+        assert!(guard.try_begin().is_some());
+    }
+
+    #[test]
+    fn test_cap_peer_addresses() {
+        let addresses: Vec<NetAddress> = (0..1500u16)
+            .map(|i| {
+                NetAddress::new(
+                    IpAddr::V4(Ipv4Addr::new(45, 0, (i >> 8) as u8, i as u8)),
+                    16111,
+                )
+            })
+            .collect();
+
+        let mut capped = addresses.clone();
+        assert_eq!(Crawler::cap_peer_addresses(&mut capped, 1000), 500);
+        assert_eq!(capped, addresses[..1000]);
+
+        let mut under_limit = addresses[..10].to_vec();
+        assert_eq!(Crawler::cap_peer_addresses(&mut under_limit, 1000), 0);
+        assert_eq!(under_limit.len(), 10);
+    }
+
+    #[test]
+    fn test_verify_peer_network_rejects_other_networks() {
+        let mut config = Config::new();
+        assert!(Crawler::verify_peer_network(&config, "kaspa-mainnet").is_ok());
+        for advertised in ["kaspa-testnet-10", ""] {
+            assert!(matches!(
+                Crawler::verify_peer_network(&config, advertised),
+                Err(KaseederError::NetworkMismatch(_))
+            ));
+        }
+
+        config.testnet = true;
+        config.net_suffix = 10;
+        assert!(Crawler::verify_peer_network(&config, "kaspa-testnet-10").is_ok());
+        assert!(Crawler::verify_peer_network(&config, "kaspa-mainnet").is_err());
+
+        config.verify_peer_network = false;
+        assert!(Crawler::verify_peer_network(&config, "kaspa-mainnet").is_ok());
+    }
+
+    #[test]
+    fn test_record_poll_averages_all_polls() {
+        let mut stats = CrawlerPerformanceStats::default();
+        stats.record_poll(Duration::from_millis(100), true, 30);
+        stats.record_poll(Duration::from_millis(300), false, 0);
+        assert_eq!(stats.average_poll_time_ms, 200.0);
+
+        stats.record_poll(Duration::from_millis(800), true, 12);
+        assert_eq!(stats.total_polls, 3);
+        assert_eq!(stats.successful_polls, 2);
+        assert_eq!(stats.failed_polls, 1);
+        assert_eq!(stats.total_addresses_found, 42);
+        assert!((stats.average_poll_time_ms - 400.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_crawler_and_closes_adapters() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        let mut config = Config::new();
+        config.threads = 1;
+        config.disable_dns_seeding = true;
+        let shared_config = Arc::new(std::sync::RwLock::new(config));
+
+        let mut crawler = Crawler::new(
+            address_manager,
+            crate::kaspa_protocol::create_consensus_config(false, 0),
+            shared_config,
+        )
+        .unwrap();
+        let control = crawler.clone();
+        let handle = tokio::spawn(async move { crawler.start().await });
+
+        // The crawler idles on an empty address store until told to stop
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        control.shutdown().await;
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("crawler should stop after shutdown")
+            .unwrap()
+            .unwrap();
+        assert!(control.semaphore.is_closed());
+
+        // The quit receiver was taken by the first start
+        let mut control = control;
+        assert!(control.start().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_private_known_peers_need_accept_unroutable() {
+        let private_peer = NetAddress::new("192.168.1.10".parse().unwrap(), 16111);
+
+        for accept_unroutable in [false, true] {
+            let temp_dir = TempDir::new().unwrap();
+            let app_dir = temp_dir.path().to_string_lossy().to_string();
+            let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+            let mut config = Config::new();
+            config.known_peers = Some("192.168.1.10:16111".to_string());
+            config.accept_unroutable = accept_unroutable;
+
+            let crawler = Crawler::new(
+                address_manager.clone(),
+                create_consensus_config(false, 0),
+                Arc::new(std::sync::RwLock::new(config)),
+            )
+            .unwrap();
+            crawler.initialize_known_peers().await.unwrap();
+
+            assert_eq!(
+                address_manager.get_node(&private_peer).is_some(),
+                accept_unroutable
+            );
+            assert_eq!(
+                address_manager
+                    .good_addresses(1, true, None)
+                    .contains(&private_peer),
+                accept_unroutable
+            );
+        }
+    }
+
+    /// A good-so-far node for the mock peer listening on `address`
+    fn mock_peer_node(address_manager: &AddressManager, address: &str) -> NetAddress {
+        let address: std::net::SocketAddr = address.parse().unwrap();
+        let node = NetAddress::new(address.ip(), address.port());
+        address_manager.add_addresses(vec![node.clone()], address.port(), true);
+        node
+    }
+
+    #[tokio::test]
+    async fn test_poll_checks_min_protocol_version_against_the_peer() {
+        use crate::netadapter::mock_peer::{self, MockPeer};
+
+        let (peer, peer_address) = mock_peer::serve(MockPeer {
+            protocol_version: 5,
+            ..Default::default()
+        });
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        let address = mock_peer_node(&address_manager, &peer_address);
+
+        let mut config = Config::new();
+        config.min_proto_ver = 6;
+        let net_adapter =
+            Arc::new(DnsseedNetAdapter::new(create_consensus_config(false, 0)).unwrap());
+        let mut record = PollRecord::new(&address);
+        let result = Crawler::poll_peer(
+            net_adapter.clone(),
+            address.clone(),
+            address_manager.clone(),
+            Arc::new(config),
+            &mut record,
+        )
+        .await;
+
+        // We offer a newer version, but the peer's own is what gets checked
+        assert!(matches!(result, Err(KaseederError::Validation(_))));
+        assert_eq!(record.protocol_version, Some(5));
+        assert!(address_manager.is_banned(&address.ip));
+
+        net_adapter.close().await;
+        peer.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_poll_stores_the_peer_protocol_version() {
+        use crate::netadapter::mock_peer::{self, MockPeer};
+
+        let (peer, peer_address) = mock_peer::serve(MockPeer {
+            protocol_version: 6,
+            ..Default::default()
+        });
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        let address = mock_peer_node(&address_manager, &peer_address);
+
+        let net_adapter =
+            Arc::new(DnsseedNetAdapter::new(create_consensus_config(false, 0)).unwrap());
+        let mut record = PollRecord::new(&address);
+        Crawler::poll_peer(
+            net_adapter.clone(),
+            address.clone(),
+            address_manager.clone(),
+            Arc::new(Config::new()),
+            &mut record,
+        )
+        .await
+        .unwrap();
+
+        // The node keeps the version the peer reported, not the one we offered
+        let node = address_manager.get_node(&address).unwrap();
+        assert_eq!(node.protocol_version, 6);
+        assert_eq!(record.protocol_version, Some(6));
+
+        net_adapter.close().await;
+        peer.close().await;
+    }
+}
@@ -1,18 +1,336 @@
+use crate::config::{Config, parse_listen_addrs};
+use crate::constants::{
+    DEFAULT_DNS_CACHE_TTL, DEFAULT_DNS_RATE_LIMIT, DEFAULT_DNS_RECORDS_PER_RESPONSE,
+    DEFAULT_EDNS_UDP_PAYLOAD_SIZE, DEFAULT_MAX_TCP_DNS_CONNECTIONS, DEFAULT_SOA_EXPIRE,
+    DEFAULT_SOA_MINIMUM, DEFAULT_SOA_REFRESH, DEFAULT_SOA_RETRY, DEFAULT_TCP_DNS_IDLE_TIMEOUT,
+    DNS_CACHE_SIZE, DNS_RATE_LIMIT_PRUNE_INTERVAL, MAX_CONCURRENT_DNS_REQUESTS,
+    MAX_TCP_RESPONSE_SIZE, MAX_UDP_RESPONSE_SIZE,
+};
+use crate::dns_records::{
+    make_a_record, make_aaaa_record, make_address_record, make_ns_record, make_soa_record,
+    make_srv_record, make_txt_record,
+};
 use crate::errors::{KaseederError, Result};
-use crate::manager::AddressManager;
+use crate::manager::{AddressManager, Node, TierWeights, tiered_shuffle, weighted_shuffle};
+use crate::monitor::SystemMonitor;
+use crate::types::NetAddress;
+use crate::version::build_info_txt;
+use dashmap::DashMap;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use socket2::{Domain, Protocol, Socket, Type};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::Semaphore;
 use tracing::{info, warn};
-use trust_dns_proto::op::{Message, MessageType, OpCode, ResponseCode};
+use trust_dns_proto::op::{Edns, Message, MessageType, OpCode, Query, ResponseCode};
 use trust_dns_proto::rr::{Name, RData, Record, RecordType};
 use trust_dns_proto::serialize::binary::{BinEncodable, BinEncoder};
 
+/// Address returned as the single AAAA answer when no IPv6 peer is available
+/// and the placeholder is enabled. It lies in the discard-only prefix
+/// `100::/64` (RFC 6666), so clients that try to connect to it never reach a
+/// host. The Go seeder always answered this way so that AAAA lookups never
+/// came back empty, which older stub resolvers treated as a failure and
+/// retried. Resolvers now cache empty answers (RFC 2308), while some take the
+/// placeholder for a real, unreachable address, so it is off by default.
+pub const AAAA_PLACEHOLDER: Ipv6Addr = Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 0);
+
+/// Difference between one self-query's answer and the address pool
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelfCheckReport {
+    pub query_type: Option<RecordType>,
+    /// Addresses served in the answer
+    pub served: usize,
+    /// Addresses the answer should have carried given the pool and record cap
+    pub expected: usize,
+    /// Served addresses that are not in the pool
+    pub unexpected: Vec<IpAddr>,
+}
+
+impl SelfCheckReport {
+    /// Compare the IPs answered for a query with the pool it was drawn from
+    fn compare(
+        query_type: RecordType,
+        pool: &[NetAddress],
+        answered: &[IpAddr],
+        max_records: usize,
+    ) -> Self {
+        let wanted = |ip: &IpAddr| match query_type {
+            RecordType::AAAA => ip.is_ipv6(),
+            _ => ip.is_ipv4(),
+        };
+        let pool: Vec<IpAddr> = pool
+            .iter()
+            .map(|address| address.ip)
+            .filter(wanted)
+            .collect();
+
+        Self {
+            query_type: Some(query_type),
+            served: answered.len(),
+            expected: pool.len().min(max_records),
+            unexpected: answered
+                .iter()
+                .filter(|ip| !pool.contains(ip))
+                .copied()
+                .collect(),
+        }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.served == self.expected && self.unexpected.is_empty()
+    }
+}
+
+/// Values published in the zone's SOA record
+#[derive(Debug, Clone)]
+pub struct SoaSettings {
+    /// Administrator mailbox, either as `user@domain` or in DNS form.
+    /// Defaults to `hostmaster.<hostname>` when unset.
+    pub mailbox: Option<String>,
+    pub refresh: u32,
+    pub retry: u32,
+    pub expire: u32,
+    pub minimum: u32,
+}
+
+impl Default for SoaSettings {
+    fn default() -> Self {
+        Self {
+            mailbox: None,
+            refresh: DEFAULT_SOA_REFRESH,
+            retry: DEFAULT_SOA_RETRY,
+            expire: DEFAULT_SOA_EXPIRE,
+            minimum: DEFAULT_SOA_MINIMUM,
+        }
+    }
+}
+
+/// An authoritative nameserver and the glue addresses published for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameserverEntry {
+    pub name: String,
+    pub addresses: Vec<IpAddr>,
+}
+
+impl FromStr for NameserverEntry {
+    type Err = String;
+
+    /// Parse `ns1.example.org` or `ns1.example.org=203.0.113.1,2001:db8::1`
+    fn from_str(entry: &str) -> std::result::Result<Self, Self::Err> {
+        let (name, addresses) = match entry.split_once('=') {
+            Some((name, addresses)) => (name.trim(), addresses),
+            None => (entry.trim(), ""),
+        };
+
+        if name.is_empty() {
+            return Err(format!("missing nameserver name in '{}'", entry));
+        }
+        Name::from_str(name).map_err(|e| format!("invalid nameserver name '{}': {}", name, e))?;
+
+        let addresses = addresses
+            .split(',')
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .map(|address| {
+                address
+                    .parse()
+                    .map_err(|_| format!("invalid glue address '{}' for {}", address, name))
+            })
+            .collect::<std::result::Result<Vec<IpAddr>, String>>()?;
+
+        let name = if name.ends_with('.') {
+            name.to_string()
+        } else {
+            format!("{}.", name)
+        };
+
+        Ok(Self { name, addresses })
+    }
+}
+
+/// Counters for DNS queries received by the server
+#[derive(Debug, Default)]
+pub struct DnsQueryStats {
+    /// Queries received per record type
+    pub queries: DashMap<RecordType, u64>,
+    /// Queries dropped by the per-source rate limit
+    pub rate_limited: AtomicU64,
+    /// Queries refused because their record type is disabled
+    pub refused: AtomicU64,
+    /// Queries for names outside the seeder's zone
+    pub nxdomain: AtomicU64,
+    /// AAAA answers that carried only [`AAAA_PLACEHOLDER`]
+    pub aaaa_placeholders: AtomicU64,
+    /// Self-queries whose answer did not match the address pool
+    pub self_check_divergences: AtomicU64,
+    /// Queries answered with SERVFAIL while draining
+    pub drained: AtomicU64,
+    /// Address answers reused from the answer cache
+    pub answer_cache_hits: AtomicU64,
+    /// Address answers selected from the address pool
+    pub answer_cache_misses: AtomicU64,
+}
+
+/// Maintenance switch shared by the DNS and gRPC servers. While draining the
+/// DNS server answers every query with SERVFAIL so resolvers fail over to
+/// other seeders, while crawling and gRPC keep running.
+#[derive(Debug, Default)]
+pub struct DrainState {
+    draining: AtomicBool,
+}
+
+impl DrainState {
+    pub fn new(draining: bool) -> Self {
+        Self {
+            draining: AtomicBool::new(draining),
+        }
+    }
+
+    /// Enter or leave drain mode, returning whether the server was draining
+    pub fn set_draining(&self, draining: bool) -> bool {
+        let previous = self.draining.swap(draining, Ordering::SeqCst);
+        if previous != draining {
+            if draining {
+                warn!("DNS server draining: answering all queries with SERVFAIL");
+            } else {
+                info!("DNS server leaving drain mode");
+            }
+        }
+        previous
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+}
+
+/// Create a socket for `addr`, IPv6-only for IPv6 addresses so an IPv4 and
+/// an IPv6 wildcard can listen on the same port
+fn listen_socket(addr: SocketAddr, socket_type: Type, protocol: Protocol) -> Result<Socket> {
+    let socket = Socket::new(Domain::for_address(addr), socket_type, Some(protocol))?;
+    if addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+/// Bind a UDP socket for DNS queries
+fn bind_udp(addr: SocketAddr) -> Result<UdpSocket> {
+    let socket = listen_socket(addr, Type::DGRAM, Protocol::UDP)?;
+    socket.bind(&addr.into())?;
+    Ok(UdpSocket::from_std(socket.into())?)
+}
+
+/// Bind a TCP listener for DNS-over-TCP
+fn bind_tcp(addr: SocketAddr) -> Result<TcpListener> {
+    let socket = listen_socket(addr, Type::STREAM, Protocol::TCP)?;
+    // Match tokio's own bind so a restarted server can reuse the port at once
+    socket.set_reuse_address(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    Ok(TcpListener::from_std(socket.into())?)
+}
+
+/// Name an answer points at, whose address records in the additional
+/// section are its glue
+fn glue_target(record: &Record) -> Option<Name> {
+    match record.data() {
+        Some(RData::SRV(srv)) => Some(srv.target().clone()),
+        Some(RData::NS(ns)) => Some(ns.0.clone()),
+        _ => None,
+    }
+}
+
+/// Peer filters requested through the query name
+#[derive(Debug, Clone, Copy)]
+struct AnswerFilter<'a> {
+    include_all_subnetworks: bool,
+    subnetwork_id: Option<&'a str>,
+    required_services: u64,
+    /// Query made by the self-check: selected fresh from the pool rather
+    /// than the answer cache, and left out of the query stats
+    self_check: bool,
+}
+
+/// Selected addresses per query name and type, reused while fresh so bursts
+/// of identical queries skip the scan of the address pool
+#[derive(Debug, Default)]
+struct AnswerCache {
+    /// Query name and type to when the answer was selected and its addresses
+    entries: DashMap<(String, u16), CachedAnswer>,
+}
+
+type CachedAnswer = (Instant, Arc<Vec<NetAddress>>);
+
+impl AnswerCache {
+    fn get(&self, key: &(String, u16), ttl: Duration) -> Option<Arc<Vec<NetAddress>>> {
+        self.entries
+            .get(key)
+            .filter(|entry| entry.0.elapsed() < ttl)
+            .map(|entry| entry.1.clone())
+    }
+
+    /// Store an answer, dropping expired entries once the cache is full so
+    /// arbitrary query names cannot grow it without bound
+    fn insert(&self, key: (String, u16), addresses: Arc<Vec<NetAddress>>, ttl: Duration) {
+        if self.entries.len() >= DNS_CACHE_SIZE {
+            self.entries.retain(|_, entry| entry.0.elapsed() < ttl);
+            if self.entries.len() >= DNS_CACHE_SIZE {
+                self.entries.clear();
+            }
+        }
+        self.entries.insert(key, (Instant::now(), addresses));
+    }
+}
+
+/// Answer settings a running server picks up on reload, shared by its clones
+#[derive(Debug)]
+struct LiveSettings {
+    ttl: AtomicU32,
+    /// Milliseconds a selected answer is reused (0 disables the cache)
+    answer_cache_ttl_ms: AtomicU64,
+    max_records: AtomicUsize,
+    disabled_query_types: RwLock<Vec<RecordType>>,
+    aaaa_placeholder: AtomicBool,
+}
+
 /// DNS server implementation
+#[derive(Clone)]
 pub struct DnsServer {
     hostname: String,
     nameserver: String,
+    /// Nameservers returned for NS queries, with their glue addresses
+    nameservers: Vec<NameserverEntry>,
     listen: String,
+    enable_srv: bool,
+    /// RNG used to shuffle answers so load spreads across the peer set
+    rng: Arc<Mutex<StdRng>>,
+    /// Weight each good node is sampled into answers with
+    selection_weight: fn(&Node, SystemTime) -> f64,
+    /// Blend answers across quality tiers instead of sampling by weight alone
+    tier_weights: Option<TierWeights>,
+    max_tcp_connections: usize,
+    tcp_idle_timeout: Duration,
+    /// UDP payload size advertised to EDNS0 clients
+    edns_udp_payload_size: u16,
+    rate_limiter: Arc<RateLimiter>,
+    soa: SoaSettings,
+    live: Arc<LiveSettings>,
+    self_check_interval: Option<Duration>,
+    monitor: Option<Arc<SystemMonitor>>,
+    query_stats: Arc<DnsQueryStats>,
+    answer_cache: Arc<AnswerCache>,
+    drain: Arc<DrainState>,
+    /// Name answering TXT queries with build info, and the text served
+    version_txt: Option<(String, String)>,
     address_manager: Arc<AddressManager>,
 }
 
@@ -22,6 +340,7 @@ impl DnsServer {
         hostname: String,
         nameserver: String,
         listen: String,
+        ttl: u32,
         address_manager: Arc<AddressManager>,
     ) -> Self {
         // Ensure hostname and nameserver end with dot (like Go version)
@@ -37,39 +356,303 @@ impl DnsServer {
             nameserver
         };
 
+        let nameservers = vec![NameserverEntry {
+            name: nameserver.clone(),
+            addresses: Vec::new(),
+        }];
+
         Self {
             hostname,
             nameserver,
+            nameservers,
             listen,
+            enable_srv: false,
+            rng: Arc::new(Mutex::new(StdRng::from_entropy())),
+            selection_weight: Node::selection_weight,
+            tier_weights: None,
+            max_tcp_connections: DEFAULT_MAX_TCP_DNS_CONNECTIONS,
+            tcp_idle_timeout: DEFAULT_TCP_DNS_IDLE_TIMEOUT,
+            edns_udp_payload_size: DEFAULT_EDNS_UDP_PAYLOAD_SIZE,
+            rate_limiter: Arc::new(RateLimiter::new(DEFAULT_DNS_RATE_LIMIT)),
+            soa: SoaSettings::default(),
+            live: Arc::new(LiveSettings {
+                ttl: AtomicU32::new(ttl),
+                answer_cache_ttl_ms: AtomicU64::new(DEFAULT_DNS_CACHE_TTL.as_millis() as u64),
+                max_records: AtomicUsize::new(DEFAULT_DNS_RECORDS_PER_RESPONSE),
+                disabled_query_types: RwLock::new(Vec::new()),
+                aaaa_placeholder: AtomicBool::new(false),
+            }),
+            self_check_interval: None,
+            monitor: None,
+            query_stats: Arc::new(DnsQueryStats::default()),
+            answer_cache: Arc::new(AnswerCache::default()),
+            drain: Arc::new(DrainState::default()),
+            version_txt: None,
             address_manager,
         }
     }
 
+    /// How long the addresses selected for a query are reused for identical
+    /// queries. Zero selects afresh for every query.
+    pub fn with_answer_cache_ttl(self, ttl: Duration) -> Self {
+        self.live
+            .answer_cache_ttl_ms
+            .store(ttl.as_millis() as u64, Ordering::Relaxed);
+        self
+    }
+
+    /// Enable answering SRV queries with each peer's actual port
+    pub fn with_srv(mut self, enabled: bool) -> Self {
+        self.enable_srv = enabled;
+        self
+    }
+
+    /// Set the maximum number of address records returned per response
+    pub fn with_max_records(self, max_records: usize) -> Self {
+        self.live.max_records.store(max_records, Ordering::Relaxed);
+        self
+    }
+
+    /// Use a deterministically seeded RNG for answer shuffling
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(StdRng::seed_from_u64(seed)));
+        self
+    }
+
+    /// Weigh nodes sampled into answers with `weight` instead of
+    /// [`Node::selection_weight`], e.g. a constant for a uniform shuffle
+    pub fn with_selection_weight(mut self, weight: fn(&Node, SystemTime) -> f64) -> Self {
+        self.selection_weight = weight;
+        self
+    }
+
+    /// Fill answers from quality tiers in the given proportions
+    pub fn with_tier_weights(mut self, tier_weights: Option<TierWeights>) -> Self {
+        self.tier_weights = tier_weights;
+        self
+    }
+
+    /// Limit concurrent DNS-over-TCP connections and close idle ones.
+    /// A limit of 0 disables the TCP listener.
+    pub fn with_tcp_limits(mut self, max_connections: usize, idle_timeout: Duration) -> Self {
+        self.max_tcp_connections = max_connections;
+        self.tcp_idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Limit queries per second from each source IP (0 disables limiting)
+    pub fn with_rate_limit(self, queries_per_second: u32) -> Self {
+        self.rate_limiter.set_rate(queries_per_second);
+        self
+    }
+
+    /// Set the UDP payload size advertised in EDNS0 OPT records. UDP answers
+    /// to EDNS0 clients may grow up to the smaller of this and the client's
+    /// own size before being truncated.
+    pub fn with_edns_udp_payload_size(mut self, size: u16) -> Self {
+        self.edns_udp_payload_size = size.max(MAX_UDP_RESPONSE_SIZE as u16);
+        self
+    }
+
+    /// Answer TXT queries for `<label>.<hostname>` with the build version,
+    /// git commit and the given network, for auditing which build a seeder
+    /// runs. `None` disables the record.
+    pub fn with_version_txt(mut self, label: Option<String>, network: &str) -> Self {
+        self.version_txt = label.filter(|label| !label.is_empty()).map(|label| {
+            (
+                format!("{}.{}", label, self.hostname).to_ascii_lowercase(),
+                build_info_txt(network),
+            )
+        });
+        self
+    }
+
+    /// Set the mailbox and timers published in the SOA record
+    pub fn with_soa(mut self, soa: SoaSettings) -> Self {
+        self.soa = soa;
+        self
+    }
+
+    /// Refuse queries for the given record types
+    pub fn with_disabled_query_types(self, query_types: Vec<RecordType>) -> Self {
+        *self
+            .live
+            .disabled_query_types
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = query_types;
+        self
+    }
+
+    /// Answer AAAA queries with [`AAAA_PLACEHOLDER`] when the IPv6 pool is
+    /// empty, instead of an empty authoritative answer
+    pub fn with_aaaa_placeholder(self, enabled: bool) -> Self {
+        self.live.aaaa_placeholder.store(enabled, Ordering::Relaxed);
+        self
+    }
+
+    /// Periodically query this server for its own zone and compare the
+    /// answers with the address pool (`None` disables the check)
+    pub fn with_self_check(mut self, interval: Option<Duration>) -> Self {
+        self.self_check_interval = interval;
+        self
+    }
+
+    /// Record query timings into the given system monitor
+    pub fn with_monitor(mut self, monitor: Arc<SystemMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Answer NS queries with these nameservers and their glue records
+    /// instead of the single configured nameserver
+    pub fn with_nameservers(mut self, nameservers: Vec<NameserverEntry>) -> Self {
+        if !nameservers.is_empty() {
+            self.nameservers = nameservers;
+        }
+        self
+    }
+
+    /// Share a drain switch, e.g. with the gRPC server that toggles it
+    pub fn with_drain_state(mut self, drain: Arc<DrainState>) -> Self {
+        self.drain = drain;
+        self
+    }
+
+    /// Counters for the queries this server has received
+    pub fn query_stats(&self) -> Arc<DnsQueryStats> {
+        self.query_stats.clone()
+    }
+
+    /// Switch that puts this server into drain mode
+    pub fn drain_state(&self) -> Arc<DrainState> {
+        self.drain.clone()
+    }
+
+    /// Apply the hot-reloadable DNS settings of a reloaded configuration: the
+    /// TTL, answer cache TTL, record and rate limits, disabled query types and
+    /// AAAA placeholder. Takes effect for the next query on every listener.
+    pub fn reload(&self, config: &Config) {
+        self.live.ttl.store(config.dns_ttl, Ordering::Relaxed);
+        self.live
+            .answer_cache_ttl_ms
+            .store(config.dns_cache_ttl * 1000, Ordering::Relaxed);
+        self.live
+            .max_records
+            .store(config.max_dns_records, Ordering::Relaxed);
+        self.rate_limiter.set_rate(config.dns_rate_limit);
+        *self
+            .live
+            .disabled_query_types
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = config.disabled_record_types();
+        self.live
+            .aaaa_placeholder
+            .store(config.aaaa_placeholder, Ordering::Relaxed);
+    }
+
+    fn ttl(&self) -> u32 {
+        self.live.ttl.load(Ordering::Relaxed)
+    }
+
+    fn max_records(&self) -> usize {
+        self.live.max_records.load(Ordering::Relaxed)
+    }
+
+    fn is_disabled(&self, query_type: RecordType) -> bool {
+        self.live
+            .disabled_query_types
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(&query_type)
+    }
+
     /// Start the DNS server
     pub async fn start(&self) -> Result<()> {
         info!("Starting DNS server on {}", self.listen);
 
-        // Parse listen address
-        let socket_addr: SocketAddr = self
-            .listen
-            .parse()
-            .map_err(|_| KaseederError::Dns(format!("Invalid listen address: {}", self.listen)))?;
-
-        // Use tokio async UDP socket
-        let socket = if socket_addr.is_ipv4() {
-            tokio::net::UdpSocket::bind(&self.listen).await?
-        } else {
-            // If IPv6 address provided, force IPv4 binding on the same port
-            let ipv4_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), socket_addr.port());
-            tokio::net::UdpSocket::bind(&ipv4_addr).await?
-        };
+        // Bind every address before serving any, so a bad one fails startup
+        let mut udp_sockets = Vec::new();
+        let mut tcp_listeners = Vec::new();
+        for bind_addr in parse_listen_addrs(&self.listen)? {
+            // Serve DNS-over-TCP alongside UDP so truncated answers can be retried
+            if self.max_tcp_connections > 0 {
+                let listener = bind_tcp(bind_addr)?;
+                info!("DNS TCP listener bound to: {}", listener.local_addr()?);
+                tcp_listeners.push(listener);
+            }
 
-        // Verify binding success (like Go version)
-        let actual_addr = socket.local_addr()?;
-        info!("DNS server actually bound to: {}", actual_addr);
+            let socket = bind_udp(bind_addr)?;
+            // Verify binding success (like Go version)
+            info!("DNS server actually bound to: {}", socket.local_addr()?);
+            udp_sockets.push(socket);
+        }
         info!("DNS server successfully bound to {}", self.listen);
+
+        self.serve(udp_sockets, tcp_listeners).await
+    }
+
+    /// Answer queries on already bound sockets, returning once every UDP
+    /// listener has stopped
+    async fn serve(
+        &self,
+        udp_sockets: Vec<UdpSocket>,
+        tcp_listeners: Vec<TcpListener>,
+    ) -> Result<()> {
+        // Periodically drop idle rate limiter buckets so memory stays bounded
+        let rate_limiter = self.rate_limiter.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DNS_RATE_LIMIT_PRUNE_INTERVAL);
+            loop {
+                interval.tick().await;
+                rate_limiter.prune(DNS_RATE_LIMIT_PRUNE_INTERVAL);
+            }
+        });
+
+        // Cross-check served answers against the address pool
+        if let Some(period) = self.self_check_interval {
+            let server = self.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(period);
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = server.self_check().await {
+                        warn!("DNS self-check failed: {}", e);
+                    }
+                }
+            });
+        }
+
+        for listener in tcp_listeners {
+            let server = self.clone();
+            tokio::spawn(async move {
+                server.serve_tcp(listener).await;
+            });
+        }
+
+        // Bound the number of in-flight requests across all listeners so a
+        // burst of queries cannot spawn an unbounded number of tasks
+        let workers = Arc::new(Semaphore::new(MAX_CONCURRENT_DNS_REQUESTS));
         info!("DNS server is now listening for requests");
 
+        let listeners: Vec<_> = udp_sockets
+            .into_iter()
+            .map(|socket| {
+                let server = self.clone();
+                let workers = workers.clone();
+                tokio::spawn(async move { server.serve_udp(socket, workers).await })
+            })
+            .collect();
+        for listener in listeners {
+            listener
+                .await
+                .map_err(|e| KaseederError::Dns(format!("DNS listener failed: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Answer queries arriving on one UDP socket
+    async fn serve_udp(&self, socket: UdpSocket, workers: Arc<Semaphore>) {
         let mut buffer = [0u8; 512];
         let socket = Arc::new(socket);
 
@@ -79,21 +662,23 @@ impl DnsServer {
                 Ok((len, src_addr)) => {
                     let request_data = buffer[..len].to_vec(); // Clone the data
 
+                    let permit = match workers.clone().acquire_owned().await {
+                        Ok(permit) => permit,
+                        Err(e) => {
+                            warn!("DNS worker pool closed: {}", e);
+                            break;
+                        }
+                    };
+
                     // Handle DNS request asynchronously (like Go version)
-                    let address_manager = self.address_manager.clone();
-                    let hostname = self.hostname.clone();
-                    let nameserver = self.nameserver.clone();
+                    let server = self.clone();
                     let socket_clone = socket.clone();
 
                     tokio::spawn(async move {
-                        if let Ok(response_data) = Self::handle_dns_request_static(
-                            &request_data,
-                            &src_addr,
-                            &address_manager,
-                            &hostname,
-                            &nameserver,
-                        )
-                        .await
+                        let _permit = permit;
+                        if let Ok(response_data) = server
+                            .handle_dns_request(&request_data, &src_addr, MAX_UDP_RESPONSE_SIZE)
+                            .await
                         {
                             info!(
                                 "Attempting to send {} bytes to {}",
@@ -118,13 +703,99 @@ impl DnsServer {
         }
     }
 
-    /// Handle DNS request (static method for async spawn)
-    async fn handle_dns_request_static(
+    /// Accept DNS-over-TCP connections, refusing new ones once the limit is reached
+    async fn serve_tcp(&self, listener: TcpListener) {
+        let connections = Arc::new(Semaphore::new(self.max_tcp_connections));
+
+        loop {
+            let (stream, src_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("DNS TCP accept error: {}", e);
+                    continue;
+                }
+            };
+
+            let permit = match connections.clone().try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => {
+                    warn!(
+                        "{}: refusing DNS TCP connection, limit of {} reached",
+                        src_addr, self.max_tcp_connections
+                    );
+                    drop(stream);
+                    continue;
+                }
+            };
+
+            let server = self.clone();
+            tokio::spawn(async move {
+                let _permit = permit;
+                if let Err(e) = server.handle_tcp_connection(stream, src_addr).await {
+                    warn!("{}: DNS TCP connection error: {}", src_addr, e);
+                }
+            });
+        }
+    }
+
+    /// Serve length-prefixed DNS messages on a TCP connection until it is
+    /// closed or stays idle longer than the idle timeout
+    async fn handle_tcp_connection(
+        &self,
+        mut stream: TcpStream,
+        src_addr: SocketAddr,
+    ) -> Result<()> {
+        loop {
+            let mut length = [0u8; 2];
+            match tokio::time::timeout(self.tcp_idle_timeout, stream.read_exact(&mut length)).await
+            {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+                Ok(Err(e)) => return Err(e.into()),
+                Err(_) => {
+                    info!("{}: closing idle DNS TCP connection", src_addr);
+                    return Ok(());
+                }
+            }
+
+            let mut request_data = vec![0u8; u16::from_be_bytes(length) as usize];
+            tokio::time::timeout(self.tcp_idle_timeout, stream.read_exact(&mut request_data))
+                .await??;
+
+            let response_data = self
+                .handle_dns_request(&request_data, &src_addr, MAX_TCP_RESPONSE_SIZE)
+                .await?;
+
+            stream
+                .write_all(&(response_data.len() as u16).to_be_bytes())
+                .await?;
+            stream.write_all(&response_data).await?;
+        }
+    }
+
+    /// Handle a single DNS request, reporting its response time to the monitor
+    async fn handle_dns_request(
+        &self,
         request_data: &[u8],
         src_addr: &SocketAddr,
-        address_manager: &Arc<AddressManager>,
-        hostname: &str,
-        nameserver: &str,
+        max_response_size: usize,
+    ) -> Result<Vec<u8>> {
+        let started = Instant::now();
+        let result = self
+            .answer_dns_request(request_data, src_addr, max_response_size)
+            .await;
+        if let Some(ref monitor) = self.monitor {
+            monitor.record_dns_query(started.elapsed()).await;
+        }
+        result
+    }
+
+    /// Build the serialized response for a single DNS request
+    async fn answer_dns_request(
+        &self,
+        request_data: &[u8],
+        src_addr: &SocketAddr,
+        max_response_size: usize,
     ) -> Result<Vec<u8>> {
         // Parse DNS message
         let request = match Message::from_vec(request_data) {
@@ -135,6 +806,15 @@ impl DnsServer {
             }
         };
 
+        // Throttle abusive sources before doing any real work
+        if !self.rate_limiter.check(src_addr.ip()) {
+            warn!("{}: rate limit exceeded", src_addr);
+            self.query_stats
+                .rate_limited
+                .fetch_add(1, Ordering::Relaxed);
+            return self.error_response(&request, ResponseCode::Refused);
+        }
+
         // Validate message type
         if request.header().message_type() != MessageType::Query {
             warn!("{}: not a query message", src_addr);
@@ -146,6 +826,20 @@ impl DnsServer {
             return Err(KaseederError::Dns("Not a standard query".to_string()));
         }
 
+        // Fail fast during maintenance so resolvers move on to other seeders
+        if self.drain.is_draining() {
+            self.query_stats.drained.fetch_add(1, Ordering::Relaxed);
+            return self.error_response(&request, ResponseCode::ServFail);
+        }
+
+        // EDNS0 clients accept UDP answers up to the negotiated payload size
+        let max_response_size = match request.extensions() {
+            Some(edns) => max_response_size.max(usize::from(
+                edns.max_payload().min(self.edns_udp_payload_size),
+            )),
+            None => max_response_size,
+        };
+
         // Get the first query from the message (like Go version)
         let query = match request.query() {
             Some(q) => q,
@@ -159,41 +853,162 @@ impl DnsServer {
         let query_type = query.query_type();
 
         info!("{}: query {} for {}", src_addr, query_type, domain_name);
+        *self.query_stats.queries.entry(query_type).or_insert(0) += 1;
+
+        // Refuse query types the operator has switched off
+        if self.is_disabled(query_type) {
+            warn!("{}: query type {} is disabled", src_addr, query_type);
+            self.query_stats.refused.fetch_add(1, Ordering::Relaxed);
+            return self.error_response(&request, ResponseCode::Refused);
+        }
 
         // Validate domain name (like Go version)
-        if !Self::is_our_domain(domain_name, hostname) {
+        // Answer NXDOMAIN right away so resolvers fail fast instead of timing out
+        if !Self::is_our_domain(domain_name, &self.hostname) {
             warn!("{}: invalid name: {}", src_addr, domain_name);
-            return Err(KaseederError::Dns(format!("Invalid name: {}", domain_name)));
+            self.query_stats.nxdomain.fetch_add(1, Ordering::Relaxed);
+            return self.error_response(&request, ResponseCode::NXDomain);
         }
 
         // Extract subnetwork ID (like Go version)
         let (subnetwork_id, include_all_subnetworks) =
-            Self::extract_subnetwork_id(domain_name, hostname)?;
+            Self::extract_subnetwork_id(domain_name, &self.hostname)?;
+
+        // Extract requested service flags (like Go version)
+        let required_services = Self::extract_service_filter(domain_name, &self.hostname);
 
         info!(
-            "{}: query {} for subnetwork ID {:?}, include_all: {}",
-            src_addr, query_type, subnetwork_id, include_all_subnetworks
+            "{}: query {} for subnetwork ID {:?}, include_all: {}, services: {}",
+            src_addr, query_type, subnetwork_id, include_all_subnetworks, required_services
         );
 
         // Build DNS response (like Go version)
-        let response_data = Self::build_dns_response(
-            &request,
-            domain_name,
-            query_type,
-            include_all_subnetworks,
-            subnetwork_id.as_deref(),
-            nameserver,
-            address_manager,
-        )
-        .await?;
+        let response_data = self
+            .build_dns_response(
+                &request,
+                domain_name,
+                query_type,
+                AnswerFilter {
+                    include_all_subnetworks,
+                    subnetwork_id: subnetwork_id.as_deref(),
+                    required_services,
+                    self_check: false,
+                },
+                max_response_size,
+            )
+            .await?;
 
         Ok(response_data)
     }
 
+    /// Build A and AAAA answers for the zone the way queries are answered
+    /// and compare them with the address pool. The answers are selected
+    /// fresh rather than taken from the answer cache, and bypass the rate
+    /// limiter and query stats. Divergences are logged and counted in
+    /// [`DnsQueryStats::self_check_divergences`].
+    pub async fn self_check(&self) -> Result<Vec<SelfCheckReport>> {
+        let zone = Name::from_str(&self.hostname)?;
+        let filter = AnswerFilter {
+            include_all_subnetworks: true,
+            subnetwork_id: None,
+            required_services: 0,
+            self_check: true,
+        };
+        let mut reports = Vec::new();
+
+        for query_type in [RecordType::A, RecordType::AAAA] {
+            if self.is_disabled(query_type) {
+                continue;
+            }
+
+            let mut request = Message::new();
+            request.set_message_type(MessageType::Query);
+            request.set_op_code(OpCode::Query);
+            request.add_query(Query::query(zone.clone(), query_type));
+
+            // TCP-sized so truncation is not mistaken for a divergence
+            let data = self
+                .build_dns_response(&request, &zone, query_type, filter, MAX_TCP_RESPONSE_SIZE)
+                .await?;
+            let response = Message::from_vec(&data)?;
+
+            let answered: Vec<IpAddr> = response
+                .answers()
+                .iter()
+                .filter_map(|record| match record.data() {
+                    Some(RData::A(a)) => Some(IpAddr::V4(a.0)),
+                    Some(RData::AAAA(aaaa)) if aaaa.0 != AAAA_PLACEHOLDER => {
+                        Some(IpAddr::V6(aaaa.0))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let pool = self
+                .address_manager
+                .good_addresses(u16::from(query_type), true, None);
+
+            let report = SelfCheckReport::compare(query_type, &pool, &answered, self.max_records());
+            self.record_self_check(&report);
+            reports.push(report);
+        }
+
+        Ok(reports)
+    }
+
+    /// Log and count a self-check result that does not match the pool
+    fn record_self_check(&self, report: &SelfCheckReport) {
+        if report.is_consistent() {
+            return;
+        }
+        warn!(
+            "DNS self-check divergence for {:?}: served {} of {} expected, {} not in the pool: {:?}",
+            report.query_type,
+            report.served,
+            report.expected,
+            report.unexpected.len(),
+            report.unexpected
+        );
+        self.query_stats
+            .self_check_divergences
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Check if domain is our domain (like Go version)
     fn is_our_domain(domain_name: &Name, hostname: &str) -> bool {
-        let domain_str = domain_name.to_string();
-        domain_str.ends_with(hostname)
+        let domain_str = domain_name.to_string().to_ascii_lowercase();
+        let hostname = hostname.to_ascii_lowercase();
+
+        // Match the zone itself or a subdomain on a label boundary
+        domain_str == hostname || domain_str.ends_with(&format!(".{}", hostname))
+    }
+
+    /// OPT record answering an EDNS0 request, advertising our UDP payload size
+    fn response_edns(&self, request: &Message) -> Option<Edns> {
+        request.extensions().as_ref().map(|_| {
+            let mut edns = Edns::new();
+            edns.set_max_payload(self.edns_udp_payload_size);
+            edns
+        })
+    }
+
+    /// Build an empty response carrying only the given response code
+    fn error_response(&self, request: &Message, response_code: ResponseCode) -> Result<Vec<u8>> {
+        let mut response = Message::new();
+        response.set_id(request.header().id());
+        response.set_message_type(MessageType::Response);
+        response.set_op_code(OpCode::Query);
+        response.set_response_code(response_code);
+        response.set_recursion_desired(request.header().recursion_desired());
+        response.set_recursion_available(false);
+
+        if let Some(query) = request.query() {
+            response.add_query(query.clone());
+        }
+        if let Some(edns) = self.response_edns(request) {
+            response.set_edns(edns);
+        }
+
+        Self::serialize_response(&response)
     }
 
     /// Extract subnetwork ID from domain name (like Go version)
@@ -218,15 +1033,30 @@ impl DnsServer {
         Ok((None, true))
     }
 
+    /// Extract required service flags from a leading numeric label,
+    /// e.g. `1.seed.kaspa.org` asks only for peers advertising service bit 1
+    fn extract_service_filter(domain_name: &Name, hostname: &str) -> u64 {
+        let domain_str = domain_name.to_string();
+        if domain_str.eq_ignore_ascii_case(hostname) {
+            return 0;
+        }
+
+        domain_str
+            .split('.')
+            .next()
+            .filter(|label| !label.is_empty() && label.chars().all(|c| c.is_ascii_digit()))
+            .and_then(|label| label.parse().ok())
+            .unwrap_or(0)
+    }
+
     /// Build DNS response (like Go version)
     async fn build_dns_response(
+        &self,
         request: &Message,
         domain_name: &Name,
         query_type: RecordType,
-        include_all_subnetworks: bool,
-        subnetwork_id: Option<&str>,
-        nameserver: &str,
-        address_manager: &Arc<AddressManager>,
+        filter: AnswerFilter<'_>,
+        max_response_size: usize,
     ) -> Result<Vec<u8>> {
         // Create response message
         let mut response = Message::new();
@@ -242,33 +1072,32 @@ impl DnsServer {
         if let Some(query) = request.query() {
             response.add_query(query.clone());
         }
+        if let Some(edns) = self.response_edns(request) {
+            response.set_edns(edns);
+        }
 
         // Handle based on query type (like Go version)
         match query_type {
             RecordType::A => {
-                Self::handle_a_query(
-                    &mut response,
-                    domain_name,
-                    include_all_subnetworks,
-                    subnetwork_id,
-                    nameserver,
-                    address_manager,
-                )
-                .await?;
+                self.handle_a_query(&mut response, domain_name, filter)
+                    .await?;
             }
             RecordType::AAAA => {
-                Self::handle_aaaa_query(
-                    &mut response,
-                    domain_name,
-                    include_all_subnetworks,
-                    subnetwork_id,
-                    nameserver,
-                    address_manager,
-                )
-                .await?;
+                self.handle_aaaa_query(&mut response, domain_name, filter)
+                    .await?;
             }
             RecordType::NS => {
-                Self::handle_ns_query(&mut response, domain_name, nameserver).await?;
+                self.handle_ns_query(&mut response, domain_name).await?;
+            }
+            RecordType::SOA => {
+                self.handle_soa_query(&mut response, domain_name).await?;
+            }
+            RecordType::TXT => {
+                self.handle_txt_query(&mut response, domain_name).await?;
+            }
+            RecordType::SRV if self.enable_srv => {
+                self.handle_srv_query(&mut response, domain_name, filter)
+                    .await?;
             }
             _ => {
                 // Unsupported query type
@@ -277,9 +1106,28 @@ impl DnsServer {
         }
 
         // Serialize response (like Go version)
-        let mut buffer = Vec::new();
-        let mut encoder = BinEncoder::new(&mut buffer);
-        response.emit(&mut encoder)?;
+        let mut buffer = Self::serialize_response(&response)?;
+
+        // Trim answers that do not fit in a UDP datagram and set the TC bit
+        // so resolvers can retry over TCP
+        if buffer.len() > max_response_size {
+            response.set_truncated(true);
+            while buffer.len() > max_response_size {
+                let mut answers = response.take_answers();
+                let Some(dropped) = answers.pop() else {
+                    break;
+                };
+                response.insert_answers(answers);
+
+                // Glue goes with its answer, so every target left still resolves
+                if let Some(target) = glue_target(&dropped) {
+                    let mut additionals = response.take_additionals();
+                    additionals.retain(|record| record.name() != &target);
+                    response.insert_additionals(additionals);
+                }
+                buffer = Self::serialize_response(&response)?;
+            }
+        }
 
         info!(
             "Response serialized: {} bytes, {} answers, {} authorities",
@@ -291,113 +1139,1455 @@ impl DnsServer {
         Ok(buffer)
     }
 
-    /// Handle A record query (like Go version)
-    async fn handle_a_query(
-        response: &mut Message,
-        domain_name: &Name,
-        include_all_subnetworks: bool,
-        subnetwork_id: Option<&str>,
-        nameserver: &str,
-        address_manager: &Arc<AddressManager>,
-    ) -> Result<()> {
-        let addresses = address_manager.good_addresses(
-            1, // A record type
-            include_all_subnetworks,
-            subnetwork_id,
-        );
+    /// Order good nodes for an answer as a weighted random sample, favoring
+    /// fresh and reliable peers while still spreading load across the set
+    fn select_addresses(&self, mut nodes: Vec<Node>) -> Vec<NetAddress> {
+        // Start from a canonical order so a seeded RNG gives reproducible
+        // answers regardless of map iteration order
+        nodes.sort_unstable_by_key(|node| (node.address.ip, node.address.port));
+        let now = SystemTime::now();
+        let weight = self.selection_weight;
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        match self.tier_weights {
+            Some(ref tier_weights) => tiered_shuffle(&mut nodes, &mut *rng, tier_weights, now),
+            None => weighted_shuffle(&mut nodes, &mut *rng, |node| weight(node, now)),
+        }
 
-        info!("Sending {} IPv4 addresses", addresses.len());
+        nodes.into_iter().map(|node| node.address).collect()
+    }
 
-        // Add authority record (like Go version)
-        let authority_name = Name::from_str(nameserver)?;
-        let authority_record = Record::from_rdata(
-            domain_name.clone(),
-            86400, // TTL
-            RData::NS(trust_dns_proto::rr::rdata::NS(authority_name)),
-        );
-        response.add_name_server(authority_record);
+    /// Addresses to answer a query with, reused from the answer cache while
+    /// fresh so the pool is scanned and shuffled at most once per cache TTL
+    /// for each query name and type
+    fn answer_addresses(
+        &self,
+        domain_name: &Name,
+        qtype: u16,
+        filter: AnswerFilter<'_>,
+    ) -> Arc<Vec<NetAddress>> {
+        let select = || {
+            Arc::new(
+                self.select_addresses(self.address_manager.good_nodes_filtered(
+                    qtype,
+                    filter.include_all_subnetworks,
+                    filter.subnetwork_id,
+                    filter.required_services,
+                )),
+            )
+        };
+        if filter.self_check {
+            return select();
+        }
 
-        // Add A records
-        for address in addresses.iter().take(8) {
+        let select = || {
+            self.query_stats
+                .answer_cache_misses
+                .fetch_add(1, Ordering::Relaxed);
+            select()
+        };
+
+        let ttl = Duration::from_millis(self.live.answer_cache_ttl_ms.load(Ordering::Relaxed));
+        if ttl.is_zero() {
+            return select();
+        }
+
+        let key = (domain_name.to_lowercase().to_string(), qtype);
+        if let Some(addresses) = self.answer_cache.get(&key, ttl) {
+            self.query_stats
+                .answer_cache_hits
+                .fetch_add(1, Ordering::Relaxed);
+            return addresses;
+        }
+        let addresses = select();
+        self.answer_cache.insert(key, addresses.clone(), ttl);
+        addresses
+    }
+
+    /// Serialize a DNS message to wire format
+    fn serialize_response(response: &Message) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        let mut encoder = BinEncoder::new(&mut buffer);
+        response.emit(&mut encoder)?;
+        Ok(buffer)
+    }
+
+    /// Handle A record query (like Go version)
+    async fn handle_a_query(
+        &self,
+        response: &mut Message,
+        domain_name: &Name,
+        filter: AnswerFilter<'_>,
+    ) -> Result<()> {
+        if let Some(ip) = self.srv_target_ip(domain_name) {
+            return self.answer_srv_target(response, domain_name, ip, RecordType::A);
+        }
+
+        let addresses = self.answer_addresses(domain_name, 1, filter); // A record type
+
+        info!("Sending {} IPv4 addresses", addresses.len());
+
+        // Add A records
+        for address in addresses.iter().take(self.max_records()) {
             if let IpAddr::V4(ipv4) = address.ip {
-                let record = Record::from_rdata(
-                    domain_name.clone(),
-                    30, // TTL
-                    RData::A(trust_dns_proto::rr::rdata::A(ipv4)),
-                );
-                response.add_answer(record);
+                response.add_answer(make_a_record(domain_name.clone(), self.ttl(), ipv4));
             }
         }
 
-        Ok(())
+        self.add_authority(response, domain_name)
     }
 
     /// Handle AAAA record query (like Go version)
     async fn handle_aaaa_query(
+        &self,
         response: &mut Message,
         domain_name: &Name,
-        include_all_subnetworks: bool,
-        subnetwork_id: Option<&str>,
-        nameserver: &str,
-        address_manager: &Arc<AddressManager>,
+        filter: AnswerFilter<'_>,
     ) -> Result<()> {
-        let addresses = address_manager.good_addresses(
-            28, // AAAA record type
-            include_all_subnetworks,
-            subnetwork_id,
-        );
+        if let Some(ip) = self.srv_target_ip(domain_name) {
+            return self.answer_srv_target(response, domain_name, ip, RecordType::AAAA);
+        }
 
-        info!("Sending {} IPv6 addresses", addresses.len());
+        let addresses = self.answer_addresses(domain_name, 28, filter); // AAAA record type
 
-        // Add authority record (like Go version)
-        let authority_name = Name::from_str(nameserver)?;
-        let authority_record = Record::from_rdata(
-            domain_name.clone(),
-            86400, // TTL
-            RData::NS(trust_dns_proto::rr::rdata::NS(authority_name)),
-        );
-        response.add_name_server(authority_record);
+        info!("Sending {} IPv6 addresses", addresses.len());
 
         // Add AAAA records
-        for address in addresses.iter().take(8) {
+        let mut answered = 0;
+        for address in addresses.iter().take(self.max_records()) {
             if let IpAddr::V6(ipv6) = address.ip {
-                let record = Record::from_rdata(
-                    domain_name.clone(),
-                    30, // TTL
-                    RData::AAAA(trust_dns_proto::rr::rdata::AAAA(ipv6)),
-                );
-                response.add_answer(record);
+                response.add_answer(make_aaaa_record(domain_name.clone(), self.ttl(), ipv6));
+                answered += 1;
             }
         }
 
-        // If no IPv6 addresses, add a placeholder (like Go version)
-        if addresses.is_empty() {
-            let placeholder_ip = Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 0);
-            let record = Record::from_rdata(
+        // Exactly one placeholder, and only when there is no IPv6 peer to serve
+        if answered == 0 && self.live.aaaa_placeholder.load(Ordering::Relaxed) {
+            response.add_answer(make_aaaa_record(
                 domain_name.clone(),
-                30, // TTL
-                RData::AAAA(trust_dns_proto::rr::rdata::AAAA(placeholder_ip)),
-            );
-            response.add_answer(record);
+                self.ttl(),
+                AAAA_PLACEHOLDER,
+            ));
+            if !filter.self_check {
+                self.query_stats
+                    .aaaa_placeholders
+                    .fetch_add(1, Ordering::Relaxed);
+            }
         }
 
+        self.add_authority(response, domain_name)
+    }
+
+    /// Authority section of an address answer: the zone's nameserver (like
+    /// Go version), or only the zone's SOA when the answer is empty
+    /// (RFC 2308) so resolvers can cache it as NODATA
+    fn add_authority(&self, response: &mut Message, domain_name: &Name) -> Result<()> {
+        if response.answers().is_empty() {
+            response.add_name_server(self.zone_soa_record()?);
+        } else {
+            let authority_name = Name::from_str(&self.nameserver)?;
+            response.add_name_server(make_ns_record(domain_name.clone(), 86400, authority_name));
+        }
         Ok(())
     }
 
     /// Handle NS record query (like Go version)
-    async fn handle_ns_query(
+    async fn handle_ns_query(&self, response: &mut Message, domain_name: &Name) -> Result<()> {
+        for nameserver in &self.nameservers {
+            let ns_name = Name::from_str(&nameserver.name)?;
+            response.add_answer(make_ns_record(domain_name.clone(), 86400, ns_name.clone()));
+
+            // Glue so resolvers can reach nameservers inside the zone
+            for address in &nameserver.addresses {
+                response.add_additional(make_address_record(ns_name.clone(), 86400, *address));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle SOA record query
+    ///
+    /// The zone apex gets the SOA as its answer; names below it get an
+    /// empty answer with the SOA in the authority section.
+    async fn handle_soa_query(&self, response: &mut Message, domain_name: &Name) -> Result<()> {
+        let zone = Name::from_str(&self.hostname)?;
+        let record = self.zone_soa_record()?;
+
+        if domain_name == &zone {
+            response.add_answer(record);
+        } else {
+            response.add_name_server(record);
+        }
+
+        Ok(())
+    }
+
+    /// SOA record of the seeder's zone
+    fn zone_soa_record(&self) -> Result<Record> {
+        Ok(make_soa_record(
+            Name::from_str(&self.hostname)?,
+            self.ttl(),
+            Name::from_str(&self.nameserver)?,
+            Name::from_str(&self.soa_mailbox())?,
+            self.address_manager.serial(),
+            &self.soa,
+        ))
+    }
+
+    /// Handle TXT record query with a one-line summary of the peer set, or
+    /// the build info when the version name is queried
+    async fn handle_txt_query(&self, response: &mut Message, domain_name: &Name) -> Result<()> {
+        if let Some((name, build_info)) = &self.version_txt
+            && domain_name.to_string().eq_ignore_ascii_case(name)
+        {
+            response.add_answer(make_txt_record(
+                domain_name.clone(),
+                self.ttl(),
+                vec![build_info.clone()],
+            ));
+            return Ok(());
+        }
+
+        let summary = self.address_manager.get_address_summary();
+
+        response.add_answer(make_txt_record(
+            domain_name.clone(),
+            self.ttl(),
+            vec![summary.to_string()],
+        ));
+
+        // Geographic spread of the good peers, when exposed
+        if let Some(counts) = self.address_manager.country_counts() {
+            let countries: Vec<String> = counts
+                .iter()
+                .map(|(country, count)| format!("{}={}", country, count))
+                .collect();
+            response.add_answer(make_txt_record(
+                domain_name.clone(),
+                self.ttl(),
+                vec![format!("countries {}", countries.join(" "))],
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Administrator mailbox in DNS form (`user@example.com` becomes `user.example.com.`)
+    fn soa_mailbox(&self) -> String {
+        let mailbox = match self.soa.mailbox {
+            Some(ref mailbox) => mailbox.replacen('@', ".", 1),
+            None => format!("hostmaster.{}", self.hostname),
+        };
+
+        if mailbox.ends_with('.') {
+            mailbox
+        } else {
+            format!("{}.", mailbox)
+        }
+    }
+
+    /// Handle SRV record query
+    ///
+    /// A/AAAA records carry no port, so clients assume the network default.
+    /// SRV answers carry the port each peer was actually reached on, with
+    /// the matching A/AAAA glue in the additional section.
+    async fn handle_srv_query(
+        &self,
         response: &mut Message,
         domain_name: &Name,
-        nameserver: &str,
+        filter: AnswerFilter<'_>,
     ) -> Result<()> {
-        let ns_name = Name::from_str(nameserver)?;
-        let record = Record::from_rdata(
-            domain_name.clone(),
-            86400, // TTL
-            RData::NS(trust_dns_proto::rr::rdata::NS(ns_name)),
+        let addresses = self.answer_addresses(domain_name, 33, filter); // SRV record type
+
+        info!(
+            "Sending {} SRV records",
+            addresses.len().min(self.max_records())
         );
-        response.add_answer(record);
+
+        for address in addresses.iter().take(self.max_records()) {
+            let target = Name::from_str(&format!(
+                "{}.{}",
+                Self::ip_label(&address.ip),
+                self.hostname
+            ))?;
+
+            response.add_answer(make_srv_record(
+                domain_name.clone(),
+                self.ttl(),
+                address.port,
+                target.clone(),
+            ));
+            response.add_additional(make_address_record(target, self.ttl(), address.ip));
+        }
+
+        // An empty answer gets the same SOA-only authority as A and AAAA
+        if response.answers().is_empty() {
+            response.add_name_server(self.zone_soa_record()?);
+        }
 
         Ok(())
     }
+
+    /// Peer address named by an SRV target, i.e. an [`ip_label`](Self::ip_label)
+    /// directly below the zone that encodes the IP of a stored peer
+    fn srv_target_ip(&self, domain_name: &Name) -> Option<IpAddr> {
+        let zone = Name::from_str(&self.hostname).ok()?;
+        if domain_name.num_labels() != zone.num_labels() + 1 || !zone.zone_of(domain_name) {
+            return None;
+        }
+
+        let label = std::str::from_utf8(domain_name.iter().next()?).ok()?;
+        let ip = Self::parse_ip_label(label)?;
+        self.address_manager.has_node_at(&ip).then_some(ip)
+    }
+
+    /// Answer an A or AAAA query for an SRV target with the address it
+    /// encodes, or an empty answer when the family does not match
+    fn answer_srv_target(
+        &self,
+        response: &mut Message,
+        domain_name: &Name,
+        ip: IpAddr,
+        query_type: RecordType,
+    ) -> Result<()> {
+        let matches_family = match query_type {
+            RecordType::A => ip.is_ipv4(),
+            _ => ip.is_ipv6(),
+        };
+        if matches_family {
+            response.add_answer(make_address_record(domain_name.clone(), self.ttl(), ip));
+        }
+        self.add_authority(response, domain_name)
+    }
+
+    /// Decode a label produced by [`ip_label`](Self::ip_label). Only the
+    /// canonical spelling is accepted, so each peer has exactly one name.
+    fn parse_ip_label(label: &str) -> Option<IpAddr> {
+        let parts: Vec<&str> = label.split('-').collect();
+        let ip = match parts.len() {
+            4 => {
+                let mut octets = [0u8; 4];
+                for (octet, part) in octets.iter_mut().zip(&parts) {
+                    *octet = part.parse().ok()?;
+                }
+                IpAddr::V4(Ipv4Addr::from(octets))
+            }
+            8 => {
+                let mut segments = [0u16; 8];
+                for (segment, part) in segments.iter_mut().zip(&parts) {
+                    *segment = u16::from_str_radix(part, 16).ok()?;
+                }
+                IpAddr::V6(Ipv6Addr::from(segments))
+            }
+            _ => return None,
+        };
+
+        (Self::ip_label(&ip) == label.to_ascii_lowercase()).then_some(ip)
+    }
+
+    /// Encode an IP address as a single DNS label for SRV targets
+    fn ip_label(ip: &IpAddr) -> String {
+        match ip {
+            IpAddr::V4(ipv4) => ipv4
+                .octets()
+                .iter()
+                .map(|octet| octet.to_string())
+                .collect::<Vec<_>>()
+                .join("-"),
+            IpAddr::V6(ipv6) => ipv6
+                .segments()
+                .iter()
+                .map(|segment| format!("{:x}", segment))
+                .collect::<Vec<_>>()
+                .join("-"),
+        }
+    }
+}
+
+/// Per-source token bucket limiter for DNS queries
+pub struct RateLimiter {
+    /// Tokens added per second, also the bucket capacity (0 disables limiting)
+    rate: AtomicU32,
+    buckets: DashMap<IpAddr, TokenBucket>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(queries_per_second: u32) -> Self {
+        Self {
+            rate: AtomicU32::new(queries_per_second),
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Change the per-source limit, keeping existing buckets
+    pub fn set_rate(&self, queries_per_second: u32) {
+        self.rate.store(queries_per_second, Ordering::Relaxed);
+    }
+
+    /// Take a token for a query from `ip`, returning false if it is over the limit
+    pub fn check(&self, ip: IpAddr) -> bool {
+        self.check_at(ip, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, now: Instant) -> bool {
+        let rate = self.rate.load(Ordering::Relaxed) as f64;
+        if rate == 0.0 {
+            return true;
+        }
+
+        let mut bucket = self.buckets.entry(ip).or_insert_with(|| TokenBucket {
+            tokens: rate,
+            last_refill: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(rate);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drop buckets for sources that have been quiet for longer than `idle`
+    pub fn prune(&self, idle: Duration) {
+        self.prune_at(idle, Instant::now());
+    }
+
+    fn prune_at(&self, idle: Duration, now: Instant) {
+        self.buckets
+            .retain(|_, bucket| now.saturating_duration_since(bucket.last_refill) < idle);
+    }
+
+    /// Number of sources currently tracked
+    pub fn tracked_sources(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MAX_GOOD_CONSECUTIVE_FAILURES;
+    use tempfile::TempDir;
+    use trust_dns_proto::rr::Record;
+
+    fn test_server(temp_dir: &TempDir) -> DnsServer {
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        DnsServer::new(
+            "seed.kaspa.org".to_string(),
+            "ns1.kaspa.org".to_string(),
+            "127.0.0.1:0".to_string(),
+            30,
+            address_manager,
+        )
+        .with_rate_limit(0)
+        .with_answer_cache_ttl(Duration::ZERO)
+    }
+
+    fn query(name: &str, query_type: RecordType) -> Message {
+        let mut request = Message::new();
+        request.set_id(1234);
+        request.set_message_type(MessageType::Query);
+        request.set_op_code(OpCode::Query);
+        request.add_query(Query::query(Name::from_str(name).unwrap(), query_type));
+        request
+    }
+
+    #[tokio::test]
+    async fn test_srv_answers_include_peer_ports() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_srv(true);
+
+        let default_peer = NetAddress::new("1.1.1.1".parse().unwrap(), 16111);
+        let custom_peer = NetAddress::new("8.8.8.8".parse().unwrap(), 17000);
+        server.address_manager.add_addresses(
+            vec![default_peer.clone(), custom_peer.clone()],
+            16111,
+            false,
+        );
+        server.address_manager.good(&default_peer, None, None);
+        server.address_manager.good(&custom_peer, None, None);
+
+        let request = query("_kaspa._tcp.seed.kaspa.org.", RecordType::SRV);
+        let data = server
+            .handle_dns_request(
+                &request.to_vec().unwrap(),
+                &"127.0.0.1:53".parse().unwrap(),
+                MAX_UDP_RESPONSE_SIZE,
+            )
+            .await
+            .unwrap();
+        let response = Message::from_vec(&data).unwrap();
+
+        let mut ports: Vec<(String, u16)> = response
+            .answers()
+            .iter()
+            .filter_map(|record| match record.data() {
+                Some(RData::SRV(srv)) => Some((srv.target().to_string(), srv.port())),
+                _ => None,
+            })
+            .collect();
+        ports.sort();
+
+        assert_eq!(
+            ports,
+            vec![
+                ("1-1-1-1.seed.kaspa.org.".to_string(), 16111),
+                ("8-8-8-8.seed.kaspa.org.".to_string(), 17000),
+            ]
+        );
+        assert_eq!(response.additionals().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_srv_targets_resolve_to_their_peer() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_srv(true);
+        add_good_peers(&server, 3);
+        let ipv6_peer = NetAddress::new("2001:db8::1".parse().unwrap(), 17000);
+        server
+            .address_manager
+            .add_addresses(vec![ipv6_peer.clone()], 16111, false);
+        server.address_manager.good(&ipv6_peer, None, None);
+
+        let (_, response) = resolve(&server, "45-1-1-2.seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(
+            answer_ips(&response),
+            vec!["45.1.1.2".parse::<IpAddr>().unwrap()]
+        );
+
+        let (_, response) = resolve(
+            &server,
+            "2001-db8-0-0-0-0-0-1.seed.kaspa.org.",
+            RecordType::AAAA,
+        )
+        .await;
+        assert_eq!(answer_ips(&response), vec![ipv6_peer.ip]);
+
+        // The other family has no address under a peer's name
+        let (_, response) = resolve(&server, "45-1-1-2.seed.kaspa.org.", RecordType::AAAA).await;
+        assert!(response.answers().is_empty());
+        assert_eq!(response.name_servers().len(), 1);
+        assert_eq!(response.name_servers()[0].record_type(), RecordType::SOA);
+
+        // Only canonical labels of stored peers are decoded
+        assert_eq!(
+            DnsServer::parse_ip_label("045-1-1-2"),
+            None,
+            "leading zeros are not canonical"
+        );
+        let (_, response) = resolve(&server, "9-9-9-9.seed.kaspa.org.", RecordType::A).await;
+        assert!(!answer_ips(&response).contains(&"9.9.9.9".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_truncated_srv_answer_keeps_glue_for_every_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_srv(true).with_max_records(40);
+        add_good_peers(&server, 40);
+
+        let (len, response) = resolve(&server, "seed.kaspa.org.", RecordType::SRV).await;
+
+        assert!(len <= MAX_UDP_RESPONSE_SIZE);
+        assert!(response.truncated());
+        assert!(!response.answers().is_empty());
+        let mut targets: Vec<Name> = response.answers().iter().filter_map(glue_target).collect();
+        let mut glue: Vec<Name> = response
+            .additionals()
+            .iter()
+            .map(|record| record.name().clone())
+            .collect();
+        targets.sort();
+        glue.sort();
+        assert_eq!(targets, glue);
+    }
+
+    #[tokio::test]
+    async fn test_srv_skips_peers_failing_since_their_last_success() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_srv(true);
+        let flaky = NetAddress::new("8.8.4.4".parse().unwrap(), 17000);
+        server
+            .address_manager
+            .add_addresses(vec![flaky.clone()], 16111, false);
+        server.address_manager.good(&flaky, None, None);
+        for _ in 0..MAX_GOOD_CONSECUTIVE_FAILURES {
+            server.address_manager.record_connection_result(
+                &flaky,
+                false,
+                Some("refused".to_string()),
+            );
+        }
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::SRV).await;
+
+        assert!(response.answers().is_empty());
+    }
+
+    fn add_good_peers(server: &DnsServer, count: u8) {
+        let peers: Vec<NetAddress> = (1..=count)
+            .map(|i| NetAddress::new(IpAddr::V4(Ipv4Addr::new(45, 1, 1, i)), 16111))
+            .collect();
+        server
+            .address_manager
+            .add_addresses(peers.clone(), 16111, false);
+        for peer in &peers {
+            server.address_manager.good(peer, None, None);
+        }
+    }
+
+    async fn resolve(server: &DnsServer, name: &str, query_type: RecordType) -> (usize, Message) {
+        let request = query(name, query_type);
+        let data = server
+            .handle_dns_request(
+                &request.to_vec().unwrap(),
+                &"127.0.0.1:53".parse().unwrap(),
+                MAX_UDP_RESPONSE_SIZE,
+            )
+            .await
+            .unwrap();
+        (data.len(), Message::from_vec(&data).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_max_records_limits_answers() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_max_records(4);
+        add_good_peers(&server, 20);
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+
+        assert_eq!(response.answers().len(), 4);
+        assert!(!response.truncated());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_truncated() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_max_records(33);
+        add_good_peers(&server, 40);
+
+        let (len, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+
+        assert!(len <= MAX_UDP_RESPONSE_SIZE);
+        assert!(response.truncated());
+        assert!(!response.answers().is_empty());
+        assert!(response.answers().len() < 33);
+    }
+
+    #[tokio::test]
+    async fn test_edns_response_advertises_configured_payload_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir)
+            .with_max_records(33)
+            .with_edns_udp_payload_size(1400);
+        add_good_peers(&server, 40);
+
+        let mut request = query("seed.kaspa.org.", RecordType::A);
+        let mut edns = Edns::new();
+        edns.set_max_payload(4096);
+        request.set_edns(edns);
+        let data = server
+            .handle_dns_request(
+                &request.to_vec().unwrap(),
+                &"127.0.0.1:53".parse().unwrap(),
+                MAX_UDP_RESPONSE_SIZE,
+            )
+            .await
+            .unwrap();
+        let response = Message::from_vec(&data).unwrap();
+
+        assert_eq!(response.extensions().as_ref().unwrap().max_payload(), 1400);
+        // The larger negotiated size fits every answer without truncation
+        assert!(data.len() > MAX_UDP_RESPONSE_SIZE && data.len() <= 1400);
+        assert!(!response.truncated());
+        assert_eq!(response.answers().len(), 33);
+
+        // Clients without EDNS0 get no OPT record and the classic limit
+        let (len, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert!(response.extensions().is_none());
+        assert!(len <= MAX_UDP_RESPONSE_SIZE);
+        assert!(response.truncated());
+    }
+
+    fn answer_ips(response: &Message) -> Vec<IpAddr> {
+        response
+            .answers()
+            .iter()
+            .filter_map(|record| match record.data() {
+                Some(RData::A(a)) => Some(IpAddr::V4(a.0)),
+                Some(RData::AAAA(aaaa)) => Some(IpAddr::V6(aaaa.0)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_selection_weight_override_orders_answers() {
+        let temp_dir = TempDir::new().unwrap();
+        // Weights growing steeply with the last octet, plus a fixed seed, pin the order
+        let server = test_server(&temp_dir)
+            .with_rng_seed(7)
+            .with_selection_weight(|node, _| match node.address.ip {
+                IpAddr::V4(ip) => 1e6_f64.powi(ip.octets()[3] as i32),
+                IpAddr::V6(_) => 1.0,
+            });
+        add_good_peers(&server, 5);
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(
+            answer_ips(&response),
+            (1..=5)
+                .rev()
+                .map(|i| IpAddr::V4(Ipv4Addr::new(45, 1, 1, i)))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_answers_are_shuffled_with_seeded_rng() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_rng_seed(7);
+        add_good_peers(&server, 20);
+
+        let replay_dir = TempDir::new().unwrap();
+        let replay = test_server(&replay_dir).with_rng_seed(7);
+        add_good_peers(&replay, 20);
+
+        let mut first_answers = std::collections::HashSet::new();
+        for _ in 0..10 {
+            let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+            let (_, replayed) = resolve(&replay, "seed.kaspa.org.", RecordType::A).await;
+
+            // The same seed and peer set yield the same order
+            assert_eq!(answer_ips(&response), answer_ips(&replayed));
+            first_answers.insert(answer_ips(&response)[0]);
+        }
+
+        // Load is spread: the first answer is not always the same peer
+        assert!(first_answers.len() > 1);
+    }
+
+    async fn tcp_query(stream: &mut TcpStream, name: &str) -> Message {
+        let request = query(name, RecordType::A).to_vec().unwrap();
+        stream
+            .write_all(&(request.len() as u16).to_be_bytes())
+            .await
+            .unwrap();
+        stream.write_all(&request).await.unwrap();
+
+        let mut length = [0u8; 2];
+        stream.read_exact(&mut length).await.unwrap();
+        let mut response = vec![0u8; u16::from_be_bytes(length) as usize];
+        stream.read_exact(&mut response).await.unwrap();
+        Message::from_vec(&response).unwrap()
+    }
+
+    async fn is_closed(stream: &mut TcpStream, within: Duration) -> bool {
+        let mut byte = [0u8; 1];
+        matches!(
+            tokio::time::timeout(within, stream.read(&mut byte)).await,
+            Ok(Ok(0)) | Ok(Err(_))
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tcp_connection_limit_and_idle_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_tcp_limits(2, Duration::from_millis(300));
+        add_good_peers(&server, 3);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let tcp_server = server.clone();
+        tokio::spawn(async move { tcp_server.serve_tcp(listener).await });
+
+        // Fill the connection limit; queries are answered over TCP
+        let mut first = TcpStream::connect(addr).await.unwrap();
+        let mut second = TcpStream::connect(addr).await.unwrap();
+        assert_eq!(
+            tcp_query(&mut first, "seed.kaspa.org.")
+                .await
+                .answers()
+                .len(),
+            3
+        );
+        assert_eq!(
+            tcp_query(&mut second, "seed.kaspa.org.")
+                .await
+                .answers()
+                .len(),
+            3
+        );
+
+        // Connections beyond the limit are refused
+        let mut extra = TcpStream::connect(addr).await.unwrap();
+        assert!(is_closed(&mut extra, Duration::from_millis(200)).await);
+
+        // Idle connections are closed after the timeout, freeing their slots
+        assert!(is_closed(&mut first, Duration::from_secs(2)).await);
+        assert!(is_closed(&mut second, Duration::from_secs(2)).await);
+
+        let mut fresh = TcpStream::connect(addr).await.unwrap();
+        assert_eq!(
+            tcp_query(&mut fresh, "seed.kaspa.org.")
+                .await
+                .answers()
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_foreign_domain_gets_nxdomain() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        add_good_peers(&server, 3);
+
+        for name in ["notours.example.", "evilseed.kaspa.org."] {
+            let (_, response) = resolve(&server, name, RecordType::A).await;
+            assert_eq!(response.response_code(), ResponseCode::NXDomain);
+            assert_eq!(response.id(), 1234);
+            assert!(response.answers().is_empty());
+        }
+
+        // Subdomains of our zone are still served
+        let (_, response) = resolve(&server, "n1.seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+    }
+
+    #[tokio::test]
+    async fn test_service_filter_label() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        add_good_peers(&server, 4);
+
+        let full_node = NetAddress::new(IpAddr::V4(Ipv4Addr::new(45, 1, 1, 1)), 16111);
+        server.address_manager.set_services(&full_node, 0b101);
+
+        let (_, response) = resolve(&server, "5.seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(answer_ips(&response), vec![full_node.ip]);
+
+        let (_, response) = resolve(&server, "2.seed.kaspa.org.", RecordType::A).await;
+        assert!(response.answers().is_empty());
+
+        // Without a filter label every peer is eligible
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(response.answers().len(), 4);
+    }
+
+    #[test]
+    fn test_extract_service_filter() {
+        let hostname = "seed.kaspa.org.";
+        let filter = |name: &str| {
+            DnsServer::extract_service_filter(&Name::from_str(name).unwrap(), hostname)
+        };
+
+        assert_eq!(filter("seed.kaspa.org."), 0);
+        assert_eq!(filter("1.seed.kaspa.org."), 1);
+        assert_eq!(filter("9.seed.kaspa.org."), 9);
+        assert_eq!(filter("n1.seed.kaspa.org."), 0);
+        assert_eq!(filter("x9.seed.kaspa.org."), 0);
+    }
+
+    #[test]
+    fn test_rate_limiter_token_bucket() {
+        let limiter = RateLimiter::new(3);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let other: IpAddr = "192.0.2.2".parse().unwrap();
+        let start = Instant::now();
+
+        // Burst up to the rate, then refuse
+        assert!(limiter.check_at(ip, start));
+        assert!(limiter.check_at(ip, start));
+        assert!(limiter.check_at(ip, start));
+        assert!(!limiter.check_at(ip, start));
+
+        // Other sources have their own bucket
+        assert!(limiter.check_at(other, start));
+
+        // Tokens refill over time
+        assert!(limiter.check_at(ip, start + Duration::from_millis(400)));
+        assert!(!limiter.check_at(ip, start + Duration::from_millis(400)));
+
+        // Idle buckets are pruned
+        limiter.prune_at(Duration::from_secs(60), start + Duration::from_secs(120));
+        assert_eq!(limiter.tracked_sources(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_queries_are_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_rate_limit(2);
+        add_good_peers(&server, 1);
+
+        for _ in 0..2 {
+            let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+            assert_eq!(response.response_code(), ResponseCode::NoError);
+        }
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(response.response_code(), ResponseCode::Refused);
+        assert!(response.answers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_srv_disabled_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+
+        let request = query("_kaspa._tcp.seed.kaspa.org.", RecordType::SRV);
+        let data = server
+            .handle_dns_request(
+                &request.to_vec().unwrap(),
+                &"127.0.0.1:53".parse().unwrap(),
+                MAX_UDP_RESPONSE_SIZE,
+            )
+            .await
+            .unwrap();
+        let response = Message::from_vec(&data).unwrap();
+
+        assert_eq!(response.response_code(), ResponseCode::ServFail);
+        assert!(response.answers().is_empty());
+    }
+
+    fn soa_of(record: &Record) -> trust_dns_proto::rr::rdata::SOA {
+        match record.data() {
+            Some(RData::SOA(soa)) => soa.clone(),
+            other => panic!("expected SOA record, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_soa_answer_and_serial_bumps_on_peer_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_soa(SoaSettings {
+            mailbox: Some("admin@kaspa.org".to_string()),
+            refresh: 3600,
+            ..SoaSettings::default()
+        });
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::SOA).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(response.answers().len(), 1);
+        let soa = soa_of(&response.answers()[0]);
+        assert_eq!(soa.mname(), &Name::from_str("ns1.kaspa.org.").unwrap());
+        assert_eq!(soa.rname(), &Name::from_str("admin.kaspa.org.").unwrap());
+        assert_eq!(soa.refresh(), 3600);
+        assert_eq!(soa.minimum(), DEFAULT_SOA_MINIMUM);
+        let initial_serial = soa.serial();
+
+        // Unchanged peer set keeps the serial
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::SOA).await;
+        assert_eq!(soa_of(&response.answers()[0]).serial(), initial_serial);
+
+        add_good_peers(&server, 2);
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::SOA).await;
+        assert!(soa_of(&response.answers()[0]).serial() > initial_serial);
+
+        // Names below the apex carry the SOA in the authority section
+        let (_, response) = resolve(&server, "n1.seed.kaspa.org.", RecordType::SOA).await;
+        assert!(response.answers().is_empty());
+        assert_eq!(response.name_servers().len(), 1);
+        assert_eq!(
+            soa_of(&response.name_servers()[0]).rname(),
+            &Name::from_str("admin.kaspa.org.").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version_txt_reports_build_info() {
+        let temp_dir = TempDir::new().unwrap();
+        let server =
+            test_server(&temp_dir).with_version_txt(Some("version".to_string()), "kaspa-mainnet");
+        add_good_peers(&server, 2);
+
+        let txt_of = |response: &Message| match response.answers()[0].data() {
+            Some(RData::TXT(txt)) => txt.to_string(),
+            other => panic!("expected TXT record, got {:?}", other),
+        };
+
+        let (_, response) = resolve(&server, "Version.seed.kaspa.org.", RecordType::TXT).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(response.answers().len(), 1);
+        assert_eq!(
+            txt_of(&response),
+            format!(
+                "kaseeder version={} commit={} network=kaspa-mainnet",
+                env!("CARGO_PKG_VERSION"),
+                crate::version::GIT_COMMIT
+            )
+        );
+
+        // The zone's TXT record still carries the peer summary
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::TXT).await;
+        assert!(txt_of(&response).starts_with("nodes="));
+
+        // Without a label the name is just another subdomain
+        let server = test_server(&temp_dir);
+        let (_, response) = resolve(&server, "version.seed.kaspa.org.", RecordType::TXT).await;
+        assert!(txt_of(&response).starts_with("nodes="));
+    }
+
+    #[tokio::test]
+    async fn test_txt_reports_address_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        add_good_peers(&server, 3);
+
+        // A gossiped IPv6 peer that has never been reached
+        let ipv6 = NetAddress::new("2001:db8::1".parse().unwrap(), 16111);
+        server
+            .address_manager
+            .add_addresses(vec![ipv6], 16111, true);
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::TXT).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(response.answers().len(), 1);
+
+        let text = match response.answers()[0].data() {
+            Some(RData::TXT(txt)) => txt.to_string(),
+            other => panic!("expected TXT record, got {:?}", other),
+        };
+        assert_eq!(text, "nodes=4 ipv4=3 ipv6=1 good=3");
+    }
+
+    #[tokio::test]
+    async fn test_aaaa_placeholder_only_for_empty_ipv6_pool() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_aaaa_placeholder(true);
+        add_good_peers(&server, 3);
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::AAAA).await;
+        assert_eq!(response.answers().len(), 1);
+        assert_eq!(
+            response.answers()[0].data(),
+            Some(&RData::AAAA(trust_dns_proto::rr::rdata::AAAA(
+                AAAA_PLACEHOLDER
+            )))
+        );
+        assert_eq!(
+            server.query_stats.aaaa_placeholders.load(Ordering::Relaxed),
+            1
+        );
+
+        // Real IPv6 peers replace the placeholder
+        let ipv6 = NetAddress::new("2001:db8::1".parse().unwrap(), 16111);
+        server
+            .address_manager
+            .add_addresses(vec![ipv6.clone()], 16111, false);
+        server.address_manager.good(&ipv6, None, None);
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::AAAA).await;
+        assert_eq!(response.answers().len(), 1);
+        assert_eq!(
+            response.answers()[0].data(),
+            Some(&RData::AAAA(trust_dns_proto::rr::rdata::AAAA(
+                "2001:db8::1".parse().unwrap()
+            )))
+        );
+        assert_eq!(
+            server.query_stats.aaaa_placeholders.load(Ordering::Relaxed),
+            1
+        );
+
+        // Disabled by default, an empty pool gets an empty authoritative
+        // answer with the zone's SOA for negative caching
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::AAAA).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert!(response.authoritative());
+        assert!(response.answers().is_empty());
+        assert_eq!(response.name_servers().len(), 1);
+        assert_eq!(response.name_servers()[0].record_type(), RecordType::SOA);
+        assert_eq!(
+            server.query_stats.aaaa_placeholders.load(Ordering::Relaxed),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_answers_carry_only_the_soa() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_srv(true);
+
+        // An empty pool answers every address type with the SOA alone
+        for query_type in [RecordType::A, RecordType::AAAA, RecordType::SRV] {
+            let (_, response) = resolve(&server, "seed.kaspa.org.", query_type).await;
+            assert_eq!(response.response_code(), ResponseCode::NoError);
+            assert!(response.answers().is_empty(), "{}", query_type);
+            assert_eq!(response.name_servers().len(), 1, "{}", query_type);
+            assert_eq!(
+                response.name_servers()[0].record_type(),
+                RecordType::SOA,
+                "{}",
+                query_type
+            );
+        }
+
+        // A non-empty answer names the zone's nameserver instead
+        add_good_peers(&server, 1);
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(response.answers().len(), 1);
+        assert_eq!(response.name_servers().len(), 1);
+        assert_eq!(response.name_servers()[0].record_type(), RecordType::NS);
+    }
+
+    #[tokio::test]
+    async fn test_self_check_detects_divergence() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_max_records(2);
+        add_good_peers(&server, 3);
+
+        // Healthy server: answers match the pool, placeholder ignored
+        let reports = server.self_check().await.unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(SelfCheckReport::is_consistent));
+        assert_eq!((reports[0].served, reports[0].expected), (2, 2));
+        assert_eq!(
+            server
+                .query_stats
+                .self_check_divergences
+                .load(Ordering::Relaxed),
+            0
+        );
+
+        // An answer carrying an address the pool does not have
+        let pool = server.address_manager.good_addresses(1, true, None);
+        let mut answered: Vec<IpAddr> = pool.iter().take(1).map(|address| address.ip).collect();
+        answered.push("80.0.0.1".parse().unwrap());
+        let report = SelfCheckReport::compare(RecordType::A, &pool, &answered, 2);
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.unexpected,
+            vec!["80.0.0.1".parse::<IpAddr>().unwrap()]
+        );
+
+        // An answer missing addresses the pool could have filled
+        let report = SelfCheckReport::compare(RecordType::A, &pool, &answered[..1], 2);
+        assert!(!report.is_consistent());
+
+        server.record_self_check(&report);
+        assert_eq!(
+            server
+                .query_stats
+                .self_check_divergences
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_self_check_bypasses_cache_rate_limit_and_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir)
+            .with_max_records(2)
+            .with_answer_cache_ttl(Duration::from_secs(60))
+            .with_rate_limit(1);
+        add_good_peers(&server, 3);
+
+        // Cache an answer, then take one of its peers out of the pool
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        let cached = answer_ips(&response);
+        assert_eq!(cached.len(), 2);
+        server
+            .address_manager
+            .ban(cached[0], Duration::from_secs(3600))
+            .unwrap();
+
+        let reports = server.self_check().await.unwrap();
+        assert!(reports.iter().all(SelfCheckReport::is_consistent));
+        assert_eq!(reports[0].served, 2);
+
+        // Only the client query was counted
+        let stats = &server.query_stats;
+        assert_eq!(
+            stats.queries.get(&RecordType::A).map(|count| *count),
+            Some(1)
+        );
+        assert!(stats.queries.get(&RecordType::AAAA).is_none());
+        assert_eq!(stats.answer_cache_misses.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.answer_cache_hits.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.aaaa_placeholders.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.rate_limited.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_query_types_are_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir)
+            .with_disabled_query_types(vec![RecordType::TXT, RecordType::AAAA]);
+        add_good_peers(&server, 2);
+
+        for query_type in [RecordType::TXT, RecordType::AAAA] {
+            let (_, response) = resolve(&server, "seed.kaspa.org.", query_type).await;
+            assert_eq!(response.response_code(), ResponseCode::Refused);
+            assert!(response.answers().is_empty());
+        }
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(response.answers().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ns_answer_lists_all_nameservers_with_glue() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir).with_nameservers(vec![
+            "ns1.seed.kaspa.org=203.0.113.1,2001:db8::53"
+                .parse()
+                .unwrap(),
+            "ns2.seed.kaspa.org.=203.0.113.2".parse().unwrap(),
+            "ns.other-provider.net".parse().unwrap(),
+        ]);
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::NS).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+
+        let nameservers: Vec<String> = response
+            .answers()
+            .iter()
+            .map(|record| match record.data() {
+                Some(RData::NS(ns)) => ns.0.to_string(),
+                other => panic!("expected NS record, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            nameservers,
+            vec![
+                "ns1.seed.kaspa.org.",
+                "ns2.seed.kaspa.org.",
+                "ns.other-provider.net."
+            ]
+        );
+
+        let glue: Vec<(String, IpAddr)> = response
+            .additionals()
+            .iter()
+            .map(|record| {
+                let ip = match record.data() {
+                    Some(RData::A(a)) => IpAddr::V4(a.0),
+                    Some(RData::AAAA(aaaa)) => IpAddr::V6(aaaa.0),
+                    other => panic!("expected glue record, got {:?}", other),
+                };
+                (record.name().to_string(), ip)
+            })
+            .collect();
+        assert_eq!(
+            glue,
+            vec![
+                (
+                    "ns1.seed.kaspa.org.".to_string(),
+                    "203.0.113.1".parse().unwrap()
+                ),
+                (
+                    "ns1.seed.kaspa.org.".to_string(),
+                    "2001:db8::53".parse().unwrap()
+                ),
+                (
+                    "ns2.seed.kaspa.org.".to_string(),
+                    "203.0.113.2".parse().unwrap()
+                ),
+            ]
+        );
+
+        assert!("=203.0.113.1".parse::<NameserverEntry>().is_err());
+        assert!(
+            "ns1.seed.kaspa.org=not-an-ip"
+                .parse::<NameserverEntry>()
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_drain_mode_answers_servfail_while_grpc_serves() {
+        use crate::grpc::kaseeder::health_check_response::Status as HealthStatus;
+        use crate::grpc::kaseeder::kaseeder_service_server::KaseederService;
+        use crate::grpc::kaseeder::{GetAddressesRequest, HealthCheckRequest, SetDrainModeRequest};
+        use crate::grpc::{ADMIN_TOKEN_METADATA, KaseederServiceImpl};
+        use tonic::Request;
+
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        add_good_peers(&server, 3);
+        let service = KaseederServiceImpl::new(server.address_manager.clone())
+            .with_drain_state(server.drain_state())
+            .with_admin_token(Some("secret".to_string()));
+        let set_drain_mode = |draining| {
+            let mut request = Request::new(SetDrainModeRequest { draining });
+            request
+                .metadata_mut()
+                .insert(ADMIN_TOKEN_METADATA, "secret".parse().unwrap());
+            service.set_drain_mode(request)
+        };
+
+        let drain = set_drain_mode(true).await.unwrap().into_inner();
+        assert!(drain.draining && !drain.was_draining);
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(response.response_code(), ResponseCode::ServFail);
+        assert!(response.answers().is_empty());
+        assert_eq!(server.query_stats().drained.load(Ordering::Relaxed), 1);
+
+        // gRPC keeps serving and reports the drain in health checks
+        let addresses = service
+            .get_addresses(Request::new(GetAddressesRequest {
+                limit: 10,
+                include_ipv4: true,
+                include_ipv6: true,
+                subnetwork_id: String::new(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(addresses.addresses.len(), 3);
+        let health = service
+            .health_check(Request::new(HealthCheckRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(health.draining);
+        assert_eq!(health.status, HealthStatus::NotServing as i32);
+
+        set_drain_mode(false).await.unwrap();
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(answer_ips(&response).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reload_applies_live_dns_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        add_good_peers(&server, 5);
+
+        let mut config = Config::new();
+        config.dns_ttl = 60;
+        config.max_dns_records = 2;
+        config.dns_rate_limit = 0;
+        config.disabled_query_types = vec!["AAAA".to_string()];
+        // Clones serving other listeners see the new settings too
+        server.clone().reload(&config);
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::A).await;
+        assert_eq!(answer_ips(&response).len(), 2);
+        assert!(response.answers().iter().all(|record| record.ttl() == 60));
+
+        let (_, response) = resolve(&server, "seed.kaspa.org.", RecordType::AAAA).await;
+        assert_eq!(response.response_code(), ResponseCode::Refused);
+    }
+
+    async fn answered_ips(server: &DnsServer, name: &str) -> Vec<IpAddr> {
+        let (_, response) = resolve(server, name, RecordType::A).await;
+        response
+            .answers()
+            .iter()
+            .filter_map(|record| match record.data() {
+                Some(RData::A(a)) => Some(IpAddr::V4(a.0)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_answer_cache_reuses_selection_for_repeated_queries() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir)
+            .with_rng_seed(7)
+            .with_answer_cache_ttl(Duration::from_millis(200));
+        add_good_peers(&server, 20);
+
+        let first = answered_ips(&server, "seed.kaspa.org.").await;
+        for _ in 0..9 {
+            assert_eq!(answered_ips(&server, "seed.kaspa.org.").await, first);
+        }
+        // Ten queries, one scan of the address pool
+        let stats = server.query_stats();
+        assert_eq!(stats.answer_cache_misses.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.answer_cache_hits.load(Ordering::Relaxed), 9);
+
+        // Another query name is cached separately
+        answered_ips(&server, "n1.seed.kaspa.org.").await;
+        assert_eq!(stats.answer_cache_misses.load(Ordering::Relaxed), 2);
+
+        // Once expired the pool is scanned again and the answer reshuffled
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        let refreshed = answered_ips(&server, "seed.kaspa.org.").await;
+        assert_eq!(stats.answer_cache_misses.load(Ordering::Relaxed), 3);
+        assert_ne!(refreshed, first);
+    }
+
+    #[tokio::test]
+    async fn test_answer_cache_disabled_selects_every_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        add_good_peers(&server, 3);
+
+        for _ in 0..3 {
+            answered_ips(&server, "seed.kaspa.org.").await;
+        }
+        let stats = server.query_stats();
+        assert_eq!(stats.answer_cache_misses.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.answer_cache_hits.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_serves_ipv4_and_ipv6_on_the_same_port() {
+        // Bind both wildcards up front and hand the live sockets to the
+        // server, so nothing can take the port in between
+        let ipv4 = bind_udp("0.0.0.0:0".parse().unwrap()).unwrap();
+        let port = ipv4.local_addr().unwrap().port();
+        let mut sockets = vec![ipv4];
+        let mut targets = vec![SocketAddr::from(([127, 0, 0, 1], port))];
+        // Hosts without IPv6 only check the IPv4 side
+        match bind_udp(format!("[::]:{port}").parse().unwrap()) {
+            Ok(ipv6) if std::net::UdpSocket::bind("[::1]:0").is_ok() => {
+                sockets.push(ipv6);
+                targets.push(format!("[::1]:{port}").parse().unwrap());
+            }
+            _ => {}
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let address_manager =
+            Arc::new(AddressManager::new(temp_dir.path().to_str().unwrap(), 16111).unwrap());
+        let server = DnsServer::new(
+            "seed.kaspa.org".to_string(),
+            "ns1.kaspa.org".to_string(),
+            format!("0.0.0.0:{port},[::]:{port}"),
+            30,
+            address_manager,
+        )
+        .with_rate_limit(0)
+        .with_tcp_limits(0, Duration::from_secs(1));
+        tokio::spawn(async move { server.serve(sockets, Vec::new()).await });
+
+        let request = query("seed.kaspa.org.", RecordType::A).to_vec().unwrap();
+        for target in targets {
+            let client = if target.is_ipv6() {
+                "[::1]:0"
+            } else {
+                "127.0.0.1:0"
+            };
+            let socket = UdpSocket::bind(client).await.unwrap();
+            socket.send_to(&request, target).await.unwrap();
+            let mut buffer = [0u8; 512];
+            let len = tokio::time::timeout(Duration::from_secs(2), socket.recv(&mut buffer))
+                .await
+                .unwrap_or_else(|_| panic!("no answer on {}", target))
+                .unwrap();
+            let response = Message::from_vec(&buffer[..len]).unwrap();
+            assert_eq!(response.id(), 1234);
+        }
+    }
 }
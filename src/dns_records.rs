@@ -0,0 +1,229 @@
+//! Builders for the resource records served by the DNS server, keeping the
+//! `trust_dns_proto` rdata types in one place
+
+use crate::dns::SoaSettings;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use trust_dns_proto::rr::rdata::{A, AAAA, NS, SOA, SRV, TXT};
+use trust_dns_proto::rr::{Name, RData, Record};
+
+/// Longest character string a TXT record can carry, as its length is one byte
+const TXT_STRING_MAX_LEN: usize = 255;
+
+/// A record for an IPv4 address
+pub fn make_a_record(name: Name, ttl: u32, ip: Ipv4Addr) -> Record {
+    Record::from_rdata(name, ttl, RData::A(A(ip)))
+}
+
+/// AAAA record for an IPv6 address
+pub fn make_aaaa_record(name: Name, ttl: u32, ip: Ipv6Addr) -> Record {
+    Record::from_rdata(name, ttl, RData::AAAA(AAAA(ip)))
+}
+
+/// A or AAAA record, whichever matches the address family
+pub fn make_address_record(name: Name, ttl: u32, ip: IpAddr) -> Record {
+    match ip {
+        IpAddr::V4(ipv4) => make_a_record(name, ttl, ipv4),
+        IpAddr::V6(ipv6) => make_aaaa_record(name, ttl, ipv6),
+    }
+}
+
+/// NS record delegating `name` to `nameserver`
+pub fn make_ns_record(name: Name, ttl: u32, nameserver: Name) -> Record {
+    Record::from_rdata(name, ttl, RData::NS(NS(nameserver)))
+}
+
+/// SOA record for `zone`. Timers above `i32::MAX` are clamped, as the wire
+/// format stores them signed.
+pub fn make_soa_record(
+    zone: Name,
+    ttl: u32,
+    mname: Name,
+    rname: Name,
+    serial: u32,
+    timers: &SoaSettings,
+) -> Record {
+    let soa = SOA::new(
+        mname,
+        rname,
+        serial,
+        i32::try_from(timers.refresh).unwrap_or(i32::MAX),
+        i32::try_from(timers.retry).unwrap_or(i32::MAX),
+        i32::try_from(timers.expire).unwrap_or(i32::MAX),
+        timers.minimum,
+    );
+    Record::from_rdata(zone, ttl, RData::SOA(soa))
+}
+
+/// TXT record with the character strings of each entry. Entries longer than
+/// 255 bytes are split over consecutive strings.
+pub fn make_txt_record(name: Name, ttl: u32, text: Vec<String>) -> Record {
+    let strings = text
+        .iter()
+        .flat_map(|entry| split_character_strings(entry))
+        .collect();
+    Record::from_rdata(name, ttl, RData::TXT(TXT::new(strings)))
+}
+
+/// Split `text` into character strings of at most 255 bytes, without
+/// breaking a UTF-8 sequence
+fn split_character_strings(mut text: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    while text.len() > TXT_STRING_MAX_LEN {
+        let mut end = TXT_STRING_MAX_LEN;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (head, tail) = text.split_at(end);
+        strings.push(head.to_string());
+        text = tail;
+    }
+    strings.push(text.to_string());
+    strings
+}
+
+/// SRV record pointing at `target:port`, with zero priority and weight
+pub fn make_srv_record(name: Name, ttl: u32, port: u16, target: Name) -> Record {
+    Record::from_rdata(name, ttl, RData::SRV(SRV::new(0, 0, port, target)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use trust_dns_proto::rr::RecordType;
+    use trust_dns_proto::serialize::binary::BinEncodable;
+
+    fn name(name: &str) -> Name {
+        Name::from_str(name).unwrap()
+    }
+
+    #[test]
+    fn test_address_records() {
+        let record = make_a_record(name("seed.example.com."), 30, Ipv4Addr::new(45, 0, 0, 1));
+        assert_eq!(record.record_type(), RecordType::A);
+        assert_eq!(record.ttl(), 30);
+        assert_eq!(record.name(), &name("seed.example.com."));
+        assert_eq!(
+            record.data(),
+            Some(&RData::A(A(Ipv4Addr::new(45, 0, 0, 1))))
+        );
+
+        let ipv6: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        let record = make_aaaa_record(name("seed.example.com."), 30, ipv6);
+        assert_eq!(record.record_type(), RecordType::AAAA);
+        assert_eq!(record.data(), Some(&RData::AAAA(AAAA(ipv6))));
+
+        let glue = make_address_record(name("ns1.example.com."), 86400, IpAddr::V6(ipv6));
+        assert_eq!(glue.record_type(), RecordType::AAAA);
+        let glue = make_address_record(
+            name("ns1.example.com."),
+            86400,
+            IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1)),
+        );
+        assert_eq!(glue.record_type(), RecordType::A);
+    }
+
+    #[test]
+    fn test_ns_record() {
+        let record = make_ns_record(name("seed.example.com."), 86400, name("ns1.example.com."));
+        assert_eq!(record.record_type(), RecordType::NS);
+        assert_eq!(record.ttl(), 86400);
+        assert_eq!(
+            record.data(),
+            Some(&RData::NS(NS(name("ns1.example.com."))))
+        );
+    }
+
+    #[test]
+    fn test_soa_record_clamps_timers() {
+        let timers = SoaSettings {
+            mailbox: None,
+            refresh: 600,
+            retry: 60,
+            expire: u32::MAX,
+            minimum: 30,
+        };
+        let record = make_soa_record(
+            name("seed.example.com."),
+            30,
+            name("ns1.example.com."),
+            name("hostmaster.example.com."),
+            7,
+            &timers,
+        );
+        assert_eq!(record.record_type(), RecordType::SOA);
+
+        let Some(RData::SOA(soa)) = record.data() else {
+            panic!("expected SOA rdata");
+        };
+        assert_eq!(soa.mname(), &name("ns1.example.com."));
+        assert_eq!(soa.rname(), &name("hostmaster.example.com."));
+        assert_eq!(soa.serial(), 7);
+        assert_eq!(soa.refresh(), 600);
+        assert_eq!(soa.retry(), 60);
+        assert_eq!(soa.expire(), i32::MAX);
+        assert_eq!(soa.minimum(), 30);
+    }
+
+    #[test]
+    fn test_txt_record() {
+        let record = make_txt_record(name("seed.example.com."), 30, vec!["good=3".to_string()]);
+        assert_eq!(record.record_type(), RecordType::TXT);
+        let Some(RData::TXT(txt)) = record.data() else {
+            panic!("expected TXT rdata");
+        };
+        assert_eq!(txt.to_string(), "good=3");
+    }
+
+    #[test]
+    fn test_txt_record_splits_long_country_list() {
+        let countries: Vec<String> = (0..200)
+            .map(|i| {
+                format!(
+                    "{}{}={}",
+                    (b'A' + i / 26) as char,
+                    (b'A' + i % 26) as char,
+                    i
+                )
+            })
+            .collect();
+        let text = format!("countries {}", countries.join(" "));
+        assert!(text.len() > 3 * TXT_STRING_MAX_LEN);
+
+        let record = make_txt_record(name("seed.example.com."), 30, vec![text.clone()]);
+        let Some(RData::TXT(txt)) = record.data() else {
+            panic!("expected TXT rdata");
+        };
+        assert!(txt.txt_data().len() > 1);
+        assert!(
+            txt.txt_data()
+                .iter()
+                .all(|string| string.len() <= TXT_STRING_MAX_LEN)
+        );
+        assert_eq!(txt.txt_data().concat(), text.into_bytes());
+        assert!(record.to_bytes().is_ok());
+
+        // Multi-byte characters stay whole
+        let strings = split_character_strings(&"é".repeat(200));
+        assert_eq!(strings.len(), 2);
+        assert_eq!(strings[0].len(), 254);
+    }
+
+    #[test]
+    fn test_srv_record() {
+        let record = make_srv_record(
+            name("_kaspa._tcp.seed.example.com."),
+            30,
+            16111,
+            name("peer.seed.example.com."),
+        );
+        assert_eq!(record.record_type(), RecordType::SRV);
+        let Some(RData::SRV(srv)) = record.data() else {
+            panic!("expected SRV rdata");
+        };
+        assert_eq!(srv.port(), 16111);
+        assert_eq!(srv.priority(), 0);
+        assert_eq!(srv.weight(), 0);
+        assert_eq!(srv.target(), &name("peer.seed.example.com."));
+    }
+}
@@ -1,8 +1,72 @@
+use crate::constants::NETWORK_DEFAULT_PORTS;
 use crate::errors::Result;
+use crate::netadapter::DnsseedNetAdapter;
 use crate::types::NetAddress;
-use std::net::ToSocketAddrs;
+use std::fmt;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::str::FromStr;
 use tracing::{debug, info, warn};
 
+/// A way of discovering peer addresses from a DNS seed server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscoveryMethod {
+    /// Peer addresses published in the seeder's DNS records
+    SeederDnsRecords,
+    /// Peer list requested over a connection to the seeder
+    SeederConnection,
+    /// Same lookup as `SeederDnsRecords`, kept so configurations naming it
+    /// still work
+    Direct,
+    /// Plain DNS resolution, accepting IP literals as-is
+    BasicDns,
+    /// Socket address resolution on the default ports of the other Kaspa
+    /// networks
+    AlternativePorts,
+}
+
+/// Discovery methods tried when none are configured, in order
+pub const DEFAULT_DISCOVERY_METHODS: [DiscoveryMethod; 5] = [
+    DiscoveryMethod::SeederDnsRecords,
+    DiscoveryMethod::SeederConnection,
+    DiscoveryMethod::Direct,
+    DiscoveryMethod::BasicDns,
+    DiscoveryMethod::AlternativePorts,
+];
+
+impl DiscoveryMethod {
+    /// Name used in the configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscoveryMethod::SeederDnsRecords => "seeder_dns_records",
+            DiscoveryMethod::SeederConnection => "seeder_connection",
+            DiscoveryMethod::Direct => "direct",
+            DiscoveryMethod::BasicDns => "basic_dns",
+            DiscoveryMethod::AlternativePorts => "alternative_ports",
+        }
+    }
+}
+
+impl fmt::Display for DiscoveryMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DiscoveryMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        DEFAULT_DISCOVERY_METHODS
+            .into_iter()
+            .find(|method| method.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("unknown discovery method '{}'", s))
+    }
+}
+
+/// Resolved seeder addresses tried by [`DiscoveryMethod::SeederConnection`]
+const MAX_SEEDER_CONNECTIONS: usize = 3;
+
 /// DNS seed discoverer
 pub struct DnsSeedDiscovery;
 
@@ -24,63 +88,37 @@ impl DnsSeedDiscovery {
                 "kaspadns.kaspacalc.net".to_string(),
                 "n-mainnet.kaspa.ws".to_string(),
             ],
-            crate::config::NetworkParams::Testnet { suffix, .. } => vec![
-                // For Testnet, we'll use mainnet seeders as fallback
-                // since testnet seeders seem to be unavailable
-                format!("seed{}.testnet.kaspa.org", suffix),
-                // Fallback to mainnet seeders for testnet
-                "seeder1.kaspad.net".to_string(),
-                "seeder2.kaspad.net".to_string(),
+            // Mainnet seeders are no use here, their peers fail the network
+            // check. Suffix 0 is the default testnet, testnet-10.
+            crate::config::NetworkParams::Testnet { suffix: 11, .. } => vec![
+                "seed11.testnet.kaspa.org".to_string(),
+                "seeder1-testnet-11.kaspad.net".to_string(),
+                "seeder2-testnet-11.kaspad.net".to_string(),
+            ],
+            crate::config::NetworkParams::Testnet { .. } => vec![
+                "seed10.testnet.kaspa.org".to_string(),
+                "seeder1-testnet.kaspad.net".to_string(),
+                "seeder2-testnet.kaspad.net".to_string(),
             ],
         }
     }
 
-    /// Query DNS seed server with multiple fallback methods
+    /// Query DNS seed server, trying each discovery method in order until one
+    /// finds addresses. Seeder connections need a network adapter and find
+    /// nothing without one.
     pub async fn query_seed_server(
         seed_server: &str,
         default_port: u16,
+        methods: &[DiscoveryMethod],
+        net_adapter: Option<&DnsseedNetAdapter>,
     ) -> Result<Vec<NetAddress>> {
-        // Try multiple query methods for better reliability
-        let mut addresses = Vec::new();
-
-        // Method 1: Try to connect to the seeder itself to get peer addresses (like Go version)
-        if let Ok(addrs) = Self::query_seeder_peer(seed_server, default_port).await {
-            addresses.extend(addrs);
-        }
-
-        // Method 2: Direct socket address resolution as fallback
-        if addresses.is_empty() {
-            if let Ok(addrs) = Self::query_seed_server_direct(seed_server, default_port).await {
-                addresses.extend(addrs);
-            }
-        }
-
-        // Method 3: Fallback to basic DNS resolution
-        if addresses.is_empty() {
-            if let Ok(addrs) = Self::query_basic_dns(seed_server, default_port).await {
-                addresses.extend(addrs);
-            }
-        }
-
-        // Method 4: Try alternative ports if the default port fails
-        if addresses.is_empty() {
-            let alternative_ports = [16110, 16112, 16113]; // Common Kaspa ports
-            for alt_port in alternative_ports {
-                if let Ok(addrs) = Self::query_seed_server_direct(seed_server, alt_port).await {
-                    addresses.extend(addrs);
-                    if !addresses.is_empty() {
-                        info!(
-                            "Found addresses using alternative port {} for {}",
-                            alt_port, seed_server
-                        );
-                        break;
-                    }
-                }
-            }
-        }
+        let addresses = Self::run_discovery_chain(methods, seed_server, |method| {
+            Self::query_with_method(method, seed_server, default_port, net_adapter)
+        })
+        .await;
 
         // Remove duplicates and filter valid addresses
-        addresses = Self::deduplicate_and_filter_addresses(addresses);
+        let addresses = Self::deduplicate_and_filter_addresses(addresses);
 
         if !addresses.is_empty() {
             info!(
@@ -98,265 +136,153 @@ impl DnsSeedDiscovery {
         Ok(addresses)
     }
 
-    /// Query DNS seed server directly using socket address resolution
-    async fn query_seed_server_direct(
+    /// Run discovery methods in order, stopping at the first that finds addresses
+    async fn run_discovery_chain<F, Fut>(
+        methods: &[DiscoveryMethod],
         seed_server: &str,
-        default_port: u16,
-    ) -> Result<Vec<NetAddress>> {
-        // Use to_socket_addrs() method to query DNS, exactly consistent with rusty-kaspa
-        let addrs = match (seed_server, default_port).to_socket_addrs() {
-            Ok(addrs) => addrs,
-            Err(e) => {
-                warn!("Error resolving DNS seeder {}: {}", seed_server, e);
-                return Ok(Vec::new());
+        mut query: F,
+    ) -> Vec<NetAddress>
+    where
+        F: FnMut(DiscoveryMethod) -> Fut,
+        Fut: Future<Output = Result<Vec<NetAddress>>>,
+    {
+        for &method in methods {
+            match query(method).await {
+                Ok(addresses) if !addresses.is_empty() => {
+                    debug!(
+                        "Discovery method {} found {} addresses for {}",
+                        method,
+                        addresses.len(),
+                        seed_server
+                    );
+                    return addresses;
+                }
+                Ok(_) => debug!(
+                    "Discovery method {} found no addresses for {}",
+                    method, seed_server
+                ),
+                Err(e) => debug!(
+                    "Discovery method {} failed for {}: {}",
+                    method, seed_server, e
+                ),
             }
-        };
-
-        let mut result = Vec::new();
-        for addr in addrs {
-            result.push(NetAddress::new(addr.ip(), addr.port()));
         }
 
-        Ok(result)
+        Vec::new()
     }
 
-    /// Try to connect to the seeder itself to get peer addresses (like Go version)
-    async fn query_seeder_peer(seed_server: &str, default_port: u16) -> Result<Vec<NetAddress>> {
-        // This is the main method - try to get peer addresses from the seeder
-        // like Go version's dnsseed.SeedFromDNS
-
-        let mut addresses = Vec::new();
-
-        // Method 1: Get addresses from the seeder's DNS records
-        // Many DNS seed servers publish peer addresses as DNS records
-        if let Ok(addrs) = Self::query_seeder_dns_records(seed_server, default_port).await {
-            addresses.extend(addrs);
-        }
-
-        // Method 2: Query known working peer addresses from multiple sources
-        if let Ok(addrs) = Self::query_known_peers(seed_server, default_port).await {
-            addresses.extend(addrs);
-        }
-
-        // Method 3: Try to connect and request peer list
-        if addresses.is_empty() {
-            if let Ok(addrs) = Self::query_seeder_connection(seed_server, default_port).await {
-                addresses.extend(addrs);
+    /// Query the seed server with a single discovery method
+    async fn query_with_method(
+        method: DiscoveryMethod,
+        seed_server: &str,
+        default_port: u16,
+        net_adapter: Option<&DnsseedNetAdapter>,
+    ) -> Result<Vec<NetAddress>> {
+        match method {
+            DiscoveryMethod::SeederDnsRecords | DiscoveryMethod::Direct => {
+                Self::query_seed_server_direct(seed_server, default_port).await
+            }
+            DiscoveryMethod::SeederConnection => {
+                Self::query_seeder_connection(seed_server, default_port, net_adapter).await
+            }
+            DiscoveryMethod::BasicDns => Self::query_basic_dns(seed_server, default_port).await,
+            DiscoveryMethod::AlternativePorts => {
+                // Default ports of the other Kaspa networks
+                let alt_ports = NETWORK_DEFAULT_PORTS
+                    .iter()
+                    .map(|(_, port)| *port)
+                    .filter(|port| *port != default_port);
+                for alt_port in alt_ports {
+                    let addresses = Self::query_seed_server_direct(seed_server, alt_port).await?;
+                    if !addresses.is_empty() {
+                        info!(
+                            "Found addresses using alternative port {} for {}",
+                            alt_port, seed_server
+                        );
+                        return Ok(addresses);
+                    }
+                }
+                Ok(Vec::new())
             }
         }
-
-        Ok(addresses)
     }
 
-    /// Query DNS records from the seeder (many seeders publish peer addresses as DNS records)
-    async fn query_seeder_dns_records(
-        _seed_server: &str,
+    /// Resolve the seeder name, whose A/AAAA records list the peers it
+    /// serves, into peer addresses on `default_port`
+    async fn query_seed_server_direct(
+        seed_server: &str,
         default_port: u16,
     ) -> Result<Vec<NetAddress>> {
-        let mut addresses = Vec::new();
-
-        // Try to query the seeder's own DNS records for peer addresses
-        // This is a common pattern used by many DNS seeders
-
-        // For now, we'll use a hardcoded list of known working peer addresses
-        // In production, you'd query the seeder's DNS records dynamically
-        // These are some known working Kaspa nodes (from previous discoveries)
-
-        let known_peers = [
-            "54.39.156.234:16111",
-            "107.220.225.108:16111", 
-            "72.28.135.10:16111",
-            "95.208.218.114:16111",
-            "23.118.8.166:16111",
-            "69.72.83.82:16111",
-            "167.179.147.155:16111",
-            "109.248.250.155:16111",
-            "118.70.175.236:16111",
-            "31.97.100.30:16111",
-            "46.21.250.122:16111",
-            "82.165.188.245:16111",
-            "188.63.232.45:16111",
-            "193.164.205.249:16111",
-            "148.251.151.149:16111",
-            "23.118.8.168:16111",
-        ];
-        
-        for peer_addr in known_peers.iter() {
-            if let Ok(addr) = peer_addr.parse::<std::net::SocketAddr>() {
-                addresses.push(NetAddress::new(addr.ip(), addr.port()));
+        let addrs = match tokio::net::lookup_host((seed_server, default_port)).await {
+            Ok(addrs) => addrs,
+            Err(e) => {
+                warn!("Error resolving DNS seeder {}: {}", seed_server, e);
+                return Ok(Vec::new());
             }
-        }
-        
-        if !addresses.is_empty() {
-            info!("Found {} known peer addresses from {}", addresses.len(), _seed_server);
-        }
-        
-        Ok(addresses)
-    }
+        };
 
-    /// Query known working peer addresses from multiple sources
-    async fn query_known_peers(_seed_server: &str, default_port: u16) -> Result<Vec<NetAddress>> {
-        let mut addresses = Vec::new();
-
-        // Source 1: Large list of known working Kaspa nodes
-        // This simulates what a real DNS seeder would discover over time
-        let large_peer_list = [
-            // North America
-            "54.39.156.234:16111", "107.220.225.108:16111", "72.28.135.10:16111",
-            "95.208.218.114:16111", "23.118.8.166:16111", "69.72.83.82:16111",
-            "167.179.147.155:16111", "109.248.250.155:16111", "118.70.175.236:16111",
-            "31.97.100.30:16111", "46.21.250.122:16111", "82.165.188.245:16111",
-            "188.63.232.45:16111", "193.164.205.249:16111", "148.251.151.149:16111",
-            "23.118.8.168:16111", "5.181.124.76:16111", "147.93.69.22:16111",
-            "57.129.84.149:16111", "151.213.166.40:16111", "23.118.8.163:16111",
-            "80.219.209.29:16111", "135.131.145.104:16111", "66.94.120.76:16111",
-            "89.58.46.206:16111", "188.226.83.207:16111", "103.95.113.96:16111",
-            "91.106.155.180:16111",
-            
-            // Europe
-            "185.199.108.153:16111", "185.199.109.153:16111", "185.199.110.153:16111",
-            "185.199.111.153:16111", "140.82.112.3:16111", "140.82.112.4:16111",
-            "140.82.112.5:16111", "140.82.112.6:16111", "140.82.112.7:16111",
-            "140.82.112.8:16111", "140.82.112.9:16111", "140.82.112.10:16111",
-            "140.82.112.11:16111", "140.82.112.12:16111", "140.82.112.13:16111",
-        ];
-        
-        for peer_addr in large_peer_list.iter() {
-            if let Ok(addr) = peer_addr.parse::<std::net::SocketAddr>() {
-                addresses.push(NetAddress::new(addr.ip(), addr.port()));
-            }
-        }
-        
-        // Source 2: Generate additional addresses from common IP ranges
-        // This simulates network scanning and discovery
-        addresses.extend(Self::generate_common_ip_ranges(default_port));
-        
-        // Source 3: Generate addresses from known hosting providers
-        // Many Kaspa nodes run on popular hosting services
-        addresses.extend(Self::generate_hosting_provider_addresses(default_port));
-        
-        if !addresses.is_empty() {
-            info!("Found {} known peer addresses from large peer list", addresses.len());
+        let result: Vec<NetAddress> = addrs
+            .map(|addr| NetAddress::new(addr.ip(), addr.port()))
+            .collect();
+        if !result.is_empty() {
+            info!(
+                "Found {} peer addresses in DNS records of {}",
+                result.len(),
+                seed_server
+            );
         }
-        
-        Ok(addresses)
+
+        Ok(result)
     }
 
-    /// Try to connect to the seeder to request peer addresses
+    /// Connect to the seeder over the p2p protocol and request its peer
+    /// list, like Go's `dnsseed.SeedFromDNS`. Uses the crawler's adapter so
+    /// the same handshake and address timeouts apply.
     async fn query_seeder_connection(
         seed_server: &str,
         default_port: u16,
+        net_adapter: Option<&DnsseedNetAdapter>,
     ) -> Result<Vec<NetAddress>> {
-        let addr = format!("{}:{}", seed_server, default_port);
-
-        // Try to establish a basic connection to see if the seeder is reachable
-        match tokio::net::TcpStream::connect(&addr).await {
-            Ok(_) => {
-                debug!("Seeder {} is reachable", seed_server);
-                // In a full implementation, you'd perform protocol handshake here
-                // and request peer addresses
-                Ok(Vec::new())
-            }
-            Err(e) => {
-                debug!("Seeder {} is not reachable: {}", seed_server, e);
-                Ok(Vec::new())
-            }
-        }
-    }
+        let Some(net_adapter) = net_adapter else {
+            debug!("No network adapter to connect to seeder {}", seed_server);
+            return Ok(Vec::new());
+        };
 
-    /// Generate addresses from common IP ranges where Kaspa nodes are often found
-    fn generate_common_ip_ranges(default_port: u16) -> Vec<NetAddress> {
-        let mut addresses = Vec::new();
-        
-        // Common IP ranges where Kaspa nodes are often found
-        let common_ranges = [
-            // GitHub Actions IPs (140.82.x.x)
-            (140, 82),
-            // DigitalOcean IPs (159.89.x.x, 167.99.x.x, 178.62.x.x)
-            (159, 89), (167, 99), (178, 62),
-            // AWS IPs (3.x.x.x, 18.x.x.x, 52.x.x.x, 54.x.x.x, 107.x.x.x)
-            (3, 0), (18, 0), (52, 0), (54, 0), (107, 0),
-            // Google Cloud IPs (35.x.x.x, 104.x.x.x, 130.x.x.x)
-            (35, 0), (104, 0), (130, 0),
-            // Azure IPs (20.x.x.x, 40.x.x.x, 51.x.x.x, 52.x.x.x)
-            (20, 0), (40, 0), (51, 0), (52, 0),
-            // Linode IPs (139.162.x.x, 172.104.x.x, 176.58.x.x)
-            (139, 162), (172, 104), (176, 58),
-            // Vultr IPs (149.28.x.x, 45.x.x.x, 66.x.x.x)
-            (149, 28), (45, 0), (66, 0),
-            // Hetzner IPs (5.x.x.x, 23.x.x.x, 37.x.x.x, 78.x.x.x, 88.x.x.x, 95.x.x.x, 135.x.x.x, 138.x.x.x, 148.x.x.x, 151.x.x.x, 152.x.x.x, 157.x.x.x, 159.x.x.x, 162.x.x.x, 167.x.x.x, 176.x.x.x, 185.x.x.x, 188.x.x.x, 193.x.x.x, 195.x.x.x, 212.x.x.x, 213.x.x.x, 217.x.x.x)
-            (5, 0), (23, 0), (37, 0), (78, 0), (88, 0), (95, 0), (135, 0), (138, 0), (148, 0), (151, 0), (152, 0), (157, 0), (159, 0), (162, 0), (167, 0), (176, 0), (185, 0), (188, 0), (193, 0), (195, 0), (212, 0), (213, 0), (217, 0),
-        ];
-        
-        for (first, second) in common_ranges.iter() {
-            // Generate some random addresses from each range
-            for i in 0..50 {
-                let third = ((i * 7 + 13) % 255) as u8; // Simple pseudo-random generation
-                let fourth = ((i * 11 + 17) % 255) as u8;
-                
-                let ip = std::net::Ipv4Addr::new(*first, *second, third, fourth);
-                addresses.push(NetAddress::new(std::net::IpAddr::V4(ip), default_port));
-            }
-        }
-        
-        info!("Generated {} addresses from common IP ranges", addresses.len());
-        addresses
-    }
-    
-    /// Generate addresses from known hosting providers
-    fn generate_hosting_provider_addresses(default_port: u16) -> Vec<NetAddress> {
-        let mut addresses = Vec::new();
-        
-        // Known hosting provider IP ranges
-        let provider_ranges = [
-            // OVH
-            (37, 120), (37, 187), (37, 59), (37, 48), (37, 49), (37, 50), (37, 51), (37, 52), (37, 53), (37, 54), (37, 55), (37, 56), (37, 57), (37, 58),
-            // Contabo
-            (38, 242), (38, 243), (38, 244), (38, 245), (38, 246), (38, 247), (38, 248), (38, 249), (38, 250), (38, 251), (38, 252), (38, 253), (38, 254), (38, 255),
-            // Netcup
-            (37, 120), (37, 187), (37, 59), (37, 48), (37, 49), (37, 50), (37, 51), (37, 52), (37, 53), (37, 54), (37, 55), (37, 56), (37, 57), (37, 58),
-            // Leaseweb
-            (37, 120), (37, 187), (37, 59), (37, 48), (37, 49), (37, 50), (37, 51), (37, 52), (37, 53), (37, 54), (37, 55), (37, 56), (37, 57), (37, 58),
-        ];
-        
-        for (first, second) in provider_ranges.iter() {
-            // Generate some addresses from each provider range
-            for i in 0..30 {
-                let third = ((i * 13 + 19) % 255) as u8;
-                let fourth = ((i * 17 + 23) % 255) as u8;
-                
-                let ip = std::net::Ipv4Addr::new(*first, *second, third, fourth);
-                addresses.push(NetAddress::new(std::net::IpAddr::V4(ip), default_port));
+        // The adapter dials socket addresses, so resolve the seeder first
+        let targets = Self::query_seed_server_direct(seed_server, default_port).await?;
+        for target in targets.iter().take(MAX_SEEDER_CONNECTIONS) {
+            let address = SocketAddr::new(target.ip, target.port).to_string();
+            match net_adapter.connect_and_get_addresses(&address).await {
+                Ok((_, batch)) if !batch.addresses.is_empty() => {
+                    info!(
+                        "Seeder {} at {} returned {} addresses",
+                        seed_server,
+                        address,
+                        batch.addresses.len()
+                    );
+                    return Ok(batch.addresses);
+                }
+                Ok(_) => debug!(
+                    "Seeder {} at {} returned no addresses",
+                    seed_server, address
+                ),
+                Err(e) => debug!(
+                    "Failed to get addresses from seeder {} at {}: {}",
+                    seed_server, address, e
+                ),
             }
         }
-        
-        info!("Generated {} addresses from hosting providers", addresses.len());
-        addresses
+
+        Ok(Vec::new())
     }
 
     /// Basic DNS resolution fallback
     async fn query_basic_dns(seed_server: &str, default_port: u16) -> Result<Vec<NetAddress>> {
-        // Simple DNS resolution using std::net
-        let addrs = match seed_server.parse::<std::net::IpAddr>() {
-            Ok(ip) => {
-                // If it's already an IP address, use it directly
-                vec![NetAddress::new(ip, default_port)]
-            }
-            Err(_) => {
-                // Try to resolve hostname
-                match (seed_server, default_port).to_socket_addrs() {
-                    Ok(addrs) => addrs
-                        .map(|addr| NetAddress::new(addr.ip(), addr.port()))
-                        .collect(),
-                    Err(e) => {
-                        warn!("Failed to resolve hostname {}: {}", seed_server, e);
-                        Vec::new()
-                    }
-                }
-            }
-        };
-
-        Ok(addrs)
+        match seed_server.parse::<std::net::IpAddr>() {
+            // If it's already an IP address, use it directly
+            Ok(ip) => Ok(vec![NetAddress::new(ip, default_port)]),
+            Err(_) => Self::query_seed_server_direct(seed_server, default_port).await,
+        }
     }
 
     /// Remove duplicate addresses and filter invalid ones - optimized version
@@ -394,36 +320,117 @@ impl DnsSeedDiscovery {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::kaspa_protocol::create_consensus_config;
 
     #[test]
     fn test_get_dns_seeders() {
         use crate::config::NetworkParams;
 
-        let mainnet_params = NetworkParams::Mainnet {
-            default_port: 16111,
-        };
+        let mainnet_params = NetworkParams::for_network(false, 0);
         let mainnet_servers =
             DnsSeedDiscovery::get_dns_seeders_from_network_params(&mainnet_params);
         assert!(!mainnet_servers.is_empty());
         assert!(mainnet_servers.contains(&"seeder1.kaspad.net".to_string()));
         assert!(mainnet_servers.contains(&"seeder1.kaspad.net".to_string()));
 
-        let testnet_params = NetworkParams::Testnet {
-            suffix: 10,
-            default_port: 16211,
-        };
+        let testnet_params = NetworkParams::for_network(true, 10);
         let testnet_servers =
             DnsSeedDiscovery::get_dns_seeders_from_network_params(&testnet_params);
         println!("Testnet servers: {:?}", testnet_servers);
         assert!(!testnet_servers.is_empty());
         assert!(testnet_servers.contains(&"seed10.testnet.kaspa.org".to_string()));
+        assert!(!testnet_servers.contains(&"seeder1.kaspad.net".to_string()));
+        assert_eq!(
+            DnsSeedDiscovery::get_dns_seeders_from_network_params(&NetworkParams::for_network(
+                true, 0
+            )),
+            testnet_servers
+        );
+
+        let testnet_11_servers = DnsSeedDiscovery::get_dns_seeders_from_network_params(
+            &NetworkParams::for_network(true, 11),
+        );
+        assert!(testnet_11_servers.contains(&"seed11.testnet.kaspa.org".to_string()));
+        assert!(!testnet_11_servers.contains(&"seed10.testnet.kaspa.org".to_string()));
     }
 
     #[tokio::test]
     async fn test_query_seed_server() {
         // Note: This test requires network connection
-        let result = DnsSeedDiscovery::query_seed_server("seeder1.kaspad.net", 16111).await;
+        let result = DnsSeedDiscovery::query_seed_server(
+            "seeder1.kaspad.net",
+            16111,
+            &DEFAULT_DISCOVERY_METHODS,
+            None,
+        )
+        .await;
         // Should not panic even if it fails
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_discovery_chain_runs_configured_methods_in_order() {
+        let methods: Vec<DiscoveryMethod> = ["basic_dns", "direct", "alternative_ports"]
+            .iter()
+            .map(|name| name.parse().unwrap())
+            .collect();
+        let found = vec![NetAddress::new("45.0.0.1".parse().unwrap(), 16111)];
+
+        let mut called = Vec::new();
+        let addresses = DnsSeedDiscovery::run_discovery_chain(&methods, "seed.example", |method| {
+            called.push(method);
+            let addresses = match method {
+                DiscoveryMethod::Direct => found.clone(),
+                _ => Vec::new(),
+            };
+            async move { Ok(addresses) }
+        })
+        .await;
+
+        // Stops at the first method that finds addresses, never touching
+        // methods left out of the chain
+        assert_eq!(addresses, found);
+        assert_eq!(
+            called,
+            vec![DiscoveryMethod::BasicDns, DiscoveryMethod::Direct]
+        );
+
+        assert!("Basic_DNS".parse::<DiscoveryMethod>().is_ok());
+        assert!("known_peers".parse::<DiscoveryMethod>().is_err());
+        assert!("carrier_pigeon".parse::<DiscoveryMethod>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_seeder_yields_no_addresses() {
+        // No discovery method may invent peers when the seeder is unreachable
+        for method in DEFAULT_DISCOVERY_METHODS {
+            let addresses =
+                DnsSeedDiscovery::query_with_method(method, "seed.invalid", 16111, None)
+                    .await
+                    .unwrap();
+            assert!(addresses.is_empty(), "{} returned addresses", method);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seeder_connection_pulls_nothing_from_unreachable_seeder() {
+        // A seeder that never accepts. The listener stays bound for the
+        // whole test so no other socket can take its port.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let net_adapter = DnsseedNetAdapter::new(create_consensus_config(false, 0)).unwrap();
+
+        let addresses =
+            DnsSeedDiscovery::query_seeder_connection("127.0.0.1", port, Some(&net_adapter))
+                .await
+                .unwrap();
+        assert!(addresses.is_empty());
+
+        // Without an adapter the seeder is not contacted at all
+        let addresses = DnsSeedDiscovery::query_seeder_connection("127.0.0.1", port, None)
+            .await
+            .unwrap();
+        assert!(addresses.is_empty());
+        drop(listener);
+    }
 }
@@ -79,6 +79,9 @@ pub enum KaseederError {
     #[error("Protocol version mismatch: {0}")]
     ProtocolVersionMismatch(String),
 
+    #[error("Network mismatch: {0}")]
+    NetworkMismatch(String),
+
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
 }
@@ -146,6 +149,12 @@ impl From<sled::Error> for KaseederError {
     }
 }
 
+impl From<rusqlite::Error> for KaseederError {
+    fn from(err: rusqlite::Error) -> Self {
+        KaseederError::Database(format!("SQLite database error: {}", err))
+    }
+}
+
 impl From<reqwest::Error> for KaseederError {
     fn from(err: reqwest::Error) -> Self {
         KaseederError::Network(format!("HTTP request error: {}", err))
@@ -181,3 +190,54 @@ impl From<tracing_subscriber::filter::ParseError> for KaseederError {
         KaseederError::Config(format!("Log level parse error: {}", err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_and_config_errors_display() {
+        let cases = [
+            (
+                KaseederError::InvalidConfigValue {
+                    field: "threads".to_string(),
+                    value: "0".to_string(),
+                    expected: "1-32".to_string(),
+                },
+                "Invalid configuration value: threads = 0, expected: 1-32",
+            ),
+            (
+                KaseederError::FileNotFound("peers.json".to_string()),
+                "File not found: peers.json",
+            ),
+            (
+                KaseederError::ConnectionFailed("45.0.0.1:16111".to_string()),
+                "Connection failed: 45.0.0.1:16111",
+            ),
+            (
+                KaseederError::ProtocolVersionMismatch("5 < 6".to_string()),
+                "Protocol version mismatch: 5 < 6",
+            ),
+            (
+                KaseederError::Protocol("bad message".to_string()),
+                "Protocol error: bad message",
+            ),
+            (
+                KaseederError::InvalidAddress("45.0.0.1".to_string()),
+                "Invalid address format: 45.0.0.1",
+            ),
+            (
+                KaseederError::PeerUnavailable("45.0.0.1:16111".to_string()),
+                "Peer unavailable: 45.0.0.1:16111",
+            ),
+            (
+                KaseederError::NetworkTimeout("handshake".to_string()),
+                "Network timeout: handshake",
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+}
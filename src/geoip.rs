@@ -0,0 +1,278 @@
+use crate::errors::{KaseederError, Result};
+use crate::types::NetAddress;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use tracing::info;
+
+/// Region assigned to peers the database does not cover
+pub const UNKNOWN_REGION: &str = "unknown";
+
+/// Country assigned to peers the database does not cover
+pub const UNKNOWN_COUNTRY: &str = "unknown";
+
+/// Autonomous system assigned to peers the database has no ASN for
+pub const UNKNOWN_ASN: &str = "unknown";
+
+/// Country, region and optionally the autonomous system a network belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeoLocation {
+    pub country: String,
+    pub region: String,
+    pub asn: Option<u32>,
+}
+
+/// A network range and its location
+#[derive(Debug, Clone)]
+struct GeoIpRange {
+    network: IpAddr,
+    prefix_len: u8,
+    location: GeoLocation,
+}
+
+impl GeoIpRange {
+    fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// GeoIP lookup table loaded from a CSV file with one
+/// `network/prefix,country,region[,asn]` entry per line, where the optional
+/// ASN is written as `AS13335` or `13335`. Blank lines and lines starting
+/// with `#` are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct GeoIpDatabase {
+    ranges: Vec<GeoIpRange>,
+}
+
+impl GeoIpDatabase {
+    /// Load the database from a CSV file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        let database = Self::parse(&content)?;
+        info!(
+            "Loaded {} GeoIP ranges from {}",
+            database.ranges.len(),
+            path.as_ref().display()
+        );
+        Ok(database)
+    }
+
+    /// Parse the CSV contents of a database
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut ranges = Vec::new();
+
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |reason: &str| {
+                KaseederError::Config(format!(
+                    "Invalid GeoIP entry on line {}: {} ({})",
+                    line_number + 1,
+                    line,
+                    reason
+                ))
+            };
+
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let (network, country, region, asn) = match fields[..] {
+                [network, country, region] => (network, country, region, None),
+                [network, country, region, asn] => (network, country, region, Some(asn)),
+                _ => return Err(invalid("expected network,country,region[,asn]")),
+            };
+            let asn = asn
+                .map(|asn| {
+                    asn.trim_start_matches("AS")
+                        .parse::<u32>()
+                        .map_err(|_| invalid("invalid ASN"))
+                })
+                .transpose()?;
+
+            let (network, prefix_len) = network
+                .split_once('/')
+                .ok_or_else(|| invalid("network must be in CIDR notation"))?;
+            let network: IpAddr = network
+                .parse()
+                .map_err(|_| invalid("invalid network address"))?;
+            let prefix_len: u8 = prefix_len
+                .parse()
+                .map_err(|_| invalid("invalid prefix length"))?;
+            let max_prefix_len = if network.is_ipv4() { 32 } else { 128 };
+            if prefix_len > max_prefix_len {
+                return Err(invalid("prefix length out of range"));
+            }
+            if region.is_empty() {
+                return Err(invalid("region must not be empty"));
+            }
+
+            ranges.push(GeoIpRange {
+                network,
+                prefix_len,
+                location: GeoLocation {
+                    country: country.to_string(),
+                    region: region.to_string(),
+                    asn,
+                },
+            });
+        }
+
+        // Most specific networks first so lookups return the longest match
+        ranges.sort_by_key(|range| std::cmp::Reverse(range.prefix_len));
+
+        Ok(Self { ranges })
+    }
+
+    /// Location of the most specific network containing the IP
+    pub fn lookup(&self, ip: &IpAddr) -> Option<&GeoLocation> {
+        self.ranges
+            .iter()
+            .find(|range| range.contains(ip))
+            .map(|range| &range.location)
+    }
+
+    /// Region of the IP, or `unknown` when the database does not cover it
+    pub fn region(&self, ip: &IpAddr) -> &str {
+        self.lookup(ip)
+            .map(|location| location.region.as_str())
+            .unwrap_or(UNKNOWN_REGION)
+    }
+
+    /// Country of the IP, or `unknown` when the database does not cover it
+    pub fn country(&self, ip: &IpAddr) -> &str {
+        self.lookup(ip)
+            .map(|location| location.country.as_str())
+            .unwrap_or(UNKNOWN_COUNTRY)
+    }
+
+    /// Autonomous system of the IP as `AS<number>`, or `unknown`
+    pub fn asn(&self, ip: &IpAddr) -> String {
+        self.lookup(ip)
+            .and_then(|location| location.asn)
+            .map(|asn| format!("AS{}", asn))
+            .unwrap_or_else(|| UNKNOWN_ASN.to_string())
+    }
+
+    /// Group peers by region
+    pub fn partition_by_region(&self, peers: &[NetAddress]) -> BTreeMap<String, Vec<NetAddress>> {
+        let mut regions: BTreeMap<String, Vec<NetAddress>> = BTreeMap::new();
+        for peer in peers {
+            regions
+                .entry(self.region(&peer.ip).to_string())
+                .or_default()
+                .push(peer.clone());
+        }
+        regions
+    }
+
+    /// Write one `peers-<region>.txt` file per region into `output_dir`, each
+    /// listing that region's peers as `ip:port` lines. Returns the number of
+    /// peers written per region.
+    pub fn export_by_region<P: AsRef<Path>>(
+        &self,
+        peers: &[NetAddress],
+        output_dir: P,
+    ) -> Result<BTreeMap<String, usize>> {
+        let output_dir = output_dir.as_ref();
+        std::fs::create_dir_all(output_dir)?;
+
+        let mut counts = BTreeMap::new();
+        for (region, peers) in self.partition_by_region(peers) {
+            let lines: String = peers
+                .iter()
+                .map(|peer| format!("{}\n", SocketAddr::new(peer.ip, peer.port)))
+                .collect();
+            std::fs::write(output_dir.join(region_file_name(&region)), lines)?;
+            counts.insert(region, peers.len());
+        }
+
+        Ok(counts)
+    }
+}
+
+/// File name a region's peers are exported to
+pub fn region_file_name(region: &str) -> String {
+    let region: String = region
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("peers-{}.txt", region)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TEST_DATABASE: &str = "\
+# network,country,region
+45.0.0.0/8,US,north-america
+45.10.0.0/16,DE,europe
+2001:4860::/32,JP,asia
+";
+
+    #[test]
+    fn test_export_by_region_places_peers_in_their_region_file() {
+        let database = GeoIpDatabase::parse(TEST_DATABASE).unwrap();
+        let peers = vec![
+            NetAddress::new("45.0.0.1".parse().unwrap(), 16111),
+            NetAddress::new("45.10.0.1".parse().unwrap(), 16111),
+            NetAddress::new("45.10.0.2".parse().unwrap(), 16112),
+            NetAddress::new("2001:4860::1".parse().unwrap(), 16111),
+            NetAddress::new("80.0.0.1".parse().unwrap(), 16111),
+        ];
+
+        assert_eq!(database.region(&peers[0].ip), "north-america");
+        assert_eq!(database.lookup(&peers[1].ip).unwrap().country, "DE");
+        assert_eq!(database.region(&peers[4].ip), UNKNOWN_REGION);
+
+        let temp_dir = TempDir::new().unwrap();
+        let counts = database.export_by_region(&peers, temp_dir.path()).unwrap();
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts["europe"], 2);
+
+        let read_region = |region: &str| {
+            std::fs::read_to_string(temp_dir.path().join(region_file_name(region))).unwrap()
+        };
+        assert_eq!(read_region("north-america"), "45.0.0.1:16111\n");
+        assert_eq!(read_region("europe"), "45.10.0.1:16111\n45.10.0.2:16112\n");
+        assert_eq!(read_region("asia"), "[2001:4860::1]:16111\n");
+        assert_eq!(read_region(UNKNOWN_REGION), "80.0.0.1:16111\n");
+    }
+
+    #[test]
+    fn test_invalid_geoip_entries_are_rejected() {
+        assert!(GeoIpDatabase::parse("45.0.0.0/8,US").is_err());
+        assert!(GeoIpDatabase::parse("45.0.0.0,US,north-america").is_err());
+        assert!(GeoIpDatabase::parse("45.0.0.0/33,US,north-america").is_err());
+        assert!(GeoIpDatabase::parse("45.0.0.0/8,US,").is_err());
+        assert!(GeoIpDatabase::parse("45.0.0.0/8,US,north-america,ASx").is_err());
+
+        let database = GeoIpDatabase::parse("45.0.0.0/8,US,north-america,AS13335").unwrap();
+        let ip = "45.0.0.1".parse().unwrap();
+        assert_eq!(database.lookup(&ip).unwrap().asn, Some(13335));
+        assert_eq!(database.asn(&ip), "AS13335");
+    }
+}
@@ -0,0 +1,119 @@
+use crate::types::NetAddress;
+use dashmap::DashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A peer reporting an address while being crawled
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CrawlEdge {
+    /// Peer that reported the address, as `ip:port`
+    pub source: String,
+    /// Reported address, as `ip:port`
+    pub discovered: String,
+    /// Unix seconds of the latest report
+    pub last_reported: u64,
+}
+
+/// Discovery edges observed while crawling, for topology analysis. Edges not
+/// reported again within the retention window are dropped on [`prune`].
+///
+/// [`prune`]: CrawlGraph::prune
+#[derive(Debug)]
+pub struct CrawlGraph {
+    /// Latest report time per (source, discovered) pair
+    edges: DashMap<(SocketAddr, SocketAddr), SystemTime>,
+    retention: Duration,
+}
+
+impl CrawlGraph {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            edges: DashMap::new(),
+            retention,
+        }
+    }
+
+    /// Record the addresses a peer reported
+    pub fn record(&self, source: &NetAddress, discovered: &[NetAddress]) {
+        let source = SocketAddr::new(source.ip, source.port);
+        let now = SystemTime::now();
+        for address in discovered {
+            self.edges
+                .insert((source, SocketAddr::new(address.ip, address.port)), now);
+        }
+    }
+
+    /// Drop edges older than the retention window, returning how many were removed
+    pub fn prune(&self) -> usize {
+        let before = self.edges.len();
+        let now = SystemTime::now();
+        self.edges.retain(|_, reported| {
+            now.duration_since(*reported).unwrap_or_default() < self.retention
+        });
+        before - self.edges.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Snapshot of all edges, ordered by source then discovered address
+    pub fn edges(&self) -> Vec<CrawlEdge> {
+        let mut edges: Vec<CrawlEdge> = self
+            .edges
+            .iter()
+            .map(|entry| {
+                let (source, discovered) = entry.key();
+                CrawlEdge {
+                    source: source.to_string(),
+                    discovered: discovered.to_string(),
+                    last_reported: entry
+                        .value()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs(),
+                }
+            })
+            .collect();
+        edges.sort();
+        edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery_edges_are_recorded_and_expire() {
+        let graph = CrawlGraph::new(Duration::from_secs(60));
+        let source = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let discovered = vec![
+            NetAddress::new("45.0.0.2".parse().unwrap(), 16111),
+            NetAddress::new("2001:4860::1".parse().unwrap(), 16111),
+        ];
+
+        graph.record(&source, &discovered);
+        // Reporting the same address again refreshes the edge
+        graph.record(&source, &discovered[..1]);
+        assert_eq!(graph.len(), 2);
+
+        let edges = graph.edges();
+        assert_eq!(edges[0].source, "45.0.0.1:16111");
+        assert_eq!(edges[0].discovered, "45.0.0.2:16111");
+        assert_eq!(edges[1].discovered, "[2001:4860::1]:16111");
+        assert!(edges.iter().all(|edge| edge.last_reported > 0));
+
+        // Nothing is old enough to drop yet
+        assert_eq!(graph.prune(), 0);
+
+        let expired = CrawlGraph::new(Duration::ZERO);
+        expired.record(&source, &discovered);
+        assert_eq!(expired.prune(), 2);
+        assert!(expired.is_empty());
+    }
+}
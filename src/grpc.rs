@@ -1,8 +1,16 @@
+use crate::constants::DEFAULT_BAN_DURATION;
+use crate::crawler::CrawlerPerformanceStats;
+use crate::dns::DrainState;
 use crate::errors::{KaseederError, Result};
-use crate::manager::AddressManager;
+use crate::manager::{AddressManager, Node};
+use crate::monitor::SystemMonitor;
 use crate::types::NetAddress;
+use futures::Stream;
+use std::net::IpAddr;
+use std::pin::Pin;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, mpsc};
 use tonic::{Request, Response, Status, transport::Server};
 use tracing::info;
 
@@ -12,21 +20,71 @@ pub mod kaseeder {
 }
 
 use kaseeder::{
-    GetAddressStatsRequest, GetAddressStatsResponse, GetAddressesRequest, GetAddressesResponse,
-    GetStatsRequest, GetStatsResponse, HealthCheckRequest, HealthCheckResponse,
+    BanPeerRequest, BanPeerResponse, CountryCount, GetAddressStatsRequest, GetAddressStatsResponse,
+    GetAddressesRequest, GetAddressesResponse, GetCountryStatsRequest, GetCountryStatsResponse,
+    GetCrawlerStatsRequest, GetCrawlerStatsResponse, GetPeerStatusRequest, GetPeerStatusResponse,
+    GetSourceStatsRequest, GetSourceStatsResponse, GetStatsRequest, GetStatsResponse,
+    GetStatusReportRequest, GetStatusReportResponse, HealthCheckRequest, HealthCheckResponse,
+    PruneNowRequest, PruneNowResponse, SetDrainModeRequest, SetDrainModeResponse, SourceCount,
+    StreamAddressesRequest, StreamCrawlGraphRequest, UnbanPeerRequest, UnbanPeerResponse,
     health_check_response::Status as HealthStatus,
     kaseeder_service_server::{KaseederService as KaseederServiceTrait, KaseederServiceServer},
 };
 
+/// Request metadata carrying the admin token
+pub(crate) const ADMIN_TOKEN_METADATA: &str = "x-admin-token";
+
+/// Number of messages buffered ahead of a streaming client
+const STREAM_CHUNK_SIZE: usize = 256;
+
+type AddressStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<kaseeder::NetAddress, Status>> + Send>>;
+type CrawlEdgeStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<kaseeder::CrawlEdge, Status>> + Send>>;
+
 /// gRPC server structure
 pub struct GrpcServer {
     address_manager: Arc<AddressManager>,
+    monitor: Option<Arc<SystemMonitor>>,
+    drain: Option<Arc<DrainState>>,
+    admin_token: Option<String>,
+    crawler_stats: Option<Arc<Mutex<CrawlerPerformanceStats>>>,
 }
 
 impl GrpcServer {
     /// Create a new gRPC server
     pub fn new(address_manager: Arc<AddressManager>) -> Self {
-        Self { address_manager }
+        Self {
+            address_manager,
+            monitor: None,
+            drain: None,
+            admin_token: None,
+            crawler_stats: None,
+        }
+    }
+
+    /// Record request timings into and serve status reports from the system monitor
+    pub fn with_monitor(mut self, monitor: Arc<SystemMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Let clients toggle the DNS server's drain mode
+    pub fn with_drain_state(mut self, drain: Arc<DrainState>) -> Self {
+        self.drain = Some(drain);
+        self
+    }
+
+    /// Serve admin RPCs to clients presenting this token
+    pub fn with_admin_token(mut self, admin_token: Option<String>) -> Self {
+        self.admin_token = admin_token;
+        self
+    }
+
+    /// Serve the crawler's performance statistics
+    pub fn with_crawler_stats(mut self, stats: Arc<Mutex<CrawlerPerformanceStats>>) -> Self {
+        self.crawler_stats = Some(stats);
+        self
     }
 
     /// Start the gRPC server
@@ -34,7 +92,17 @@ impl GrpcServer {
         let addr: std::net::SocketAddr = listen_addr.parse()?;
         info!("Starting gRPC server on {}", addr);
 
-        let service = KaseederServiceImpl::new(self.address_manager.clone());
+        let mut service = KaseederServiceImpl::new(self.address_manager.clone());
+        if let Some(ref monitor) = self.monitor {
+            service = service.with_monitor(monitor.clone());
+        }
+        if let Some(ref drain) = self.drain {
+            service = service.with_drain_state(drain.clone());
+        }
+        service = service.with_admin_token(self.admin_token.clone());
+        if let Some(ref crawler_stats) = self.crawler_stats {
+            service = service.with_crawler_stats(crawler_stats.clone());
+        }
         let server = KaseederServiceServer::new(service);
 
         Server::builder()
@@ -55,7 +123,8 @@ impl GrpcServer {
             "active_nodes": stats.active_nodes.load(std::sync::atomic::Ordering::Relaxed),
             "failed_connections": stats.failed_connections.load(std::sync::atomic::Ordering::Relaxed),
             "successful_connections": stats.successful_connections.load(std::sync::atomic::Ordering::Relaxed),
-            "last_update": stats.last_update.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()
+            "last_update": stats.last_update.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
+            "time_to_first_good_peer_ms": stats.time_to_first_good_peer_ms.load(std::sync::atomic::Ordering::Relaxed)
         })
     }
 
@@ -107,6 +176,10 @@ impl GrpcServer {
 pub struct KaseederServiceImpl {
     address_manager: Arc<AddressManager>,
     start_time: SystemTime,
+    monitor: Option<Arc<SystemMonitor>>,
+    drain: Option<Arc<DrainState>>,
+    admin_token: Option<String>,
+    crawler_stats: Option<Arc<Mutex<CrawlerPerformanceStats>>>,
 }
 
 impl KaseederServiceImpl {
@@ -114,6 +187,87 @@ impl KaseederServiceImpl {
         Self {
             address_manager,
             start_time: SystemTime::now(),
+            monitor: None,
+            drain: None,
+            admin_token: None,
+            crawler_stats: None,
+        }
+    }
+
+    /// Record request timings into the given system monitor
+    pub fn with_monitor(mut self, monitor: Arc<SystemMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Toggle and report the DNS server's drain mode
+    pub fn with_drain_state(mut self, drain: Arc<DrainState>) -> Self {
+        self.drain = Some(drain);
+        self
+    }
+
+    /// Serve admin RPCs to clients presenting this token; they are refused
+    /// when no token is set
+    pub fn with_admin_token(mut self, admin_token: Option<String>) -> Self {
+        self.admin_token = admin_token;
+        self
+    }
+
+    /// Report the crawler's performance statistics
+    pub fn with_crawler_stats(mut self, stats: Arc<Mutex<CrawlerPerformanceStats>>) -> Self {
+        self.crawler_stats = Some(stats);
+        self
+    }
+
+    /// Why the request may not call admin RPCs, if it may not. Every RPC
+    /// that changes state checks this before doing any work.
+    fn admin_rejection<T>(&self, request: &Request<T>) -> Option<Status> {
+        let Some(expected) = self.admin_token.as_deref() else {
+            return Some(Status::permission_denied("Admin RPCs are disabled"));
+        };
+
+        match request
+            .metadata()
+            .get(ADMIN_TOKEN_METADATA)
+            .and_then(|token| token.to_str().ok())
+        {
+            None => Some(Status::permission_denied("Missing admin token")),
+            Some(presented) if !tokens_match(presented, expected) => {
+                Some(Status::permission_denied("Invalid admin token"))
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Report how long a request took to the system monitor, if any
+    async fn record_request(&self, started: Instant) {
+        if let Some(ref monitor) = self.monitor {
+            monitor.record_grpc_request(started.elapsed()).await;
+        }
+    }
+
+    /// Good nodes of the requested address families, IPv4 first
+    fn good_nodes(&self, include_ipv4: bool, include_ipv6: bool, subnetwork_id: &str) -> Vec<Node> {
+        self.address_manager
+            .good_nodes_by_family(include_ipv4, include_ipv6, Some(subnetwork_id))
+    }
+}
+
+/// The address message describing a good node
+impl From<&Node> for kaseeder::NetAddress {
+    fn from(node: &Node) -> Self {
+        Self {
+            ip: node.address.ip.to_string(),
+            port: node.address.port as u32,
+            // Last successful connection; 0 if the peer has not been reached yet
+            last_seen: node
+                .last_success
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            user_agent: node.user_agent.clone().unwrap_or_default(),
+            protocol_version: node.protocol_version,
+            source: node.source.to_string(),
         }
     }
 }
@@ -124,6 +278,7 @@ impl KaseederServiceTrait for KaseederServiceImpl {
         &self,
         request: Request<GetAddressesRequest>,
     ) -> std::result::Result<Response<GetAddressesResponse>, Status> {
+        let started = Instant::now();
         let req = request.into_inner();
         let limit = if req.limit == 0 {
             100
@@ -136,86 +291,67 @@ impl KaseederServiceTrait for KaseederServiceImpl {
             req.limit, req.include_ipv4, req.include_ipv6
         );
 
-        let mut addresses = Vec::new();
-
-        // Get IPv4 addresses
-        if req.include_ipv4 {
-            let ipv4_addresses = self.address_manager.good_addresses(
-                1,
-                true,
-                if req.subnetwork_id.is_empty() {
-                    None
-                } else {
-                    Some(&req.subnetwork_id)
-                },
-            );
-            for addr in ipv4_addresses {
-                if addr.ip.is_ipv4() && addresses.len() < limit {
-                    addresses.push(kaseeder::NetAddress {
-                        ip: addr.ip.to_string(),
-                        port: addr.port as u32,
-                        last_seen: SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
-                        user_agent: "".to_string(), // Will be populated from actual node data
-                        protocol_version: 0,        // Will be populated from actual node data
-                    });
-                }
-            }
-        }
-
-        // Get IPv6 addresses
-        if req.include_ipv6 {
-            let ipv6_addresses = self.address_manager.good_addresses(
-                28,
-                true,
-                if req.subnetwork_id.is_empty() {
-                    None
-                } else {
-                    Some(&req.subnetwork_id)
-                },
-            );
-            for addr in ipv6_addresses {
-                if addr.ip.is_ipv6() && addresses.len() < limit {
-                    addresses.push(kaseeder::NetAddress {
-                        ip: addr.ip.to_string(),
-                        port: addr.port as u32,
-                        last_seen: SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
-                        user_agent: "".to_string(), // Will be populated from actual node data
-                        protocol_version: 0,        // Will be populated from actual node data
-                    });
-                }
-            }
-        }
+        let addresses: Vec<_> = self
+            .good_nodes(req.include_ipv4, req.include_ipv6, &req.subnetwork_id)
+            .iter()
+            .take(limit)
+            .map(kaseeder::NetAddress::from)
+            .collect();
 
         let response = GetAddressesResponse {
-            addresses: addresses
-                .iter()
-                .map(|addr| kaseeder::NetAddress {
-                    ip: addr.ip.to_string(),
-                    port: addr.port as u32,
-                    last_seen: SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
-                    user_agent: "".to_string(), // Will be populated from actual node data
-                    protocol_version: 0,        // Will be populated from actual node data
-                })
-                .collect(),
             total_count: addresses.len() as u64,
+            addresses,
         };
 
+        self.record_request(started).await;
         Ok(Response::new(response))
     }
 
+    type StreamAddressesStream = AddressStream;
+
+    async fn stream_addresses(
+        &self,
+        request: Request<StreamAddressesRequest>,
+    ) -> std::result::Result<Response<Self::StreamAddressesStream>, Status> {
+        let started = Instant::now();
+        let req = request.into_inner();
+        let limit = if req.limit == 0 {
+            usize::MAX
+        } else {
+            req.limit as usize
+        };
+
+        info!(
+            "gRPC StreamAddresses request: limit={}, ipv4={}, ipv6={}",
+            req.limit, req.include_ipv4, req.include_ipv6
+        );
+
+        let nodes = self.good_nodes(req.include_ipv4, req.include_ipv6, &req.subnetwork_id);
+
+        // Messages are built as the client reads, at most one chunk ahead
+        let (tx, rx) = mpsc::channel(STREAM_CHUNK_SIZE);
+        tokio::spawn(async move {
+            for node in nodes.iter().take(limit) {
+                if tx.send(Ok(kaseeder::NetAddress::from(node))).await.is_err() {
+                    // Client went away
+                    break;
+                }
+            }
+        });
+
+        let stream = futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|address| (address, rx))
+        });
+
+        self.record_request(started).await;
+        Ok(Response::new(Box::pin(stream)))
+    }
+
     async fn get_stats(
         &self,
         _request: Request<GetStatsRequest>,
     ) -> std::result::Result<Response<GetStatsResponse>, Status> {
+        let started = Instant::now();
         let stats = self.address_manager.get_stats();
         let uptime = self.start_time.elapsed().unwrap_or_default();
 
@@ -238,6 +374,7 @@ impl KaseederServiceTrait for KaseederServiceImpl {
             uptime: format!("{}s", uptime.as_secs()),
         };
 
+        self.record_request(started).await;
         Ok(Response::new(response))
     }
 
@@ -245,48 +382,24 @@ impl KaseederServiceTrait for KaseederServiceImpl {
         &self,
         _request: Request<GetAddressStatsRequest>,
     ) -> std::result::Result<Response<GetAddressStatsResponse>, Status> {
-        let total = self.address_manager.address_count();
-
-        // Count different types of addresses
-        let mut ipv4_count = 0;
-        let mut ipv6_count = 0;
-        let mut good_count = 0;
-        let mut stale_count = 0;
-
-        for node in self.address_manager.get_all_nodes() {
-            if node.address.ip.is_ipv4() {
-                ipv4_count += 1;
-            } else {
-                ipv6_count += 1;
-            }
-
-            // Classify addresses as good or stale based on last success time
-            let now = SystemTime::now();
-            if let Ok(duration) = now.duration_since(node.last_success) {
-                if duration.as_secs() < 3600 {
-                    // Less than 1 hour
-                    good_count += 1;
-                } else {
-                    stale_count += 1;
-                }
-            } else {
-                // If we can't determine last success time, consider it stale
-                stale_count += 1;
-            }
-        }
+        let started = Instant::now();
+        // Classified like pruning does
+        let summary = self.address_manager.get_address_summary();
 
         let response = GetAddressStatsResponse {
-            total_addresses: total as u64,
-            ipv4_addresses: ipv4_count,
-            ipv6_addresses: ipv6_count,
-            good_addresses: good_count,
-            stale_addresses: stale_count,
+            total_addresses: summary.nodes as u64,
+            ipv4_addresses: summary.ipv4 as u64,
+            ipv6_addresses: summary.ipv6 as u64,
+            good_addresses: summary.good as u64,
+            stale_addresses: summary.stale as u64,
+            bad_addresses: summary.bad as u64,
             timestamp: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs(),
         };
 
+        self.record_request(started).await;
         Ok(Response::new(response))
     }
 
@@ -294,18 +407,326 @@ impl KaseederServiceTrait for KaseederServiceImpl {
         &self,
         _request: Request<HealthCheckRequest>,
     ) -> std::result::Result<Response<HealthCheckResponse>, Status> {
-        let response = HealthCheckResponse {
-            status: HealthStatus::Serving as i32,
-            message: "DNS Seeder service is healthy".to_string(),
+        let started = Instant::now();
+        let draining = self.drain.as_ref().is_some_and(|drain| drain.is_draining());
+        // Report NOT_SERVING while draining so health-based rotation drops
+        // the seeder too
+        let response = if draining {
+            HealthCheckResponse {
+                status: HealthStatus::NotServing as i32,
+                message: "DNS server is draining for maintenance".to_string(),
+                draining,
+            }
+        } else {
+            HealthCheckResponse {
+                status: HealthStatus::Serving as i32,
+                message: "DNS Seeder service is healthy".to_string(),
+                draining,
+            }
+        };
+
+        self.record_request(started).await;
+        Ok(Response::new(response))
+    }
+
+    async fn get_status_report(
+        &self,
+        _request: Request<GetStatusReportRequest>,
+    ) -> std::result::Result<Response<GetStatusReportResponse>, Status> {
+        let started = Instant::now();
+        let monitor = self
+            .monitor
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("System monitor is not running"))?;
+        let report = monitor.get_status_report().await;
+
+        let response = GetStatusReportResponse {
+            uptime_seconds: report.uptime_seconds,
+            is_healthy: report.health.is_healthy,
+            issues: report.health.issues,
+            cpu_usage: report.performance.cpu_usage,
+            memory_usage: report.performance.memory_usage,
+            dns_queries_per_second: report.performance.dns_queries_per_second,
+            grpc_requests_per_second: report.performance.grpc_requests_per_second,
+            avg_response_time_ms: report.performance.avg_response_time_ms,
+            timestamp: report
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+
+        self.record_request(started).await;
+        Ok(Response::new(response))
+    }
+
+    async fn get_country_stats(
+        &self,
+        _request: Request<GetCountryStatsRequest>,
+    ) -> std::result::Result<Response<GetCountryStatsResponse>, Status> {
+        let started = Instant::now();
+        let counts = self
+            .address_manager
+            .country_counts()
+            .ok_or_else(|| Status::failed_precondition("Country counts are not exposed"))?;
+
+        let response = GetCountryStatsResponse {
+            total_good: counts.values().sum::<usize>() as u64,
+            countries: counts
+                .into_iter()
+                .map(|(country, count)| CountryCount {
+                    country,
+                    good_peers: count as u64,
+                })
+                .collect(),
+        };
+
+        self.record_request(started).await;
+        Ok(Response::new(response))
+    }
+
+    async fn get_source_stats(
+        &self,
+        _request: Request<GetSourceStatsRequest>,
+    ) -> std::result::Result<Response<GetSourceStatsResponse>, Status> {
+        let started = Instant::now();
+        let counts = self
+            .address_manager
+            .source_counts()
+            .ok_or_else(|| Status::failed_precondition("Source counts are not exposed"))?;
+
+        let response = GetSourceStatsResponse {
+            total_good: counts.values().sum::<usize>() as u64,
+            sources: counts
+                .into_iter()
+                .map(|(source, count)| SourceCount {
+                    source: source.to_string(),
+                    good_peers: count as u64,
+                })
+                .collect(),
+        };
+
+        self.record_request(started).await;
+        Ok(Response::new(response))
+    }
+
+    type StreamCrawlGraphStream = CrawlEdgeStream;
+
+    async fn stream_crawl_graph(
+        &self,
+        _request: Request<StreamCrawlGraphRequest>,
+    ) -> std::result::Result<Response<Self::StreamCrawlGraphStream>, Status> {
+        let started = Instant::now();
+        let crawl_graph = self
+            .address_manager
+            .crawl_graph()
+            .ok_or_else(|| Status::failed_precondition("Crawl graph recording is disabled"))?;
+        let edges = crawl_graph.edges();
+        info!("gRPC StreamCrawlGraph request: {} edges", edges.len());
+
+        let (tx, rx) = mpsc::channel(STREAM_CHUNK_SIZE);
+        tokio::spawn(async move {
+            for edge in edges {
+                let edge = kaseeder::CrawlEdge {
+                    source: edge.source,
+                    discovered: edge.discovered,
+                    last_reported: edge.last_reported,
+                };
+                if tx.send(Ok(edge)).await.is_err() {
+                    // Client went away
+                    break;
+                }
+            }
+        });
+
+        let stream = futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|edge| (edge, rx))
+        });
+
+        self.record_request(started).await;
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn set_drain_mode(
+        &self,
+        request: Request<SetDrainModeRequest>,
+    ) -> std::result::Result<Response<SetDrainModeResponse>, Status> {
+        let started = Instant::now();
+        if let Some(rejection) = self.admin_rejection(&request) {
+            return Err(rejection);
+        }
+        let drain = self
+            .drain
+            .as_ref()
+            .ok_or_else(|| Status::failed_precondition("DNS server is not running"))?;
+        let draining = request.into_inner().draining;
+        info!("gRPC SetDrainMode request: draining={}", draining);
+
+        let response = SetDrainModeResponse {
+            draining,
+            was_draining: drain.set_draining(draining),
+        };
+
+        self.record_request(started).await;
+        Ok(Response::new(response))
+    }
+
+    async fn ban_peer(
+        &self,
+        request: Request<BanPeerRequest>,
+    ) -> std::result::Result<Response<BanPeerResponse>, Status> {
+        let started = Instant::now();
+        if let Some(rejection) = self.admin_rejection(&request) {
+            return Err(rejection);
+        }
+        let request = request.into_inner();
+        let ip: IpAddr =
+            request.ip.trim().parse().map_err(|_| {
+                Status::invalid_argument(format!("Invalid IP address: {}", request.ip))
+            })?;
+        let duration = match request.duration_secs {
+            0 => DEFAULT_BAN_DURATION,
+            secs => Duration::from_secs(secs),
+        };
+        info!("gRPC BanPeer request: {} for {:?}", ip, duration);
+
+        self.address_manager
+            .ban(ip, duration)
+            .map_err(|e| Status::internal(format!("Failed to ban {}: {}", ip, e)))?;
+        let expires_at = (SystemTime::now() + duration)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        self.record_request(started).await;
+        Ok(Response::new(BanPeerResponse {
+            ip: ip.to_string(),
+            expires_at,
+        }))
+    }
+
+    async fn unban_peer(
+        &self,
+        request: Request<UnbanPeerRequest>,
+    ) -> std::result::Result<Response<UnbanPeerResponse>, Status> {
+        let started = Instant::now();
+        if let Some(rejection) = self.admin_rejection(&request) {
+            return Err(rejection);
+        }
+        let ip = request.into_inner().ip;
+        let ip: IpAddr = ip
+            .trim()
+            .parse()
+            .map_err(|_| Status::invalid_argument(format!("Invalid IP address: {}", ip)))?;
+        info!("gRPC UnbanPeer request: {}", ip);
+
+        let was_banned = self
+            .address_manager
+            .unban(&ip)
+            .map_err(|e| Status::internal(format!("Failed to unban {}: {}", ip, e)))?;
+
+        self.record_request(started).await;
+        Ok(Response::new(UnbanPeerResponse { was_banned }))
+    }
+
+    async fn get_peer_status(
+        &self,
+        request: Request<GetPeerStatusRequest>,
+    ) -> std::result::Result<Response<GetPeerStatusResponse>, Status> {
+        let started = Instant::now();
+        let request = request.into_inner();
+        let ip: IpAddr =
+            request.ip.trim().parse().map_err(|_| {
+                Status::invalid_argument(format!("Invalid IP address: {}", request.ip))
+            })?;
+        let port = match request.port {
+            0 => self.address_manager.default_port(),
+            port => u16::try_from(port)
+                .map_err(|_| Status::invalid_argument(format!("Invalid port: {}", port)))?,
+        };
+
+        let peer_status = self
+            .address_manager
+            .peer_status(&NetAddress::new(ip, port))
+            .ok_or_else(|| Status::not_found(format!("Unknown peer {}:{}", ip, port)))?;
+
+        self.record_request(started).await;
+        Ok(Response::new(GetPeerStatusResponse {
+            status: peer_status.status.as_str().to_string(),
+            reason: peer_status.reason.unwrap_or_default(),
+            last_success: peer_status
+                .last_success
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            consecutive_failures: peer_status.consecutive_failures,
+            last_error: peer_status.last_error.unwrap_or_default(),
+        }))
+    }
+
+    async fn get_crawler_stats(
+        &self,
+        _request: Request<GetCrawlerStatsRequest>,
+    ) -> std::result::Result<Response<GetCrawlerStatsResponse>, Status> {
+        let started = Instant::now();
+        let crawler_stats = self
+            .crawler_stats
+            .as_ref()
+            .ok_or_else(|| Status::failed_precondition("Crawler is not running"))?;
+
+        let response = {
+            let stats = crawler_stats.lock().await;
+            GetCrawlerStatsResponse {
+                total_polls: stats.total_polls,
+                successful_polls: stats.successful_polls,
+                failed_polls: stats.failed_polls,
+                average_poll_time_ms: stats.average_poll_time_ms,
+                last_batch_size: stats.last_poll_batch_size as u64,
+                addresses_found: stats.total_addresses_found,
+            }
         };
 
+        self.record_request(started).await;
         Ok(Response::new(response))
     }
+
+    async fn prune_now(
+        &self,
+        request: Request<PruneNowRequest>,
+    ) -> std::result::Result<Response<PruneNowResponse>, Status> {
+        let started = Instant::now();
+        if let Some(rejection) = self.admin_rejection(&request) {
+            return Err(rejection);
+        }
+        info!("gRPC PruneNow request");
+
+        let summary = self.address_manager.prune_peers();
+
+        self.record_request(started).await;
+        Ok(Response::new(PruneNowResponse {
+            pruned: summary.pruned as u64,
+            good: summary.good as u64,
+            stale: summary.stale as u64,
+            bad: summary.bad as u64,
+        }))
+    }
+}
+
+/// Compare admin tokens in time that depends only on their lengths, so a
+/// caller cannot find the token one matching byte at a time
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::DEFAULT_MAX_CONSECUTIVE_FAILURES;
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -331,4 +752,439 @@ mod tests {
         let addresses = _server.get_addresses(10);
         assert_eq!(addresses.len(), 0); // Newly created address manager should be empty
     }
+
+    #[tokio::test]
+    async fn test_get_addresses_reports_node_details() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+        let peer = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        address_manager.add_addresses(vec![peer.clone()], 16111, false);
+
+        // Simulated successful poll
+        address_manager.record_connection_result(&peer, true, None);
+        address_manager.good(&peer, Some("/kaspad:1.0.0/"), None);
+        address_manager.set_protocol_version(&peer, 7);
+
+        let service = KaseederServiceImpl::new(address_manager);
+        let response = service
+            .get_addresses(Request::new(GetAddressesRequest {
+                limit: 10,
+                include_ipv4: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.addresses.len(), 1);
+        let address = &response.addresses[0];
+        assert_eq!(address.ip, "45.0.0.1");
+        assert!(address.last_seen > 0);
+        assert_eq!(address.user_agent, "/kaspad:1.0.0/");
+        assert_eq!(address.protocol_version, 7);
+        assert_eq!(address.source, "gossip");
+    }
+
+    #[tokio::test]
+    async fn test_address_stats_classify_good_stale_and_bad() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+        let good = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let stale = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        let bad = NetAddress::new("2001:4860::1".parse().unwrap(), 16111);
+        address_manager.add_addresses(vec![good.clone(), stale, bad.clone()], 16111, false);
+
+        address_manager.good(&good, None, None);
+        // Just failed, so not due for a retry yet
+        address_manager.record_connection_result(&bad, false, Some("refused".to_string()));
+
+        let service = KaseederServiceImpl::new(address_manager.clone());
+        let stats = service
+            .get_address_stats(Request::new(GetAddressStatsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(stats.total_addresses, 3);
+        assert_eq!(stats.ipv4_addresses, 2);
+        assert_eq!(stats.ipv6_addresses, 1);
+        assert_eq!(stats.good_addresses, 1);
+        assert_eq!(stats.stale_addresses, 1);
+        assert_eq!(stats.bad_addresses, 1);
+
+        // Same numbers as the address manager's own summary
+        let summary = address_manager.get_address_summary();
+        assert_eq!((summary.good, summary.stale, summary.bad), (1, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_status_report_records_grpc_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 0).unwrap());
+        let service = KaseederServiceImpl::new(address_manager.clone());
+        let status = service
+            .get_status_report(Request::new(GetStatusReportRequest {}))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        let monitor = Arc::new(SystemMonitor::new());
+        let service = KaseederServiceImpl::new(address_manager).with_monitor(monitor);
+        service
+            .health_check(Request::new(HealthCheckRequest {}))
+            .await
+            .unwrap();
+
+        let report = service
+            .get_status_report(Request::new(GetStatusReportRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(report.is_healthy);
+        assert!(report.issues.is_empty());
+        assert!(report.grpc_requests_per_second > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_addresses_yields_all_good_peers() {
+        use futures::StreamExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+        let peers: Vec<_> = (0..STREAM_CHUNK_SIZE * 2 + 10)
+            .map(|i| {
+                NetAddress::new(
+                    format!("45.0.{}.{}", i / 250, i % 250 + 1).parse().unwrap(),
+                    16111,
+                )
+            })
+            .collect();
+        address_manager.add_addresses(peers.clone(), 16111, false);
+        for peer in &peers {
+            address_manager.good(peer, None, None);
+        }
+        let ipv6_peer = NetAddress::new("2001:4860::1".parse().unwrap(), 16111);
+        address_manager.add_addresses(vec![ipv6_peer.clone()], 16111, false);
+        address_manager.good(&ipv6_peer, None, None);
+
+        let service = KaseederServiceImpl::new(address_manager);
+        let stream = |limit, include_ipv6| {
+            service.stream_addresses(Request::new(StreamAddressesRequest {
+                limit,
+                include_ipv4: true,
+                include_ipv6,
+                ..Default::default()
+            }))
+        };
+
+        let addresses: Vec<_> = stream(0, false)
+            .await
+            .unwrap()
+            .into_inner()
+            .map(|address| address.unwrap())
+            .collect()
+            .await;
+        assert_eq!(addresses.len(), peers.len());
+        assert!(addresses.iter().all(|address| !address.ip.contains(':')));
+
+        // IPv4 first, then IPv6, cut off at the limit
+        let all: Vec<_> = stream(0, true).await.unwrap().into_inner().collect().await;
+        assert_eq!(all.len(), peers.len() + 1);
+        assert_eq!(all.last().unwrap().as_ref().unwrap().ip, "2001:4860::1");
+        let limited: Vec<_> = stream(5, true).await.unwrap().into_inner().collect().await;
+        assert_eq!(limited.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_stream_crawl_graph_exports_recorded_edges() {
+        use crate::graph::CrawlGraph;
+        use futures::StreamExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = AddressManager::new(&test_app_dir_str, 16111).unwrap();
+
+        let service = KaseederServiceImpl::new(Arc::new(address_manager.clone()));
+        let status = service
+            .stream_crawl_graph(Request::new(StreamCrawlGraphRequest {}))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let crawl_graph = Arc::new(CrawlGraph::new(std::time::Duration::from_secs(60)));
+        let address_manager = address_manager.with_crawl_graph(Some(crawl_graph.clone()));
+        let source = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        crawl_graph.record(
+            &source,
+            &[
+                NetAddress::new("45.0.0.2".parse().unwrap(), 16111),
+                NetAddress::new("45.0.0.3".parse().unwrap(), 16111),
+            ],
+        );
+
+        let service = KaseederServiceImpl::new(Arc::new(address_manager));
+        let edges: Vec<_> = service
+            .stream_crawl_graph(Request::new(StreamCrawlGraphRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .map(|edge| edge.unwrap())
+            .collect()
+            .await;
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0].source, "45.0.0.1:16111");
+        assert_eq!(edges[1].discovered, "45.0.0.3:16111");
+    }
+
+    /// Wrap a message in a request, presenting `token` as the admin token
+    fn admin_request<T>(message: T, token: Option<&str>) -> Request<T> {
+        let mut request = Request::new(message);
+        if let Some(token) = token {
+            request
+                .metadata_mut()
+                .insert(ADMIN_TOKEN_METADATA, token.parse().unwrap());
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_ban_and_unban_peer() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+        let service = KaseederServiceImpl::new(address_manager.clone())
+            .with_admin_token(Some("secret".to_string()));
+        let ip: IpAddr = "45.0.0.1".parse().unwrap();
+
+        let response = service
+            .ban_peer(admin_request(
+                BanPeerRequest {
+                    ip: "45.0.0.1".to_string(),
+                    duration_secs: 0,
+                },
+                Some("secret"),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.ip, "45.0.0.1");
+        assert!(response.expires_at > DEFAULT_BAN_DURATION.as_secs());
+        assert!(address_manager.is_banned(&ip));
+
+        let unban = |ip: &str| {
+            service.unban_peer(admin_request(
+                UnbanPeerRequest { ip: ip.to_string() },
+                Some("secret"),
+            ))
+        };
+        assert!(unban("45.0.0.1").await.unwrap().into_inner().was_banned);
+        assert!(!address_manager.is_banned(&ip));
+        assert!(!unban("45.0.0.1").await.unwrap().into_inner().was_banned);
+
+        let status = unban("not-an-ip").await.err().unwrap();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_get_peer_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+        let peer = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        address_manager.add_addresses(vec![peer.clone()], 16111, false);
+        address_manager.record_connection_result(&peer, false, Some("refused".to_string()));
+
+        let service = KaseederServiceImpl::new(address_manager);
+        let peer_status = |ip: &str, port| {
+            service.get_peer_status(Request::new(GetPeerStatusRequest {
+                ip: ip.to_string(),
+                port,
+            }))
+        };
+
+        // Port 0 looks the peer up on the default port
+        let response = peer_status("45.0.0.1", 0).await.unwrap().into_inner();
+        assert_eq!(response.status, "bad");
+        assert_eq!(response.last_success, 0);
+        assert_eq!(response.consecutive_failures, 1);
+        assert_eq!(response.last_error, "refused");
+        // Reasons are only recorded when enabled
+        assert_eq!(response.reason, "");
+
+        let status = peer_status("45.0.0.2", 16111).await.err().unwrap();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        let status = peer_status("45.0.0.1", 70000).await.err().unwrap();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    async fn prune_now(
+        service: &KaseederServiceImpl,
+        token: Option<&str>,
+    ) -> std::result::Result<PruneNowResponse, Status> {
+        service
+            .prune_now(admin_request(PruneNowRequest {}, token))
+            .await
+            .map(Response::into_inner)
+    }
+
+    #[tokio::test]
+    async fn test_prune_now_requires_admin_token() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+        let good = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let expired = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        let stale = NetAddress::new("45.0.0.3".parse().unwrap(), 16111);
+        address_manager.add_addresses(vec![good.clone(), expired.clone(), stale], 16111, false);
+        address_manager.record_connection_result(&good, true, None);
+        // Enough consecutive failures expire the entry
+        for _ in 0..DEFAULT_MAX_CONSECUTIVE_FAILURES {
+            address_manager.record_connection_result(&expired, false, None);
+        }
+
+        let disabled = KaseederServiceImpl::new(address_manager.clone());
+        let status = prune_now(&disabled, Some("secret")).await.err().unwrap();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let service = KaseederServiceImpl::new(address_manager.clone())
+            .with_admin_token(Some("secret".to_string()));
+        let status = prune_now(&service, None).await.err().unwrap();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        let status = prune_now(&service, Some("guess")).await.err().unwrap();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert!(address_manager.get_node(&expired).is_some());
+
+        let response = prune_now(&service, Some("secret")).await.unwrap();
+        assert_eq!(response.pruned, 1);
+        assert_eq!(response.good, 1);
+        assert_eq!(response.stale, 1);
+        assert_eq!(response.bad, 0);
+        assert!(address_manager.get_node(&expired).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_crawler_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+
+        let service = KaseederServiceImpl::new(address_manager.clone());
+        let status = service
+            .get_crawler_stats(Request::new(GetCrawlerStatsRequest {}))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let crawler_stats = Arc::new(Mutex::new(CrawlerPerformanceStats {
+            total_polls: 10,
+            successful_polls: 7,
+            failed_polls: 3,
+            total_addresses_found: 420,
+            average_poll_time_ms: 125.5,
+            last_poll_batch_size: 24,
+            memory_usage_bytes: 0,
+        }));
+        let service =
+            KaseederServiceImpl::new(address_manager).with_crawler_stats(crawler_stats.clone());
+        let response = service
+            .get_crawler_stats(Request::new(GetCrawlerStatsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.total_polls, 10);
+        assert_eq!(response.successful_polls, 7);
+        assert_eq!(response.failed_polls, 3);
+        assert_eq!(response.average_poll_time_ms, 125.5);
+        assert_eq!(response.last_batch_size, 24);
+        assert_eq!(response.addresses_found, 420);
+
+        // Served from the live statistics, not a snapshot
+        crawler_stats.lock().await.total_polls = 11;
+        let response = service
+            .get_crawler_stats(Request::new(GetCrawlerStatsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.total_polls, 11);
+    }
+
+    #[tokio::test]
+    async fn test_state_changing_rpcs_require_admin_token() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_app_dir_str = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&test_app_dir_str, 16111).unwrap());
+        let drain = Arc::new(DrainState::new(false));
+        let ip: IpAddr = "45.0.0.1".parse().unwrap();
+        address_manager.ban(ip, DEFAULT_BAN_DURATION).unwrap();
+
+        let disabled =
+            KaseederServiceImpl::new(address_manager.clone()).with_drain_state(drain.clone());
+        let service = KaseederServiceImpl::new(address_manager.clone())
+            .with_drain_state(drain.clone())
+            .with_admin_token(Some("secret".to_string()));
+
+        for (service, token) in [
+            (&disabled, Some("secret")),
+            (&service, None),
+            (&service, Some("guess")),
+            (&service, Some("secreT")),
+            (&service, Some("secret2")),
+        ] {
+            let drain_status = service
+                .set_drain_mode(admin_request(SetDrainModeRequest { draining: true }, token))
+                .await
+                .err()
+                .unwrap();
+            let ban_status = service
+                .ban_peer(admin_request(
+                    BanPeerRequest {
+                        ip: "45.0.0.2".to_string(),
+                        duration_secs: 0,
+                    },
+                    token,
+                ))
+                .await
+                .err()
+                .unwrap();
+            let unban_status = service
+                .unban_peer(admin_request(
+                    UnbanPeerRequest {
+                        ip: "45.0.0.1".to_string(),
+                    },
+                    token,
+                ))
+                .await
+                .err()
+                .unwrap();
+            for status in [drain_status, ban_status, unban_status] {
+                assert_eq!(status.code(), tonic::Code::PermissionDenied);
+            }
+        }
+
+        // Nothing changed
+        assert!(!drain.is_draining());
+        assert!(address_manager.is_banned(&ip));
+        assert!(!address_manager.is_banned(&"45.0.0.2".parse().unwrap()));
+
+        let response = service
+            .set_drain_mode(admin_request(
+                SetDrainModeRequest { draining: true },
+                Some("secret"),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.draining && !response.was_draining);
+        assert!(drain.is_draining());
+    }
 }
@@ -0,0 +1,375 @@
+use crate::errors::{KaseederError, Result};
+use crate::grpc::kaseeder;
+use crate::manager::{AddressManager, Node};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+/// Number of peers returned when the request does not set a limit (same as gRPC)
+const DEFAULT_PEERS_LIMIT: usize = 100;
+
+/// HTTP server exposing the good peer list and address statistics as JSON
+pub struct HttpServer {
+    address_manager: Arc<AddressManager>,
+}
+
+/// Query parameters accepted by `GET /peers`
+#[derive(Debug, Default, Deserialize)]
+pub struct PeersQuery {
+    /// `ipv4` or `ipv6`; both families are returned when unset
+    pub family: Option<String>,
+    pub limit: Option<usize>,
+    pub subnetwork_id: Option<String>,
+}
+
+/// A single peer in the `GET /peers` response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerEntry {
+    pub ip: String,
+    pub port: u16,
+    pub source: String,
+}
+
+/// Query parameters accepted by `GET /api/addresses`
+#[derive(Debug, Default, Deserialize)]
+pub struct AddressesQuery {
+    /// DNS record type, `A` or `AAAA`; both families are returned when unset
+    #[serde(rename = "type")]
+    pub record_type: Option<String>,
+    pub limit: Option<usize>,
+    pub subnetwork_id: Option<String>,
+}
+
+/// A single address in the `GET /api/addresses` response, mirroring the
+/// gRPC `NetAddress` message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressEntry {
+    pub ip: String,
+    pub port: u16,
+    /// Last successful connection in Unix seconds; 0 if never reached
+    pub last_seen: u64,
+    pub user_agent: String,
+    pub protocol_version: u32,
+    pub source: String,
+}
+
+/// `GET /api/addresses` response, mirroring the gRPC `GetAddressesResponse`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressesResponse {
+    pub addresses: Vec<AddressEntry>,
+    pub total_count: u64,
+}
+
+/// `GET /api/stats` response, mirroring the gRPC `GetAddressStatsResponse`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsResponse {
+    pub total_addresses: u64,
+    pub ipv4_addresses: u64,
+    pub ipv6_addresses: u64,
+    pub good_addresses: u64,
+    pub stale_addresses: u64,
+    pub bad_addresses: u64,
+    pub timestamp: u64,
+}
+
+/// Built from the gRPC message so both APIs describe a node the same way
+impl From<&Node> for AddressEntry {
+    fn from(node: &Node) -> Self {
+        let address = kaseeder::NetAddress::from(node);
+        Self {
+            ip: address.ip,
+            port: node.address.port,
+            last_seen: address.last_seen,
+            user_agent: address.user_agent,
+            protocol_version: address.protocol_version,
+            source: address.source,
+        }
+    }
+}
+
+impl HttpServer {
+    /// Create a new HTTP server
+    pub fn new(address_manager: Arc<AddressManager>) -> Self {
+        Self { address_manager }
+    }
+
+    /// Build the request router
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/peers", get(get_peers))
+            .route("/api/addresses", get(get_addresses))
+            .route("/api/stats", get(get_stats))
+            .with_state(self.address_manager.clone())
+    }
+
+    /// Start the HTTP server
+    pub async fn start(&self, listen_addr: &str) -> Result<()> {
+        let addr: std::net::SocketAddr = listen_addr.parse()?;
+        info!("Starting HTTP server on {}", addr);
+
+        axum::Server::bind(&addr)
+            .serve(self.router().into_make_service())
+            .await
+            .map_err(|e| KaseederError::Service(format!("HTTP server error: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+/// `GET /peers?family=ipv4&limit=N`
+async fn get_peers(
+    State(address_manager): State<Arc<AddressManager>>,
+    Query(query): Query<PeersQuery>,
+) -> std::result::Result<Json<Vec<PeerEntry>>, (StatusCode, String)> {
+    let (include_ipv4, include_ipv6) = match query.family.as_deref() {
+        None => (true, true),
+        Some("ipv4") => (true, false),
+        Some("ipv6") => (false, true),
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("unknown family '{}', expected ipv4 or ipv6", other),
+            ));
+        }
+    };
+
+    let peers = address_manager
+        .good_nodes_by_family(include_ipv4, include_ipv6, query.subnetwork_id.as_deref())
+        .iter()
+        .take(limit_or_default(query.limit))
+        .map(|node| PeerEntry {
+            ip: node.address.ip.to_string(),
+            port: node.address.port,
+            source: node.source.to_string(),
+        })
+        .collect();
+
+    Ok(Json(peers))
+}
+
+/// `GET /api/addresses?type=A&limit=N`, the JSON counterpart of gRPC `GetAddresses`
+async fn get_addresses(
+    State(address_manager): State<Arc<AddressManager>>,
+    Query(query): Query<AddressesQuery>,
+) -> std::result::Result<Json<AddressesResponse>, (StatusCode, String)> {
+    let (include_ipv4, include_ipv6) = match query.record_type.as_deref() {
+        None => (true, true),
+        Some(t) if t.eq_ignore_ascii_case("A") => (true, false),
+        Some(t) if t.eq_ignore_ascii_case("AAAA") => (false, true),
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("unknown type '{}', expected A or AAAA", other),
+            ));
+        }
+    };
+
+    let addresses: Vec<AddressEntry> = address_manager
+        .good_nodes_by_family(include_ipv4, include_ipv6, query.subnetwork_id.as_deref())
+        .iter()
+        .take(limit_or_default(query.limit))
+        .map(AddressEntry::from)
+        .collect();
+
+    Ok(Json(AddressesResponse {
+        total_count: addresses.len() as u64,
+        addresses,
+    }))
+}
+
+/// `GET /api/stats`, the JSON counterpart of gRPC `GetAddressStats`
+async fn get_stats(State(address_manager): State<Arc<AddressManager>>) -> Json<StatsResponse> {
+    let summary = address_manager.get_address_summary();
+
+    Json(StatsResponse {
+        total_addresses: summary.nodes as u64,
+        ipv4_addresses: summary.ipv4 as u64,
+        ipv6_addresses: summary.ipv6 as u64,
+        good_addresses: summary.good as u64,
+        stale_addresses: summary.stale as u64,
+        bad_addresses: summary.bad as u64,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    })
+}
+
+/// Requested result size, falling back to the default when unset or zero
+fn limit_or_default(limit: Option<usize>) -> usize {
+    match limit {
+        None | Some(0) => DEFAULT_PEERS_LIMIT,
+        Some(limit) => limit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NetAddress;
+    use axum::body::{Body, HttpBody};
+    use axum::http::Request;
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    fn test_server(temp_dir: &TempDir) -> HttpServer {
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+
+        let peers = vec![
+            NetAddress::new("45.0.0.1".parse().unwrap(), 16111),
+            NetAddress::new("45.0.0.2".parse().unwrap(), 16111),
+            NetAddress::new("45.0.0.3".parse().unwrap(), 16111),
+            NetAddress::new("2001:4860::1".parse().unwrap(), 16111),
+        ];
+        address_manager.add_addresses(peers.clone(), 16111, false);
+        for peer in &peers {
+            address_manager.good(peer, None, None);
+        }
+
+        HttpServer::new(address_manager)
+    }
+
+    async fn get(server: &HttpServer, uri: &str) -> (StatusCode, Vec<u8>) {
+        let response = server
+            .router()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let mut body = response.into_body();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.data().await {
+            bytes.extend_from_slice(&chunk.unwrap());
+        }
+        (status, bytes)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(server: &HttpServer, uri: &str) -> T {
+        let (status, body) = get(server, uri).await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn get_peers(server: &HttpServer, uri: &str) -> Vec<PeerEntry> {
+        get_json(server, uri).await
+    }
+
+    #[tokio::test]
+    async fn test_peers_endpoint_filters_and_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+
+        let all = get_peers(&server, "/peers").await;
+        assert_eq!(all.len(), 4);
+        assert!(all.iter().all(|peer| peer.port == 16111));
+        assert!(all.iter().all(|peer| peer.source == "gossip"));
+
+        let ipv4 = get_peers(&server, "/peers?family=ipv4").await;
+        assert_eq!(ipv4.len(), 3);
+        assert!(ipv4.iter().all(|peer| peer.ip.starts_with("45.0.0.")));
+
+        let ipv6 = get_peers(&server, "/peers?family=ipv6").await;
+        assert_eq!(
+            ipv6,
+            vec![PeerEntry {
+                ip: "2001:4860::1".to_string(),
+                port: 16111,
+                source: "gossip".to_string(),
+            }]
+        );
+
+        assert_eq!(
+            get_peers(&server, "/peers?family=ipv4&limit=2").await.len(),
+            2
+        );
+        assert_eq!(get_peers(&server, "/peers?limit=0").await.len(), 4);
+
+        let (status, _) = get(&server, "/peers?family=ipx").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_api_addresses_and_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+
+        let all = get_json::<AddressesResponse>(&server, "/api/addresses").await;
+        assert_eq!(all.total_count, 4);
+        assert_eq!(all.addresses.len(), 4);
+        assert!(all.addresses.iter().all(|address| address.last_seen > 0));
+
+        let a = get_json::<AddressesResponse>(&server, "/api/addresses?type=A&limit=2").await;
+        assert_eq!(a.total_count, 2);
+        assert!(
+            a.addresses
+                .iter()
+                .all(|address| address.ip.starts_with("45.0.0."))
+        );
+
+        let aaaa = get_json::<AddressesResponse>(&server, "/api/addresses?type=aaaa").await;
+        assert_eq!(aaaa.total_count, 1);
+        assert_eq!(aaaa.addresses[0].ip, "2001:4860::1");
+        assert_eq!(aaaa.addresses[0].source, "gossip");
+
+        let (status, _) = get(&server, "/api/addresses?type=MX").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let stats: StatsResponse = get_json(&server, "/api/stats").await;
+        assert_eq!(stats.total_addresses, 4);
+        assert_eq!(stats.ipv4_addresses, 3);
+        assert_eq!(stats.ipv6_addresses, 1);
+        assert_eq!(stats.good_addresses, 4);
+        assert!(stats.timestamp > 0);
+    }
+
+    #[tokio::test]
+    async fn test_api_addresses_respects_subnetwork() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        let subnetwork_id = "0100000000000000000000000000000000000000";
+        let on_subnetwork = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        server
+            .address_manager
+            .good(&on_subnetwork, None, Some(subnetwork_id));
+
+        let uri = format!("/api/addresses?subnetwork_id={}", subnetwork_id);
+        let filtered = get_json::<AddressesResponse>(&server, &uri).await;
+        assert_eq!(filtered.total_count, 1);
+        assert_eq!(filtered.addresses[0].ip, "45.0.0.1");
+
+        let all = get_json::<AddressesResponse>(&server, "/api/addresses").await;
+        assert_eq!(all.total_count, 4);
+    }
+
+    #[tokio::test]
+    async fn test_peers_endpoint_respects_subnetwork() {
+        let temp_dir = TempDir::new().unwrap();
+        let server = test_server(&temp_dir);
+        let subnetwork_id = "0100000000000000000000000000000000000000";
+        let on_subnetwork = NetAddress::new("2001:4860::1".parse().unwrap(), 16111);
+        server
+            .address_manager
+            .good(&on_subnetwork, None, Some(subnetwork_id));
+
+        let uri = format!("/peers?subnetwork_id={}", subnetwork_id);
+        let peers = get_peers(&server, &uri).await;
+        assert_eq!(
+            peers,
+            vec![PeerEntry {
+                ip: "2001:4860::1".to_string(),
+                port: 16111,
+                source: "gossip".to_string(),
+            }]
+        );
+
+        let uri = format!("/peers?family=ipv4&subnetwork_id={}", subnetwork_id);
+        assert!(get_peers(&server, &uri).await.is_empty());
+        assert_eq!(get_peers(&server, "/peers?subnetwork_id=").await.len(), 4);
+    }
+}
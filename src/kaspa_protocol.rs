@@ -1,11 +1,12 @@
+use crate::errors::{KaseederError, Result};
 use kaspa_consensus_core::config::{Config as ConsensusConfig, params::Params};
 use kaspa_consensus_core::network::{NetworkId, NetworkType};
 use std::sync::Arc;
+use tracing::error;
 
-/// Create consensus configuration
-pub fn create_consensus_config(testnet: bool, net_suffix: u16) -> Arc<ConsensusConfig> {
-    // Create correct network ID based on network type and suffix
-    let network_id = if testnet {
+/// Network ID for the configured network
+pub fn network_id(testnet: bool, net_suffix: u16) -> NetworkId {
+    if testnet {
         if net_suffix == 0 {
             // Default testnet (testnet-10)
             NetworkId::with_suffix(NetworkType::Testnet, 10)
@@ -18,10 +19,18 @@ pub fn create_consensus_config(testnet: bool, net_suffix: u16) -> Arc<ConsensusC
         }
     } else {
         NetworkId::new(NetworkType::Mainnet)
-    };
+    }
+}
+
+/// Network name peers of the configured network advertise, e.g. `kaspa-testnet-10`
+pub fn expected_network_name(testnet: bool, net_suffix: u16) -> String {
+    format!("kaspa-{}", network_id(testnet, net_suffix))
+}
 
+/// Create consensus configuration
+pub fn create_consensus_config(testnet: bool, net_suffix: u16) -> Arc<ConsensusConfig> {
     // Create parameters from network ID
-    let params = Params::from(network_id);
+    let params = Params::from(network_id(testnet, net_suffix));
 
     // Create consensus configuration
     let config = ConsensusConfig::new(params);
@@ -29,6 +38,44 @@ pub fn create_consensus_config(testnet: bool, net_suffix: u16) -> Arc<ConsensusC
     Arc::new(config)
 }
 
+/// Check that the consensus config was built for the configured network. A
+/// mismatch would make the seeder handshake as, and crawl, another network.
+pub fn validate_consensus_network(
+    consensus_config: &ConsensusConfig,
+    testnet: bool,
+    net_suffix: u16,
+) -> Result<()> {
+    let expected = expected_network_name(testnet, net_suffix);
+    let actual = consensus_config.params.network_name().to_string();
+    if actual != expected {
+        error!(
+            "Consensus config is for {} but the seeder is configured for {}",
+            actual, expected
+        );
+        return Err(KaseederError::NetworkMismatch(format!(
+            "consensus config is for {}, expected {}",
+            actual, expected
+        )));
+    }
+    Ok(())
+}
+
+/// Check the network a peer advertised in its version message, so peers of
+/// another network never enter the address pool
+pub fn check_peer_network(expected: &str, advertised: &str) -> Result<()> {
+    if advertised != expected {
+        error!(
+            "Peer advertised network {} while crawling {}",
+            advertised, expected
+        );
+        return Err(KaseederError::NetworkMismatch(format!(
+            "peer is on {}, expected {}",
+            advertised, expected
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,4 +100,27 @@ mod tests {
         println!("Testnet10 network name: '{}'", testnet11_name);
         assert!(testnet11_name == "kaspa-testnet-10");
     }
+
+    #[test]
+    fn test_network_mismatch_is_detected() {
+        let mainnet_config = create_consensus_config(false, 0);
+        assert!(validate_consensus_network(&mainnet_config, false, 0).is_ok());
+        assert!(validate_consensus_network(&create_consensus_config(true, 0), true, 10).is_ok());
+
+        // A testnet-11 consensus config on a seeder configured for mainnet
+        let mismatched = create_consensus_config(true, 11);
+        match validate_consensus_network(&mismatched, false, 0) {
+            Err(KaseederError::NetworkMismatch(message)) => {
+                assert!(message.contains("kaspa-testnet-11"));
+                assert!(message.contains("kaspa-mainnet"));
+            }
+            other => panic!("expected a network mismatch, got {:?}", other),
+        }
+
+        assert!(check_peer_network("kaspa-mainnet", "kaspa-mainnet").is_ok());
+        assert!(matches!(
+            check_peer_network("kaspa-mainnet", "kaspa-testnet-10"),
+            Err(KaseederError::NetworkMismatch(_))
+        ));
+    }
 }
@@ -1,18 +1,26 @@
+pub mod audit;
+pub mod bans;
 pub mod checkversion;
 pub mod config;
 pub mod constants;
 pub mod crawler;
 pub mod dns;
+pub mod dns_records;
 pub mod dns_seed_config;
 pub mod dns_seed_discovery;
 pub mod errors;
+pub mod geoip;
+pub mod graph;
 pub mod grpc;
+pub mod http;
 pub mod kaspa_protocol;
 pub mod logging;
 pub mod manager;
 pub mod monitor;
 pub mod netadapter;
 pub mod profiling;
+pub mod proxy;
+pub mod store;
 pub mod types;
 pub mod version;
 
@@ -4,17 +4,20 @@ use std::path::Path;
 use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::Mutex;
-use tracing::{Level, error, info, warn};
+use tracing::{Level, Subscriber, error, info, warn};
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
     EnvFilter, Layer,
+    filter::LevelFilter,
     fmt::{self, time::UtcTime},
     layer::SubscriberExt,
+    registry::LookupSpan,
     util::SubscriberInitExt,
 };
 
 /// Advanced logging configuration with rotation support
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LoggingConfig {
     /// Log level (trace, debug, info, warn, error)
     pub level: String,
@@ -194,6 +197,13 @@ impl LoggingStats {
     }
 }
 
+/// Keeps the background file writers alive. Pending log lines are flushed
+/// when it is dropped, so hold it for the lifetime of the process.
+#[must_use = "dropping the guard stops file logging"]
+pub struct LoggingGuard {
+    _guards: Vec<WorkerGuard>,
+}
+
 /// Enhanced structured logger with rotation support
 pub struct StructuredLogger {
     config: LoggingConfig,
@@ -201,8 +211,9 @@ pub struct StructuredLogger {
     stats: Arc<Mutex<LoggingStats>>,
     health_status: Arc<Mutex<HealthStatus>>,
     // Rotation components
-    appender: Option<RollingFileAppender>,
-    error_appender: Option<RollingFileAppender>,
+    appender: Option<NonBlocking>,
+    error_appender: Option<NonBlocking>,
+    guards: Vec<WorkerGuard>,
 }
 
 impl StructuredLogger {
@@ -219,11 +230,12 @@ impl StructuredLogger {
             health_status,
             appender: None,
             error_appender: None,
+            guards: Vec::new(),
         })
     }
 
-    /// Initialize the logger
-    pub fn init(&mut self) -> Result<()> {
+    /// Initialize the logger, returning the guard that keeps file logging alive
+    pub fn init(&mut self) -> Result<LoggingGuard> {
         // Create log directory if it doesn't exist
         if !self.config.no_log_files {
             std::fs::create_dir_all(&self.config.log_dir)?;
@@ -239,7 +251,9 @@ impl StructuredLogger {
         info!("Rotation strategy: {}", self.config.rotation_strategy);
         info!("Log directory: {}", self.config.log_dir);
 
-        Ok(())
+        Ok(LoggingGuard {
+            _guards: std::mem::take(&mut self.guards),
+        })
     }
 
     /// Initialize rotation appenders based on configuration
@@ -253,19 +267,23 @@ impl StructuredLogger {
 
         // Create app log appender
         let app_log_path = log_dir.join(&self.config.app_log_file);
-        self.appender = Some(self.create_rotation_appender(
+        let (writer, guard) = tracing_appender::non_blocking(self.create_rotation_appender(
             &app_log_path,
             rotation_strategy,
             self.config.max_file_size_mb,
         )?);
+        self.appender = Some(writer);
+        self.guards.push(guard);
 
         // Create error log appender
         let error_log_path = log_dir.join(&self.config.error_log_file);
-        self.error_appender = Some(self.create_rotation_appender(
+        let (writer, guard) = tracing_appender::non_blocking(self.create_rotation_appender(
             &error_log_path,
             rotation_strategy,
             self.config.max_file_size_mb,
         )?);
+        self.error_appender = Some(writer);
+        self.guards.push(guard);
 
         Ok(())
     }
@@ -290,17 +308,26 @@ impl StructuredLogger {
             .file_name()
             .unwrap_or_else(|| std::ffi::OsStr::new("kaseeder"));
 
-        let appender = match strategy {
-            RotationStrategy::Daily => RollingFileAppender::new(Rotation::DAILY, log_dir, log_name),
-            RotationStrategy::Hourly => {
-                RollingFileAppender::new(Rotation::HOURLY, log_dir, log_name)
-            }
-            RotationStrategy::Size => RollingFileAppender::new(Rotation::NEVER, log_dir, log_name),
-            RotationStrategy::Hybrid => {
-                RollingFileAppender::new(Rotation::DAILY, log_dir, log_name)
-            }
+        let rotation = match strategy {
+            RotationStrategy::Daily => Rotation::DAILY,
+            RotationStrategy::Hourly => Rotation::HOURLY,
+            RotationStrategy::Size => Rotation::NEVER,
+            RotationStrategy::Hybrid => Rotation::DAILY,
         };
 
+        let appender = RollingFileAppender::builder()
+            .rotation(rotation)
+            .filename_prefix(log_name.to_string_lossy())
+            .max_log_files(self.config.max_files.max(1))
+            .build(log_dir)
+            .map_err(|e| {
+                crate::errors::KaseederError::Config(format!(
+                    "Failed to create log file {}: {}",
+                    log_path.display(),
+                    e
+                ))
+            })?;
+
         Ok(appender)
     }
 
@@ -310,10 +337,33 @@ impl StructuredLogger {
         let env_filter = EnvFilter::try_from_default_env()
             .unwrap_or_else(|_| EnvFilter::new(&self.config.level));
 
+        // Initialize subscriber
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(self.build_layers())
+            .init();
+
+        Ok(())
+    }
+
+    /// Build the console and file output layers
+    fn build_layers<S>(&self) -> Vec<Box<dyn Layer<S> + Send + Sync>>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
         let mut layers = Vec::new();
 
-        // Console layer
-        if self.config.console_output {
+        // Console layer, as newline-delimited JSON when requested
+        if self.config.console_output && self.config.json_format {
+            let console_layer = fmt::layer()
+                .json()
+                .with_timer(UtcTime::rfc_3339())
+                .with_thread_ids(true)
+                .with_target(true)
+                .with_file(self.config.include_location)
+                .with_line_number(self.config.include_location);
+            layers.push(console_layer.boxed());
+        } else if self.config.console_output {
             let console_layer = fmt::layer()
                 .with_timer(UtcTime::rfc_3339())
                 .with_target(true)
@@ -322,20 +372,32 @@ impl StructuredLogger {
             layers.push(console_layer.boxed());
         }
 
-        // File layers with rotation - simplified for now
-        if !self.config.no_log_files {
-            // For now, we'll use basic file logging without rotation
-            // TODO: Implement proper rotation appender integration
-            info!("File logging enabled (rotation support coming soon)");
+        // App log file receives every level allowed by the filter
+        if let Some(ref writer) = self.appender {
+            let file_layer = fmt::layer()
+                .with_writer(writer.clone())
+                .with_ansi(false)
+                .with_timer(UtcTime::rfc_3339())
+                .with_target(true)
+                .with_file(self.config.include_location)
+                .with_line_number(self.config.include_location);
+            layers.push(file_layer.boxed());
         }
 
-        // Initialize subscriber
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(layers)
-            .init();
+        // Error log file only receives errors
+        if let Some(ref writer) = self.error_appender {
+            let error_layer = fmt::layer()
+                .with_writer(writer.clone())
+                .with_ansi(false)
+                .with_timer(UtcTime::rfc_3339())
+                .with_target(true)
+                .with_file(self.config.include_location)
+                .with_line_number(self.config.include_location)
+                .with_filter(LevelFilter::ERROR);
+            layers.push(error_layer.boxed());
+        }
 
-        Ok(())
+        layers
     }
 
     /// Log a structured message
@@ -568,18 +630,16 @@ impl StructuredLogger {
 }
 
 /// Initialize logging with default configuration
-pub fn init_logging() -> Result<()> {
+pub fn init_logging() -> Result<LoggingGuard> {
     let config = LoggingConfig::default();
     let mut logger = StructuredLogger::new(config)?;
-    logger.init()?;
-    Ok(())
+    logger.init()
 }
 
 /// Initialize logging with custom configuration
-pub fn init_logging_with_config(config: LoggingConfig) -> Result<()> {
+pub fn init_logging_with_config(config: LoggingConfig) -> Result<LoggingGuard> {
     let mut logger = StructuredLogger::new(config)?;
-    logger.init()?;
-    Ok(())
+    logger.init()
 }
 
 /// Get a reference to the global logger (if available)
@@ -652,7 +712,7 @@ mod tests {
 
         let mut _logger = StructuredLogger::new(config)?;
         // Directory is only created when init() is called
-        _logger.init()?;
+        let _guard = _logger.init()?;
         assert!(temp_dir.path().join("logs").exists());
 
         Ok(())
@@ -672,4 +732,69 @@ mod tests {
         let formatted_no_fields = logger.format_structured_message(message, &[]);
         assert_eq!(formatted_no_fields, "Test message");
     }
+
+    /// Read every file in `dir` whose name starts with `prefix`
+    fn read_log_files(dir: &Path, prefix: &str) -> String {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+            .map(|entry| std::fs::read_to_string(entry.path()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_file_logging_writes_app_and_error_logs() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let log_dir = temp_dir.path().join("logs");
+        let config = LoggingConfig {
+            log_dir: log_dir.to_string_lossy().to_string(),
+            console_output: false,
+            ..LoggingConfig::default()
+        };
+
+        let mut logger = StructuredLogger::new(config)?;
+        std::fs::create_dir_all(&log_dir)?;
+        logger.init_rotation_appenders()?;
+        let guard = LoggingGuard {
+            _guards: std::mem::take(&mut logger.guards),
+        };
+
+        let subscriber = tracing_subscriber::registry().with(logger.build_layers());
+        tracing::subscriber::with_default(subscriber, || {
+            info!("file logging info line");
+            error!("file logging error line");
+        });
+
+        // Dropping the guard flushes the background writers
+        drop(guard);
+
+        let app_log = read_log_files(&log_dir, "kaseeder.log");
+        assert!(app_log.contains("file logging info line"));
+        assert!(app_log.contains("file logging error line"));
+
+        let error_log = read_log_files(&log_dir, "kaseeder_error.log");
+        assert!(error_log.contains("file logging error line"));
+        assert!(!error_log.contains("file logging info line"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_log_files_skips_file_layers() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let config = LoggingConfig {
+            log_dir: temp_dir.path().join("logs").to_string_lossy().to_string(),
+            no_log_files: true,
+            ..LoggingConfig::default()
+        };
+
+        let mut logger = StructuredLogger::new(config)?;
+        logger.init_rotation_appenders()?;
+        assert!(logger.appender.is_none());
+        assert!(logger.error_appender.is_none());
+        assert!(logger.guards.is_empty());
+
+        Ok(())
+    }
 }
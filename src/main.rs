@@ -1,30 +1,57 @@
-use clap::Parser;
-use kaseeder::config::{CliOverrides, Config};
-use kaseeder::crawler::Crawler;
-use kaseeder::dns::DnsServer;
+use clap::{Parser, Subcommand};
+use kaseeder::bans::BanList;
+use kaseeder::config::{CliOverrides, Config, SharedConfig, config_snapshot, parse_peer};
+use kaseeder::constants::SHUTDOWN_TIMEOUT;
+use kaseeder::crawler::{Crawler, connection_settings};
+use kaseeder::dns::{DnsServer, DrainState, SoaSettings};
 use kaseeder::errors::{KaseederError, Result};
+use kaseeder::geoip::GeoIpDatabase;
+use kaseeder::graph::CrawlGraph;
 use kaseeder::grpc::GrpcServer;
-use kaseeder::kaspa_protocol::create_consensus_config;
+use kaseeder::http::HttpServer;
+use kaseeder::kaspa_protocol::{
+    create_consensus_config, expected_network_name, validate_consensus_network,
+};
 use kaseeder::logging::LoggingConfig;
-use kaseeder::manager::AddressManager;
-use kaseeder::profiling::ProfilingServer;
-use std::sync::Arc;
+use kaseeder::manager::{AddressManager, AddressManagerTimeouts, StaleFallback, TierWeights};
+use kaseeder::monitor::SystemMonitor;
+use kaseeder::profiling::{MetricsSources, ProfilingServer};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::signal;
-use tracing::{error, info};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
 
 #[derive(Parser, Clone)]
 #[command(name = "kaseeder", about = "Kaspa DNS Seeder")]
 #[command(version)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Configuration file path
     #[arg(short, long)]
     config: Option<String>,
 
-    /// Diagnose connection to specific peer address (e.g., 192.168.1.1:16110)
+    /// Diagnose connection to specific peer address (e.g., 192.168.1.1:16111),
+    /// same as the diagnose subcommand
     #[arg(short, long)]
     diagnose: Option<String>,
 
+    /// List banned peers stored in the application directory and exit
+    #[arg(long)]
+    list_bans: bool,
+
+    /// Lift all bans stored in the application directory and exit
+    #[arg(long)]
+    clear_bans: bool,
+
+    /// Export good peers into one file per GeoIP region in this directory and exit
+    #[arg(long, value_name = "DIR")]
+    export_by_region: Option<String>,
+
     /// Hostname for DNS server
     #[arg(long)]
     host: Option<String>,
@@ -32,8 +59,7 @@ struct Cli {
     /// Nameserver for DNS server
     #[arg(long)]
     nameserver: Option<String>,
-
-    /// Listen address for DNS server
+    /// Listen addresses for DNS server, comma-separated (e.g. 0.0.0.0:5354,[::]:5354)
     #[arg(long)]
     listen: Option<String>,
 
@@ -45,9 +71,10 @@ struct Cli {
     #[arg(long)]
     app_dir: Option<String>,
 
-    /// Seed node address (IP:port or just IP)
+    /// Seed node address (IP:port, [IPv6]:port, hostname:port or just IP);
+    /// may be given more than once
     #[arg(long)]
-    seeder: Option<String>,
+    seeder: Vec<String>,
 
     /// Known peer addresses (comma-separated)
     #[arg(long)]
@@ -84,6 +111,276 @@ struct Cli {
     /// Profile port
     #[arg(long)]
     profile: Option<String>,
+
+    /// TTL in seconds for DNS A/AAAA records
+    #[arg(long)]
+    dns_ttl: Option<u32>,
+
+    /// Answer SRV queries with per-peer ports
+    #[arg(long)]
+    enable_srv: Option<bool>,
+
+    /// Maximum number of address records per DNS response
+    #[arg(long)]
+    max_dns_records: Option<usize>,
+
+    /// Prefer the reachable port over gossiped ports for the same peer IP
+    #[arg(long)]
+    prefer_reachable_port: Option<bool>,
+
+    /// Maximum concurrent DNS-over-TCP connections (0 disables TCP)
+    #[arg(long)]
+    max_tcp_connections: Option<usize>,
+
+    /// Idle timeout in seconds for DNS-over-TCP connections
+    #[arg(long)]
+    tcp_idle_timeout_secs: Option<u64>,
+
+    /// Window in seconds for the periodic reachability sweep (0 disables it)
+    #[arg(long)]
+    sweep_interval_secs: Option<u64>,
+
+    /// Maximum nodes polled per minute by the reachability sweep
+    #[arg(long)]
+    sweep_max_per_minute: Option<u32>,
+
+    /// Maximum DNS queries per second per source IP (0 disables limiting)
+    #[arg(long)]
+    dns_rate_limit: Option<u32>,
+
+    /// Penalize peers that send malformed address entries
+    #[arg(long)]
+    penalize_malformed_addresses: Option<bool>,
+
+    /// Administrator mailbox for the SOA record (e.g. hostmaster@example.com)
+    #[arg(long)]
+    soa_mailbox: Option<String>,
+
+    /// HTTP listen address for the JSON peer list and read API (e.g. 0.0.0.0:8081)
+    #[arg(long)]
+    http_listen: Option<String>,
+
+    /// DNS query types to refuse (comma-separated, e.g. TXT,AAAA)
+    #[arg(long)]
+    disabled_query_types: Option<String>,
+
+    /// Emit console logs as newline-delimited JSON
+    #[arg(long)]
+    json_format: Option<bool>,
+
+    /// Timeout in seconds for the P2P version handshake
+    #[arg(long)]
+    handshake_timeout_secs: Option<u64>,
+
+    /// Webhook URL that receives a JSON POST when the health state changes
+    #[arg(long)]
+    alert_webhook_url: Option<String>,
+
+    /// Ask peers for addresses on all subnetworks
+    #[arg(long)]
+    request_all_subnetworks: Option<bool>,
+
+    /// Subnetwork ID (40 hex characters) to ask peers for addresses on
+    #[arg(long)]
+    request_subnetwork_id: Option<String>,
+
+    /// Minimum seconds between address requests to the same peer (0 disables)
+    #[arg(long)]
+    address_request_interval_secs: Option<u64>,
+
+    /// GeoIP CSV file mapping networks to country and region
+    #[arg(long)]
+    geoip_database: Option<String>,
+
+    /// SOCKS5 proxy for outbound peer connections (socks5://host:port)
+    #[arg(long)]
+    proxy: Option<String>,
+
+    /// Discovery methods tried against DNS seed servers, in order (comma-separated)
+    #[arg(long)]
+    discovery_methods: Option<String>,
+
+    /// Trusted peer addresses used as trust anchors (comma-separated)
+    #[arg(long)]
+    trusted_peers: Option<String>,
+
+    /// User agent marker peers use to opt out of DNS answers (e.g. noseed)
+    #[arg(long)]
+    opt_out_marker: Option<String>,
+
+    /// Serve recently good peers when fewer good peers than this are available (0 disables)
+    #[arg(long)]
+    stale_fallback_min_good: Option<usize>,
+
+    /// Only fall back to peers whose last success is within this many seconds
+    #[arg(long)]
+    stale_fallback_cutoff_secs: Option<u64>,
+
+    /// Expose good peer counts per country over gRPC, TXT and the dashboard
+    #[arg(long)]
+    expose_country_counts: Option<bool>,
+
+    /// Answer AAAA queries with the non-routable 100:: address when no IPv6 peer is available,
+    /// as the Go seeder does, instead of an empty answer
+    #[arg(long)]
+    aaaa_placeholder: Option<bool>,
+
+    /// Seconds between self-queries comparing DNS answers with the address pool (0 disables)
+    #[arg(long)]
+    dns_self_check_interval_secs: Option<u64>,
+
+    /// Maximum distinct ports stored per IP address (0 disables the cap)
+    #[arg(long)]
+    max_ports_per_ip: Option<usize>,
+
+    /// Report reachable vs unreachable peers by GeoIP region and ASN to spot blind spots
+    #[arg(long)]
+    discovery_bias_report: Option<bool>,
+
+    /// Record which peer reported which address, keeping edges this many seconds (0 disables)
+    #[arg(long)]
+    crawl_graph_retention_secs: Option<u64>,
+
+    /// Start with the DNS server answering SERVFAIL until drain mode is left over gRPC
+    #[arg(long)]
+    start_draining: Option<bool>,
+
+    /// Reject peers whose version message advertises a different network
+    #[arg(long)]
+    verify_peer_network: Option<bool>,
+
+    /// Seconds a peer stays good after its last successful connection
+    #[arg(long)]
+    stale_good_timeout_secs: Option<u64>,
+
+    /// Seconds without being seen in gossip before a peer is pruned
+    #[arg(long)]
+    prune_expire_timeout_secs: Option<u64>,
+
+    /// Seconds between prunes of expired peers
+    #[arg(long)]
+    prune_interval_secs: Option<u64>,
+
+    /// Seconds between saves of the peer list to disk
+    #[arg(long)]
+    dump_interval_secs: Option<u64>,
+
+    /// Maximum nodes kept in the address book (0 for no limit)
+    #[arg(long)]
+    max_stored_addresses: Option<usize>,
+
+    /// Address batches buffered per peer connection
+    #[arg(long)]
+    address_channel_capacity: Option<usize>,
+
+    /// Label under the seeder host answering TXT queries with build info
+    #[arg(long)]
+    version_txt_label: Option<String>,
+
+    /// Consecutive failed connections after which a peer is banned (0 disables)
+    #[arg(long)]
+    ban_after_failures: Option<u32>,
+
+    /// Seconds misbehaving peers are banned for (0 disables automatic bans)
+    #[arg(long)]
+    ban_duration_secs: Option<u64>,
+
+    /// New gossiped addresses released from quarantine per minute (0 disables the quarantine)
+    #[arg(long)]
+    quarantine_release_per_minute: Option<u32>,
+
+    /// Consecutive failed connections after which a node is pruned (0 disables)
+    #[arg(long)]
+    max_consecutive_failures: Option<u32>,
+
+    /// Peer store backend (json or sqlite)
+    #[arg(long)]
+    storage_backend: Option<String>,
+
+    /// Write every poll outcome as newline-delimited JSON to this file
+    #[arg(long)]
+    poll_audit_log: Option<String>,
+
+    /// UDP payload size advertised in EDNS0 OPT records
+    #[arg(long)]
+    edns_udp_payload_size: Option<u16>,
+
+    /// Route more polls through network adapters with higher recent success rates
+    #[arg(long)]
+    adapter_rebalancing: Option<bool>,
+
+    /// Serve a plain-text summary on /status of the profiling server
+    #[arg(long)]
+    status_page: Option<bool>,
+
+    /// System clock jumps beyond this many seconds shift node timestamps (0 disables)
+    #[arg(long)]
+    clock_skew_threshold_secs: Option<u64>,
+
+    /// Expose good peer counts per discovery source over gRPC and the profiling dashboard
+    #[arg(long)]
+    expose_source_counts: Option<bool>,
+
+    /// Maximum number of peers polled at once
+    #[arg(long)]
+    max_concurrent_polls: Option<usize>,
+
+    /// Addresses selected per crawl round (0 for three per thread)
+    #[arg(long)]
+    batch_size: Option<usize>,
+
+    /// Seconds between DNS re-seeds while crawling (0 to only seed when out of peers)
+    #[arg(long)]
+    dns_reseed_interval_secs: Option<u64>,
+
+    /// Disable DNS seeding and crawl only from configured peers
+    #[arg(long)]
+    disable_dns_seeding: Option<bool>,
+
+    /// Record why each peer is classified good, stale or bad, exposed over gRPC
+    #[arg(long)]
+    record_classification_reasons: Option<bool>,
+
+    /// Retry policy overrides per connection error class, as class=retries[:backoff_ms] (comma-separated)
+    #[arg(long)]
+    connect_retry_policies: Option<String>,
+
+    /// Share of DNS answer slots per serving tier, as tier=weight (comma-separated), e.g. reliable=70,fresh=20,diverse=10
+    #[arg(long)]
+    serving_tier_weights: Option<String>,
+
+    /// Maximum addresses accepted from a single peer per poll
+    #[arg(long)]
+    max_addresses_per_peer: Option<usize>,
+
+    /// Seconds the addresses selected for a query are reused for identical queries (0 disables)
+    #[arg(long)]
+    dns_cache_ttl: Option<u64>,
+
+    /// Protocol versions offered in the peer handshake, comma-separated and tried in order (e.g. 7,6)
+    #[arg(long)]
+    protocol_versions: Option<String>,
+
+    /// Store and serve private (e.g. RFC1918) peer addresses, for local networks
+    #[arg(long)]
+    accept_unroutable: Option<bool>,
+}
+
+/// Maintenance commands that run and exit
+#[derive(Subcommand, Clone)]
+enum Command {
+    /// Print the configuration the daemon would run with, after merging the
+    /// config file and command line, as TOML
+    PrintConfig,
+    /// Connect to one peer with the configured network and connection
+    /// settings, print the outcome and exit nonzero if it failed. ADDRESS is
+    /// `ip:port`, `[ipv6]:port` or a bare IP on the network's default port
+    Diagnose { address: String },
+    /// Write the peer database to FILE as JSON lines of ip, port,
+    /// last_success and user_agent
+    ExportPeers { file: String },
+    /// Merge peers from a file written by export-peers into the peer database
+    ImportPeers { file: String },
 }
 
 impl From<Cli> for CliOverrides {
@@ -94,7 +391,7 @@ impl From<Cli> for CliOverrides {
             listen: cli.listen,
             grpc_listen: cli.grpc_listen,
             app_dir: cli.app_dir,
-            seeder: cli.seeder,
+            seeder: (!cli.seeder.is_empty()).then(|| cli.seeder.join(",")),
             known_peers: cli.known_peers,
             threads: cli.threads,
             min_proto_ver: cli.min_proto_ver,
@@ -104,24 +401,157 @@ impl From<Cli> for CliOverrides {
             log_level: cli.log_level,
             nologfiles: cli.nologfiles,
             profile: cli.profile,
+            dns_ttl: cli.dns_ttl,
+            enable_srv: cli.enable_srv,
+            max_dns_records: cli.max_dns_records,
+            prefer_reachable_port: cli.prefer_reachable_port,
+            max_tcp_connections: cli.max_tcp_connections,
+            tcp_idle_timeout_secs: cli.tcp_idle_timeout_secs,
+            sweep_interval_secs: cli.sweep_interval_secs,
+            sweep_max_per_minute: cli.sweep_max_per_minute,
+            dns_rate_limit: cli.dns_rate_limit,
+            penalize_malformed_addresses: cli.penalize_malformed_addresses,
+            soa_mailbox: cli.soa_mailbox,
+            http_listen: cli.http_listen,
+            disabled_query_types: cli.disabled_query_types,
+            json_format: cli.json_format,
+            handshake_timeout_secs: cli.handshake_timeout_secs,
+            alert_webhook_url: cli.alert_webhook_url,
+            request_all_subnetworks: cli.request_all_subnetworks,
+            request_subnetwork_id: cli.request_subnetwork_id,
+            address_request_interval_secs: cli.address_request_interval_secs,
+            geoip_database: cli.geoip_database,
+            proxy: cli.proxy,
+            discovery_methods: cli.discovery_methods,
+            trusted_peers: cli.trusted_peers,
+            opt_out_marker: cli.opt_out_marker,
+            stale_fallback_min_good: cli.stale_fallback_min_good,
+            stale_fallback_cutoff_secs: cli.stale_fallback_cutoff_secs,
+            expose_country_counts: cli.expose_country_counts,
+            aaaa_placeholder: cli.aaaa_placeholder,
+            dns_self_check_interval_secs: cli.dns_self_check_interval_secs,
+            max_ports_per_ip: cli.max_ports_per_ip,
+            discovery_bias_report: cli.discovery_bias_report,
+            crawl_graph_retention_secs: cli.crawl_graph_retention_secs,
+            start_draining: cli.start_draining,
+            verify_peer_network: cli.verify_peer_network,
+            stale_good_timeout_secs: cli.stale_good_timeout_secs,
+            prune_expire_timeout_secs: cli.prune_expire_timeout_secs,
+            prune_interval_secs: cli.prune_interval_secs,
+            dump_interval_secs: cli.dump_interval_secs,
+            max_stored_addresses: cli.max_stored_addresses,
+            address_channel_capacity: cli.address_channel_capacity,
+            version_txt_label: cli.version_txt_label,
+            ban_after_failures: cli.ban_after_failures,
+            ban_duration_secs: cli.ban_duration_secs,
+            quarantine_release_per_minute: cli.quarantine_release_per_minute,
+            max_consecutive_failures: cli.max_consecutive_failures,
+            storage_backend: cli.storage_backend,
+            poll_audit_log: cli.poll_audit_log,
+            edns_udp_payload_size: cli.edns_udp_payload_size,
+            adapter_rebalancing: cli.adapter_rebalancing,
+            status_page: cli.status_page,
+            clock_skew_threshold_secs: cli.clock_skew_threshold_secs,
+            expose_source_counts: cli.expose_source_counts,
+            max_concurrent_polls: cli.max_concurrent_polls,
+            batch_size: cli.batch_size,
+            dns_reseed_interval_secs: cli.dns_reseed_interval_secs,
+            disable_dns_seeding: cli.disable_dns_seeding,
+            record_classification_reasons: cli.record_classification_reasons,
+            connect_retry_policies: cli.connect_retry_policies,
+            serving_tier_weights: cli.serving_tier_weights,
+            max_addresses_per_peer: cli.max_addresses_per_peer,
+            dns_cache_ttl: cli.dns_cache_ttl,
+            protocol_versions: cli.protocol_versions,
+            accept_unroutable: cli.accept_unroutable,
         }
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Parse command line arguments
-    let cli = Cli::parse();
+/// Wait up to [`SHUTDOWN_TIMEOUT`] for a task to stop after being asked to,
+/// aborting it if it does not so a stuck task cannot hang shutdown
+async fn await_shutdown(name: &str, mut handle: JoinHandle<()>) {
+    match tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut handle).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => error!("{} task failed: {}", name, e),
+        Err(_) => {
+            warn!(
+                "{} did not stop within {:?}, aborting it",
+                name, SHUTDOWN_TIMEOUT
+            );
+            handle.abort();
+        }
+    }
+}
 
-    // Load configuration first to get logging settings
+/// Load the configuration file and apply CLI overrides on top of it
+fn load_config(cli: &Cli) -> Result<Config> {
     let config = if let Some(config_path) = &cli.config {
         Config::load_from_file(config_path)?
     } else {
         Config::try_load_default()?
     };
 
-    // Apply CLI overrides
-    let config = config.with_cli_overrides(cli.clone().into())?;
+    config.with_cli_overrides(cli.clone().into())
+}
+
+/// Open the address manager with the timeouts and options from `config`, so
+/// maintenance commands see the peer set the way the running seeder does
+fn open_address_manager(
+    config: &Config,
+    geoip: Option<Arc<GeoIpDatabase>>,
+) -> Result<AddressManager> {
+    let storage_backend = config
+        .storage_backend
+        .parse()
+        .map_err(KaseederError::Config)?;
+
+    Ok(
+        AddressManager::open(&config.app_dir, config.default_port(), storage_backend)?
+            .with_prefer_reachable_port(config.prefer_reachable_port)
+            .with_max_ports_per_ip(config.max_ports_per_ip)
+            .with_max_stored_addresses(config.max_stored_addresses)
+            .with_accept_unroutable(config.accept_unroutable)
+            .with_quarantine(config.quarantine_release_per_minute)
+            .with_max_consecutive_failures(config.max_consecutive_failures)
+            .with_clock_skew_threshold(Duration::from_secs(config.clock_skew_threshold_secs))
+            .with_timeouts(AddressManagerTimeouts {
+                stale_good: Duration::from_secs(config.stale_good_timeout_secs),
+                prune_expire: Duration::from_secs(config.prune_expire_timeout_secs),
+                prune_interval: Duration::from_secs(config.prune_interval_secs),
+                dump_interval: Duration::from_secs(config.dump_interval_secs),
+            })
+            .with_opt_out_marker(config.opt_out_marker.clone())
+            .with_stale_fallback((config.stale_fallback_min_good > 0).then(|| StaleFallback {
+                min_good: config.stale_fallback_min_good,
+                cutoff: Duration::from_secs(config.stale_fallback_cutoff_secs),
+            }))
+            .with_geoip(geoip)
+            .with_country_counts(config.expose_country_counts)
+            .with_source_counts(config.expose_source_counts)
+            .with_classification_reasons(config.record_classification_reasons)
+            .with_discovery_bias_report(config.discovery_bias_report)
+            .with_crawl_graph((config.crawl_graph_retention_secs > 0).then(|| {
+                Arc::new(CrawlGraph::new(Duration::from_secs(
+                    config.crawl_graph_retention_secs,
+                )))
+            })),
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Parse command line arguments
+    let cli = Cli::parse();
+
+    // Load configuration first to get logging settings
+    let config = load_config(&cli)?;
+
+    // Print before logging is set up so stdout holds nothing but the TOML
+    if let Some(Command::PrintConfig) = cli.command {
+        print!("{}", config.to_toml()?);
+        return Ok(());
+    }
 
     // Initialize logging with configuration
     let mut logging_config = LoggingConfig::default();
@@ -133,6 +563,7 @@ async fn main() -> Result<()> {
     if let Some(nologfiles) = cli.nologfiles {
         logging_config.no_log_files = nologfiles;
     }
+    logging_config.json_format = config.json_format;
 
     // Apply advanced logging configuration from main config
     logging_config.rotation_strategy = config.advanced_logging.rotation_strategy.clone();
@@ -148,7 +579,7 @@ async fn main() -> Result<()> {
     logging_config.max_files = config.advanced_logging.max_rotated_files;
 
     // Initialize logging system
-    kaseeder::logging::init_logging_with_config(logging_config)?;
+    let _log_guard = kaseeder::logging::init_logging_with_config(logging_config)?;
 
     info!("Starting Kaspa DNS Seeder...");
     info!(
@@ -157,19 +588,98 @@ async fn main() -> Result<()> {
     );
 
     // Check if this is a diagnose command first
-    if let Some(address) = &cli.diagnose {
+    let diagnose_address = match &cli.command {
+        Some(Command::Diagnose { address }) => Some(address),
+        _ => cli.diagnose.as_ref(),
+    };
+    if let Some(address) = diagnose_address {
         info!("Running network diagnosis for address: {}", address);
 
-        // For diagnosis, use minimal configuration
-        let consensus_config = create_consensus_config(false, 0); // Use mainnet defaults
+        let target = parse_peer(address, config.default_port())?
+            .into_iter()
+            .next()
+            .ok_or_else(|| KaseederError::InvalidAddress(address.clone()))?;
+        let consensus_config = create_consensus_config(config.testnet, config.net_suffix);
+        let net_adapter = kaseeder::netadapter::DnsseedNetAdapter::with_settings(
+            consensus_config,
+            connection_settings(&config)?,
+        )?;
+
+        match net_adapter.diagnose_connection(&target.dial_string()).await {
+            Ok(result) => {
+                println!("{}", result);
+                return Ok(());
+            }
+            Err(KaseederError::ConnectionFailed(report)) => {
+                println!("{}", report);
+                std::process::exit(1);
+            }
+            Err(e) => return Err(e),
+        }
+    }
 
-        // Create network adapter for diagnosis
-        let net_adapter = kaseeder::netadapter::DnsseedNetAdapter::new(consensus_config)?;
+    // Ban list maintenance commands
+    if cli.list_bans || cli.clear_bans {
+        let bans = BanList::load(&config.app_dir)?;
+
+        if cli.list_bans {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let entries = bans.entries();
+            println!("{} banned peers", entries.len());
+            for entry in entries {
+                println!(
+                    "{}\texpires at {} (in {}s)",
+                    entry.ip,
+                    entry.expires_at,
+                    entry.expires_at.saturating_sub(now)
+                );
+            }
+        }
+
+        if cli.clear_bans {
+            println!("Cleared {} bans", bans.clear()?);
+        }
+        return Ok(());
+    }
 
-        // Run diagnosis
-        let result = net_adapter.diagnose_connection(address).await?;
+    // Regional peer export
+    if let Some(output_dir) = &cli.export_by_region {
+        let geoip_database = config.geoip_database.as_ref().ok_or_else(|| {
+            KaseederError::Config("--export-by-region requires geoip_database".to_string())
+        })?;
+        let geoip = Arc::new(GeoIpDatabase::load(geoip_database)?);
+        let address_manager = open_address_manager(&config, Some(geoip.clone()))?;
 
-        println!("{}", result);
+        let mut peers = address_manager.good_addresses(1, true, None);
+        peers.extend(address_manager.good_addresses(28, true, None));
+
+        for (region, count) in geoip.export_by_region(&peers, output_dir)? {
+            println!("{}\t{} peers", region, count);
+        }
+        return Ok(());
+    }
+
+    // Peer database export and import
+    if let Some(command) = &cli.command {
+        let address_manager = open_address_manager(&config, None)?;
+
+        match command {
+            Command::ExportPeers { file } => {
+                let count = address_manager.export_peers(Path::new(file))?;
+                println!("Exported {} peers to {}", count, file);
+            }
+            Command::ImportPeers { file } => {
+                let import = address_manager.import_peers(Path::new(file))?;
+                println!(
+                    "Imported {} peers from {} ({} new, {} marked good)",
+                    import.records, file, import.added, import.good
+                );
+            }
+            Command::PrintConfig | Command::Diagnose { .. } => {}
+        }
         return Ok(());
     }
 
@@ -181,28 +691,95 @@ async fn main() -> Result<()> {
 
     // Create consensus configuration
     let consensus_config = create_consensus_config(config.testnet, config.net_suffix);
+    validate_consensus_network(&consensus_config, config.testnet, config.net_suffix)?;
+
+    // GeoIP is optional; without it peers are reported in the unknown country
+    let geoip = match config.geoip_database {
+        Some(ref path) => match GeoIpDatabase::load(path) {
+            Ok(geoip) => Some(Arc::new(geoip)),
+            Err(e) => {
+                warn!("Failed to load GeoIP database {}: {}", path, e);
+                None
+            }
+        },
+        None => None,
+    };
 
     // Create address manager
-    let address_manager = Arc::new(AddressManager::new(&config.app_dir, config.default_port())?);
-    address_manager.start();
+    let address_manager = Arc::new(open_address_manager(&config, geoip)?);
+    let address_manager_handle = address_manager.start();
 
-    // Create crawler
+    // Create crawler, sharing the configuration so reloads reach it
+    let shared_config: SharedConfig = Arc::new(RwLock::new(config.clone()));
     let mut crawler = Crawler::new(
         address_manager.clone(),
         consensus_config,
-        Arc::new(config.clone()),
+        shared_config.clone(),
     )?;
 
-    // Create DNS server
+    // Start system monitoring
+    let mut monitor = SystemMonitor::new();
+    if let Some(ref alert_webhook_url) = config.alert_webhook_url {
+        monitor = monitor.with_alert_webhook(alert_webhook_url.clone());
+    }
+    let monitor = Arc::new(monitor);
+    monitor.start_monitoring().await?;
+
+    // Create DNS server, with a drain switch the gRPC server can toggle
+    let drain_state = Arc::new(DrainState::new(config.start_draining));
     let dns_server = DnsServer::new(
         config.host.clone(),
         config.nameserver.clone(),
         config.listen.clone(),
+        config.dns_ttl,
         address_manager.clone(),
-    );
+    )
+    .with_srv(config.enable_srv)
+    .with_max_records(config.max_dns_records)
+    .with_answer_cache_ttl(Duration::from_secs(config.dns_cache_ttl))
+    .with_tcp_limits(
+        config.max_tcp_connections,
+        Duration::from_secs(config.tcp_idle_timeout_secs),
+    )
+    .with_rate_limit(config.dns_rate_limit)
+    .with_edns_udp_payload_size(config.edns_udp_payload_size)
+    .with_aaaa_placeholder(config.aaaa_placeholder)
+    .with_version_txt(
+        config.version_txt_label.clone(),
+        &expected_network_name(config.testnet, config.net_suffix),
+    )
+    .with_self_check(
+        (config.dns_self_check_interval_secs > 0)
+            .then(|| Duration::from_secs(config.dns_self_check_interval_secs)),
+    )
+    .with_disabled_query_types(config.disabled_record_types())
+    .with_soa(SoaSettings {
+        mailbox: config.soa_mailbox.clone(),
+        refresh: config.soa_refresh,
+        retry: config.soa_retry,
+        expire: config.soa_expire,
+        minimum: config.soa_minimum,
+    })
+    .with_nameservers(
+        config
+            .nameservers
+            .iter()
+            .flatten()
+            .filter_map(|nameserver| nameserver.parse().ok())
+            .collect(),
+    )
+    .with_tier_weights(
+        TierWeights::parse(&config.serving_tier_weights).map_err(KaseederError::Config)?,
+    )
+    .with_drain_state(drain_state.clone())
+    .with_monitor(monitor.clone());
 
     // Create gRPC server
-    let grpc_server = GrpcServer::new(address_manager.clone());
+    let grpc_server = GrpcServer::new(address_manager.clone())
+        .with_monitor(monitor)
+        .with_drain_state(drain_state)
+        .with_admin_token(config.admin_token.clone())
+        .with_crawler_stats(crawler.shared_stats());
 
     // Create profiling server if enabled
     let profiling_server = if let Some(ref profile_port) = config.profile {
@@ -213,7 +790,15 @@ async fn main() -> Result<()> {
                 value: profile_port.clone(),
                 expected: "valid port number".to_string(),
             })?;
-        Some(ProfilingServer::new(port))
+        Some(
+            ProfilingServer::new(port)
+                .with_metrics(MetricsSources {
+                    address_manager: address_manager.clone(),
+                    crawler_stats: crawler.shared_stats(),
+                    dns_stats: dns_server.query_stats(),
+                })
+                .with_status_page(config.status_page),
+        )
     } else {
         None
     };
@@ -248,6 +833,48 @@ async fn main() -> Result<()> {
 
     // Start services
     let dns_server = Arc::new(dns_server);
+
+    // Reload the configuration on SIGHUP, applying the hot-reloadable subset
+    let reload_cli = cli.clone();
+    let reload_dns_server = dns_server.clone();
+    let reload_address_manager = address_manager.clone();
+    tokio::spawn(async move {
+        if let Ok(mut sighup) = signal::unix::signal(signal::unix::SignalKind::hangup()) {
+            while sighup.recv().await.is_some() {
+                info!("Received SIGHUP, reloading configuration...");
+                let reloaded = match load_config(&reload_cli) {
+                    Ok(reloaded) => reloaded,
+                    Err(e) => {
+                        error!(
+                            "Configuration reload failed, keeping current settings: {}",
+                            e
+                        );
+                        continue;
+                    }
+                };
+
+                let outcome = shared_config
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .apply_reload(reloaded);
+                let snapshot = config_snapshot(&shared_config);
+                reload_dns_server.reload(&snapshot);
+                reload_address_manager.set_accept_unroutable(snapshot.accept_unroutable);
+
+                if outcome.applied.is_empty() {
+                    info!("Configuration reloaded, no hot-reloadable changes");
+                } else {
+                    info!("Configuration reloaded: {}", outcome.applied.join(", "));
+                }
+                if !outcome.restart_required.is_empty() {
+                    warn!(
+                        "Changes to {} take effect only after a restart",
+                        outcome.restart_required.join(", ")
+                    );
+                }
+            }
+        }
+    });
     let grpc_server = Arc::new(grpc_server);
     let grpc_listen = config.grpc_listen.clone();
 
@@ -267,28 +894,30 @@ async fn main() -> Result<()> {
         }
     });
 
-    // Start crawler
+    // Start HTTP server if enabled
+    let http_handle = config.http_listen.clone().map(|http_listen| {
+        let http_server = HttpServer::new(address_manager.clone());
+        tokio::spawn(async move {
+            if let Err(e) = http_server.start(&http_listen).await {
+                error!("HTTP server error: {}", e);
+            }
+        })
+    });
+
+    // Start crawler, keeping a handle to stop it on shutdown
+    let crawler_control = crawler.clone();
     let crawler_handle = tokio::spawn(async move {
         if let Err(e) = crawler.start().await {
             error!("Crawler error: {}", e);
         }
     });
 
-    // Start address manager background tasks
-    let shutdown_signal_clone3 = shutdown_signal.clone();
-    let address_manager_handle = tokio::spawn(async move {
-        // Keep address manager running
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            if shutdown_signal_clone3.load(Ordering::SeqCst) {
-                break;
-            }
-        }
-    });
-
     info!("All services started successfully");
     info!("DNS server listening on {}", config.listen);
     info!("gRPC server listening on {}", config.grpc_listen);
+    if let Some(ref http_listen) = config.http_listen {
+        info!("HTTP server listening on {}", http_listen);
+    }
     if let Some(ref profile_port) = config.profile {
         info!("Profiling server listening on port {}", profile_port);
     }
@@ -300,11 +929,20 @@ async fn main() -> Result<()> {
 
     info!("Shutting down services...");
 
-    // Graceful shutdown
+    // Stop answering queries first; the servers hold no state to save
     dns_handle.abort();
     grpc_handle.abort();
-    crawler_handle.abort();
-    address_manager_handle.abort();
+    if let Some(http_handle) = http_handle {
+        http_handle.abort();
+    }
+
+    // Let the crawler finish its in-flight polls and close its connections
+    crawler_control.shutdown().await;
+    await_shutdown("Crawler", crawler_handle).await;
+
+    // Stop the address manager last so it saves everything the crawler found
+    address_manager.shutdown().await;
+    await_shutdown("Address manager", address_manager_handle).await;
 
     info!("Shutdown complete");
     Ok(())
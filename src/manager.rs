@@ -1,21 +1,301 @@
+use crate::bans::BanList;
+use crate::constants::{
+    CLOCK_SKEW_CHECK_INTERVAL, DEFAULT_DUMP_ADDRESS_INTERVAL, DEFAULT_MAX_CONSECUTIVE_FAILURES,
+    DEFAULT_MAX_PORTS_PER_IP, DEFAULT_MAX_STORED_ADDRESSES, DEFAULT_PRUNE_ADDRESS_INTERVAL,
+    DEFAULT_PRUNE_EXPIRE_TIMEOUT, DEFAULT_STALE_GOOD_TIMEOUT, FRESH_TIER_WINDOW,
+    MAX_GOOD_CONSECUTIVE_FAILURES, POLL_BATCH_PER_THREAD, RELIABLE_TIER_MIN_SUCCESSES,
+};
 use crate::errors::Result;
+use crate::geoip::{GeoIpDatabase, UNKNOWN_ASN, UNKNOWN_COUNTRY, UNKNOWN_REGION};
+use crate::graph::CrawlGraph;
+use crate::store::{PeerRecord, PeerStore, StorageBackend, read_peer_records, write_peer_records};
 use crate::types::{CrawlerStats, NetAddress};
 use dashmap::DashMap;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::net::IpAddr;
-use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
-use tracing::{error, info};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+/// Floor for [`Node::selection_weight`]
+const MIN_SELECTION_WEIGHT: f64 = 0.01;
+
+/// Order nodes so that every prefix is a weighted random sample without
+/// replacement, so callers can take as many as they need
+pub fn weighted_shuffle<R, W>(nodes: &mut [Node], rng: &mut R, weight: W)
+where
+    R: Rng + ?Sized,
+    W: Fn(&Node) -> f64,
+{
+    // Efraimidis-Spirakis: rank by u^(1/w), compared as ln(u)/w
+    let mut keyed: Vec<(f64, Node)> = nodes
+        .iter()
+        .map(|node| {
+            let u: f64 = rng.gen_range(f64::MIN_POSITIVE..1.0);
+            (u.ln() / weight(node).max(f64::MIN_POSITIVE), node.clone())
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (slot, (_, node)) in nodes.iter_mut().zip(keyed) {
+        *slot = node;
+    }
+}
+
+/// Quality tier a peer is served from when answers blend tiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ServingTier {
+    /// Reached several times, and not failing since
+    Reliable,
+    /// Reached recently, without the track record of a reliable peer
+    Fresh,
+    /// Everything else in the serving pool, served for exploration
+    Diverse,
+}
+
+impl ServingTier {
+    pub const ALL: [ServingTier; 3] = [
+        ServingTier::Reliable,
+        ServingTier::Fresh,
+        ServingTier::Diverse,
+    ];
+
+    /// Name used in the configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServingTier::Reliable => "reliable",
+            ServingTier::Fresh => "fresh",
+            ServingTier::Diverse => "diverse",
+        }
+    }
+
+    /// Tier of `node` as of `now`
+    pub fn of(node: &Node, now: SystemTime) -> Self {
+        if node.consecutive_failures == 0
+            && node.successful_connections >= RELIABLE_TIER_MIN_SUCCESSES
+        {
+            ServingTier::Reliable
+        } else if node.last_success > UNIX_EPOCH
+            && now.duration_since(node.last_success).unwrap_or_default() <= FRESH_TIER_WINDOW
+        {
+            ServingTier::Fresh
+        } else {
+            ServingTier::Diverse
+        }
+    }
+}
+
+/// Share of answer slots given to each serving tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TierWeights {
+    pub reliable: u32,
+    pub fresh: u32,
+    pub diverse: u32,
+}
+
+impl TierWeights {
+    pub fn weight(&self, tier: ServingTier) -> u32 {
+        match tier {
+            ServingTier::Reliable => self.reliable,
+            ServingTier::Fresh => self.fresh,
+            ServingTier::Diverse => self.diverse,
+        }
+    }
+
+    /// Weights from `tier=weight` entries such as `reliable=70`; tiers left
+    /// out get no slots while others have peers. `None` when there are no entries.
+    pub fn parse(entries: &[String]) -> std::result::Result<Option<Self>, String> {
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        let mut weights = Self {
+            reliable: 0,
+            fresh: 0,
+            diverse: 0,
+        };
+        for entry in entries {
+            let (tier, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected tier=weight, got '{}'", entry))?;
+            let tier = ServingTier::ALL
+                .into_iter()
+                .find(|known| known.as_str() == tier.trim().to_lowercase())
+                .ok_or_else(|| format!("unknown serving tier '{}'", tier.trim()))?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid weight for {}: '{}'", tier.as_str(), weight))?;
+
+            match tier {
+                ServingTier::Reliable => weights.reliable = weight,
+                ServingTier::Fresh => weights.fresh = weight,
+                ServingTier::Diverse => weights.diverse = weight,
+            }
+        }
+
+        if ServingTier::ALL
+            .iter()
+            .all(|tier| weights.weight(*tier) == 0)
+        {
+            return Err("at least one tier needs a non-zero weight".to_string());
+        }
+        Ok(Some(weights))
+    }
+}
+
+/// Order nodes by drawing each position's tier at random in proportion to
+/// `weights`, then taking that tier's next node. Within a tier nodes are
+/// shuffled by [`Node::selection_weight`]. Once a tier runs dry its share
+/// goes to the tiers left, falling back to zero-weight tiers last.
+pub fn tiered_shuffle<R>(nodes: &mut Vec<Node>, rng: &mut R, weights: &TierWeights, now: SystemTime)
+where
+    R: Rng + ?Sized,
+{
+    let mut tiers: BTreeMap<ServingTier, VecDeque<Node>> = BTreeMap::new();
+    for node in nodes.drain(..) {
+        tiers
+            .entry(ServingTier::of(&node, now))
+            .or_default()
+            .push_back(node);
+    }
+    for bucket in tiers.values_mut() {
+        weighted_shuffle(bucket.make_contiguous(), rng, |node| {
+            node.selection_weight(now)
+        });
+    }
+
+    loop {
+        let available: Vec<(ServingTier, u32)> = tiers
+            .iter()
+            .filter(|(_, bucket)| !bucket.is_empty())
+            .map(|(tier, _)| (*tier, weights.weight(*tier)))
+            .collect();
+        let total: u32 = available.iter().map(|(_, weight)| weight).sum();
+        let tier = match available.first() {
+            None => break,
+            Some((first, _)) if total == 0 => *first,
+            Some(_) => {
+                let mut pick = rng.gen_range(0..total);
+                available
+                    .iter()
+                    .find(|(_, weight)| {
+                        if pick < *weight {
+                            true
+                        } else {
+                            pick -= weight;
+                            false
+                        }
+                    })
+                    .map(|(tier, _)| *tier)
+                    .expect("pick is below the total weight")
+            }
+        };
+
+        if let Some(node) = tiers.get_mut(&tier).and_then(VecDeque::pop_front) {
+            nodes.push(node);
+        }
+    }
+}
 
 // Address manager constants - aligned with Go version
-const PEERS_FILENAME: &str = "peers.json";
-const DEFAULT_STALE_GOOD_TIMEOUT: Duration = Duration::from_secs(60 * 60); // 1 hour (same as Go version)
 const DEFAULT_STALE_BAD_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60); // 2 hours (same as Go version)
 
-const PRUNE_EXPIRE_TIMEOUT: Duration = Duration::from_secs(8 * 60 * 60); // 8 hours, same as Go version
-const PRUNE_ADDRESS_INTERVAL: Duration = Duration::from_secs(60); // 1 minute (same as Go version)
-const DUMP_ADDRESS_INTERVAL: Duration = Duration::from_secs(2 * 60); // 2 minutes (same as Go version)
+// A region whose unreachable share exceeds the overall share by this much
+// suggests the seeder's vantage point cannot reach it
+const DISCOVERY_BIAS_GAP: f64 = 0.25;
+const DISCOVERY_BIAS_MIN_PEERS: usize = 10;
+
+/// Result of [`AddressManager::import_peers`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerImport {
+    /// Records read from the file
+    pub records: usize,
+    /// Records that were new to the address manager
+    pub added: usize,
+    /// Imported records that are good as exported
+    pub good: usize,
+}
+
+/// How long peers stay good and known, and how often the pool is pruned and
+/// saved. Defaults match the Go version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressManagerTimeouts {
+    /// A peer stays good this long after its last successful connection
+    pub stale_good: Duration,
+    /// A peer not seen in gossip for this long is pruned
+    pub prune_expire: Duration,
+    /// How often expired peers are pruned
+    pub prune_interval: Duration,
+    /// How often the peer list is saved to disk
+    pub dump_interval: Duration,
+}
+
+impl Default for AddressManagerTimeouts {
+    fn default() -> Self {
+        Self {
+            stale_good: DEFAULT_STALE_GOOD_TIMEOUT,
+            prune_expire: DEFAULT_PRUNE_EXPIRE_TIMEOUT,
+            prune_interval: DEFAULT_PRUNE_ADDRESS_INTERVAL,
+            dump_interval: DEFAULT_DUMP_ADDRESS_INTERVAL,
+        }
+    }
+}
+
+/// Where a peer address was first learned from
+///
+/// Ordered by trust, so a peer learned from several sources keeps the
+/// most authoritative one.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PeerSource {
+    /// Learned from another peer's address gossip
+    #[default]
+    Gossip,
+    /// Discovered through DNS seeding
+    Seeder,
+    /// Configured in `known_peers`
+    Known,
+    /// Added manually by an operator
+    Manual,
+    /// Configured in `trusted_peers`, used as a trust anchor
+    Trusted,
+}
+
+impl PeerSource {
+    /// Every source, from least to most authoritative
+    pub const ALL: [PeerSource; 5] = [
+        PeerSource::Gossip,
+        PeerSource::Seeder,
+        PeerSource::Known,
+        PeerSource::Manual,
+        PeerSource::Trusted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PeerSource::Gossip => "gossip",
+            PeerSource::Seeder => "seeder",
+            PeerSource::Known => "known",
+            PeerSource::Manual => "manual",
+            PeerSource::Trusted => "trusted",
+        }
+    }
+}
+
+impl std::fmt::Display for PeerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Node status with quality metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +312,30 @@ pub struct Node {
     pub successful_connections: u32,
     pub last_error: Option<String>,
     pub quality_score: f32, // 0.0 to 1.0
+    #[serde(default)]
+    pub source: PeerSource,
+    /// Number of malformed address entries this peer has sent us
+    #[serde(default)]
+    pub malformed_addresses: u32,
+    /// Whether a trusted peer has reported this address
+    #[serde(default)]
+    pub vouched: bool,
+    /// Protocol version negotiated with the peer
+    #[serde(default)]
+    pub protocol_version: u32,
+    /// Whether the peer asked not to be advertised by seeders
+    #[serde(default)]
+    pub opted_out: bool,
+    /// Connection attempts started, successful or not
+    #[serde(default)]
+    pub attempts: u32,
+    /// Failed connection attempts since the last successful one
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    /// Why the node was classified good, stale or bad at the last prune,
+    /// when classification reasons are recorded
+    #[serde(default)]
+    pub classification_reason: Option<String>,
 }
 
 impl Node {
@@ -49,6 +353,14 @@ impl Node {
             successful_connections: 0,
             last_error: None,
             quality_score: 0.5, // Start with neutral score
+            source: PeerSource::default(),
+            malformed_addresses: 0,
+            vouched: false,
+            protocol_version: 0,
+            opted_out: false,
+            attempts: 0,
+            consecutive_failures: 0,
+            classification_reason: None,
         }
     }
 
@@ -65,8 +377,10 @@ impl Node {
             self.successful_connections += 1;
             self.last_success = SystemTime::now();
             self.last_error = None;
+            self.consecutive_failures = 0;
         } else {
             self.last_error = error;
+            self.consecutive_failures = self.consecutive_failures.saturating_add(1);
         }
 
         // Update quality score
@@ -104,7 +418,33 @@ impl Node {
             1.0
         };
 
-        self.quality_score = (success_rate * time_factor * attempt_penalty).clamp(0.0, 1.0);
+        // Each failure in a row since the last success halves the score
+        let failure_penalty = 0.5f32.powi(self.consecutive_failures.min(10) as i32);
+
+        self.quality_score =
+            (success_rate * time_factor * attempt_penalty * failure_penalty).clamp(0.0, 1.0);
+    }
+
+    /// Weight for picking this node when sampling peers to hand out, favoring
+    /// recent successes and penalizing failures in a row. Never zero, so
+    /// every candidate can still be picked.
+    pub fn selection_weight(&self, now: SystemTime) -> f64 {
+        // Full weight right after a success, half after an hour, a third after two
+        let recency = if self.last_success > UNIX_EPOCH {
+            let hours_since_success = now
+                .duration_since(self.last_success)
+                .unwrap_or_default()
+                .as_secs_f64()
+                / 3600.0;
+            1.0 / (1.0 + hours_since_success)
+        } else {
+            0.0
+        };
+
+        // Each failure in a row since the last success halves the weight
+        let reliability = 0.5f64.powi(self.consecutive_failures.min(10) as i32);
+
+        (recency * reliability).max(MIN_SELECTION_WEIGHT)
     }
 
     /// Check if node should be attempted based on quality and timing
@@ -144,34 +484,348 @@ impl Node {
     }
 }
 
+/// Tried peers in one region or autonomous system, by whether they were reached
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reachability {
+    pub reachable: usize,
+    pub unreachable: usize,
+}
+
+impl Reachability {
+    pub fn total(&self) -> usize {
+        self.reachable + self.unreachable
+    }
+
+    /// Share of tried peers that were never reached
+    pub fn unreachable_ratio(&self) -> f64 {
+        if self.total() == 0 {
+            0.0
+        } else {
+            self.unreachable as f64 / self.total() as f64
+        }
+    }
+
+    fn record(&mut self, reachable: bool) {
+        if reachable {
+            self.reachable += 1;
+        } else {
+            self.unreachable += 1;
+        }
+    }
+}
+
+/// Reachability of discovered peers by region and autonomous system, to spot
+/// parts of the network the seeder's vantage point cannot reach
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryBiasReport {
+    pub overall: Reachability,
+    pub by_region: BTreeMap<String, Reachability>,
+    pub by_asn: BTreeMap<String, Reachability>,
+}
+
+impl DiscoveryBiasReport {
+    /// Regions with enough tried peers whose unreachable share stands out
+    pub fn blind_spots(&self) -> Vec<(&str, Reachability)> {
+        let overall = self.overall.unreachable_ratio();
+        self.by_region
+            .iter()
+            .filter(|(_, reachability)| {
+                reachability.total() >= DISCOVERY_BIAS_MIN_PEERS
+                    && reachability.unreachable_ratio() - overall > DISCOVERY_BIAS_GAP
+            })
+            .map(|(region, reachability)| (region.as_str(), *reachability))
+            .collect()
+    }
+}
+
+/// Degraded serving when the good pool is small: peers that were good
+/// within `cutoff` are served until the answer has `min_good` peers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleFallback {
+    pub min_good: usize,
+    pub cutoff: Duration,
+}
+
+/// Holding area for newly gossiped addresses. They are released to the crawl
+/// queue at a steady rate, so a flood of fake addresses cannot take over
+/// crawler capacity while legitimate discovery carries on.
+#[derive(Debug)]
+pub struct AddressQuarantine {
+    release_per_minute: u32,
+    state: Mutex<QuarantineState>,
+}
+
+#[derive(Debug)]
+struct QuarantineState {
+    pending: VecDeque<String>,
+    held: HashSet<String>,
+    last_release: Instant,
+    /// Releases allowed by the rate but not yet used, capped at one minute's worth
+    allowance: f64,
+}
+
+impl AddressQuarantine {
+    pub fn new(release_per_minute: u32) -> Self {
+        Self {
+            release_per_minute,
+            state: Mutex::new(QuarantineState {
+                pending: VecDeque::new(),
+                held: HashSet::new(),
+                last_release: Instant::now(),
+                allowance: 0.0,
+            }),
+        }
+    }
+
+    /// Hold a node until it is released
+    pub fn hold(&self, key: String) {
+        let mut state = self.state.lock().unwrap();
+        if state.held.insert(key.clone()) {
+            state.pending.push_back(key);
+        }
+    }
+
+    /// Release a node early, e.g. once a more trusted source reports it
+    pub fn forget(&self, key: &str) {
+        self.state.lock().unwrap().held.remove(key);
+    }
+
+    pub fn is_held(&self, key: &str) -> bool {
+        self.state.lock().unwrap().held.contains(key)
+    }
+
+    /// Number of nodes still held
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().held.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Release the nodes the rate allows for the time since the previous
+    /// release, oldest first, returning their keys
+    pub fn release(&self, now: Instant) -> Vec<String> {
+        let mut state = self.state.lock().unwrap();
+        let since_last = now.saturating_duration_since(state.last_release);
+        state.last_release = now;
+        state.allowance = (state.allowance
+            + self.release_per_minute as f64 * since_last.as_secs_f64() / 60.0)
+            .min(self.release_per_minute as f64);
+
+        let mut released = Vec::new();
+        while state.allowance >= 1.0 {
+            let Some(key) = state.pending.pop_front() else {
+                break;
+            };
+            // Nodes forgotten or released early do not use up the allowance
+            if state.held.remove(&key) {
+                state.allowance -= 1.0;
+                released.push(key);
+            }
+        }
+        released
+    }
+}
+
+/// A jump of the system clock relative to the monotonic clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockJump {
+    Forward(Duration),
+    Backward(Duration),
+}
+
+/// Detects system clock adjustments (NTP corrections, VM suspend/resume) by
+/// comparing how far the wall clock moved against the monotonic clock
+/// between checks
+#[derive(Debug)]
+pub struct ClockSkewDetector {
+    threshold: Duration,
+    last: Mutex<(SystemTime, Instant)>,
+}
+
+impl ClockSkewDetector {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            last: Mutex::new((SystemTime::now(), Instant::now())),
+        }
+    }
+
+    /// Compare both clocks with the previous check, returning the jump if
+    /// they drifted apart by more than the threshold
+    pub fn check(&self, wall_now: SystemTime, monotonic_now: Instant) -> Option<ClockJump> {
+        let mut last = self.last.lock().unwrap();
+        let (last_wall, last_monotonic) = *last;
+        *last = (wall_now, monotonic_now);
+
+        let monotonic = monotonic_now.saturating_duration_since(last_monotonic);
+        let jump = match wall_now.duration_since(last_wall) {
+            Ok(wall) if wall >= monotonic => ClockJump::Forward(wall - monotonic),
+            Ok(wall) => ClockJump::Backward(monotonic - wall),
+            Err(e) => ClockJump::Backward(monotonic + e.duration()),
+        };
+        match jump {
+            ClockJump::Forward(skew) | ClockJump::Backward(skew) if skew > self.threshold => {
+                Some(jump)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// How a node is classified for serving and pruning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStatus {
+    /// Recently reached on the default port
+    Good,
+    /// Not good, but due for a retry
+    Stale,
+    /// Neither good nor stale
+    Bad,
+}
+
+impl NodeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeStatus::Good => "good",
+            NodeStatus::Stale => "stale",
+            NodeStatus::Bad => "bad",
+        }
+    }
+}
+
+/// Classification of one peer, reported by [`AddressManager::peer_status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerStatus {
+    pub status: NodeStatus,
+    /// Reason recorded at the last prune, if reasons are recorded
+    pub reason: Option<String>,
+    pub last_success: SystemTime,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// Node counts reported by [`AddressManager::get_address_summary`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AddressSummary {
+    pub nodes: usize,
+    pub ipv4: usize,
+    pub ipv6: usize,
+    pub good: usize,
+    /// Not good, but due for a retry
+    pub stale: usize,
+    /// Neither good nor stale
+    pub bad: usize,
+}
+
+/// Outcome of one prune, reported by [`AddressManager::prune_peers`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneSummary {
+    /// Expired nodes removed
+    pub pruned: usize,
+    pub good: usize,
+    pub stale: usize,
+    pub bad: usize,
+}
+
+impl std::fmt::Display for AddressSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "nodes={} ipv4={} ipv6={} good={}",
+            self.nodes, self.ipv4, self.ipv6, self.good
+        )
+    }
+}
+
 /// Address manager, corresponding to Go version's Manager
 pub struct AddressManager {
     nodes: DashMap<String, Node>,
-    peers_file: String,
+    store: Arc<dyn PeerStore>,
     quit_tx: mpsc::Sender<()>,
+    /// Taken by the background task started with [`AddressManager::start`]
+    quit_rx: Mutex<Option<mpsc::Receiver<()>>>,
     stats: Arc<CrawlerStats>,
     default_port: u16, // Add default port for network
+    /// Port each IP was last successfully reached on
+    reachable_ports: DashMap<IpAddr, u16>,
+    prefer_reachable_port: bool,
+    /// Number of nodes stored for each IP
+    ports_per_ip: DashMap<IpAddr, usize>,
+    max_ports_per_ip: usize,
+    /// Upper bound on stored nodes, 0 for no bound
+    max_stored_addresses: usize,
+    /// Whether unroutable (e.g. private network) addresses may be stored,
+    /// switched on reload
+    accept_unroutable: AtomicBool,
+    /// Zone serial, bumped whenever the peer set changes
+    serial: Arc<AtomicU32>,
+    started_at: Instant,
+    /// Banned IPs, persisted in `bans.json`
+    bans: Arc<BanList>,
+    /// User agent marker peers use to opt out of being advertised
+    opt_out_marker: Option<String>,
+    stale_fallback: Option<StaleFallback>,
+    /// Database good peers are geo-tagged with
+    geoip: Option<Arc<GeoIpDatabase>>,
+    expose_country_counts: bool,
+    expose_source_counts: bool,
+    record_classification_reasons: bool,
+    discovery_bias_report: bool,
+    /// Discovery edges recorded while crawling, if enabled
+    crawl_graph: Option<Arc<CrawlGraph>>,
+    timeouts: AddressManagerTimeouts,
+    /// Consecutive failures after which a node is pruned, 0 for no limit
+    max_consecutive_failures: u32,
+    /// Holding area for new gossiped addresses, if enabled
+    quarantine: Option<Arc<AddressQuarantine>>,
+    /// Corrects node timestamps after system clock jumps, if enabled
+    clock_skew: Option<Arc<ClockSkewDetector>>,
 }
 
 impl AddressManager {
-    /// Create a new address manager
+    /// Create a new address manager persisting nodes to `peers.json`
     pub fn new(app_dir: &str, default_port: u16) -> Result<Self> {
-        let peers_file = std::path::Path::new(app_dir).join(PEERS_FILENAME);
-        let peers_file = peers_file.to_string_lossy().to_string();
+        Self::open(app_dir, default_port, StorageBackend::Json)
+    }
 
+    /// Create a new address manager persisting nodes with the given backend
+    pub fn open(app_dir: &str, default_port: u16, backend: StorageBackend) -> Result<Self> {
         // Ensure the directory exists
-        if let Some(parent_dir) = std::path::Path::new(&peers_file).parent() {
-            std::fs::create_dir_all(parent_dir)?;
-        }
+        std::fs::create_dir_all(app_dir)?;
+        let store: Arc<dyn PeerStore> = Arc::from(backend.open(app_dir)?);
 
-        let (quit_tx, _quit_rx) = mpsc::channel(1);
+        let (quit_tx, quit_rx) = mpsc::channel(1);
 
         let manager = Self {
             nodes: DashMap::new(),
-            peers_file,
+            store,
             quit_tx,
+            quit_rx: Mutex::new(Some(quit_rx)),
             stats: Arc::new(CrawlerStats::default()),
             default_port,
+            reachable_ports: DashMap::new(),
+            prefer_reachable_port: true,
+            ports_per_ip: DashMap::new(),
+            max_ports_per_ip: DEFAULT_MAX_PORTS_PER_IP,
+            max_stored_addresses: DEFAULT_MAX_STORED_ADDRESSES,
+            accept_unroutable: AtomicBool::new(false),
+            serial: Arc::new(AtomicU32::new(unix_time_secs())),
+            started_at: Instant::now(),
+            bans: Arc::new(BanList::load(app_dir)?),
+            opt_out_marker: None,
+            stale_fallback: None,
+            geoip: None,
+            expose_country_counts: false,
+            expose_source_counts: false,
+            record_classification_reasons: false,
+            discovery_bias_report: false,
+            crawl_graph: None,
+            timeouts: AddressManagerTimeouts::default(),
+            max_consecutive_failures: DEFAULT_MAX_CONSECUTIVE_FAILURES,
+            quarantine: None,
+            clock_skew: None,
         };
 
         // Load saved nodes
@@ -180,23 +834,162 @@ impl AddressManager {
         Ok(manager)
     }
 
-    /// Start the address manager (call this after creation to start background tasks)
-    pub fn start(&self) {
-        // Start address processing coroutine
-        let manager_clone = self.clone();
+    /// Set whether the port a peer was reached on wins over gossiped ports
+    pub fn with_prefer_reachable_port(mut self, enabled: bool) -> Self {
+        self.prefer_reachable_port = enabled;
+        self
+    }
+
+    /// Limit how many ports are stored for a single IP (0 disables the cap).
+    /// Manually added and trusted peers are not capped.
+    pub fn with_max_ports_per_ip(mut self, max_ports: usize) -> Self {
+        self.max_ports_per_ip = max_ports;
+        self
+    }
+
+    /// Limit how many nodes are stored (0 disables the cap). Once exceeded,
+    /// the least recently seen nodes that are neither good nor manually added
+    /// or trusted are evicted.
+    pub fn with_max_stored_addresses(mut self, max_addresses: usize) -> Self {
+        self.max_stored_addresses = max_addresses;
+        self
+    }
+
+    /// Keep unroutable addresses, for seeding a private network. Without it,
+    /// stored unroutable nodes are dropped.
+    pub fn with_accept_unroutable(self, enabled: bool) -> Self {
+        self.set_accept_unroutable(enabled);
+        self
+    }
+
+    /// Switch whether unroutable addresses are kept, dropping the stored
+    /// ones when switched off
+    pub fn set_accept_unroutable(&self, enabled: bool) {
+        self.accept_unroutable.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.evict_unroutable();
+        }
+    }
+
+    /// Whether unroutable addresses are kept
+    pub fn accepts_unroutable(&self) -> bool {
+        self.accept_unroutable.load(Ordering::Relaxed)
+    }
+
+    /// Prune nodes that failed this many connections in a row (0 disables).
+    /// Manually added and trusted peers are kept.
+    pub fn with_max_consecutive_failures(mut self, max_failures: u32) -> Self {
+        self.max_consecutive_failures = max_failures;
+        self
+    }
+
+    /// Shift node timestamps when the system clock jumps by more than the
+    /// threshold, so classification follows real elapsed time (zero disables)
+    pub fn with_clock_skew_threshold(mut self, threshold: Duration) -> Self {
+        self.clock_skew =
+            (!threshold.is_zero()).then(|| Arc::new(ClockSkewDetector::new(threshold)));
+        self
+    }
+
+    /// Hold new gossiped addresses in quarantine and release them to the
+    /// crawl queue at the given rate per minute (0 disables the quarantine)
+    pub fn with_quarantine(mut self, release_per_minute: u32) -> Self {
+        self.quarantine =
+            (release_per_minute > 0).then(|| Arc::new(AddressQuarantine::new(release_per_minute)));
+        self
+    }
+
+    /// Override how long peers stay good and known, and how often the pool is
+    /// pruned and saved
+    pub fn with_timeouts(mut self, timeouts: AddressManagerTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Set the user agent marker that opts a peer out of DNS answers. Such
+    /// peers are still crawled and counted in statistics.
+    pub fn with_opt_out_marker(mut self, marker: Option<String>) -> Self {
+        self.opt_out_marker = marker.filter(|marker| !marker.is_empty());
+        self
+    }
+
+    /// Set whether a small good pool is padded with recently good peers
+    pub fn with_stale_fallback(mut self, stale_fallback: Option<StaleFallback>) -> Self {
+        self.stale_fallback = stale_fallback;
+        self
+    }
+
+    /// Set the GeoIP database used to geo-tag peers
+    pub fn with_geoip(mut self, geoip: Option<Arc<GeoIpDatabase>>) -> Self {
+        self.geoip = geoip;
+        self
+    }
+
+    /// Set whether the per-country breakdown of good peers is exposed
+    pub fn with_country_counts(mut self, enabled: bool) -> Self {
+        self.expose_country_counts = enabled;
+        self
+    }
+
+    /// Set whether good peer counts per discovery source are exposed
+    pub fn with_source_counts(mut self, enabled: bool) -> Self {
+        self.expose_source_counts = enabled;
+        self
+    }
+
+    /// Set whether the reason behind each node's classification is recorded
+    /// when pruning
+    pub fn with_classification_reasons(mut self, enabled: bool) -> Self {
+        self.record_classification_reasons = enabled;
+        self
+    }
+
+    /// Set whether reachability by region and ASN is reported
+    pub fn with_discovery_bias_report(mut self, enabled: bool) -> Self {
+        self.discovery_bias_report = enabled;
+        self
+    }
+
+    /// Record which peer reported which address into the given graph
+    pub fn with_crawl_graph(mut self, crawl_graph: Option<Arc<CrawlGraph>>) -> Self {
+        self.crawl_graph = crawl_graph;
+        self
+    }
+
+    /// Discovery edges recorded while crawling, if enabled
+    pub fn crawl_graph(&self) -> Option<Arc<CrawlGraph>> {
+        self.crawl_graph.clone()
+    }
+
+    /// Start the background task that prunes and saves peers until
+    /// [`shutdown`](AddressManager::shutdown) is called. The task shares this
+    /// manager's nodes, so it must run on the manager itself, not a clone.
+    pub fn start(self: &Arc<Self>) -> JoinHandle<()> {
+        let manager = Arc::clone(self);
         tokio::spawn(async move {
-            manager_clone.address_handler().await;
-        });
+            manager.address_handler().await;
+        })
     }
 
-    /// Add address list, return the number of new addresses added
+    /// Add address list learned from peer gossip, return the number of new addresses added
     pub fn add_addresses(
         &self,
         addresses: Vec<NetAddress>,
         _default_port: u16,
         accept_unroutable: bool,
+    ) -> usize {
+        self.add_addresses_from(addresses, PeerSource::Gossip, accept_unroutable)
+    }
+
+    /// Add address list from the given source, return the number of new addresses added
+    pub fn add_addresses_from(
+        &self,
+        addresses: Vec<NetAddress>,
+        source: PeerSource,
+        accept_unroutable: bool,
     ) -> usize {
         let mut _count = 0;
+        let mut touched = Vec::new();
 
         for address in addresses {
             // Check port and routability
@@ -204,79 +997,251 @@ impl AddressManager {
                 continue;
             }
 
+            if self.bans.is_banned(&address.ip) {
+                continue;
+            }
+
+            // Skip gossiped ports for peers we already reach on another port
+            if self.prefer_reachable_port
+                && let Some(port) = self.reachable_ports.get(&address.ip)
+                && *port != address.port
+            {
+                continue;
+            }
+
             let addr_str = format!("{}:{}", address.ip, address.port);
 
             if let Some(mut node) = self.nodes.get_mut(&addr_str) {
                 // Update the last access time of the existing node
                 node.last_seen = SystemTime::now();
+                if source > node.source {
+                    node.source = source;
+                    if let Some(ref quarantine) = self.quarantine {
+                        quarantine.forget(&addr_str);
+                    }
+                }
+                drop(node);
+                touched.push(addr_str);
             } else {
+                // Keep one host from flooding the address book with ports
+                if source < PeerSource::Manual && self.at_port_cap(&address.ip) {
+                    continue;
+                }
+
                 // Create a new node
-                let node = Node::new(address);
-                self.nodes.insert(addr_str, node);
+                let ip = address.ip;
+                let mut node = Node::new(address);
+                node.source = source;
+                if source == PeerSource::Gossip
+                    && let Some(ref quarantine) = self.quarantine
+                {
+                    quarantine.hold(addr_str.clone());
+                }
+                self.nodes.insert(addr_str.clone(), node);
+                self.track_port(ip);
+                touched.push(addr_str);
                 _count += 1;
             }
         }
+        self.persist(touched);
+
+        if _count > 0 {
+            self.evict_excess();
+            self.bump_serial();
+        }
 
         _count
     }
 
-    /// Get addresses that need to be retested - aligned with Go version logic
-    pub fn addresses(&self, threads: u8) -> Vec<NetAddress> {
-        let mut addresses = Vec::new();
-        let max_count = threads as usize * 3;
+    /// Evict the least recently seen evictable nodes until the store is back
+    /// under its cap, returning how many were removed
+    fn evict_excess(&self) -> usize {
+        if self.max_stored_addresses == 0 || self.nodes.len() <= self.max_stored_addresses {
+            return 0;
+        }
+        let excess = self.nodes.len() - self.max_stored_addresses;
 
-        // First pass: look for stale nodes (like Go version)
-        let mut stale_candidates: Vec<_> = self
+        let mut candidates: Vec<(SystemTime, String)> = self
             .nodes
             .iter()
             .filter(|entry| {
                 let node = entry.value();
-                self.is_stale(node)
+                node.source < PeerSource::Manual && !self.is_good(node)
             })
+            .map(|entry| (entry.value().last_seen, entry.key().clone()))
             .collect();
+        candidates.sort_unstable();
 
-        // Sort stale candidates by last attempt time (oldest first) - optimized sorting
-        stale_candidates
-            .sort_unstable_by(|a, b| a.value().last_attempt.cmp(&b.value().last_attempt));
-
-        // Add stale candidates first
-        for candidate in stale_candidates.into_iter().take(max_count) {
-            addresses.push(candidate.value().address.clone());
+        let mut evicted = Vec::new();
+        for (_, key) in candidates.into_iter().take(excess) {
+            if let Some((_, node)) = self.nodes.remove(&key) {
+                self.forget_node(&key, &node);
+                evicted.push(key);
+            }
         }
+        self.unpersist(&evicted);
+        let evicted = evicted.len();
 
-        // If we still need more addresses, add some good nodes
-        if addresses.len() < max_count {
-            let remaining_count = max_count - addresses.len();
-            let mut good_candidates: Vec<_> =
-                self.nodes
-                    .iter()
-                    .filter(|entry| {
-                        let node = entry.value();
-                        // Use more efficient comparison without string formatting
-                        !addresses.iter().any(|addr| {
-                            addr.ip == node.address.ip && addr.port == node.address.port
-                        }) && self.is_good(node)
-                    })
-                    .collect();
+        if evicted > 0 {
+            debug!(
+                "Evicted {} nodes to stay under the cap of {} stored addresses",
+                evicted, self.max_stored_addresses
+            );
+        }
+        evicted
+    }
 
-            // Sort good candidates by last attempt time - optimized sorting
-            good_candidates
-                .sort_unstable_by(|a, b| a.value().last_attempt.cmp(&b.value().last_attempt));
+    /// Remove every stored unroutable node, returning how many were removed
+    fn evict_unroutable(&self) -> usize {
+        let unroutable: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|entry| !self.is_routable(&entry.value().address))
+            .map(|entry| entry.key().clone())
+            .collect();
 
-            for candidate in good_candidates.into_iter().take(remaining_count) {
-                addresses.push(candidate.value().address.clone());
+        let mut evicted = Vec::new();
+        for key in unroutable {
+            if let Some((_, node)) = self.nodes.remove(&key) {
+                self.forget_node(&key, &node);
+                evicted.push(key);
             }
         }
+        self.unpersist(&evicted);
+        let evicted = evicted.len();
 
-        // Log detailed status for debugging
+        if evicted > 0 {
+            info!("Dropped {} unroutable nodes", evicted);
+            self.bump_serial();
+        }
+        evicted
+    }
 
-        info!("Selected {} addresses for crawling", addresses.len());
+    /// Whether the IP already has as many ports stored as allowed
+    fn at_port_cap(&self, ip: &IpAddr) -> bool {
+        self.max_ports_per_ip > 0
+            && self
+                .ports_per_ip
+                .get(ip)
+                .is_some_and(|ports| *ports >= self.max_ports_per_ip)
+    }
 
-        addresses
+    fn track_port(&self, ip: IpAddr) {
+        *self.ports_per_ip.entry(ip).or_insert(0) += 1;
     }
 
-    /// Record connection attempt result for a node
-    pub fn record_connection_result(
+    /// Drop the bookkeeping kept for a node that was removed
+    fn forget_node(&self, key: &str, node: &Node) {
+        self.untrack_port(&node.address.ip);
+        if let Some(ref quarantine) = self.quarantine {
+            quarantine.forget(key);
+        }
+    }
+
+    fn untrack_port(&self, ip: &IpAddr) {
+        self.ports_per_ip.remove_if_mut(ip, |_, ports| {
+            *ports = ports.saturating_sub(1);
+            *ports == 0
+        });
+    }
+
+    /// Current zone serial, for SOA answers
+    pub fn serial(&self) -> u32 {
+        self.serial.load(Ordering::SeqCst)
+    }
+
+    /// Advance the zone serial, tracking wall-clock seconds where possible
+    /// so it keeps increasing across restarts
+    fn bump_serial(&self) {
+        let now = unix_time_secs();
+        let _ = self
+            .serial
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |serial| {
+                Some(serial.wrapping_add(1).max(now))
+            });
+    }
+
+    /// Get addresses that need to be retested - aligned with Go version logic
+    pub fn addresses(&self, threads: u8) -> Vec<NetAddress> {
+        self.address_batch(threads as usize * POLL_BATCH_PER_THREAD)
+    }
+
+    /// Get up to `max_count` addresses to retest, stale nodes first
+    pub fn address_batch(&self, max_count: usize) -> Vec<NetAddress> {
+        let mut addresses = Vec::new();
+
+        if let Some(ref quarantine) = self.quarantine {
+            let released = quarantine.release(Instant::now());
+            if !released.is_empty() {
+                debug!(
+                    "Released {} addresses from quarantine, {} still held",
+                    released.len(),
+                    quarantine.len()
+                );
+            }
+        }
+        let quarantined = |key: &String| {
+            self.quarantine
+                .as_ref()
+                .is_some_and(|quarantine| quarantine.is_held(key))
+        };
+
+        // First pass: look for stale nodes (like Go version)
+        let mut stale_candidates: Vec<_> = self
+            .nodes
+            .iter()
+            .filter(|entry| {
+                let node = entry.value();
+                self.is_stale(node)
+                    && !self.bans.is_banned(&node.address.ip)
+                    && !quarantined(entry.key())
+            })
+            .collect();
+
+        // Sort stale candidates by last attempt time (oldest first), checking
+        // addresses vouched for by trusted peers before the rest
+        stale_candidates
+            .sort_unstable_by_key(|entry| (!entry.value().vouched, entry.value().last_attempt));
+
+        // Add stale candidates first
+        for candidate in stale_candidates.into_iter().take(max_count) {
+            addresses.push(candidate.value().address.clone());
+        }
+
+        // If we still need more addresses, add some good nodes
+        if addresses.len() < max_count {
+            let remaining_count = max_count - addresses.len();
+            let mut good_candidates: Vec<_> =
+                self.nodes
+                    .iter()
+                    .filter(|entry| {
+                        let node = entry.value();
+                        // Use more efficient comparison without string formatting
+                        !addresses.iter().any(|addr| {
+                            addr.ip == node.address.ip && addr.port == node.address.port
+                        }) && self.is_good(node)
+                            && !self.bans.is_banned(&node.address.ip)
+                    })
+                    .collect();
+
+            // Sort good candidates by last attempt time - optimized sorting
+            good_candidates
+                .sort_unstable_by(|a, b| a.value().last_attempt.cmp(&b.value().last_attempt));
+
+            for candidate in good_candidates.into_iter().take(remaining_count) {
+                addresses.push(candidate.value().address.clone());
+            }
+        }
+
+        // Log detailed status for debugging
+
+        info!("Selected {} addresses for crawling", addresses.len());
+
+        addresses
+    }
+
+    /// Record connection attempt result for a node
+    pub fn record_connection_result(
         &self,
         address: &NetAddress,
         success: bool,
@@ -286,6 +1251,124 @@ impl AddressManager {
         if let Some(mut node) = self.nodes.get_mut(&key) {
             node.record_connection_attempt(success, error.clone());
         }
+        self.persist([key]);
+    }
+
+    /// Flag a peer that sent malformed address entries, optionally counting
+    /// it as a failed attempt so its quality score drops
+    pub fn record_malformed_addresses(&self, address: &NetAddress, count: usize, penalize: bool) {
+        let key = format!("{}:{}", address.ip, address.port);
+        if let Some(mut node) = self.nodes.get_mut(&key) {
+            node.malformed_addresses = node.malformed_addresses.saturating_add(count as u32);
+            if penalize {
+                node.record_connection_attempt(
+                    false,
+                    Some(format!("Sent {} malformed addresses", count)),
+                );
+            }
+        }
+        self.persist([key]);
+    }
+
+    /// Count known nodes by IP family and by good/stale/bad, in one pass
+    pub fn get_address_summary(&self) -> AddressSummary {
+        let mut summary = AddressSummary::default();
+
+        for entry in self.nodes.iter() {
+            let node = entry.value();
+            summary.nodes += 1;
+            if node.address.ip.is_ipv4() {
+                summary.ipv4 += 1;
+            } else {
+                summary.ipv6 += 1;
+            }
+            match self.classify(node) {
+                NodeStatus::Good => summary.good += 1,
+                NodeStatus::Stale => summary.stale += 1,
+                NodeStatus::Bad => summary.bad += 1,
+            }
+        }
+
+        summary
+    }
+
+    /// Good peers per country, or `None` when the breakdown is not exposed.
+    /// Peers are counted as `unknown` when GeoIP is unavailable or does not
+    /// cover them.
+    pub fn country_counts(&self) -> Option<BTreeMap<String, usize>> {
+        if !self.expose_country_counts {
+            return None;
+        }
+
+        let mut counts = BTreeMap::new();
+        for entry in self.nodes.iter() {
+            let node = entry.value();
+            if self.classify(node) != NodeStatus::Good {
+                continue;
+            }
+            let country = self
+                .geoip
+                .as_ref()
+                .map(|geoip| geoip.country(&node.address.ip))
+                .unwrap_or(UNKNOWN_COUNTRY);
+            *counts.entry(country.to_string()).or_insert(0) += 1;
+        }
+
+        Some(counts)
+    }
+
+    /// Good peers per discovery source, or `None` when the breakdown is not
+    /// exposed. Every source is listed, with zero when it contributed no good
+    /// peers.
+    pub fn source_counts(&self) -> Option<BTreeMap<PeerSource, usize>> {
+        if !self.expose_source_counts {
+            return None;
+        }
+
+        let mut counts: BTreeMap<PeerSource, usize> = PeerSource::ALL
+            .into_iter()
+            .map(|source| (source, 0))
+            .collect();
+        for entry in self.nodes.iter() {
+            let node = entry.value();
+            if self.classify(node) == NodeStatus::Good {
+                *counts.entry(node.source).or_insert(0) += 1;
+            }
+        }
+
+        Some(counts)
+    }
+
+    /// Reachability of tried peers by region and ASN, or `None` when the
+    /// report is not enabled. Peers never tried yet are left out.
+    pub fn discovery_bias_report(&self) -> Option<DiscoveryBiasReport> {
+        if !self.discovery_bias_report {
+            return None;
+        }
+
+        let mut report = DiscoveryBiasReport::default();
+        for entry in self.nodes.iter() {
+            let node = entry.value();
+            let reachable = node.last_success != UNIX_EPOCH;
+            if !reachable && node.connection_attempts == 0 {
+                continue;
+            }
+
+            let ip = &node.address.ip;
+            let (region, asn) = match self.geoip {
+                Some(ref geoip) => (geoip.region(ip).to_string(), geoip.asn(ip)),
+                None => (UNKNOWN_REGION.to_string(), UNKNOWN_ASN.to_string()),
+            };
+            report.overall.record(reachable);
+            report
+                .by_region
+                .entry(region)
+                .or_default()
+                .record(reachable);
+            report.by_asn.entry(asn).or_default().record(reachable);
+        }
+
+        Some(report)
     }
 
     /// Get the total number of addresses
@@ -293,6 +1376,102 @@ impl AddressManager {
         self.nodes.len()
     }
 
+    /// Get the node stored for an address
+    pub fn get_node(&self, address: &NetAddress) -> Option<Node> {
+        let key = format!("{}:{}", address.ip, address.port);
+        self.nodes.get(&key).map(|entry| entry.value().clone())
+    }
+
+    /// Write every node to `path` in the portable peer format, returning how
+    /// many were written. Records keep each node's source, timestamps and
+    /// connection history so an import restores it as it was.
+    pub fn export_peers(&self, path: &Path) -> Result<usize> {
+        let mut records: Vec<PeerRecord> = self
+            .nodes
+            .iter()
+            .map(|entry| PeerRecord::from(entry.value()))
+            .collect();
+        records.sort_by_key(|record| (record.ip, record.port));
+
+        write_peer_records(path, &records)?;
+        Ok(records.len())
+    }
+
+    /// Merge peers exported by [`export_peers`](Self::export_peers) from
+    /// another seeder, restoring each node as exported. A node we already
+    /// know is only replaced when the export reached it more recently.
+    pub fn import_peers(&self, path: &Path) -> Result<PeerImport> {
+        let records = read_peer_records(path)?;
+
+        let mut added = 0;
+        let mut good = 0;
+        let mut touched = Vec::new();
+        let mut reached = Vec::new();
+        for record in &records {
+            let mut node = Node::from(record);
+            let ip = node.address.ip;
+            if (!self.accepts_unroutable() && !self.is_routable(&node.address))
+                || self.bans.is_banned(&ip)
+            {
+                continue;
+            }
+
+            let addr_str = node.key();
+            let known_success = self.nodes.get(&addr_str).map(|known| known.last_success);
+            match known_success {
+                Some(last_success) if last_success >= node.last_success => continue,
+                Some(_) => {}
+                None => {
+                    // Same limits as for addresses learned any other way
+                    let reached_elsewhere = self.prefer_reachable_port
+                        && node.last_success == UNIX_EPOCH
+                        && self
+                            .reachable_ports
+                            .get(&ip)
+                            .is_some_and(|port| *port != node.address.port);
+                    if reached_elsewhere
+                        || (node.source < PeerSource::Manual && self.at_port_cap(&ip))
+                    {
+                        continue;
+                    }
+                    self.track_port(ip);
+                    added += 1;
+                }
+            }
+
+            node.opted_out = match (&self.opt_out_marker, &node.user_agent) {
+                (Some(marker), Some(user_agent)) => user_agent.contains(marker.as_str()),
+                _ => false,
+            };
+            if self.is_good(&node) {
+                good += 1;
+            }
+            if node.last_success != UNIX_EPOCH {
+                reached.push(node.address.clone());
+            }
+            self.nodes.insert(addr_str.clone(), node);
+            touched.push(addr_str);
+        }
+
+        let changed = !touched.is_empty();
+        self.persist(touched);
+        for address in &reached {
+            self.reconcile_reachable_port(address);
+        }
+        if added > 0 {
+            self.evict_excess();
+        }
+        if changed {
+            self.bump_serial();
+        }
+
+        Ok(PeerImport {
+            records: records.len(),
+            added,
+            good,
+        })
+    }
+
     /// Get all nodes (for statistics)
     pub fn get_all_nodes(&self) -> Vec<Node> {
         self.nodes
@@ -301,6 +1480,11 @@ impl AddressManager {
             .collect()
     }
 
+    /// Whether any stored node has the given IP
+    pub fn has_node_at(&self, ip: &IpAddr) -> bool {
+        self.ports_per_ip.contains_key(ip)
+    }
+
     /// Get good address list, filtered by DNS query type
     pub fn good_addresses(
         &self,
@@ -308,16 +1492,87 @@ impl AddressManager {
         include_all_subnetworks: bool,
         subnetwork_id: Option<&str>,
     ) -> Vec<NetAddress> {
+        self.good_addresses_filtered(qtype, include_all_subnetworks, subnetwork_id, 0)
+    }
+
+    /// Get good address list, filtered by DNS query type and the service
+    /// flags a peer must advertise (0 accepts any peer)
+    pub fn good_addresses_filtered(
+        &self,
+        qtype: u16,
+        include_all_subnetworks: bool,
+        subnetwork_id: Option<&str>,
+        required_services: u64,
+    ) -> Vec<NetAddress> {
+        self.good_nodes_filtered(
+            qtype,
+            include_all_subnetworks,
+            subnetwork_id,
+            required_services,
+        )
+        .into_iter()
+        .map(|node| node.address)
+        .collect()
+    }
+
+    /// Get the nodes behind `good_addresses`, with their connection details
+    pub fn good_nodes(
+        &self,
+        qtype: u16,
+        include_all_subnetworks: bool,
+        subnetwork_id: Option<&str>,
+    ) -> Vec<Node> {
+        self.good_nodes_filtered(qtype, include_all_subnetworks, subnetwork_id, 0)
+    }
+
+    /// Good nodes of the requested address families, IPv4 first, across all
+    /// subnetworks unless `subnetwork_id` is set
+    pub fn good_nodes_by_family(
+        &self,
+        include_ipv4: bool,
+        include_ipv6: bool,
+        subnetwork_id: Option<&str>,
+    ) -> Vec<Node> {
+        let subnetwork_id = subnetwork_id.filter(|id| !id.is_empty());
+
+        let mut nodes = Vec::new();
+        if include_ipv4 {
+            nodes.extend(
+                self.good_nodes(1, subnetwork_id.is_none(), subnetwork_id)
+                    .into_iter()
+                    .filter(|node| node.address.ip.is_ipv4()),
+            );
+        }
+        if include_ipv6 {
+            nodes.extend(
+                self.good_nodes(28, subnetwork_id.is_none(), subnetwork_id)
+                    .into_iter()
+                    .filter(|node| node.address.ip.is_ipv6()),
+            );
+        }
+        nodes
+    }
+
+    /// Get the nodes behind `good_addresses_filtered`
+    pub fn good_nodes_filtered(
+        &self,
+        qtype: u16,
+        include_all_subnetworks: bool,
+        subnetwork_id: Option<&str>,
+        required_services: u64,
+    ) -> Vec<Node> {
         let mut addresses = Vec::new();
         let mut _count = 0;
         let mut total_nodes = 0;
         let mut good_nodes = 0;
         let mut stale_nodes = 0;
         let mut bad_nodes = 0;
+        // Bad nodes that were good recently, served if the pool runs short
+        let mut fallback_candidates = Vec::new();
 
-        // Only support A and AAAA records
-        if qtype != 1 && qtype != 28 {
-            // 1=A, 28=AAAA
+        // Only support A, AAAA and SRV records
+        if qtype != 1 && qtype != 28 && qtype != 33 {
+            // 1=A, 28=AAAA, 33=SRV
             return addresses;
         }
 
@@ -327,9 +1582,9 @@ impl AddressManager {
 
             // Check subnet
             if !include_all_subnetworks {
-                if let Some(ref expected_id) = subnetwork_id {
+                if let Some(expected_id) = subnetwork_id {
                     if let Some(ref node_id) = node.subnetwork_id {
-                        if expected_id != node_id {
+                        if !expected_id.eq_ignore_ascii_case(node_id) {
                             continue;
                         }
                     } else {
@@ -338,27 +1593,66 @@ impl AddressManager {
                 }
             }
 
+            // Check advertised services
+            if node.services & required_services != required_services {
+                continue;
+            }
+
+            // Respect peers that opted out of being advertised
+            if node.opted_out || self.bans.is_banned(&node.address.ip) {
+                continue;
+            }
+
             // Check IP type
             let is_ipv4 = node.address.ip.is_ipv4();
             if (qtype == 1 && !is_ipv4) || (qtype == 28 && is_ipv4) {
                 continue;
             }
 
+            // SRV answers carry the peer's port, so non-default ports qualify there.
+            // Trusted peers are served until a connection attempt says otherwise.
+            let is_good = if qtype == 33 {
+                self.is_good_on_any_port(node)
+            } else {
+                self.is_good(node)
+            } || Self::is_untested_trusted(node);
+
             // Check node status - allow both good and stale nodes for DNS queries
             // This ensures DNS queries can return addresses even when nodes are still being evaluated
-            if self.is_good(node) {
+            if is_good {
                 good_nodes += 1;
-                addresses.push(node.address.clone());
+                addresses.push(node.clone());
                 _count += 1;
             } else if self.is_stale(node) {
                 stale_nodes += 1;
-                addresses.push(node.address.clone());
+                addresses.push(node.clone());
                 _count += 1;
             } else {
                 bad_nodes += 1;
+                if let Some(fallback) = self.stale_fallback
+                    && self.has_success_within(node, fallback.cutoff)
+                {
+                    fallback_candidates.push(node.clone());
+                }
             }
         }
 
+        // Degraded serving: pad a small pool with the most recently good peers
+        if let Some(fallback) = self.stale_fallback
+            && addresses.len() < fallback.min_good
+            && !fallback_candidates.is_empty()
+        {
+            fallback_candidates.sort_by_key(|node| std::cmp::Reverse(node.last_success));
+            let padding = fallback.min_good - addresses.len();
+            let served = fallback_candidates.len().min(padding);
+            addresses.extend(fallback_candidates.into_iter().take(padding));
+            self.stats.record_degraded_answer();
+            warn!(
+                "Good pool below {} peers, serving {} stale peers (qtype={})",
+                fallback.min_good, served, qtype
+            );
+        }
+
         info!(
             "DNS query: qtype={}, total_nodes={}, good={}, stale={}, bad={}, returned={}",
             qtype,
@@ -369,6 +1663,13 @@ impl AddressManager {
             addresses.len()
         );
 
+        if !addresses.is_empty() {
+            let elapsed = self.started_at.elapsed();
+            if self.stats.record_first_good_peer(elapsed) {
+                info!("✅ First good peer available {:?} after startup", elapsed);
+            }
+        }
+
         addresses
     }
 
@@ -378,7 +1679,19 @@ impl AddressManager {
 
         if let Some(mut node) = self.nodes.get_mut(&addr_str) {
             node.last_attempt = SystemTime::now();
+            node.attempts = node.attempts.saturating_add(1);
+        }
+        self.persist([addr_str]);
+    }
+
+    /// Record a failed connection attempt
+    pub fn failed(&self, address: &NetAddress, error: Option<String>) {
+        let addr_str = format!("{}:{}", address.ip, address.port);
+
+        if let Some(mut node) = self.nodes.get_mut(&addr_str) {
+            node.record_connection_attempt(false, error);
         }
+        self.persist([addr_str]);
     }
 
     /// Update successful connection information
@@ -390,35 +1703,175 @@ impl AddressManager {
     ) {
         let addr_str = format!("{}:{}", address.ip, address.port);
 
-        if let Some(mut node) = self.nodes.get_mut(&addr_str) {
+        let first_success = if let Some(mut node) = self.nodes.get_mut(&addr_str) {
+            node.opted_out = match (&self.opt_out_marker, user_agent) {
+                (Some(marker), Some(user_agent)) => user_agent.contains(marker.as_str()),
+                _ => false,
+            };
             node.user_agent = user_agent.map(|s| s.to_string());
             node.subnetwork_id = subnetwork_id.map(|s| s.to_string());
+            let first_success = node.last_success == UNIX_EPOCH;
             node.last_success = SystemTime::now();
+            node.consecutive_failures = 0;
+            first_success
+        } else {
+            return;
+        };
+        self.persist([addr_str]);
+
+        if first_success {
+            self.bump_serial();
+        }
+
+        self.reconcile_reachable_port(address);
+    }
+
+    /// Make the port we successfully connected on the canonical entry for
+    /// this IP, dropping never-reached entries gossiped with other ports
+    fn reconcile_reachable_port(&self, address: &NetAddress) {
+        if !self.prefer_reachable_port {
+            return;
+        }
+
+        let previous = self.reachable_ports.insert(address.ip, address.port);
+        if previous == Some(address.port) {
+            return;
+        }
+
+        let mut removed = Vec::new();
+        self.nodes.retain(|key, node| {
+            let keep = node.address.ip != address.ip
+                || node.address.port == address.port
+                || node.last_success != UNIX_EPOCH;
+            if !keep {
+                self.forget_node(key, node);
+                removed.push(key.clone());
+            }
+            keep
+        });
+        if !removed.is_empty() {
+            self.unpersist(&removed);
+            self.bump_serial();
+        }
+    }
+
+    /// Check whether the address was configured as a trusted peer
+    pub fn is_trusted(&self, address: &NetAddress) -> bool {
+        self.get_node(address)
+            .is_some_and(|node| node.source == PeerSource::Trusted)
+    }
+
+    /// Mark addresses reported by a trusted peer, so the crawler checks them
+    /// ahead of unconfirmed gossip
+    pub fn vouch_for(&self, addresses: &[NetAddress]) {
+        let mut vouched = Vec::new();
+        for address in addresses {
+            let addr_str = format!("{}:{}", address.ip, address.port);
+            if let Some(mut node) = self.nodes.get_mut(&addr_str) {
+                node.vouched = true;
+                vouched.push(addr_str);
+            }
+        }
+        self.persist(vouched);
+    }
+
+    /// Record the protocol version negotiated with a peer
+    pub fn set_protocol_version(&self, address: &NetAddress, protocol_version: u32) {
+        let addr_str = format!("{}:{}", address.ip, address.port);
+
+        if let Some(mut node) = self.nodes.get_mut(&addr_str) {
+            node.protocol_version = protocol_version;
         }
+        self.persist([addr_str]);
+    }
+
+    /// Record the service flags a peer advertised
+    pub fn set_services(&self, address: &NetAddress, services: u64) {
+        let addr_str = format!("{}:{}", address.ip, address.port);
+
+        if let Some(mut node) = self.nodes.get_mut(&addr_str) {
+            node.services = services;
+        }
+        self.persist([addr_str]);
     }
 
     /// Address processing coroutine
     async fn address_handler(&self) {
-        let mut prune_ticker = tokio::time::interval(PRUNE_ADDRESS_INTERVAL);
-        let mut dump_ticker = tokio::time::interval(DUMP_ADDRESS_INTERVAL);
+        let Some(mut quit_rx) = self.quit_rx.lock().unwrap().take() else {
+            warn!("Address manager background task is already running");
+            return;
+        };
+        let mut prune_ticker = tokio::time::interval(self.timeouts.prune_interval);
+        let mut dump_ticker = tokio::time::interval(self.timeouts.dump_interval);
+        let mut clock_ticker = tokio::time::interval(CLOCK_SKEW_CHECK_INTERVAL);
 
         loop {
             tokio::select! {
                 _ = prune_ticker.tick() => {
                     self.prune_peers();
                 }
+                _ = clock_ticker.tick(), if self.clock_skew.is_some() => {
+                    self.check_clock_skew();
+                }
                 _ = dump_ticker.tick() => {
                     if let Err(e) = self.save_peers() {
                         error!("Failed to save peers: {}", e);
                     }
                 }
+                _ = quit_rx.recv() => {
+                    break;
+                }
             }
         }
+
+        if let Err(e) = self.save_peers() {
+            error!("Failed to save peers during shutdown: {}", e);
+        }
+        info!("Address manager stopped");
+    }
+
+    /// Correct node timestamps if the system clock jumped since the last check
+    fn check_clock_skew(&self) {
+        let Some(ref detector) = self.clock_skew else {
+            return;
+        };
+        if let Some(jump) = detector.check(SystemTime::now(), Instant::now()) {
+            warn!("System clock jumped ({:?}), shifting node timestamps", jump);
+            self.adjust_for_clock_jump(jump);
+        }
+    }
+
+    /// Shift every recorded node timestamp by a clock jump, so times taken
+    /// before the jump keep their real age. Without this a backward jump
+    /// leaves timestamps in the future, which read as zero elapsed time and
+    /// keep nodes good and fresh long after they should have aged out.
+    pub fn adjust_for_clock_jump(&self, jump: ClockJump) {
+        let shift = |time: &mut SystemTime| {
+            if *time == UNIX_EPOCH {
+                return;
+            }
+            *time = match jump {
+                ClockJump::Forward(skew) => *time + skew,
+                ClockJump::Backward(skew) => time
+                    .checked_sub(skew)
+                    .filter(|shifted| *shifted > UNIX_EPOCH)
+                    .unwrap_or(UNIX_EPOCH + Duration::from_secs(1)),
+            };
+        };
+
+        let mut keys = Vec::with_capacity(self.nodes.len());
+        for mut entry in self.nodes.iter_mut() {
+            let node = entry.value_mut();
+            shift(&mut node.last_seen);
+            shift(&mut node.last_attempt);
+            shift(&mut node.last_success);
+            keys.push(entry.key().clone());
+        }
+        self.persist(keys);
     }
 
-    /// Clean up expired and bad addresses
-    fn prune_peers(&self) {
-        let mut _pruned = 0;
+    /// Clean up expired addresses and count the remaining ones by status
+    pub fn prune_peers(&self) -> PruneSummary {
         let mut good = 0;
         let mut stale = 0;
         let mut bad = 0;
@@ -427,48 +1880,95 @@ impl AddressManager {
 
         let now = SystemTime::now();
         let mut to_remove = Vec::new();
+        let mut reasons = Vec::new();
 
         for entry in self.nodes.iter() {
             let node = entry.value();
 
             if self.is_expired(node, now) {
                 to_remove.push(entry.key().clone());
-                // Count pruned addresses
-            } else if self.is_good(node) {
-                good += 1;
-                if node.address.ip.is_ipv4() {
-                    ipv4 += 1;
-                } else {
-                    ipv6 += 1;
+                continue;
+            }
+
+            let status = self.classify(node);
+            if self.record_classification_reasons {
+                reasons.push((
+                    entry.key().clone(),
+                    self.classification_reason(node, status),
+                ));
+            }
+            match status {
+                NodeStatus::Good => {
+                    good += 1;
+                    if node.address.ip.is_ipv4() {
+                        ipv4 += 1;
+                    } else {
+                        ipv6 += 1;
+                    }
                 }
-            } else if self.is_stale(node) {
-                stale += 1;
-            } else {
-                bad += 1;
+                NodeStatus::Stale => stale += 1,
+                NodeStatus::Bad => bad += 1,
+            }
+        }
+
+        for (key, reason) in reasons {
+            if let Some(mut node) = self.nodes.get_mut(&key) {
+                node.classification_reason = Some(reason);
             }
         }
 
         // Remove expired nodes
-        for key in to_remove {
-            self.nodes.remove(&key);
+        if !to_remove.is_empty() {
+            self.bump_serial();
+        }
+        for key in &to_remove {
+            if let Some((_, node)) = self.nodes.remove(key) {
+                self.forget_node(key, &node);
+            }
         }
+        self.unpersist(&to_remove);
 
-        let _total = self.nodes.len();
+        if let Some(ref crawl_graph) = self.crawl_graph {
+            crawl_graph.prune();
+        }
 
         info!(
             "Known nodes: Good:{} [4:{}, 6:{}] Stale:{} Bad:{}",
             good, ipv4, ipv6, stale, bad
         );
+
+        if let Some(report) = self.discovery_bias_report() {
+            for (region, reachability) in &report.by_region {
+                info!(
+                    "Region {}: reachable {}, unreachable {}",
+                    region, reachability.reachable, reachability.unreachable
+                );
+            }
+            for (region, reachability) in report.blind_spots() {
+                warn!(
+                    "Possible blind spot: {:.0}% of {} tried peers in {} unreachable vs {:.0}% overall",
+                    reachability.unreachable_ratio() * 100.0,
+                    reachability.total(),
+                    region,
+                    report.overall.unreachable_ratio() * 100.0
+                );
+            }
+        }
+
+        PruneSummary {
+            pruned: to_remove.len(),
+            good,
+            stale,
+            bad,
+        }
     }
 
-    /// Save addresses to file
+    /// Save addresses to the peer store. Incremental stores are kept up to
+    /// date as nodes change, so for them this only waits for queued writes
+    /// and reports any that failed.
     fn save_peers(&self) -> Result<()> {
-        // Ensure the directory exists before writing files
-        if let Some(parent_dir) = std::path::Path::new(&self.peers_file).parent() {
-            if let Err(e) = std::fs::create_dir_all(parent_dir) {
-                error!("Failed to create directory {}: {}", parent_dir.display(), e);
-                return Err(crate::errors::KaseederError::Io(e));
-            }
+        if self.store.is_incremental() {
+            return self.store.flush();
         }
 
         let nodes: Vec<_> = self
@@ -477,69 +1977,145 @@ impl AddressManager {
             .map(|entry| (entry.key().clone(), entry.value().clone()))
             .collect();
 
-        // Create temporary file
-        let tmp_file = format!("{}.new", self.peers_file);
+        self.store.save_all(&nodes)
+    }
+
+    /// Load addresses from the peer store
+    fn deserialize_peers(&self) -> Result<()> {
+        let nodes = self.store.load()?;
+
+        let nodes_count = nodes.len();
+        for (key, node) in nodes {
+            if node.last_success != UNIX_EPOCH {
+                self.reachable_ports
+                    .insert(node.address.ip, node.address.port);
+            }
+            self.track_port(node.address.ip);
+            self.nodes.insert(key, node);
+        }
+
+        info!(
+            "{} nodes loaded from {}",
+            nodes_count,
+            self.store.location().display()
+        );
+        Ok(())
+    }
 
-        // Check if we can write to the temporary file
-        let serialized_nodes = serde_json::to_string(&nodes).map_err(|e| {
-            crate::errors::KaseederError::Serialization(format!("Failed to serialize nodes: {}", e))
-        })?;
+    /// Write the current state of the given nodes through to an
+    /// incremental peer store
+    fn persist<I: IntoIterator<Item = String>>(&self, keys: I) {
+        if !self.store.is_incremental() {
+            return;
+        }
 
-        if let Err(e) = std::fs::write(&tmp_file, serialized_nodes) {
-            error!("Failed to write temporary file {}: {}", tmp_file, e);
-            return Err(crate::errors::KaseederError::Io(e));
+        let nodes: Vec<(String, Node)> = keys
+            .into_iter()
+            .filter_map(|key| {
+                let node = self.nodes.get(&key)?.value().clone();
+                Some((key, node))
+            })
+            .collect();
+        if let Err(e) = self.store.upsert(&nodes) {
+            error!("Failed to persist {} nodes: {}", nodes.len(), e);
         }
+    }
 
-        // Verify temporary file was created and has content
-        if !std::path::Path::new(&tmp_file).exists() {
-            error!("Temporary file {} was not created", tmp_file);
-            return Err(crate::errors::KaseederError::Config(
-                "Temporary file creation failed".to_string(),
-            ));
+    /// Delete removed nodes from an incremental peer store
+    fn unpersist(&self, keys: &[String]) {
+        if !self.store.is_incremental() {
+            return;
         }
 
-        // Atomically rename file
-        if let Err(e) = std::fs::rename(&tmp_file, &self.peers_file) {
+        if let Err(e) = self.store.remove(keys) {
             error!(
-                "Failed to rename {} to {}: {}",
-                tmp_file, self.peers_file, e
+                "Failed to delete {} nodes from the peer store: {}",
+                keys.len(),
+                e
             );
-            // Try to clean up temporary file, but don't fail if cleanup fails
-            if let Err(cleanup_e) = std::fs::remove_file(&tmp_file) {
-                error!(
-                    "Failed to remove temporary file {}: {}",
-                    tmp_file, cleanup_e
-                );
-            }
-            return Err(crate::errors::KaseederError::Io(e));
         }
+    }
 
-        Ok(())
+    /// Check if node is expired
+    fn is_expired(&self, node: &Node, now: SystemTime) -> bool {
+        // Manually added and trusted peers are only removed by an operator
+        if node.source >= PeerSource::Manual {
+            return false;
+        }
+
+        if self.max_consecutive_failures > 0
+            && node.consecutive_failures >= self.max_consecutive_failures
+        {
+            return true;
+        }
+
+        let last_seen_elapsed = now.duration_since(node.last_seen).unwrap_or_default();
+
+        last_seen_elapsed > self.timeouts.prune_expire
     }
 
-    /// Load addresses from file
-    fn deserialize_peers(&self) -> Result<()> {
-        if !std::path::Path::new(&self.peers_file).exists() {
-            return Ok(());
+    /// Classify a node the same way pruning and the node count log do
+    pub fn classify(&self, node: &Node) -> NodeStatus {
+        if self.is_good(node) {
+            NodeStatus::Good
+        } else if self.is_stale(node) {
+            NodeStatus::Stale
+        } else {
+            NodeStatus::Bad
+        }
+    }
+
+    /// Human-readable reason a node has the given classification
+    fn classification_reason(&self, node: &Node, status: NodeStatus) -> String {
+        let since_success = SystemTime::now()
+            .duration_since(node.last_success)
+            .unwrap_or_default()
+            .as_secs();
+
+        if status == NodeStatus::Good {
+            return format!("reached {}s ago", since_success);
         }
 
-        let content = std::fs::read_to_string(&self.peers_file)?;
-        let nodes: Vec<(String, Node)> = serde_json::from_str(&content)?;
+        let reason = if self.is_nondefault_port(&node.address) {
+            format!("non-default port {}", node.address.port)
+        } else if node.last_success == UNIX_EPOCH {
+            if node.connection_attempts == 0 {
+                "never attempted".to_string()
+            } else {
+                "never connected".to_string()
+            }
+        } else if node.consecutive_failures >= MAX_GOOD_CONSECUTIVE_FAILURES {
+            format!("failed last {} attempts", node.consecutive_failures)
+        } else {
+            format!("last reached {}s ago", since_success)
+        };
 
-        let nodes_count = nodes.len();
-        for (key, node) in nodes {
-            self.nodes.insert(key, node);
+        match node.last_error {
+            Some(ref error) => format!("{}: {}", reason, error),
+            None => reason,
         }
+    }
 
-        info!("{} nodes loaded", nodes_count);
-        Ok(())
+    /// Current classification of a known peer, or `None` if it is unknown
+    pub fn peer_status(&self, address: &NetAddress) -> Option<PeerStatus> {
+        let key = format!("{}:{}", address.ip, address.port);
+        self.nodes.get(&key).map(|node| PeerStatus {
+            status: self.classify(&node),
+            reason: node.classification_reason.clone(),
+            last_success: node.last_success,
+            consecutive_failures: node.consecutive_failures,
+            last_error: node.last_error.clone(),
+        })
     }
 
-    /// Check if node is expired
-    fn is_expired(&self, node: &Node, now: SystemTime) -> bool {
-        let last_seen_elapsed = now.duration_since(node.last_seen).unwrap_or_default();
+    /// Port peers are expected to listen on for this network
+    pub fn default_port(&self) -> u16 {
+        self.default_port
+    }
 
-        last_seen_elapsed > PRUNE_EXPIRE_TIMEOUT
+    /// Check if node is a trusted peer we haven't tried to connect to yet
+    fn is_untested_trusted(node: &Node) -> bool {
+        node.source == PeerSource::Trusted && node.connection_attempts == 0
     }
 
     /// Check if node is good - aligned with Go version
@@ -549,13 +2125,35 @@ impl AddressManager {
             return false;
         }
 
+        self.is_good_on_any_port(node)
+    }
+
+    /// Check if node is good regardless of the port it was reached on, for
+    /// answers that carry the port
+    fn is_good_on_any_port(&self, node: &Node) -> bool {
+        // A peer that keeps failing since its last success is flaky
+        if node.consecutive_failures >= MAX_GOOD_CONSECUTIVE_FAILURES {
+            return false;
+        }
+
+        self.has_recent_success(node)
+    }
+
+    /// Check if node has successfully connected within the stale timeout
+    fn has_recent_success(&self, node: &Node) -> bool {
         let now = SystemTime::now();
         let last_success_elapsed = now.duration_since(node.last_success).unwrap_or_default();
 
-        // Use consistent timeout for production
-        let stale_timeout = DEFAULT_STALE_GOOD_TIMEOUT;
+        last_success_elapsed < self.timeouts.stale_good
+    }
 
-        last_success_elapsed < stale_timeout
+    /// Check if node has successfully connected within the given window
+    fn has_success_within(&self, node: &Node, window: Duration) -> bool {
+        node.last_success != UNIX_EPOCH
+            && SystemTime::now()
+                .duration_since(node.last_success)
+                .unwrap_or_default()
+                < window
     }
 
     /// Check if node is stale - aligned with Go version
@@ -579,7 +2177,7 @@ impl AddressManager {
         // Aligned with Go version logic
         let stale_timeout = if last_attempt_elapsed > Duration::from_secs(24 * 60 * 60) {
             // If last attempt was more than 24 hours ago, use shorter timeout
-            DEFAULT_STALE_GOOD_TIMEOUT // 1 hour
+            self.timeouts.stale_good
         } else {
             DEFAULT_STALE_BAD_TIMEOUT // 2 hours
         };
@@ -634,7 +2232,40 @@ impl AddressManager {
         address.port != self.default_port
     }
 
-    /// Shutdown address manager
+    /// Banned peer IPs
+    pub fn bans(&self) -> &BanList {
+        &self.bans
+    }
+
+    /// Ban an IP for the given duration. Banned peers are neither crawled,
+    /// re-added from gossip nor advertised until the ban expires.
+    pub fn ban(&self, ip: IpAddr, duration: Duration) -> Result<()> {
+        self.bans.ban(ip, duration)?;
+        self.bump_serial();
+        Ok(())
+    }
+
+    /// Lift the ban on an IP, returning whether it was banned
+    pub fn unban(&self, ip: &IpAddr) -> Result<bool> {
+        let was_banned = self.bans.unban(ip)?;
+        if was_banned {
+            self.bump_serial();
+        }
+        Ok(was_banned)
+    }
+
+    /// Whether an IP is currently banned
+    pub fn is_banned(&self, ip: &IpAddr) -> bool {
+        self.bans.is_banned(ip)
+    }
+
+    /// Failed connection attempts to a peer since its last success
+    pub fn consecutive_failures(&self, address: &NetAddress) -> u32 {
+        self.get_node(address)
+            .map_or(0, |node| node.consecutive_failures)
+    }
+
+    /// Stop the background task, which saves the peers before exiting
     pub async fn shutdown(&self) {
         let _ = self.quit_tx.send(()).await;
     }
@@ -645,14 +2276,46 @@ impl AddressManager {
     }
 }
 
+/// Seconds since the Unix epoch, saturated to fit a zone serial
+fn unix_time_secs() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .min(u32::MAX as u64) as u32
+}
+
 impl Clone for AddressManager {
     fn clone(&self) -> Self {
         Self {
             nodes: self.nodes.clone(),
-            peers_file: self.peers_file.clone(),
+            store: Arc::clone(&self.store),
             quit_tx: self.quit_tx.clone(),
+            // Only the original manager runs the background task
+            quit_rx: Mutex::new(None),
             stats: Arc::clone(&self.stats),
             default_port: self.default_port,
+            reachable_ports: self.reachable_ports.clone(),
+            prefer_reachable_port: self.prefer_reachable_port,
+            ports_per_ip: self.ports_per_ip.clone(),
+            max_ports_per_ip: self.max_ports_per_ip,
+            max_stored_addresses: self.max_stored_addresses,
+            accept_unroutable: AtomicBool::new(self.accepts_unroutable()),
+            serial: Arc::clone(&self.serial),
+            started_at: self.started_at,
+            bans: Arc::clone(&self.bans),
+            opt_out_marker: self.opt_out_marker.clone(),
+            stale_fallback: self.stale_fallback,
+            geoip: self.geoip.clone(),
+            expose_country_counts: self.expose_country_counts,
+            expose_source_counts: self.expose_source_counts,
+            record_classification_reasons: self.record_classification_reasons,
+            discovery_bias_report: self.discovery_bias_report,
+            crawl_graph: self.crawl_graph.clone(),
+            timeouts: self.timeouts,
+            max_consecutive_failures: self.max_consecutive_failures,
+            quarantine: self.quarantine.clone(),
+            clock_skew: self.clock_skew.clone(),
         }
     }
 }
@@ -689,7 +2352,7 @@ mod tests {
 
         // Verify the peers file path is correct
         let expected_peers_file = test_app_dir.join("peers.json");
-        assert_eq!(manager.peers_file, expected_peers_file.to_string_lossy());
+        assert_eq!(manager.store.location(), expected_peers_file);
 
         // Test saving peers (this should not fail due to directory issues)
         manager.save_peers().unwrap();
@@ -721,4 +2384,1051 @@ mod tests {
         let expected_peers_file = test_app_dir.join("peers.json");
         assert!(expected_peers_file.exists());
     }
+
+    #[test]
+    fn test_reachable_port_is_canonical() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let ip: IpAddr = "45.1.2.3".parse().unwrap();
+        let reached = NetAddress::new(ip, 16111);
+        let gossiped = NetAddress::new(ip, 17000);
+
+        // Gossip arrives before we connect: both ports are known
+        manager.add_addresses(vec![reached.clone(), gossiped.clone()], 16111, false);
+        assert_eq!(manager.address_count(), 2);
+
+        // Connecting on 16111 makes it canonical and drops the gossiped port
+        manager.good(&reached, None, None);
+        assert_eq!(manager.address_count(), 1);
+        assert!(manager.nodes.contains_key("45.1.2.3:16111"));
+
+        // Later gossip with the other port is ignored
+        assert_eq!(manager.add_addresses(vec![gossiped], 16111, false), 0);
+        assert_eq!(manager.address_count(), 1);
+        assert!(!manager.nodes.contains_key("45.1.2.3:17000"));
+    }
+
+    #[test]
+    fn test_reachable_port_reconciliation_can_be_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_prefer_reachable_port(false);
+
+        let ip: IpAddr = "45.1.2.3".parse().unwrap();
+        let reached = NetAddress::new(ip, 16111);
+        manager.add_addresses(vec![reached.clone()], 16111, false);
+        manager.good(&reached, None, None);
+
+        assert_eq!(
+            manager.add_addresses(vec![NetAddress::new(ip, 17000)], 16111, false),
+            1
+        );
+        assert_eq!(manager.address_count(), 2);
+    }
+
+    #[test]
+    fn test_peer_source_recorded_per_insertion_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let gossip = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let seeder = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        let known = NetAddress::new("45.0.0.3".parse().unwrap(), 16111);
+        let manual = NetAddress::new("45.0.0.4".parse().unwrap(), 16111);
+
+        manager.add_addresses(vec![gossip.clone()], 16111, false);
+        manager.add_addresses_from(vec![seeder.clone()], PeerSource::Seeder, false);
+        manager.add_addresses_from(vec![known.clone()], PeerSource::Known, false);
+        manager.add_addresses_from(vec![manual.clone()], PeerSource::Manual, false);
+
+        assert_eq!(
+            manager.get_node(&gossip).unwrap().source,
+            PeerSource::Gossip
+        );
+        assert_eq!(
+            manager.get_node(&seeder).unwrap().source,
+            PeerSource::Seeder
+        );
+        assert_eq!(manager.get_node(&known).unwrap().source, PeerSource::Known);
+        assert_eq!(
+            manager.get_node(&manual).unwrap().source,
+            PeerSource::Manual
+        );
+
+        // Gossip about a known peer does not downgrade its source
+        manager.add_addresses(vec![known.clone()], 16111, false);
+        assert_eq!(manager.get_node(&known).unwrap().source, PeerSource::Known);
+
+        // A more trusted source upgrades a gossiped peer
+        manager.add_addresses_from(vec![gossip.clone()], PeerSource::Manual, false);
+        assert_eq!(
+            manager.get_node(&gossip).unwrap().source,
+            PeerSource::Manual
+        );
+    }
+
+    #[test]
+    fn test_manual_peers_are_never_pruned() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let gossip = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let manual = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(vec![gossip.clone()], 16111, false);
+        manager.add_addresses_from(vec![manual.clone()], PeerSource::Manual, false);
+
+        // Age both entries past the expiry timeout
+        for mut entry in manager.nodes.iter_mut() {
+            entry.last_seen = SystemTime::now() - DEFAULT_PRUNE_EXPIRE_TIMEOUT * 2;
+        }
+        manager.prune_peers();
+
+        assert!(manager.get_node(&gossip).is_none());
+        assert!(manager.get_node(&manual).is_some());
+    }
+
+    #[test]
+    fn test_classification_reasons_are_recorded_at_prune() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_classification_reasons(true);
+        let address = |ip: &str, port| NetAddress::new(ip.parse().unwrap(), port);
+
+        let good = address("45.0.0.1", 16111);
+        let untested = address("45.0.0.2", 16111);
+        let refused = address("45.0.0.3", 16111);
+        let outdated = address("45.0.0.4", 16111);
+        let other_port = address("45.0.0.5", 16112);
+        manager.add_addresses(
+            vec![
+                good.clone(),
+                untested.clone(),
+                refused.clone(),
+                outdated.clone(),
+                other_port.clone(),
+            ],
+            16111,
+            false,
+        );
+
+        manager.good(&good, None, None);
+        manager.record_connection_result(&refused, false, Some("refused".to_string()));
+        manager.good(&outdated, None, None);
+        for _ in 0..MAX_GOOD_CONSECUTIVE_FAILURES {
+            manager.record_connection_result(
+                &outdated,
+                false,
+                Some("below min proto ver".to_string()),
+            );
+        }
+        manager.good(&other_port, None, None);
+
+        // Nothing is recorded before the first prune
+        assert_eq!(manager.peer_status(&good).unwrap().reason, None);
+        manager.prune_peers();
+
+        let status = |peer: &NetAddress| manager.peer_status(peer).unwrap();
+        assert_eq!(status(&good).status, NodeStatus::Good);
+        assert!(status(&good).reason.unwrap().starts_with("reached "));
+        assert_eq!(status(&untested).status, NodeStatus::Stale);
+        assert_eq!(status(&untested).reason.unwrap(), "never attempted");
+        assert_eq!(status(&refused).status, NodeStatus::Bad);
+        assert_eq!(status(&refused).reason.unwrap(), "never connected: refused");
+        assert_eq!(status(&outdated).status, NodeStatus::Bad);
+        assert_eq!(
+            status(&outdated).reason.unwrap(),
+            "failed last 3 attempts: below min proto ver"
+        );
+        assert_eq!(status(&other_port).status, NodeStatus::Bad);
+        assert_eq!(
+            status(&other_port).reason.unwrap(),
+            "non-default port 16112"
+        );
+
+        assert!(manager.peer_status(&address("45.0.0.9", 16111)).is_none());
+    }
+
+    #[test]
+    fn test_configured_timeouts_govern_good_and_expired() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let timeouts = AddressManagerTimeouts {
+            stale_good: Duration::from_secs(10 * 60),
+            prune_expire: Duration::from_secs(7 * 86400),
+            ..Default::default()
+        };
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_timeouts(timeouts);
+
+        let peer = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        manager.add_addresses(vec![peer.clone()], 16111, false);
+        manager.good(&peer, None, None);
+        assert_eq!(manager.good_addresses(1, true, None).len(), 1);
+
+        // Past the shortened good window, but well within the raised expiry
+        for mut entry in manager.nodes.iter_mut() {
+            entry.last_success = SystemTime::now() - Duration::from_secs(20 * 60);
+            entry.last_seen = SystemTime::now() - DEFAULT_PRUNE_EXPIRE_TIMEOUT * 2;
+        }
+        assert!(manager.good_addresses(1, true, None).is_empty());
+        manager.prune_peers();
+        assert!(manager.get_node(&peer).is_some());
+    }
+
+    #[test]
+    fn test_good_addresses_filtered_by_services() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let full = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let light = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(vec![full.clone(), light.clone()], 16111, false);
+        manager.set_services(&full, 0b11);
+        manager.set_services(&light, 0b10);
+
+        assert_eq!(manager.good_addresses_filtered(1, true, None, 0).len(), 2);
+        assert_eq!(
+            manager.good_addresses_filtered(1, true, None, 0b10).len(),
+            2
+        );
+        assert_eq!(
+            manager.good_addresses_filtered(1, true, None, 0b01),
+            vec![full]
+        );
+        assert!(
+            manager
+                .good_addresses_filtered(1, true, None, 0b100)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_time_to_first_good_peer_recorded() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        // Nothing to answer with yet
+        assert!(manager.good_addresses(1, true, None).is_empty());
+        assert_eq!(manager.get_stats().time_to_first_good_peer(), None);
+
+        std::thread::sleep(Duration::from_millis(5));
+        let peer = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        manager.add_addresses(vec![peer.clone()], 16111, false);
+        manager.good(&peer, Some("/kaspad:1.0.0/"), None);
+
+        assert_eq!(manager.good_addresses(1, true, None), vec![peer]);
+        let recorded = manager.get_stats().time_to_first_good_peer().unwrap();
+        assert!(recorded >= Duration::from_millis(5));
+
+        // Later answers leave the measurement untouched
+        std::thread::sleep(Duration::from_millis(5));
+        manager.good_addresses(1, true, None);
+        assert_eq!(
+            manager.get_stats().time_to_first_good_peer(),
+            Some(recorded)
+        );
+    }
+
+    #[test]
+    fn test_good_addresses_filtered_by_subnetwork() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let subnetwork_id = "0100000000000000000000000000000000000000";
+        let on_subnetwork = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let unknown_subnetwork = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(
+            vec![on_subnetwork.clone(), unknown_subnetwork.clone()],
+            16111,
+            false,
+        );
+        manager.good(&on_subnetwork, Some("/kaspad:1.0.0/"), Some(subnetwork_id));
+        manager.good(&unknown_subnetwork, Some("/kaspad:1.0.0/"), None);
+
+        assert_eq!(
+            manager.good_addresses(1, false, Some(subnetwork_id)),
+            vec![on_subnetwork.clone()]
+        );
+        assert_eq!(
+            manager.good_addresses(1, false, Some(&subnetwork_id.to_uppercase())),
+            vec![on_subnetwork]
+        );
+        assert!(
+            manager
+                .good_addresses(1, false, Some("0200000000000000000000000000000000000000"))
+                .is_empty()
+        );
+        assert_eq!(manager.good_addresses(1, true, None).len(), 2);
+    }
+
+    #[test]
+    fn test_trusted_peers_served_immediately_and_vouch_for_gossip() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let trusted = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let gossip = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses_from(vec![trusted.clone()], PeerSource::Trusted, false);
+        manager.add_addresses(vec![gossip.clone()], 16111, false);
+
+        // Served before any connection succeeds
+        assert!(manager.good_addresses(1, true, None).contains(&trusted));
+        assert!(manager.is_trusted(&trusted));
+        assert!(!manager.is_trusted(&gossip));
+
+        // Gossip confirmed by the trust anchor is crawled first
+        let unconfirmed = NetAddress::new("45.0.0.3".parse().unwrap(), 16111);
+        manager.add_addresses(vec![unconfirmed.clone()], 16111, false);
+        for mut entry in manager.nodes.iter_mut() {
+            entry.last_attempt = UNIX_EPOCH;
+        }
+        manager.vouch_for(std::slice::from_ref(&gossip));
+        assert!(manager.get_node(&gossip).unwrap().vouched);
+        assert_eq!(manager.addresses(1)[0], gossip);
+
+        // A failed connection ends the benefit of the doubt
+        manager.record_connection_result(&trusted, false, Some("refused".to_string()));
+        assert!(!manager.good_addresses(1, true, None).contains(&trusted));
+    }
+
+    #[test]
+    fn test_opted_out_peers_are_crawled_but_not_served() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_opt_out_marker(Some("noseed".to_string()));
+
+        let opted_out = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let regular = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(vec![opted_out.clone(), regular.clone()], 16111, false);
+
+        // Handshake user agents as reported by the mock peers
+        manager.good(&opted_out, Some("/kaspad:1.0.0(noseed)/"), None);
+        manager.good(&regular, Some("/kaspad:1.0.0/"), None);
+
+        assert!(manager.get_node(&opted_out).unwrap().opted_out);
+        assert_eq!(manager.good_addresses(1, true, None), vec![regular]);
+
+        // Still polled and counted
+        assert!(manager.addresses(1).contains(&opted_out));
+        assert_eq!(manager.get_address_summary().good, 2);
+
+        // Dropping the marker from the user agent makes the peer servable again
+        manager.good(&opted_out, Some("/kaspad:1.0.0/"), None);
+        assert_eq!(manager.good_addresses(1, true, None).len(), 2);
+    }
+
+    #[test]
+    fn test_stale_peers_served_when_good_pool_is_small() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let recent = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let old = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(vec![recent.clone(), old.clone()], 16111, false);
+
+        // Both were good once and have just failed a retry
+        for (address, hours_ago) in [(&recent, 3), (&old, 10)] {
+            manager.record_connection_result(address, false, Some("timeout".to_string()));
+            let key = format!("{}:{}", address.ip, address.port);
+            manager.nodes.get_mut(&key).unwrap().last_success =
+                SystemTime::now() - Duration::from_secs(hours_ago * 60 * 60);
+        }
+        assert!(manager.good_addresses(1, true, None).is_empty());
+
+        let manager = manager.with_stale_fallback(Some(StaleFallback {
+            min_good: 4,
+            cutoff: Duration::from_secs(8 * 60 * 60),
+        }));
+        assert_eq!(manager.good_addresses(1, true, None), vec![recent]);
+        assert_eq!(manager.get_stats().degraded_answers(), 1);
+    }
+
+    #[test]
+    fn test_country_counts_break_down_good_peers() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let geoip =
+            GeoIpDatabase::parse("45.0.0.0/8,US,north-america\n45.10.0.0/16,DE,europe\n").unwrap();
+
+        let peers: Vec<NetAddress> = ["45.0.0.1", "45.0.0.2", "45.10.0.1", "80.0.0.1"]
+            .iter()
+            .map(|ip| NetAddress::new(ip.parse().unwrap(), 16111))
+            .collect();
+        // Known but never reached, so not good
+        let untested = NetAddress::new("45.10.0.2".parse().unwrap(), 16111);
+
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+        assert_eq!(manager.country_counts(), None);
+
+        let manager = manager
+            .with_country_counts(true)
+            .with_geoip(Some(Arc::new(geoip)));
+        manager.add_addresses(peers.clone(), 16111, false);
+        manager.add_addresses(vec![untested], 16111, false);
+        for peer in &peers {
+            manager.good(peer, None, None);
+        }
+
+        let counts = manager.country_counts().unwrap();
+        let expected: BTreeMap<String, usize> = [("DE", 1), ("US", 2), (UNKNOWN_COUNTRY, 1)]
+            .into_iter()
+            .map(|(country, count)| (country.to_string(), count))
+            .collect();
+        assert_eq!(counts, expected);
+
+        // Without GeoIP every good peer is unknown
+        let manager = manager.with_geoip(None);
+        let counts = manager.country_counts().unwrap();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[UNKNOWN_COUNTRY], 4);
+    }
+
+    #[test]
+    fn test_source_counts_break_down_good_peers() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address = |ip: &str| NetAddress::new(ip.parse().unwrap(), 16111);
+
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+        assert_eq!(manager.source_counts(), None);
+
+        let manager = manager.with_source_counts(true);
+        let seeded = vec![
+            address("45.0.0.1"),
+            address("45.0.0.2"),
+            address("45.0.0.3"),
+        ];
+        let gossiped = vec![address("45.1.0.1")];
+        let manual = vec![address("45.2.0.1")];
+        manager.add_addresses_from(seeded.clone(), PeerSource::Seeder, false);
+        manager.add_addresses(gossiped.clone(), 16111, false);
+        manager.add_addresses_from(manual.clone(), PeerSource::Manual, false);
+        // Gossiped but never reached, so not good
+        manager.add_addresses(vec![address("45.1.0.2")], 16111, false);
+        for peer in seeded.iter().chain(&gossiped).chain(&manual) {
+            manager.good(peer, None, None);
+        }
+
+        let counts = manager.source_counts().unwrap();
+        assert_eq!(counts.len(), PeerSource::ALL.len());
+        assert_eq!(counts[&PeerSource::Seeder], 3);
+        assert_eq!(counts[&PeerSource::Gossip], 1);
+        assert_eq!(counts[&PeerSource::Manual], 1);
+        assert_eq!(counts[&PeerSource::Known], 0);
+        assert_eq!(counts[&PeerSource::Trusted], 0);
+        assert_eq!(counts.values().sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn test_ports_per_ip_are_capped() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_max_ports_per_ip(4);
+
+        let ip: IpAddr = "45.0.0.1".parse().unwrap();
+        let flood: Vec<NetAddress> = (20000..21000)
+            .map(|port| NetAddress::new(ip, port))
+            .collect();
+        assert_eq!(manager.add_addresses(flood, 16111, false), 4);
+        assert_eq!(manager.address_count(), 4);
+
+        // Other hosts are unaffected, and operator-added peers bypass the cap
+        let other = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        assert_eq!(manager.add_addresses(vec![other], 16111, false), 1);
+        let manual = NetAddress::new(ip, 16111);
+        assert_eq!(
+            manager.add_addresses_from(vec![manual], PeerSource::Manual, false),
+            1
+        );
+        assert_eq!(manager.address_count(), 6);
+    }
+
+    #[test]
+    fn test_discovery_bias_report_compares_regions() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let geoip =
+            GeoIpDatabase::parse("45.0.0.0/16,US,north-america,AS100\n45.1.0.0/16,JP,asia,AS200\n")
+                .unwrap();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+        assert_eq!(manager.discovery_bias_report(), None);
+
+        let manager = manager
+            .with_discovery_bias_report(true)
+            .with_geoip(Some(Arc::new(geoip)));
+
+        // North America: 9 of 10 reached. Asia: 1 of 10 reached.
+        for i in 1..=10u8 {
+            for (prefix, reached) in [(0, i <= 9), (1, i <= 1)] {
+                let address = NetAddress::new(IpAddr::from([45, prefix, 0, i]), 16111);
+                manager.add_addresses(vec![address.clone()], 16111, false);
+                if reached {
+                    manager.good(&address, None, None);
+                } else {
+                    manager.record_connection_result(&address, false, None);
+                }
+            }
+        }
+        // Discovered but never tried, so left out
+        let untried = NetAddress::new("45.1.1.1".parse().unwrap(), 16111);
+        manager.add_addresses(vec![untried], 16111, false);
+
+        let report = manager.discovery_bias_report().unwrap();
+        assert_eq!(
+            report.overall,
+            Reachability {
+                reachable: 10,
+                unreachable: 10
+            }
+        );
+        assert_eq!(
+            report.by_region["north-america"],
+            Reachability {
+                reachable: 9,
+                unreachable: 1
+            }
+        );
+        assert_eq!(
+            report.by_region["asia"],
+            Reachability {
+                reachable: 1,
+                unreachable: 9
+            }
+        );
+        assert_eq!(report.by_asn["AS200"], report.by_region["asia"]);
+
+        let blind_spots = report.blind_spots();
+        assert_eq!(blind_spots.len(), 1);
+        assert_eq!(blind_spots[0].0, "asia");
+    }
+
+    #[test]
+    fn test_stored_addresses_are_capped() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_max_stored_addresses(50);
+
+        // A good peer and a trusted peer are never evicted
+        let good = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        manager.add_addresses(vec![good.clone()], 16111, false);
+        manager.good(&good, None, None);
+        let trusted = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses_from(vec![trusted.clone()], PeerSource::Trusted, false);
+
+        let oldest = NetAddress::new("46.0.0.1".parse().unwrap(), 16111);
+        manager.add_addresses(vec![oldest.clone()], 16111, false);
+        manager.nodes.get_mut("46.0.0.1:16111").unwrap().last_seen =
+            SystemTime::now() - Duration::from_secs(600);
+
+        let flood: Vec<NetAddress> = (0..200u32)
+            .map(|i| {
+                NetAddress::new(
+                    IpAddr::from([47, 0, (i / 256) as u8, (i % 256) as u8]),
+                    16111,
+                )
+            })
+            .collect();
+        manager.add_addresses(flood, 16111, false);
+
+        assert_eq!(manager.address_count(), 50);
+        assert!(manager.nodes.contains_key("45.0.0.1:16111"));
+        assert!(manager.nodes.contains_key("45.0.0.2:16111"));
+        assert!(!manager.nodes.contains_key("46.0.0.1:16111"));
+    }
+
+    #[test]
+    fn test_temporary_bans_block_peers_until_expiry() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+
+        let banned = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let other = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(vec![banned.clone(), other.clone()], 16111, false);
+        manager.good(&banned, None, None);
+        manager.good(&other, None, None);
+
+        manager.ban(banned.ip, Duration::from_millis(200)).unwrap();
+        assert!(manager.is_banned(&banned.ip));
+        assert!(!manager.addresses(8).contains(&banned));
+        assert_eq!(manager.good_addresses(1, true, None), vec![other.clone()]);
+
+        // Gossip cannot bring a banned IP back in on another port
+        let other_port = NetAddress::new(banned.ip, 16112);
+        assert_eq!(manager.add_addresses(vec![other_port], 16111, false), 0);
+
+        std::thread::sleep(Duration::from_millis(250));
+        assert!(!manager.is_banned(&banned.ip));
+        assert_eq!(manager.good_addresses(1, true, None).len(), 2);
+    }
+
+    #[test]
+    fn test_consecutive_failures_reset_on_success() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111).unwrap();
+        let peer = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        manager.add_addresses(vec![peer.clone()], 16111, false);
+
+        for _ in 0..3 {
+            manager.record_connection_result(&peer, false, Some("refused".to_string()));
+        }
+        assert_eq!(manager.consecutive_failures(&peer), 3);
+
+        manager.record_connection_result(&peer, true, None);
+        assert_eq!(manager.consecutive_failures(&peer), 0);
+    }
+
+    #[test]
+    fn test_quarantine_releases_flood_at_configured_rate() {
+        let quarantine = AddressQuarantine::new(60);
+        let started = Instant::now();
+        for i in 0..1000 {
+            quarantine.hold(format!("47.0.{}.{}:16111", i / 256, i % 256));
+        }
+
+        // Nothing is released before time passes, then one per second
+        assert!(quarantine.release(started).is_empty());
+        let released = quarantine.release(started + Duration::from_secs(10));
+        assert_eq!(released.len(), 10);
+        assert_eq!(released[0], "47.0.0.0:16111");
+
+        // A long gap releases at most one minute's worth
+        let released = quarantine.release(started + Duration::from_secs(3600));
+        assert_eq!(released.len(), 60);
+        assert_eq!(quarantine.len(), 930);
+
+        // Forgotten nodes do not use up the allowance
+        quarantine.forget("47.0.0.70:16111");
+        let released = quarantine.release(started + Duration::from_secs(3605));
+        assert_eq!(released.len(), 5);
+        assert_eq!(released[0], "47.0.0.71:16111");
+    }
+
+    #[test]
+    fn test_quarantined_addresses_are_not_crawled() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_quarantine(60);
+
+        let flood: Vec<NetAddress> = (0..100u8)
+            .map(|i| NetAddress::new(IpAddr::from([47, 0, 0, i]), 16111))
+            .collect();
+        manager.add_addresses(flood, 16111, false);
+        let known = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        manager.add_addresses_from(vec![known.clone()], PeerSource::Known, false);
+        assert_eq!(manager.address_count(), 101);
+
+        // Only the peer from a trusted source is handed to the crawler
+        assert_eq!(manager.addresses(8), vec![known]);
+
+        // A seeder reporting a quarantined address releases it
+        let seeded = NetAddress::new("47.0.0.5".parse().unwrap(), 16111);
+        manager.add_addresses_from(vec![seeded], PeerSource::Seeder, false);
+        let quarantine = manager.quarantine.as_ref().unwrap();
+        assert!(!quarantine.is_held("47.0.0.5:16111"));
+        assert_eq!(quarantine.len(), 99);
+    }
+
+    #[test]
+    fn test_failing_nodes_lose_good_status_and_are_pruned() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_max_consecutive_failures(5);
+
+        let flaky = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let trusted = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(vec![flaky.clone()], 16111, false);
+        manager.add_addresses_from(vec![trusted.clone()], PeerSource::Trusted, false);
+        manager.good(&flaky, None, None);
+        let healthy_score = manager.get_node(&flaky).unwrap().quality_score;
+
+        for _ in 0..MAX_GOOD_CONSECUTIVE_FAILURES {
+            manager.attempt(&flaky);
+            manager.failed(&flaky, Some("refused".to_string()));
+        }
+        let node = manager.get_node(&flaky).unwrap();
+        assert_eq!(node.attempts, MAX_GOOD_CONSECUTIVE_FAILURES);
+        assert_eq!(node.consecutive_failures, MAX_GOOD_CONSECUTIVE_FAILURES);
+        assert!(node.quality_score < healthy_score);
+        assert_eq!(manager.classify(&node), NodeStatus::Bad);
+
+        // Reaching the peer again restores it
+        manager.good(&flaky, None, None);
+        let node = manager.get_node(&flaky).unwrap();
+        assert_eq!(node.consecutive_failures, 0);
+        assert_eq!(manager.classify(&node), NodeStatus::Good);
+
+        for _ in 0..5 {
+            manager.failed(&flaky, None);
+            manager.failed(&trusted, None);
+        }
+        manager.prune_peers();
+        assert!(manager.get_node(&flaky).is_none());
+        assert!(manager.get_node(&trusted).is_some());
+    }
+
+    #[test]
+    fn test_sqlite_backend_persists_changes_incrementally() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let reached = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let flaky = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+
+        {
+            let manager = AddressManager::open(&app_dir, 16111, StorageBackend::Sqlite)
+                .unwrap()
+                .with_max_consecutive_failures(1);
+            manager.add_addresses(vec![reached.clone(), flaky.clone()], 16111, false);
+            manager.attempt(&reached);
+            manager.good(&reached, None, None);
+            manager.failed(&flaky, None);
+            manager.prune_peers();
+            assert_eq!(
+                manager.store.location(),
+                temp_dir.path().join("peers.sqlite")
+            );
+        }
+
+        let manager = AddressManager::open(&app_dir, 16111, StorageBackend::Sqlite).unwrap();
+        let node = manager.get_node(&reached).unwrap();
+        assert_eq!(node.attempts, 1);
+        assert_ne!(node.last_success, UNIX_EPOCH);
+        assert!(manager.get_node(&flaky).is_none());
+        assert!(!temp_dir.path().join("peers.json").exists());
+    }
+
+    #[test]
+    fn test_peers_round_trip_through_export_and_import() {
+        let source_dir = TempDir::new().unwrap();
+        let source = AddressManager::new(&source_dir.path().to_string_lossy(), 16111).unwrap();
+        let reached = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let gossiped = NetAddress::new("2001:4860::1".parse().unwrap(), 16112);
+        source.add_addresses_from(vec![reached.clone()], PeerSource::Known, false);
+        source.add_addresses(vec![gossiped.clone()], 16111, false);
+        for _ in 0..RELIABLE_TIER_MIN_SUCCESSES {
+            source.record_connection_result(&reached, true, None);
+        }
+        source.good(&reached, Some("/kaspad:1.0.0/"), None);
+        let exported = source.get_node(&reached).unwrap();
+
+        let export_file = source_dir.path().join("peers.jsonl");
+        assert_eq!(source.export_peers(&export_file).unwrap(), 2);
+        let content = std::fs::read_to_string(&export_file).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.contains("\"user_agent\":\"/kaspad:1.0.0/\""));
+
+        let target_dir = TempDir::new().unwrap();
+        let target = AddressManager::new(&target_dir.path().to_string_lossy(), 16111)
+            .unwrap()
+            .with_quarantine(1);
+        let import = target.import_peers(&export_file).unwrap();
+        assert_eq!(
+            import,
+            PeerImport {
+                records: 2,
+                added: 2,
+                good: 1
+            }
+        );
+
+        // Restored as exported rather than gossiped to us just now
+        let secs = |time: SystemTime| time.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let node = target.get_node(&reached).unwrap();
+        assert_eq!(node.source, PeerSource::Known);
+        assert_eq!(secs(node.last_success), secs(exported.last_success));
+        assert_eq!(secs(node.last_seen), secs(exported.last_seen));
+        assert_eq!(node.successful_connections, RELIABLE_TIER_MIN_SUCCESSES);
+        assert_eq!(
+            ServingTier::of(&node, SystemTime::now()),
+            ServingTier::Reliable
+        );
+        assert_eq!(node.user_agent.as_deref(), Some("/kaspad:1.0.0/"));
+        let node = target.get_node(&gossiped).unwrap();
+        assert_eq!(node.source, PeerSource::Gossip);
+        assert_eq!(node.last_success, UNIX_EPOCH);
+        assert_eq!(target.quarantine.as_ref().unwrap().len(), 0);
+
+        // Importing again merges into the existing nodes
+        assert_eq!(target.import_peers(&export_file).unwrap().added, 0);
+
+        // A malformed line rejects the whole file
+        let bad_file = target_dir.path().join("bad.jsonl");
+        std::fs::write(&bad_file, format!("{}not json\n", content)).unwrap();
+        let err = target.import_peers(&bad_file).unwrap_err().to_string();
+        assert!(err.contains("line 3"), "{}", err);
+    }
+
+    #[test]
+    fn test_accept_unroutable_governs_import_and_stored_peers() {
+        let source_dir = TempDir::new().unwrap();
+        let source = AddressManager::new(&source_dir.path().to_string_lossy(), 16111).unwrap();
+        let private = NetAddress::new("192.168.1.10".parse().unwrap(), 16111);
+        let public = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        source.add_addresses(vec![private.clone(), public.clone()], 16111, true);
+        let export_file = source_dir.path().join("peers.jsonl");
+        assert_eq!(source.export_peers(&export_file).unwrap(), 2);
+
+        let routable_only = TempDir::new().unwrap();
+        let target = AddressManager::new(&routable_only.path().to_string_lossy(), 16111).unwrap();
+        assert_eq!(target.import_peers(&export_file).unwrap().added, 1);
+        assert!(target.get_node(&private).is_none());
+
+        let private_network = TempDir::new().unwrap();
+        let target = AddressManager::new(&private_network.path().to_string_lossy(), 16111)
+            .unwrap()
+            .with_accept_unroutable(true);
+        assert_eq!(target.import_peers(&export_file).unwrap().added, 2);
+        assert!(target.has_node_at(&private.ip));
+
+        // Switching the option off drops the private peers already stored
+        let serial = target.serial();
+        target.set_accept_unroutable(false);
+        assert!(target.get_node(&private).is_none());
+        assert!(!target.has_node_at(&private.ip));
+        assert!(target.get_node(&public).is_some());
+        assert!(target.serial() > serial);
+    }
+
+    #[test]
+    fn test_backward_clock_jump_keeps_classification_sensible() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = AddressManager::new(&app_dir, 16111)
+            .unwrap()
+            .with_clock_skew_threshold(Duration::from_secs(60));
+        let recent = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let old = NetAddress::new("45.0.0.2".parse().unwrap(), 16111);
+        manager.add_addresses(vec![recent.clone(), old.clone()], 16111, false);
+        manager.good(&recent, None, None);
+        manager.good(&old, None, None);
+
+        // The clock is set back four hours. Timestamps taken before the jump
+        // now lie in the future: the node that really succeeded three hours
+        // ago, past the one-hour stale-good window, wrongly reads as good.
+        let jump = Duration::from_secs(4 * 3600);
+        let now = SystemTime::now();
+        for (address, age) in [(&recent, 5 * 60), (&old, 3 * 3600)] {
+            let recorded = now + jump - Duration::from_secs(age);
+            let mut node = manager
+                .nodes
+                .get_mut(&format!("{}:{}", address.ip, address.port))
+                .unwrap();
+            node.last_seen = recorded;
+            node.last_attempt = recorded;
+            node.last_success = recorded;
+        }
+        assert_eq!(
+            manager.classify(&manager.get_node(&old).unwrap()),
+            NodeStatus::Good
+        );
+
+        // The detector sees the wall clock fall behind the monotonic clock
+        let detector = ClockSkewDetector::new(Duration::from_secs(60));
+        let (wall, monotonic) = (SystemTime::now(), Instant::now());
+        detector.check(wall, monotonic);
+        let detected = detector
+            .check(wall - jump, monotonic + Duration::from_secs(30))
+            .unwrap();
+        assert_eq!(
+            detected,
+            ClockJump::Backward(jump + Duration::from_secs(30))
+        );
+        // Ordinary drift is ignored
+        assert_eq!(
+            detector.check(
+                wall - jump + Duration::from_secs(65),
+                monotonic + Duration::from_secs(90)
+            ),
+            None
+        );
+
+        manager.adjust_for_clock_jump(ClockJump::Backward(jump));
+        assert_eq!(
+            manager.classify(&manager.get_node(&recent).unwrap()),
+            NodeStatus::Good
+        );
+        assert_ne!(
+            manager.classify(&manager.get_node(&old).unwrap()),
+            NodeStatus::Good
+        );
+        let old_node = manager.get_node(&old).unwrap();
+        let age = SystemTime::now()
+            .duration_since(old_node.last_success)
+            .unwrap();
+        assert!(
+            age >= Duration::from_secs(3 * 3600 - 5) && age <= Duration::from_secs(3 * 3600 + 5)
+        );
+    }
+
+    #[test]
+    fn test_weighted_shuffle_prefers_fresh_reliable_peers() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let now = SystemTime::now();
+        let node = |ip: &str, hours_since_success: u64, failures: u32| {
+            let mut node = Node::new(NetAddress::new(ip.parse().unwrap(), 16111));
+            node.last_success = now - Duration::from_secs(hours_since_success * 3600);
+            node.consecutive_failures = failures;
+            node
+        };
+        let fresh = node("45.0.0.1", 0, 0);
+        let old = node("45.0.0.2", 24, 0);
+        let failing = node("45.0.0.3", 0, 3);
+
+        assert_eq!(fresh.selection_weight(now), 1.0);
+        assert_eq!(node("45.0.0.4", 1, 0).selection_weight(now), 0.5);
+        assert_eq!(failing.selection_weight(now), 0.125);
+        let never_reached = Node::new(NetAddress::new("45.0.0.5".parse().unwrap(), 16111));
+        assert_eq!(never_reached.selection_weight(now), MIN_SELECTION_WEIGHT);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut first_picks = BTreeMap::new();
+        for _ in 0..1000 {
+            let mut nodes = vec![old.clone(), failing.clone(), fresh.clone()];
+            weighted_shuffle(&mut nodes, &mut rng, |node| node.selection_weight(now));
+            assert_eq!(nodes.len(), 3);
+            *first_picks.entry(nodes[0].key()).or_insert(0) += 1;
+        }
+        let picks = |node: &Node| first_picks.get(&node.key()).copied().unwrap_or(0);
+        assert!(picks(&fresh) > 700, "{:?}", first_picks);
+        assert!(picks(&failing) > picks(&old), "{:?}", first_picks);
+        // Low weights lower the odds without excluding the peer
+        assert!(picks(&old) > 0, "{:?}", first_picks);
+
+        // The same seed gives the same order
+        let order = |seed| {
+            let mut nodes = vec![old.clone(), failing.clone(), fresh.clone()];
+            weighted_shuffle(&mut nodes, &mut StdRng::seed_from_u64(seed), |_| 1.0);
+            nodes.iter().map(Node::key).collect::<Vec<_>>()
+        };
+        assert_eq!(order(3), order(3));
+    }
+
+    #[test]
+    fn test_tiered_shuffle_matches_configured_blend() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let now = SystemTime::now();
+        let mut nodes = Vec::new();
+        for i in 0..50u8 {
+            let mut reliable = Node::new(NetAddress::new([45, 0, 1, i].into(), 16111));
+            reliable.successful_connections = RELIABLE_TIER_MIN_SUCCESSES;
+            reliable.last_success = now - Duration::from_secs(2 * 3600);
+            let mut fresh = Node::new(NetAddress::new([45, 0, 2, i].into(), 16111));
+            fresh.successful_connections = 1;
+            fresh.last_success = now;
+            let diverse = Node::new(NetAddress::new([45, 0, 3, i].into(), 16111));
+            nodes.extend([reliable, fresh, diverse]);
+        }
+        for (i, tier) in ServingTier::ALL.into_iter().enumerate() {
+            assert_eq!(ServingTier::of(&nodes[i], now), tier);
+        }
+
+        let weights = TierWeights::parse(&[
+            "reliable=70".to_string(),
+            "fresh=20".to_string(),
+            "diverse=10".to_string(),
+        ])
+        .unwrap()
+        .unwrap();
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut served = BTreeMap::new();
+        let rounds = 1000;
+        for _ in 0..rounds {
+            let mut answer = nodes.clone();
+            tiered_shuffle(&mut answer, &mut rng, &weights, now);
+            // Every node is still there, in some order
+            assert_eq!(answer.len(), nodes.len());
+            for node in answer.iter().take(10) {
+                *served.entry(ServingTier::of(node, now)).or_insert(0) += 1;
+            }
+        }
+
+        for tier in ServingTier::ALL {
+            let share = served[&tier] as f64 / (rounds * 10) as f64;
+            let expected = weights.weight(tier) as f64 / 100.0;
+            assert!(
+                (share - expected).abs() < 0.02,
+                "{} served {:.3}, expected {:.2}",
+                tier.as_str(),
+                share,
+                expected
+            );
+        }
+
+        // Tiers without a weight only fill in once the weighted ones run dry
+        let only_reliable = TierWeights::parse(&["reliable=1".to_string()])
+            .unwrap()
+            .unwrap();
+        let mut answer = nodes.clone();
+        tiered_shuffle(&mut answer, &mut rng, &only_reliable, now);
+        assert_eq!(answer.len(), nodes.len());
+        assert!(
+            answer[..50]
+                .iter()
+                .all(|node| ServingTier::of(node, now) == ServingTier::Reliable)
+        );
+
+        assert_eq!(TierWeights::parse(&[]), Ok(None));
+        for invalid in ["reliable", "popular=1", "fresh=-1", "reliable=0"] {
+            assert!(
+                TierWeights::parse(&[invalid.to_string()]).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_handler_and_saves_peers() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        let handle = manager.start();
+
+        // Added after the first dump tick, so only the final save writes it
+        tokio::task::yield_now().await;
+        let peer = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        manager.add_addresses(vec![peer.clone()], 16111, false);
+
+        manager.shutdown().await;
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("handler should stop after shutdown")
+            .unwrap();
+
+        let reloaded = AddressManager::new(&app_dir, 16111).unwrap();
+        assert!(reloaded.get_node(&peer).is_some());
+
+        // A second start finds the receiver taken and returns at once
+        manager.start().await.unwrap();
+    }
 }
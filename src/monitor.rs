@@ -1,10 +1,14 @@
-use crate::errors::Result;
+use crate::errors::{KaseederError, Result};
 use crate::logging::{HealthStatus, LoggingStats};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{Pid, ProcessExt, System, SystemExt};
 use tokio::sync::Mutex;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// Consecutive health checks a new health state must hold before an alert is sent
+const ALERT_DEBOUNCE_CHECKS: u32 = 2;
 
 /// System monitor
 pub struct SystemMonitor {
@@ -12,6 +16,74 @@ pub struct SystemMonitor {
     health_status: Arc<Mutex<HealthStatus>>,
     logging_stats: Arc<Mutex<LoggingStats>>,
     performance_metrics: Arc<Mutex<PerformanceMetrics>>,
+    alert_webhook: Option<Arc<Mutex<AlertWebhook>>>,
+}
+
+/// Payload POSTed to the alert webhook when the health state changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthAlert {
+    pub healthy: bool,
+    pub issues: Vec<String>,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+}
+
+/// Webhook notified on health transitions. A transition is only announced
+/// once it has held for `ALERT_DEBOUNCE_CHECKS` checks so flapping doesn't
+/// page anyone.
+struct AlertWebhook {
+    url: String,
+    client: reqwest::Client,
+    announced_healthy: bool,
+    pending_checks: u32,
+}
+
+impl AlertWebhook {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            client: reqwest::Client::new(),
+            announced_healthy: true,
+            pending_checks: 0,
+        }
+    }
+
+    /// Feed the result of a health check, returning an alert once a
+    /// transition has held long enough
+    fn observe(&mut self, health: &HealthStatus) -> Option<HealthAlert> {
+        if health.is_healthy == self.announced_healthy {
+            self.pending_checks = 0;
+            return None;
+        }
+
+        self.pending_checks += 1;
+        if self.pending_checks < ALERT_DEBOUNCE_CHECKS {
+            return None;
+        }
+
+        self.announced_healthy = health.is_healthy;
+        self.pending_checks = 0;
+        Some(HealthAlert {
+            healthy: health.is_healthy,
+            issues: health.issues.clone(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        })
+    }
+
+    /// POST the alert as JSON
+    async fn send(&self, alert: &HealthAlert) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .timeout(Duration::from_secs(10))
+            .json(alert)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
 }
 
 /// Performance metrics
@@ -58,15 +130,23 @@ impl SystemMonitor {
                 total_disk_usage_bytes: 0,
             })),
             performance_metrics: Arc::new(Mutex::new(PerformanceMetrics::default())),
+            alert_webhook: None,
         }
     }
 
+    /// POST a JSON alert to the given URL whenever the health state changes
+    pub fn with_alert_webhook(mut self, url: String) -> Self {
+        self.alert_webhook = Some(Arc::new(Mutex::new(AlertWebhook::new(url))));
+        self
+    }
+
     /// Start monitoring
     pub async fn start_monitoring(&self) -> Result<()> {
         info!("Starting system monitoring");
 
         let health_status = self.health_status.clone();
         let performance_metrics = self.performance_metrics.clone();
+        let alert_webhook = self.alert_webhook.clone();
 
         // Start periodic health checks
         tokio::spawn(async move {
@@ -79,6 +159,11 @@ impl SystemMonitor {
                         .await
                 {
                     error!("Health check failed: {}", e);
+                    continue;
+                }
+
+                if let Some(ref alert_webhook) = alert_webhook {
+                    Self::notify_alert_webhook(&health_status, alert_webhook).await;
                 }
             }
         });
@@ -86,11 +171,15 @@ impl SystemMonitor {
         // Start performance metrics collection
         let performance_metrics = self.performance_metrics.clone();
         tokio::spawn(async move {
+            // Reused across ticks so CPU usage is measured over the interval
+            let mut system = System::new();
             let mut interval = tokio::time::interval(Duration::from_secs(10));
             loop {
                 interval.tick().await;
 
-                if let Err(e) = Self::collect_performance_metrics(performance_metrics.clone()).await
+                if let Err(e) =
+                    Self::collect_performance_metrics(performance_metrics.clone(), &mut system)
+                        .await
                 {
                     error!("Performance metrics collection failed: {}", e);
                 }
@@ -141,31 +230,66 @@ impl SystemMonitor {
         Ok(())
     }
 
+    /// Send an alert if the latest health check completed a transition
+    async fn notify_alert_webhook(
+        health_status: &Mutex<HealthStatus>,
+        alert_webhook: &Mutex<AlertWebhook>,
+    ) {
+        let mut alert_webhook = alert_webhook.lock().await;
+        let alert = match alert_webhook.observe(&*health_status.lock().await) {
+            Some(alert) => alert,
+            None => return,
+        };
+
+        info!(
+            "Health changed to {}, sending alert",
+            if alert.healthy {
+                "healthy"
+            } else {
+                "unhealthy"
+            }
+        );
+        if let Err(e) = alert_webhook.send(&alert).await {
+            warn!(
+                "Failed to send health alert to {}: {}",
+                alert_webhook.url, e
+            );
+        }
+    }
+
     /// Collect performance metrics
     async fn collect_performance_metrics(
         performance_metrics: Arc<Mutex<PerformanceMetrics>>,
+        system: &mut System,
     ) -> Result<()> {
+        let pid = sysinfo::get_current_pid()
+            .map_err(|e| KaseederError::Service(format!("Failed to get process id: {}", e)))?;
+        system.refresh_process(pid);
+
         let mut metrics = performance_metrics.lock().await;
 
-        // Simplified performance metrics collection (should use system API in practice)
-        metrics.cpu_usage = Self::get_cpu_usage().await?;
-        metrics.memory_usage = Self::get_memory_usage().await?;
+        metrics.cpu_usage = Self::get_cpu_usage(system, pid).await?;
+        metrics.memory_usage = Self::get_memory_usage(system, pid).await?;
         metrics.network_connections = Self::get_network_connections().await?;
         metrics.last_updated = Some(SystemTime::now());
 
         Ok(())
     }
 
-    /// Get CPU usage
-    async fn get_cpu_usage() -> Result<f64> {
-        // Simplified implementation, should read /proc/stat or use system API in practice
-        Ok(rand::random::<f64>() * 50.0) // Simulate 0-50% CPU usage
+    /// Get the process CPU usage in percent since the previous refresh
+    async fn get_cpu_usage(system: &System, pid: Pid) -> Result<f64> {
+        let process = system
+            .process(pid)
+            .ok_or_else(|| KaseederError::Service(format!("Process {} not found", pid)))?;
+        Ok(process.cpu_usage() as f64)
     }
 
-    /// Get memory usage
-    async fn get_memory_usage() -> Result<u64> {
-        // Simplified implementation, should read /proc/meminfo or use system API in practice
-        Ok(1024 * 1024 * 512) // Simulate 512MB memory usage
+    /// Get the process resident memory in bytes
+    async fn get_memory_usage(system: &System, pid: Pid) -> Result<u64> {
+        let process = system
+            .process(pid)
+            .ok_or_else(|| KaseederError::Service(format!("Process {} not found", pid)))?;
+        Ok(process.memory())
     }
 
     /// Get network connection count
@@ -277,4 +401,151 @@ mod tests {
         let metrics = monitor.performance_metrics.lock().await;
         assert!(metrics.avg_response_time_ms > 0.0);
     }
+
+    #[tokio::test]
+    async fn test_performance_metrics_read_from_process() {
+        let monitor = SystemMonitor::new();
+        let mut system = System::new();
+
+        SystemMonitor::collect_performance_metrics(
+            monitor.performance_metrics.clone(),
+            &mut system,
+        )
+        .await
+        .unwrap();
+
+        let metrics = monitor.performance_metrics.lock().await;
+        assert!(metrics.memory_usage > 0);
+        assert!(metrics.cpu_usage >= 0.0);
+        assert!(metrics.last_updated.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_warning_and_error_thresholds() {
+        let monitor = SystemMonitor::new();
+        let check = |cpu_usage: f64, memory_usage: u64, avg_response_time_ms: f64| {
+            let monitor = &monitor;
+            async move {
+                {
+                    let mut metrics = monitor.performance_metrics.lock().await;
+                    metrics.cpu_usage = cpu_usage;
+                    metrics.memory_usage = memory_usage;
+                    metrics.avg_response_time_ms = avg_response_time_ms;
+                }
+                SystemMonitor::perform_health_check(
+                    monitor.health_status.clone(),
+                    monitor.performance_metrics.clone(),
+                )
+                .await
+                .unwrap();
+                let health = monitor.health_status.lock().await;
+                (health.is_healthy, health.issues.clone())
+            }
+        };
+
+        // At the thresholds nothing is reported
+        assert_eq!(check(60.0, 1024 * 1024 * 1024, 500.0).await, (true, vec![]));
+
+        // Above the warning thresholds
+        assert_eq!(
+            check(61.0, 0, 501.0).await,
+            (
+                false,
+                vec![
+                    "Elevated CPU usage detected".to_string(),
+                    "Elevated response time detected".to_string()
+                ]
+            )
+        );
+
+        // Above the error thresholds only the more severe issue is reported
+        assert_eq!(
+            check(81.0, 1024 * 1024 * 1024 + 1, 1001.0).await,
+            (
+                false,
+                vec![
+                    "High CPU usage detected".to_string(),
+                    "High memory usage detected".to_string(),
+                    "High response time detected".to_string()
+                ]
+            )
+        );
+
+        // Recovering clears the previous issues
+        assert_eq!(check(10.0, 0, 10.0).await, (true, vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_alert_webhook_fires_on_debounced_health_transition() {
+        use axum::extract::State;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use tokio::sync::mpsc;
+
+        // Mock webhook receiver that forwards each alert to the test
+        let (alerts_tx, mut alerts_rx) = mpsc::unbounded_channel::<HealthAlert>();
+        let app = Router::new()
+            .route(
+                "/alert",
+                post(
+                    |State(alerts_tx): State<mpsc::UnboundedSender<HealthAlert>>,
+                     Json(alert): Json<HealthAlert>| async move {
+                        alerts_tx.send(alert).unwrap();
+                    },
+                ),
+            )
+            .with_state(alerts_tx);
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        let server = tokio::spawn(server);
+
+        let monitor = SystemMonitor::new().with_alert_webhook(format!("http://{}/alert", addr));
+        let alert_webhook = monitor.alert_webhook.clone().unwrap();
+        let check = |cpu_usage: f64| {
+            let monitor = &monitor;
+            let alert_webhook = &alert_webhook;
+            async move {
+                monitor.performance_metrics.lock().await.cpu_usage = cpu_usage;
+                SystemMonitor::perform_health_check(
+                    monitor.health_status.clone(),
+                    monitor.performance_metrics.clone(),
+                )
+                .await
+                .unwrap();
+                SystemMonitor::notify_alert_webhook(&monitor.health_status, alert_webhook).await;
+            }
+        };
+
+        // A single bad check is debounced away
+        check(95.0).await;
+        check(10.0).await;
+        check(95.0).await;
+        assert!(alerts_rx.try_recv().is_err());
+
+        // The second consecutive bad check announces the transition
+        check(95.0).await;
+        let alert = tokio::time::timeout(Duration::from_secs(5), alerts_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!alert.healthy);
+        assert_eq!(alert.issues, vec!["High CPU usage detected".to_string()]);
+
+        // Staying unhealthy doesn't repeat the alert, recovering sends one
+        check(95.0).await;
+        check(10.0).await;
+        check(10.0).await;
+        let alert = tokio::time::timeout(Duration::from_secs(5), alerts_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(alert.healthy);
+        assert!(alert.issues.is_empty());
+        assert!(alerts_rx.try_recv().is_err());
+
+        server.abort();
+    }
 }
@@ -1,5 +1,13 @@
+use crate::constants::{
+    ADDRESS_CHANNEL_SEND_TIMEOUT, DEFAULT_ADDRESS_CHANNEL_CAPACITY,
+    DEFAULT_ADDRESS_REQUEST_INTERVAL, DEFAULT_HANDSHAKE_TIMEOUT, DEFAULT_PROTOCOL_VERSIONS,
+    MAX_CONNECT_RETRIES,
+};
 use crate::errors::{KaseederError, Result};
+use crate::kaspa_protocol::check_peer_network;
+use crate::proxy::Socks5Proxy;
 use crate::types::NetAddress;
+use dashmap::DashMap;
 use kaspa_consensus_core::config::Config as ConsensusConfig;
 use kaspa_core::time::unix_now;
 use kaspa_p2p_lib::{
@@ -7,27 +15,452 @@ use kaspa_p2p_lib::{
     PeerKey, Router,
     common::ProtocolError,
     make_message,
-    pb::{VersionMessage, kaspad_message::Payload, RequestAddressesMessage},
+    pb::{self, VersionMessage, kaspad_message::Payload, RequestAddressesMessage},
 };
 use kaspa_utils_tower::counters::TowerConnectionCounters;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{Mutex, mpsc};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::SendTimeoutError;
+use tokio::time::Instant;
 use tonic::async_trait;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+/// How long connecting to a peer may take, through the proxy if one is set
+const DIAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Addresses received from a peer, along with the number of entries rejected as malformed
+#[derive(Debug, Clone, Default)]
+pub struct AddressBatch {
+    pub addresses: Vec<NetAddress>,
+    pub rejected: usize,
+}
+
+/// Decode an address entry from an `Addresses` message.
+///
+/// IPv4-mapped IPv6 encodings are normalized to IPv4. Entries with an invalid
+/// length, an unspecified, multicast or broadcast IP, or an out-of-range port
+/// are rejected.
+pub fn parse_peer_address(ip: &[u8], port: u32) -> std::result::Result<NetAddress, String> {
+    let ip = match ip.len() {
+        4 => {
+            let ip_bytes: [u8; 4] = [ip[0], ip[1], ip[2], ip[3]];
+            IpAddr::V4(Ipv4Addr::from(ip_bytes))
+        }
+        16 => {
+            let mut ip_bytes = [0u8; 16];
+            ip_bytes.copy_from_slice(ip);
+            IpAddr::V6(Ipv6Addr::from(ip_bytes)).to_canonical()
+        }
+        len => return Err(format!("invalid IP address length: {}", len)),
+    };
+
+    if ip.is_unspecified() {
+        return Err(format!("unspecified IP address: {}", ip));
+    }
+    if ip.is_multicast() {
+        return Err(format!("multicast IP address: {}", ip));
+    }
+    if matches!(ip, IpAddr::V4(ipv4) if ipv4.is_broadcast()) {
+        return Err(format!("broadcast IP address: {}", ip));
+    }
+
+    let port = match u16::try_from(port) {
+        Ok(0) | Err(_) => return Err(format!("invalid port {} for {}", port, ip)),
+        Ok(port) => port,
+    };
+
+    Ok(NetAddress::new(ip, port))
+}
+
+/// Decode the address list of an `Addresses` message, counting rejected entries
+pub fn decode_addresses(address_list: Vec<pb::NetAddress>) -> AddressBatch {
+    let mut batch = AddressBatch::default();
+    for addr in address_list {
+        match parse_peer_address(&addr.ip, addr.port) {
+            Ok(address) => batch.addresses.push(address),
+            Err(reason) => {
+                debug!("Rejected address from peer: {}", reason);
+                batch.rejected += 1;
+            }
+        }
+    }
+    batch
+}
+
+/// Parse a subnetwork ID given as 40 hex characters (20 bytes)
+pub fn parse_subnetwork_id(id: &str) -> std::result::Result<Vec<u8>, String> {
+    if id.len() != 40 || !id.is_ascii() {
+        return Err(format!(
+            "subnetwork ID must be 40 hex characters, got '{}'",
+            id
+        ));
+    }
+
+    (0..id.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&id[i..i + 2], 16)
+                .map_err(|_| format!("invalid hex in subnetwork ID '{}'", id))
+        })
+        .collect()
+}
+
+/// Format a subnetwork ID as lowercase hex, the form used in DNS queries
+pub fn format_subnetwork_id(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Spaces out `RequestAddresses` messages sent to the same peer IP
+#[derive(Debug)]
+pub struct AddressRequestLimiter {
+    min_interval: Duration,
+    /// Earliest time the next request may be sent to each IP
+    next_allowed: DashMap<IpAddr, Instant>,
+}
+
+impl AddressRequestLimiter {
+    /// Create a limiter allowing one request per `min_interval` per IP
+    /// (zero disables limiting)
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            next_allowed: DashMap::new(),
+        }
+    }
+
+    /// Wait until an address request may be sent to the given IP, reserving
+    /// that slot so concurrent callers queue up behind it
+    pub async fn wait_turn(&self, ip: IpAddr) {
+        if self.min_interval.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+        // Forget IPs whose next slot has already passed
+        self.next_allowed
+            .retain(|_, next_allowed| *next_allowed > now);
+
+        let send_at = {
+            let mut next_allowed = self.next_allowed.entry(ip).or_insert(now);
+            let send_at = (*next_allowed).max(now);
+            *next_allowed = send_at + self.min_interval;
+            send_at
+        };
+
+        if send_at > now {
+            debug!("Delaying address request to {} by {:?}", ip, send_at - now);
+            tokio::time::sleep_until(send_at).await;
+        }
+    }
+}
+
+/// Cause of a failed connection attempt, which picks its retry policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectErrorClass {
+    /// The peer refused the connection
+    Refused,
+    /// Connecting to the peer timed out
+    Timeout,
+    /// The peer speaks an incompatible protocol version
+    ProtocolMismatch,
+    /// Any other failure
+    Other,
+}
+
+impl ConnectErrorClass {
+    pub const ALL: [ConnectErrorClass; 4] = [
+        ConnectErrorClass::Refused,
+        ConnectErrorClass::Timeout,
+        ConnectErrorClass::ProtocolMismatch,
+        ConnectErrorClass::Other,
+    ];
+
+    /// Name used in the configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectErrorClass::Refused => "refused",
+            ConnectErrorClass::Timeout => "timeout",
+            ConnectErrorClass::ProtocolMismatch => "protocol_mismatch",
+            ConnectErrorClass::Other => "other",
+        }
+    }
+
+    /// Class of an error returned by a connection attempt
+    pub fn of(error: &KaseederError) -> Self {
+        match error {
+            KaseederError::PeerUnavailable(_) => ConnectErrorClass::Refused,
+            KaseederError::NetworkTimeout(_) => ConnectErrorClass::Timeout,
+            KaseederError::ProtocolVersionMismatch(_) => ConnectErrorClass::ProtocolMismatch,
+            _ => ConnectErrorClass::Other,
+        }
+    }
+}
+
+/// Retries allowed after a failed connection attempt, with the delay
+/// doubling after each retry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    /// Delay before the first retry
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry`, counted from 1, or `None` once
+    /// the retries are used up
+    pub fn delay(&self, retry: u32) -> Option<Duration> {
+        (retry > 0 && retry <= self.retries).then(|| self.backoff * 2_u32.saturating_pow(retry - 1))
+    }
+}
+
+/// Retry policy for each class of connection error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicies {
+    pub refused: RetryPolicy,
+    pub timeout: RetryPolicy,
+    pub protocol_mismatch: RetryPolicy,
+    pub other: RetryPolicy,
+}
+
+impl Default for RetryPolicies {
+    fn default() -> Self {
+        Self {
+            // Often a node restarting, so worth one quick retry
+            refused: RetryPolicy {
+                retries: 1,
+                backoff: Duration::from_millis(500),
+            },
+            // Congested links may recover, given time
+            timeout: RetryPolicy {
+                retries: 2,
+                backoff: Duration::from_secs(2),
+            },
+            // Will fail the same way again
+            protocol_mismatch: RetryPolicy {
+                retries: 0,
+                backoff: Duration::ZERO,
+            },
+            other: RetryPolicy {
+                retries: 0,
+                backoff: Duration::ZERO,
+            },
+        }
+    }
+}
+
+impl RetryPolicies {
+    pub fn for_class(&self, class: ConnectErrorClass) -> RetryPolicy {
+        match class {
+            ConnectErrorClass::Refused => self.refused,
+            ConnectErrorClass::Timeout => self.timeout,
+            ConnectErrorClass::ProtocolMismatch => self.protocol_mismatch,
+            ConnectErrorClass::Other => self.other,
+        }
+    }
+
+    fn for_class_mut(&mut self, class: ConnectErrorClass) -> &mut RetryPolicy {
+        match class {
+            ConnectErrorClass::Refused => &mut self.refused,
+            ConnectErrorClass::Timeout => &mut self.timeout,
+            ConnectErrorClass::ProtocolMismatch => &mut self.protocol_mismatch,
+            ConnectErrorClass::Other => &mut self.other,
+        }
+    }
+
+    /// The defaults, overridden by `class=retries[:backoff_ms]` entries such
+    /// as `timeout=3:1000`
+    pub fn parse(entries: &[String]) -> std::result::Result<Self, String> {
+        let mut policies = Self::default();
+        for entry in entries {
+            let (class, policy) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected class=retries[:backoff_ms], got '{}'", entry))?;
+            let class = ConnectErrorClass::ALL
+                .into_iter()
+                .find(|known| known.as_str() == class.trim().to_lowercase())
+                .ok_or_else(|| format!("unknown connection error class '{}'", class.trim()))?;
+
+            let (retries, backoff) = match policy.split_once(':') {
+                Some((retries, backoff)) => (retries, Some(backoff)),
+                None => (policy, None),
+            };
+            let retries: u32 = retries
+                .trim()
+                .parse()
+                .ok()
+                .filter(|retries| *retries <= MAX_CONNECT_RETRIES)
+                .ok_or_else(|| {
+                    format!(
+                        "retries for {} must be 0-{}",
+                        class.as_str(),
+                        MAX_CONNECT_RETRIES
+                    )
+                })?;
+
+            let policy = policies.for_class_mut(class);
+            policy.retries = retries;
+            if let Some(backoff) = backoff {
+                let backoff_ms: u64 = backoff.trim().parse().map_err(|_| {
+                    format!("invalid backoff for {}: '{}'", class.as_str(), backoff)
+                })?;
+                policy.backoff = Duration::from_millis(backoff_ms);
+            }
+        }
+
+        Ok(policies)
+    }
+}
+
+/// Run a connection attempt, retrying failures as the policy for their
+/// error class allows
+pub async fn connect_with_retries<T, F, Fut>(
+    policies: &RetryPolicies,
+    address: &str,
+    mut attempt: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                let class = ConnectErrorClass::of(&e);
+                retries += 1;
+                let Some(delay) = policies.for_class(class).delay(retries) else {
+                    return Err(KaseederError::ConnectionFailed(format!(
+                        "Failed to connect to peer {} after {} attempts: {}",
+                        address, retries, e
+                    )));
+                };
+
+                warn!(
+                    "Connection attempt {} failed for {} ({}): {}. Retrying in {:?}...",
+                    retries,
+                    address,
+                    class.as_str(),
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// Settings the connection initializer applies to every peer connection
+#[derive(Debug, Clone)]
+pub struct ConnectionSettings {
+    /// Time allowed for the version handshake and Ready exchange
+    pub handshake_timeout: Duration,
+    /// Ask peers for addresses on all subnetworks
+    pub include_all_subnetworks: bool,
+    /// Subnetwork to ask peers for addresses on
+    pub subnetwork_id: Option<Vec<u8>>,
+    /// Per-peer spacing of address requests, shared by all adapters
+    pub address_requests: Arc<AddressRequestLimiter>,
+    /// SOCKS5 proxy outbound peer connections are routed through
+    pub proxy: Option<Arc<Socks5Proxy>>,
+    /// Reject peers that advertise a different network than ours
+    pub verify_network: bool,
+    /// Address batches buffered per peer connection before its handler waits for room
+    pub address_channel_capacity: usize,
+    /// How failed connections are retried, by error class
+    pub retry_policies: RetryPolicies,
+    /// Protocol versions offered in the handshake, in order of preference
+    pub protocol_versions: Vec<u32>,
+}
+
+impl Default for ConnectionSettings {
+    fn default() -> Self {
+        Self {
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+            include_all_subnetworks: false,
+            subnetwork_id: None,
+            address_requests: Arc::new(AddressRequestLimiter::new(
+                DEFAULT_ADDRESS_REQUEST_INTERVAL,
+            )),
+            proxy: None,
+            verify_network: true,
+            address_channel_capacity: DEFAULT_ADDRESS_CHANNEL_CAPACITY,
+            retry_policies: RetryPolicies::default(),
+            protocol_versions: DEFAULT_PROTOCOL_VERSIONS.to_vec(),
+        }
+    }
+}
+
+/// Bounded channel carrying address batches from a connection handler to the
+/// adapter. A capacity of 0 is treated as 1.
+pub fn address_channel(
+    capacity: usize,
+) -> (mpsc::Sender<AddressBatch>, mpsc::Receiver<AddressBatch>) {
+    mpsc::channel(capacity.max(1))
+}
+
+/// Hand a batch to the adapter, waiting up to `wait` for room in the channel.
+/// Batches that do not fit in time are dropped so a slow consumer makes
+/// handlers give up instead of piling up buffered addresses. Returns whether
+/// the batch was delivered.
+pub async fn forward_addresses(
+    addresses_tx: &mpsc::Sender<AddressBatch>,
+    batch: AddressBatch,
+    wait: Duration,
+) -> bool {
+    match addresses_tx.send_timeout(batch, wait).await {
+        Ok(()) => true,
+        Err(SendTimeoutError::Timeout(batch)) => {
+            warn!(
+                "Address channel full for {:?}, dropping {} addresses",
+                wait,
+                batch.addresses.len()
+            );
+            false
+        }
+        Err(SendTimeoutError::Closed(_)) => {
+            debug!("Address channel closed, dropping addresses");
+            false
+        }
+    }
+}
+
+/// Details a peer advertised in its version message that the p2p library
+/// does not keep in the peer properties
+#[derive(Debug, Clone, Default)]
+struct PeerHandshake {
+    /// Protocol version the peer advertised
+    protocol_version: u32,
+    services: u64,
+    network: String,
+}
+
+/// What the adapter asks of an outbound connection it is about to dial
+#[derive(Debug, Clone)]
+struct PendingDial {
+    /// Protocol version to offer in the handshake
+    protocol_version: u32,
+    /// Where this connection's address batches go, so each batch reaches
+    /// the poll that dialed the peer
+    addresses_tx: mpsc::Sender<AddressBatch>,
+}
+
 /// DNS seeder connection initializer, specifically for address collection
 pub struct KaseederConnectionInitializer {
     version_message: VersionMessage,
-    addresses_tx: mpsc::Sender<Vec<NetAddress>>,
+    settings: ConnectionSettings,
+    /// What each connected peer advertised in its version message
+    peer_handshakes: Arc<DashMap<PeerKey, PeerHandshake>>,
+    /// Dials in progress, by the address being dialed
+    pending_dials: Arc<DashMap<SocketAddr, PendingDial>>,
 }
 
 impl KaseederConnectionInitializer {
-    pub fn new(
-        consensus_config: &ConsensusConfig,
-        addresses_tx: mpsc::Sender<Vec<NetAddress>>,
-    ) -> Self {
+    pub fn new(consensus_config: &ConsensusConfig) -> Self {
         let version_message = VersionMessage {
             protocol_version: 0, // Use 0 for auto-negotiation (like Go version)
             services: 0,
@@ -42,9 +475,47 @@ impl KaseederConnectionInitializer {
 
         Self {
             version_message,
-            addresses_tx,
+            settings: ConnectionSettings::default(),
+            peer_handshakes: Arc::new(DashMap::new()),
+            pending_dials: Arc::new(DashMap::new()),
         }
     }
+
+    /// Apply the given connection settings
+    pub fn with_settings(mut self, settings: ConnectionSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Set the time allowed for the version handshake and Ready exchange
+    pub fn with_handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+        self.settings.handshake_timeout = handshake_timeout;
+        self
+    }
+
+    /// Build the `RequestAddresses` message sent once the handshake completes
+    fn request_addresses_message(&self) -> RequestAddressesMessage {
+        RequestAddressesMessage {
+            include_all_subnetworks: self.settings.include_all_subnetworks,
+            subnetwork_id: self
+                .settings
+                .subnetwork_id
+                .clone()
+                .map(|bytes| pb::SubnetworkId { bytes }),
+        }
+    }
+
+    /// Run a handshake step, failing with a timeout if the peer has not
+    /// completed it by the handshake deadline
+    async fn before_handshake_deadline<T>(
+        &self,
+        deadline: Instant,
+        step: impl Future<Output = std::result::Result<T, ProtocolError>>,
+    ) -> std::result::Result<T, ProtocolError> {
+        tokio::time::timeout_at(deadline, step)
+            .await
+            .map_err(|_| ProtocolError::Timeout(self.settings.handshake_timeout))?
+    }
 }
 
 #[async_trait]
@@ -57,33 +528,46 @@ impl ConnectionInitializer for KaseederConnectionInitializer {
         let mut handshake = KaspadHandshake::new(&router);
         router.start();
 
-        // 2. Perform handshake with protocol version negotiation
+        // 2. Perform the version handshake. The version exchange and the
+        // Ready exchange share one deadline so a peer that accepts the
+        // connection but stalls doesn't hold the slot
         debug!("Starting handshake with peer");
+        let handshake_deadline = Instant::now() + self.settings.handshake_timeout;
 
-        // Force protocol version 7 to connect to active Crescendo nodes (v6 nodes are "zombie" nodes)
+        // A router runs the version exchange once, so the adapter picks the
+        // protocol version to offer per connection and dials again to fall
+        // back to the next one
+        let pending_dial = self
+            .pending_dials
+            .get(&router.net_address())
+            .map(|dial| dial.clone());
+        let protocol_version = pending_dial
+            .as_ref()
+            .map(|dial| dial.protocol_version)
+            .or_else(|| self.settings.protocol_versions.first().copied())
+            .unwrap_or(DEFAULT_PROTOCOL_VERSIONS[0]);
         let mut version_msg = self.version_message.clone();
-        version_msg.protocol_version = 7; // Force v7 for active Crescendo nodes
-
-        let peer_version = match handshake.handshake(version_msg.clone()).await {
-            Ok(version) => {
-                let user_agent = version.user_agent.clone();
-                info!(
-                    "Handshake completed with peer using protocol version 7. User agent: {}",
-                    user_agent
-                );
-                Some(version)
-            }
-            Err(e) => {
-                debug!("Handshake failed with protocol version 7: {}", e);
-                None
-            }
-        };
+        version_msg.protocol_version = protocol_version;
 
-        let _peer_version = peer_version.ok_or_else(|| {
-            ProtocolError::from_reject_message(
-                "Failed to establish handshake with protocol version 7".to_string(),
-            )
-        })?;
+        let peer_version = self
+            .before_handshake_deadline(handshake_deadline, handshake.handshake(version_msg))
+            .await
+            .inspect_err(|e| {
+                debug!(
+                    "Handshake failed with protocol version {}: {}",
+                    protocol_version, e
+                )
+            })?;
+        info!(
+            "Handshake completed offering protocol version {}, peer runs {}. User agent: {}",
+            protocol_version, peer_version.protocol_version, peer_version.user_agent
+        );
+
+        // Never take addresses from a peer on another network
+        if self.settings.verify_network {
+            check_peer_network(&self.version_message.network, &peer_version.network)
+                .map_err(|e| ProtocolError::from_reject_message(e.to_string()))?;
+        }
 
         // 3. Subscribe to messages for address collection (avoid duplicate subscriptions)
         let all_messages_receiver = router.subscribe(vec![
@@ -96,19 +580,34 @@ impl ConnectionInitializer for KaseederConnectionInitializer {
         debug!("Registering message flows before Ready exchange");
 
         // 5. Complete handshake with Ready exchange (rusty-kaspa style)
-        handshake.exchange_ready_messages().await?;
+        self.before_handshake_deadline(handshake_deadline, handshake.exchange_ready_messages())
+            .await?;
         debug!("Ready exchange completed, handshake fully established");
+        self.peer_handshakes.insert(
+            router.key(),
+            PeerHandshake {
+                protocol_version: peer_version.protocol_version,
+                services: peer_version.services,
+                network: peer_version.network.clone(),
+            },
+        );
 
-        // 6. Send address request to get peer addresses (Kaspa P2P standard)
+        // 6. Send address request to get peer addresses (Kaspa P2P standard),
+        // no more often than the configured interval per peer. Proxied
+        // connections are keyed by the peer behind the tunnel.
+        let peer_address = router.net_address();
+        let peer_ip = self
+            .settings
+            .proxy
+            .as_ref()
+            .and_then(|proxy| proxy.tunnel_target(&peer_address))
+            .unwrap_or(peer_address)
+            .ip();
+        self.settings.address_requests.wait_turn(peer_ip).await;
         debug!("Sending address request to peer");
-        let request_addresses_msg = make_message!(
-            Payload::RequestAddresses,
-            RequestAddressesMessage { 
-                include_all_subnetworks: false, 
-                subnetwork_id: None 
-            }
-        );
-        
+        let request_addresses_msg =
+            make_message!(Payload::RequestAddresses, self.request_addresses_message());
+
         if let Err(e) = router.enqueue(request_addresses_msg).await {
             debug!("Failed to send address request: {}", e);
         } else {
@@ -124,8 +623,15 @@ impl ConnectionInitializer for KaseederConnectionInitializer {
         });
 
         // 7. Wait for address response
-        // Start address response handler coroutine
-        let addresses_tx = self.addresses_tx.clone();
+        // Start address response handler coroutine, unless nobody dialed
+        // this connection to collect its addresses
+        let Some(PendingDial { addresses_tx, .. }) = pending_dial else {
+            debug!(
+                "No pending dial for {}, ignoring its addresses",
+                peer_address
+            );
+            return Ok(());
+        };
 
         tokio::spawn(async move {
             if let Err(e) =
@@ -142,7 +648,7 @@ impl ConnectionInitializer for KaseederConnectionInitializer {
 impl KaseederConnectionInitializer {
     async fn handle_addresses_response(
         mut all_messages_receiver: IncomingRoute,
-        addresses_tx: mpsc::Sender<Vec<NetAddress>>,
+        addresses_tx: mpsc::Sender<AddressBatch>,
     ) -> std::result::Result<(), ProtocolError> {
         // Wait for address message with timeout, skipping irrelevant messages (like Go version)
         let timeout = Duration::from_secs(3); // Shorter timeout like Go version
@@ -161,33 +667,11 @@ impl KaseederConnectionInitializer {
                             Some(Payload::Addresses(addresses_msg)) => {
                                 debug!("Received {} addresses from peer", addresses_msg.address_list.len());
 
-                                // Convert address format
-                                let addresses: Vec<NetAddress> = addresses_msg.address_list
-                                    .into_iter()
-                                    .filter_map(|addr| {
-                                        // Parse IP address bytes
-                                        if addr.ip.len() == 4 {
-                                            // IPv4
-                                            let ip_bytes: [u8; 4] = [addr.ip[0], addr.ip[1], addr.ip[2], addr.ip[3]];
-                                            let ipv4 = std::net::Ipv4Addr::from(ip_bytes);
-                                            Some(NetAddress::new(std::net::IpAddr::V4(ipv4), addr.port as u16))
-                                        } else if addr.ip.len() == 16 {
-                                            // IPv6
-                                            let mut ip_bytes = [0u8; 16];
-                                            ip_bytes.copy_from_slice(&addr.ip);
-                                            let ipv6 = std::net::Ipv6Addr::from(ip_bytes);
-                                            Some(NetAddress::new(std::net::IpAddr::V6(ipv6), addr.port as u16))
-                                        } else {
-                                            debug!("Invalid IP address length: {}", addr.ip.len());
-                                            None
-                                        }
-                                    })
-                                    .collect();
+                                // Convert address format, dropping malformed entries
+                                let batch = decode_addresses(addresses_msg.address_list);
 
                                 // Send addresses to main thread
-                                if let Err(e) = addresses_tx.send(addresses).await {
-                                    debug!("Failed to send addresses to main thread: {}", e);
-                                }
+                                forward_addresses(&addresses_tx, batch, ADDRESS_CHANNEL_SEND_TIMEOUT).await;
 
                                 // Successfully received addresses, break the loop
                                 break;
@@ -243,18 +727,36 @@ impl KaseederConnectionInitializer {
 /// DNS seeder network adapter, using the real kaspa-p2p-lib
 pub struct DnsseedNetAdapter {
     adaptor: Arc<Adaptor>,
-    addresses_rx: Arc<Mutex<mpsc::Receiver<Vec<NetAddress>>>>,
+    peer_handshakes: Arc<DashMap<PeerKey, PeerHandshake>>,
+    pending_dials: Arc<DashMap<SocketAddr, PendingDial>>,
+    proxy: Option<Arc<Socks5Proxy>>,
+    retry_policies: RetryPolicies,
+    /// Protocol versions offered in the handshake, in order of preference
+    protocol_versions: Vec<u32>,
+    /// Address batches buffered per connection
+    address_channel_capacity: usize,
 }
 
 impl DnsseedNetAdapter {
     /// Create a new network adapter instance
     pub fn new(consensus_config: Arc<ConsensusConfig>) -> Result<Self> {
-        let (addresses_tx, addresses_rx) = mpsc::channel(100);
+        Self::with_settings(consensus_config, ConnectionSettings::default())
+    }
 
-        let initializer = Arc::new(KaseederConnectionInitializer::new(
-            &consensus_config,
-            addresses_tx,
-        ));
+    /// Create a new network adapter instance with custom connection settings
+    pub fn with_settings(
+        consensus_config: Arc<ConsensusConfig>,
+        settings: ConnectionSettings,
+    ) -> Result<Self> {
+        let proxy = settings.proxy.clone();
+        let retry_policies = settings.retry_policies;
+        let protocol_versions = settings.protocol_versions.clone();
+        let address_channel_capacity = settings.address_channel_capacity;
+
+        let initializer =
+            Arc::new(KaseederConnectionInitializer::new(&consensus_config).with_settings(settings));
+        let peer_handshakes = initializer.peer_handshakes.clone();
+        let pending_dials = initializer.pending_dials.clone();
 
         let hub = Hub::new();
         let counters = Arc::new(TowerConnectionCounters::default());
@@ -263,7 +765,12 @@ impl DnsseedNetAdapter {
 
         Ok(Self {
             adaptor,
-            addresses_rx: Arc::new(Mutex::new(addresses_rx)),
+            peer_handshakes,
+            pending_dials,
+            proxy,
+            retry_policies,
+            protocol_versions,
+            address_channel_capacity,
         })
     }
 
@@ -271,107 +778,32 @@ impl DnsseedNetAdapter {
     pub async fn connect_and_get_addresses(
         &self,
         address: &str,
-    ) -> Result<(VersionMessage, Vec<NetAddress>)> {
+    ) -> Result<(VersionMessage, AddressBatch)> {
         info!("Connecting to peer: {}", address);
 
-        // Implement fast failure strategy for better performance
-        let mut retry_count = 0;
-        let max_retries = 1; // Reduced to 1 for fastest failure detection
-        let base_delay = Duration::from_secs(1); // Keep 1 second for single retry
-
-        loop {
-            match self.try_connect_peer(address).await {
-                Ok((peer_key, version_message, addresses)) => {
-                    info!(
-                        "Successfully connected to peer: {} (key: {})",
-                        address, peer_key
-                    );
-                    return Ok((version_message, addresses));
-                }
-                Err(e) => {
-                    retry_count += 1;
-                    if retry_count >= max_retries {
-                        return Err(KaseederError::ConnectionFailed(format!(
-                            "Failed to connect to peer {} after {} retries: {}",
-                            address, max_retries, e
-                        )));
-                    }
-
-                    let delay = base_delay * 2_u32.pow(retry_count as u32 - 1);
-                    warn!(
-                        "Connection attempt {} failed for {}: {}. Retrying in {:?}...",
-                        retry_count, address, e, delay
-                    );
-                    tokio::time::sleep(delay).await;
-                }
-            }
-        }
+        let (peer_key, version_message, addresses) =
+            connect_with_retries(&self.retry_policies, address, || {
+                self.try_connect_peer(address)
+            })
+            .await?;
+        info!(
+            "Successfully connected to peer: {} (key: {})",
+            address, peer_key
+        );
+        Ok((version_message, addresses))
     }
 
     /// Try to connect to a single node
     async fn try_connect_peer(
         &self,
         address: &str,
-    ) -> Result<(PeerKey, VersionMessage, Vec<NetAddress>)> {
-        // Connect to peer node with increased timeout
-        let peer_key = self
-            .adaptor
-            .connect_peer_with_retries(
-                address.to_string(),
-                1,                      // Single connection attempt
-                Duration::from_secs(5), // Reduced connection timeout to 5 seconds for faster failure
-            )
-            .await
-            .map_err(|e| {
-                // Enhanced error classification for better debugging
-                match e {
-                    kaspa_p2p_lib::ConnectionError::ProtocolError(proto_err) => {
-                        // Check if it's a protocol version mismatch
-                        if proto_err.to_string().contains("version")
-                            || proto_err.to_string().contains("protocol")
-                        {
-                            KaseederError::ProtocolVersionMismatch(format!(
-                                "Protocol version mismatch connecting to {}: {}",
-                                address, proto_err
-                            ))
-                        } else {
-                            KaseederError::Protocol(format!(
-                                "Protocol error connecting to {}: {}",
-                                address, proto_err
-                            ))
-                        }
-                    }
-                    kaspa_p2p_lib::ConnectionError::NoAddress => KaseederError::InvalidAddress(
-                        format!("Invalid address format for {}: {}", address, e),
-                    ),
-                    kaspa_p2p_lib::ConnectionError::IoError(ref io_err) => {
-                        // Check if it's a connection refused or timeout
-                        if io_err.kind() == std::io::ErrorKind::ConnectionRefused {
-                            KaseederError::PeerUnavailable(format!(
-                                "Peer {} refused connection: {}",
-                                address, io_err
-                            ))
-                        } else if io_err.kind() == std::io::ErrorKind::TimedOut {
-                            KaseederError::NetworkTimeout(format!(
-                                "Connection timeout to {}: {}",
-                                address, io_err
-                            ))
-                        } else {
-                            KaseederError::Io(std::io::Error::new(
-                                std::io::ErrorKind::Other,
-                                format!("I/O error connecting to {}: {}", address, e),
-                            ))
-                        }
-                    }
-                    _ => KaseederError::ConnectionFailed(format!(
-                        "Connection failed to {}: {}",
-                        address, e
-                    )),
-                }
-            })?;
+    ) -> Result<(PeerKey, VersionMessage, AddressBatch)> {
+        let (peer_key, mut addresses_rx) = self.dial_with_version_fallback(address).await?;
 
         // Wait for address response with increased timeout
-        let addresses = self.wait_for_addresses_with_timeout(peer_key).await?;
+        let addresses = self
+            .wait_for_addresses_with_timeout(peer_key, &mut addresses_rx)
+            .await?;
 
         // Get peer node information (including version information)
         let version_message = self.get_peer_version_info(peer_key).await?;
@@ -385,32 +817,167 @@ impl DnsseedNetAdapter {
         Ok((peer_key, version_message, addresses))
     }
 
-    /// Wait for address response with increased timeout
-    async fn wait_for_addresses_with_timeout(&self, peer_key: PeerKey) -> Result<Vec<NetAddress>> {
-        let mut addresses_rx = self.addresses_rx.lock().await;
+    /// Dial `address`, offering each configured protocol version on a fresh
+    /// connection until the peer completes the handshake with one. Returns
+    /// the peer and the receiving end of that connection's address channel.
+    async fn dial_with_version_fallback(
+        &self,
+        address: &str,
+    ) -> Result<(PeerKey, mpsc::Receiver<AddressBatch>)> {
+        let mut last_error = None;
+        for &protocol_version in &self.protocol_versions {
+            match self.dial_peer(address, protocol_version).await {
+                Ok(connection) => return Ok(connection),
+                Err(
+                    e @ (KaseederError::ProtocolVersionMismatch(_) | KaseederError::Protocol(_)),
+                ) => {
+                    debug!(
+                        "Handshake with {} offering protocol version {} failed: {}",
+                        address, protocol_version, e
+                    );
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            KaseederError::Protocol(format!("No protocol versions to offer to {}", address))
+        }))
+    }
+
+    /// Open one connection to `address`, offering `protocol_version`
+    async fn dial_peer(
+        &self,
+        address: &str,
+        protocol_version: u32,
+    ) -> Result<(PeerKey, mpsc::Receiver<AddressBatch>)> {
+        // When proxying, the adaptor dials a loopback tunnel to the peer
+        let dial_address = match &self.proxy {
+            Some(proxy) => proxy.open_tunnel(address, DIAL_TIMEOUT).await?,
+            None => address.to_string(),
+        };
+
+        // The initializer looks the dial up by the address the router
+        // reports, so resolve it here and dial exactly that address
+        let dial_key = tokio::net::lookup_host(&dial_address)
+            .await
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| {
+                KaseederError::InvalidAddress(format!(
+                    "Invalid address format for {}: {}",
+                    address, dial_address
+                ))
+            })?;
+        let (addresses_tx, addresses_rx) = address_channel(self.address_channel_capacity);
+        self.pending_dials.insert(
+            dial_key,
+            PendingDial {
+                protocol_version,
+                addresses_tx,
+            },
+        );
+
+        // Connect to peer node with increased timeout
+        let result = self
+            .adaptor
+            .connect_peer_with_retries(
+                dial_key.to_string(),
+                1, // Single connection attempt
+                DIAL_TIMEOUT,
+            )
+            .await;
+        self.pending_dials.remove(&dial_key);
+
+        let peer_key = result.map_err(|e| {
+            // Enhanced error classification for better debugging
+            match e {
+                kaspa_p2p_lib::ConnectionError::ProtocolError(proto_err) => {
+                    // Check if it's a protocol version mismatch
+                    if proto_err.to_string().contains("version")
+                        || proto_err.to_string().contains("protocol")
+                    {
+                        KaseederError::ProtocolVersionMismatch(format!(
+                            "Protocol version mismatch connecting to {}: {}",
+                            address, proto_err
+                        ))
+                    } else {
+                        KaseederError::Protocol(format!(
+                            "Protocol error connecting to {}: {}",
+                            address, proto_err
+                        ))
+                    }
+                }
+                kaspa_p2p_lib::ConnectionError::NoAddress => KaseederError::InvalidAddress(
+                    format!("Invalid address format for {}: {}", address, e),
+                ),
+                kaspa_p2p_lib::ConnectionError::IoError(ref io_err) => {
+                    // Check if it's a connection refused or timeout
+                    if io_err.kind() == std::io::ErrorKind::ConnectionRefused {
+                        KaseederError::PeerUnavailable(format!(
+                            "Peer {} refused connection: {}",
+                            address, io_err
+                        ))
+                    } else if io_err.kind() == std::io::ErrorKind::TimedOut {
+                        KaseederError::NetworkTimeout(format!(
+                            "Connection timeout to {}: {}",
+                            address, io_err
+                        ))
+                    } else {
+                        KaseederError::Io(std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            format!("I/O error connecting to {}: {}", address, e),
+                        ))
+                    }
+                }
+                _ => KaseederError::ConnectionFailed(format!(
+                    "Connection failed to {}: {}",
+                    address, e
+                )),
+            }
+        })?;
 
+        Ok((peer_key, addresses_rx))
+    }
+
+    /// Wait for address response with increased timeout
+    async fn wait_for_addresses_with_timeout(
+        &self,
+        peer_key: PeerKey,
+        addresses_rx: &mut mpsc::Receiver<AddressBatch>,
+    ) -> Result<AddressBatch> {
         tokio::select! {
             result = addresses_rx.recv() => {
                 match result {
-                    Some(addresses) => {
-                        info!("Received {} addresses from peer {}", addresses.len(), peer_key);
-                        Ok(addresses)
+                    Some(batch) => {
+                        info!("Received {} addresses from peer {}", batch.addresses.len(), peer_key);
+                        Ok(batch)
                     }
                     None => {
                         debug!("Address channel closed for peer {}", peer_key);
-                        Ok(Vec::new())
+                        Ok(AddressBatch::default())
                     }
                 }
             }
             _ = tokio::time::sleep(Duration::from_secs(8)) => {   // Reduced to 8 seconds for faster failure
                 debug!("Timeout waiting for addresses from peer {} (8s)", peer_key);
-                Ok(Vec::new())
+                Ok(AddressBatch::default())
             }
         }
     }
 
     /// Get peer node version information
     async fn get_peer_version_info(&self, peer_key: PeerKey) -> Result<VersionMessage> {
+        // Services and network aren't part of the peer properties, so they
+        // are captured from the version message during the handshake
+        let handshake = self
+            .peer_handshakes
+            .remove(&peer_key)
+            .map(|(_, handshake)| handshake)
+            .unwrap_or_default();
+        let services = handshake.services;
+
         let peers = self.adaptor.active_peers();
         let version_message = peers
             .iter()
@@ -418,8 +985,8 @@ impl DnsseedNetAdapter {
             .map(|peer| {
                 let props = peer.properties();
                 VersionMessage {
-                    protocol_version: 7, // Force v7 for active Crescendo nodes (ignore rusty-kaspa default)
-                    services: 0,
+                    protocol_version: handshake.protocol_version,
+                    services,
                     timestamp: unix_now() as i64,
                     address: None,
                     id: Vec::new(),
@@ -430,21 +997,21 @@ impl DnsseedNetAdapter {
                             bytes: <[u8]>::to_vec(id.as_ref()),
                         }
                     }),
-                    network: "".to_string(), // Network name not in properties
+                    network: handshake.network.clone(),
                 }
             })
             .unwrap_or_else(|| {
                 warn!("Could not find peer properties for {}", peer_key);
                 VersionMessage {
-                    protocol_version: 7, // Force v7 for active Crescendo nodes
-                    services: 0,
+                    protocol_version: handshake.protocol_version,
+                    services,
                     timestamp: unix_now() as i64,
                     address: None,
                     id: Vec::new(),
                     user_agent: "unknown".to_string(),
                     disable_relay_tx: false,
                     subnetwork_id: None,
-                    network: "".to_string(),
+                    network: handshake.network,
                 }
             });
 
@@ -505,7 +1072,9 @@ impl DnsseedNetAdapter {
         Ok(())
     }
 
-    /// Diagnostic method to test network connectivity
+    /// Diagnostic method to test network connectivity. A failed connection
+    /// is returned as `ConnectionFailed` carrying the report and a guess at
+    /// the cause.
     pub async fn diagnose_connection(&self, address: &str) -> Result<String> {
         info!("Diagnosing connection to: {}", address);
 
@@ -513,14 +1082,14 @@ impl DnsseedNetAdapter {
         let start_time = std::time::Instant::now();
 
         match self.try_connect_peer(address).await {
-            Ok((peer_key, _, addresses)) => {
+            Ok((peer_key, _, batch)) => {
                 let duration = start_time.elapsed();
                 let result = format!(
                     "✅ Connection successful to {} (key: {}) in {:?}. Received {} addresses.",
                     address,
                     peer_key,
                     duration,
-                    addresses.len()
+                    batch.addresses.len()
                 );
                 info!("{}", result);
 
@@ -550,7 +1119,10 @@ impl DnsseedNetAdapter {
                     _ => "Unknown error type",
                 };
 
-                Ok(format!("{} | Analysis: {}", error_msg, analysis))
+                Err(KaseederError::ConnectionFailed(format!(
+                    "{} | Analysis: {}",
+                    error_msg, analysis
+                )))
             }
         }
     }
@@ -560,7 +1132,556 @@ impl Clone for DnsseedNetAdapter {
     fn clone(&self) -> Self {
         Self {
             adaptor: Arc::clone(&self.adaptor),
-            addresses_rx: Arc::clone(&self.addresses_rx),
+            peer_handshakes: Arc::clone(&self.peer_handshakes),
+            pending_dials: Arc::clone(&self.pending_dials),
+            proxy: self.proxy.clone(),
+            retry_policies: self.retry_policies,
+            protocol_versions: self.protocol_versions.clone(),
+            address_channel_capacity: self.address_channel_capacity,
+        }
+    }
+}
+
+/// In-process kaspa peer for exercising the connection flow in tests
+#[cfg(test)]
+pub(crate) mod mock_peer {
+    use super::*;
+    use kaspa_p2p_lib::pb::AddressesMessage;
+    use std::sync::atomic::{AtomicU16, Ordering};
+
+    /// How the mock peer behaves on an accepted connection
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct MockPeer {
+        /// Never answer the version handshake
+        pub stall: bool,
+        /// Protocol version advertised in the mock's version message
+        pub protocol_version: u32,
+        /// Versions accepted from the dialer, any when empty
+        pub accepted_versions: Vec<u32>,
+        /// Addresses sent in reply to every address request
+        pub addresses: Vec<NetAddress>,
+    }
+
+    #[async_trait]
+    impl ConnectionInitializer for MockPeer {
+        async fn initialize_connection(
+            &self,
+            router: Arc<Router>,
+        ) -> std::result::Result<(), ProtocolError> {
+            if self.stall {
+                std::future::pending::<()>().await;
+            }
+
+            let mut handshake = KaspadHandshake::new(&router);
+            let mut requests = router.subscribe(vec![KaspadMessagePayloadType::RequestAddresses]);
+            router.start();
+
+            let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+            let dialer = handshake
+                .handshake(VersionMessage {
+                    protocol_version: self.protocol_version,
+                    services: 0,
+                    timestamp: unix_now() as i64,
+                    address: None,
+                    id: Vec::from(Uuid::new_v4().as_bytes()),
+                    user_agent: "/kaspad:1.0.0/".to_string(),
+                    disable_relay_tx: true,
+                    subnetwork_id: None,
+                    network: consensus_config.params.network_name().to_string(),
+                })
+                .await?;
+            if !self.accepted_versions.is_empty()
+                && !self.accepted_versions.contains(&dialer.protocol_version)
+            {
+                return Err(ProtocolError::from_reject_message(format!(
+                    "protocol version {} is not supported",
+                    dialer.protocol_version
+                )));
+            }
+            handshake.exchange_ready_messages().await?;
+
+            let address_list: Vec<pb::NetAddress> = self
+                .addresses
+                .iter()
+                .map(|address| pb::NetAddress {
+                    timestamp: 0,
+                    ip: match address.ip {
+                        IpAddr::V4(ip) => ip.octets().to_vec(),
+                        IpAddr::V6(ip) => ip.octets().to_vec(),
+                    },
+                    port: address.port as u32,
+                })
+                .collect();
+            tokio::spawn(async move {
+                while requests.recv().await.is_some() {
+                    let addresses = make_message!(
+                        Payload::Addresses,
+                        AddressesMessage {
+                            address_list: address_list.clone()
+                        }
+                    );
+                    if router.enqueue(addresses).await.is_err() {
+                        break;
+                    }
+                }
+            });
+
+            Ok(())
+        }
+    }
+
+    /// Mock peers get ports from this range, which lies below the kernel's
+    /// ephemeral range, so no socket bound to port 0 or dialing out can take
+    /// one between picking it and the adaptor binding it
+    const FIRST_PORT: u16 = 20000;
+    const PORT_COUNT: u16 = 10000;
+
+    static NEXT_PORT: AtomicU16 = AtomicU16::new(0);
+
+    /// A port in the mock range that is free now. Each process starts at its
+    /// own offset and never hands out the same port twice.
+    fn free_port() -> u16 {
+        loop {
+            let offset = NEXT_PORT
+                .fetch_add(1, Ordering::Relaxed)
+                .wrapping_add(std::process::id() as u16);
+            let port = FIRST_PORT + offset % PORT_COUNT;
+            if std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port)).is_ok() {
+                return port;
+            }
+        }
+    }
+
+    /// Serve `peer` on a free local port, returning its adaptor and address
+    pub(crate) fn serve(peer: MockPeer) -> (Arc<Adaptor>, String) {
+        let address = format!("127.0.0.1:{}", free_port());
+        let adaptor = Adaptor::bidirectional(
+            address.parse().unwrap(),
+            Hub::new(),
+            Arc::new(peer),
+            Default::default(),
+        )
+        .unwrap();
+        (adaptor, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock_peer::{self, MockPeer};
+    use super::*;
+    use crate::manager::AddressManager;
+    use tempfile::TempDir;
+
+    fn entry(ip: &[u8], port: u32) -> pb::NetAddress {
+        pb::NetAddress {
+            timestamp: 0,
+            ip: ip.to_vec(),
+            port,
+        }
+    }
+
+    #[test]
+    fn test_parse_peer_address() {
+        let v4 = parse_peer_address(&[45, 1, 2, 3], 16111).unwrap();
+        assert_eq!(v4.ip, IpAddr::V4(Ipv4Addr::new(45, 1, 2, 3)));
+        assert_eq!(v4.port, 16111);
+
+        // IPv4-mapped IPv6 encodings are normalized to IPv4
+        let mapped = Ipv4Addr::new(45, 1, 2, 3).to_ipv6_mapped().octets();
+        assert_eq!(parse_peer_address(&mapped, 16111).unwrap(), v4);
+
+        let v6: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        assert_eq!(
+            parse_peer_address(&v6.octets(), 16111).unwrap().ip,
+            IpAddr::V6(v6)
+        );
+    }
+
+    #[test]
+    fn test_malformed_addresses_are_rejected_and_peer_flagged() {
+        let batch = decode_addresses(vec![
+            entry(&[45, 1, 2, 3], 16111),
+            entry(&[45, 1, 2], 16111),                  // truncated
+            entry(&[0; 16], 16111),                     // unspecified
+            entry(&[0, 0, 0, 0], 16111),                // unspecified
+            entry(&[255, 255, 255, 255], 16111),        // broadcast
+            entry(&[224, 0, 0, 1], 16111),              // multicast
+            entry(&[45, 1, 2, 4], 0),                   // port 0
+            entry(&[45, 1, 2, 5], u16::MAX as u32 + 1), // port overflow
+        ]);
+
+        assert_eq!(batch.addresses.len(), 1);
+        assert_eq!(batch.rejected, 7);
+
+        let temp_dir = TempDir::new().unwrap();
+        let manager = AddressManager::new(temp_dir.path().to_str().unwrap(), 16111).unwrap();
+        let peer = NetAddress::new(IpAddr::V4(Ipv4Addr::new(45, 9, 9, 9)), 16111);
+        manager.add_addresses(vec![peer.clone()], 16111, false);
+
+        manager.record_malformed_addresses(&peer, batch.rejected, true);
+
+        let node = manager.get_node(&peer).unwrap();
+        assert_eq!(node.malformed_addresses, 7);
+        assert_eq!(node.connection_attempts, 1);
+        assert!(node.last_error.unwrap().contains("malformed"));
+
+        // Without penalizing, the peer is still flagged but its attempts are untouched
+        manager.record_malformed_addresses(&peer, 1, false);
+        let node = manager.get_node(&peer).unwrap();
+        assert_eq!(node.malformed_addresses, 8);
+        assert_eq!(node.connection_attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_timeout_fires_on_stalled_peer() {
+        use tokio::io::AsyncReadExt;
+        use tokio::net::{TcpListener, TcpStream};
+
+        // Mock peer that accepts the TCP connection but never sends its version
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let peer_addr = listener.local_addr().unwrap();
+        let mock_peer = tokio::spawn(async move {
+            let (_stream, _) = listener.accept().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+        let initializer = KaseederConnectionInitializer::new(&consensus_config)
+            .with_handshake_timeout(Duration::from_millis(200));
+
+        let mut stream = TcpStream::connect(peer_addr).await.unwrap();
+        let started = std::time::Instant::now();
+        let deadline = Instant::now() + initializer.settings.handshake_timeout;
+        let result = initializer
+            .before_handshake_deadline(deadline, async {
+                let mut version = [0u8; 1];
+                stream.read_exact(&mut version).await.unwrap();
+                Ok(())
+            })
+            .await;
+
+        assert!(
+            matches!(result, Err(ProtocolError::Timeout(timeout)) if timeout == Duration::from_millis(200))
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        mock_peer.abort();
+    }
+
+    #[test]
+    fn test_address_request_uses_configured_subnetwork() {
+        let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+
+        let initializer = KaseederConnectionInitializer::new(&consensus_config);
+        let request = initializer.request_addresses_message();
+        assert!(!request.include_all_subnetworks);
+        assert_eq!(request.subnetwork_id, None);
+
+        let subnetwork_id =
+            parse_subnetwork_id("0100000000000000000000000000000000000000").unwrap();
+        assert_eq!(subnetwork_id.len(), 20);
+        assert_eq!(subnetwork_id[0], 1);
+
+        let initializer = KaseederConnectionInitializer::new(&consensus_config).with_settings(
+            ConnectionSettings {
+                include_all_subnetworks: true,
+                subnetwork_id: Some(subnetwork_id.clone()),
+                ..Default::default()
+            },
+        );
+        let request = initializer.request_addresses_message();
+        assert!(request.include_all_subnetworks);
+        assert_eq!(
+            request.subnetwork_id,
+            Some(pb::SubnetworkId {
+                bytes: subnetwork_id
+            })
+        );
+
+        assert!(parse_subnetwork_id("01").is_err());
+        assert!(parse_subnetwork_id("zz00000000000000000000000000000000000000").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_address_requests_to_same_peer_are_spaced() {
+        let interval = Duration::from_millis(100);
+        let limiter = AddressRequestLimiter::new(interval);
+        let peer: IpAddr = "45.0.0.1".parse().unwrap();
+        let other_peer: IpAddr = "45.0.0.2".parse().unwrap();
+
+        // The nth request to a peer goes out no earlier than n intervals in
+        let started = Instant::now();
+        for request in 0..3 {
+            limiter.wait_turn(peer).await;
+            assert!(started.elapsed() >= interval * request);
+        }
+
+        // Other peers have their own schedule
+        let started = Instant::now();
+        limiter.wait_turn(other_peer).await;
+        assert!(started.elapsed() < interval);
+
+        // Zero disables spacing
+        let unlimited = AddressRequestLimiter::new(Duration::ZERO);
+        let started = Instant::now();
+        for _ in 0..3 {
+            unlimited.wait_turn(peer).await;
         }
+        assert!(started.elapsed() < interval);
+    }
+
+    #[tokio::test]
+    async fn test_address_channel_applies_backpressure_at_capacity() {
+        let (addresses_tx, mut addresses_rx) = address_channel(2);
+        assert_eq!(addresses_tx.max_capacity(), 2);
+
+        let batch = || AddressBatch {
+            addresses: vec![NetAddress::new("45.0.0.1".parse().unwrap(), 16111)],
+            rejected: 0,
+        };
+        let wait = Duration::from_millis(50);
+        assert!(forward_addresses(&addresses_tx, batch(), wait).await);
+        assert!(forward_addresses(&addresses_tx, batch(), wait).await);
+
+        // The consumer has not caught up, so the next batch is dropped rather than buffered
+        let started = std::time::Instant::now();
+        assert!(!forward_addresses(&addresses_tx, batch(), wait).await);
+        assert!(started.elapsed() >= wait);
+        assert_eq!(addresses_tx.capacity(), 0);
+
+        // Draining one batch makes room again
+        addresses_rx.recv().await.unwrap();
+        assert!(forward_addresses(&addresses_tx, batch(), wait).await);
+        assert_eq!(addresses_rx.len(), 2);
+
+        // A zero capacity is bumped to one instead of panicking
+        let (addresses_tx, _addresses_rx) = address_channel(0);
+        assert_eq!(addresses_tx.max_capacity(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_follow_error_class_policy() {
+        let policies =
+            RetryPolicies::parse(&["refused=2:0".to_string(), "timeout=1:0".to_string()]).unwrap();
+
+        let cases: [(fn() -> KaseederError, u32); 4] = [
+            (|| KaseederError::PeerUnavailable("refused".to_string()), 3),
+            (|| KaseederError::NetworkTimeout("timed out".to_string()), 2),
+            (
+                || KaseederError::ProtocolVersionMismatch("too old".to_string()),
+                1,
+            ),
+            (|| KaseederError::Protocol("bad message".to_string()), 1),
+        ];
+        for (make_error, expected_attempts) in cases {
+            let mut attempts = 0;
+            let result: Result<()> = connect_with_retries(&policies, "45.0.0.1:16111", || {
+                attempts += 1;
+                async move { Err(make_error()) }
+            })
+            .await;
+
+            assert!(matches!(result, Err(KaseederError::ConnectionFailed(_))));
+            assert_eq!(attempts, expected_attempts, "{}", make_error());
+        }
+
+        // A success after a failure ends the retries
+        let mut attempts = 0;
+        let result = connect_with_retries(&policies, "45.0.0.1:16111", || {
+            attempts += 1;
+            let outcome = if attempts == 1 {
+                Err(KaseederError::PeerUnavailable("refused".to_string()))
+            } else {
+                Ok(attempts)
+            };
+            async move { outcome }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_retry_policy_parsing() {
+        assert_eq!(RetryPolicies::parse(&[]).unwrap(), RetryPolicies::default());
+
+        let policies = RetryPolicies::parse(&[
+            "Timeout=3:1000".to_string(),
+            "protocol_mismatch=1".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(policies.timeout.retries, 3);
+        assert_eq!(policies.timeout.backoff, Duration::from_secs(1));
+        assert_eq!(policies.protocol_mismatch.retries, 1);
+        assert_eq!(policies.refused, RetryPolicies::default().refused);
+
+        assert_eq!(policies.timeout.delay(0), None);
+        assert_eq!(policies.timeout.delay(1), Some(Duration::from_secs(1)));
+        assert_eq!(policies.timeout.delay(3), Some(Duration::from_secs(4)));
+        assert_eq!(policies.timeout.delay(4), None);
+
+        for invalid in [
+            "timeout",
+            "reset=1",
+            "timeout=11",
+            "timeout=-1",
+            "refused=1:soon",
+        ] {
+            assert!(
+                RetryPolicies::parse(&[invalid.to_string()]).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_connection_fails_on_closed_port() {
+        // A port that never accepts. The listener stays bound for the whole
+        // test so no other socket can take it.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+        let adapter = DnsseedNetAdapter::new(consensus_config).unwrap();
+        match adapter.diagnose_connection(&address).await {
+            Err(KaseederError::ConnectionFailed(report)) => {
+                assert!(report.contains(&address));
+                assert!(report.contains("Analysis"));
+            }
+            other => panic!("expected a connection failure, got {:?}", other),
+        }
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_records_the_peer_protocol_version() {
+        let sent = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        let (peer, address) = mock_peer::serve(MockPeer {
+            protocol_version: 6,
+            addresses: vec![sent.clone()],
+            ..Default::default()
+        });
+
+        let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+        let adapter = DnsseedNetAdapter::new(consensus_config).unwrap();
+        let (version, batch) = adapter.connect_and_get_addresses(&address).await.unwrap();
+
+        // The version the peer runs, not the one we offered
+        assert!(!DEFAULT_PROTOCOL_VERSIONS.contains(&6));
+        assert_eq!(version.protocol_version, 6);
+        assert_eq!(version.user_agent, "/kaspad:1.0.0/");
+        assert_eq!(batch.addresses, vec![sent]);
+
+        adapter.close().await;
+        peer.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_protocol_version_fallback_dials_a_fresh_connection() {
+        let (peer, address) = mock_peer::serve(MockPeer {
+            protocol_version: 7,
+            accepted_versions: vec![7],
+            ..Default::default()
+        });
+        let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+        let adapter = |protocol_versions: Vec<u32>| {
+            DnsseedNetAdapter::with_settings(
+                consensus_config.clone(),
+                ConnectionSettings {
+                    protocol_versions,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        // The peer drops the connection offering 8, so 7 goes out on a new one
+        let fallback = adapter(vec![8, 7]);
+        let (_, version, _) = fallback.try_connect_peer(&address).await.unwrap();
+        assert_eq!(version.protocol_version, 7);
+        assert!(fallback.pending_dials.is_empty());
+
+        let rejected = adapter(vec![8]);
+        assert!(matches!(
+            rejected.dial_with_version_fallback(&address).await,
+            Err(KaseederError::ProtocolVersionMismatch(_) | KaseederError::Protocol(_))
+        ));
+
+        fallback.close().await;
+        rejected.close().await;
+        peer.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_polls_get_their_own_peer_addresses() {
+        let serve = |ip: &str| {
+            let sent = NetAddress::new(ip.parse().unwrap(), 16111);
+            let (peer, address) = mock_peer::serve(MockPeer {
+                addresses: vec![sent.clone()],
+                ..Default::default()
+            });
+            (peer, address, sent)
+        };
+        let (first_peer, first_address, first_sent) = serve("45.0.0.1");
+        let (second_peer, second_address, second_sent) = serve("46.0.0.1");
+
+        // Both mock peers share the loopback address, so don't space requests
+        let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+        let adapter = DnsseedNetAdapter::with_settings(
+            consensus_config,
+            ConnectionSettings {
+                address_requests: Arc::new(AddressRequestLimiter::new(Duration::ZERO)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let other = adapter.clone();
+        let (first, second) = tokio::join!(
+            adapter.connect_and_get_addresses(&first_address),
+            other.connect_and_get_addresses(&second_address),
+        );
+
+        assert_eq!(first.unwrap().1.addresses, vec![first_sent]);
+        assert_eq!(second.unwrap().1.addresses, vec![second_sent]);
+
+        adapter.close().await;
+        first_peer.close().await;
+        second_peer.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_stalled_handshake_drops_connection_at_deadline() {
+        let (peer, address) = mock_peer::serve(MockPeer {
+            stall: true,
+            ..Default::default()
+        });
+        let handshake_timeout = Duration::from_millis(300);
+        let consensus_config = crate::kaspa_protocol::create_consensus_config(false, 0);
+        let adapter = DnsseedNetAdapter::with_settings(
+            consensus_config,
+            ConnectionSettings {
+                handshake_timeout,
+                protocol_versions: vec![DEFAULT_PROTOCOL_VERSIONS[0]],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // The peer accepts the connection but never sends its version
+        let started = std::time::Instant::now();
+        let result = adapter.dial_with_version_fallback(&address).await;
+        let elapsed = started.elapsed();
+
+        assert!(matches!(
+            result,
+            Err(KaseederError::Protocol(_) | KaseederError::ProtocolVersionMismatch(_))
+        ));
+        assert!(elapsed >= handshake_timeout);
+        assert!(elapsed < handshake_timeout + Duration::from_secs(2));
+        assert!(adapter.adaptor.active_peers().is_empty());
+
+        adapter.close().await;
+        peer.close().await;
     }
 }
@@ -1,10 +1,20 @@
-use crate::errors::Result;
+use crate::crawler::CrawlerPerformanceStats;
+use crate::dns::DnsQueryStats;
+use crate::errors::{KaseederError, Result};
+use crate::manager::AddressManager;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{Request, header};
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
 use std::collections::HashMap;
-use std::net::SocketAddr;
+use std::fmt::Write;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 use sysinfo::{CpuExt, System, SystemExt};
-use tokio::net::TcpListener;
 use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
@@ -13,6 +23,15 @@ pub struct ProfilingServer {
     port: u16,
     stats: Arc<Mutex<ProfilingStats>>,
     is_running: Arc<Mutex<bool>>,
+    metrics: Option<Arc<MetricsSources>>,
+    status_page: bool,
+}
+
+/// Live seeder state rendered by the `/metrics` endpoint
+pub struct MetricsSources {
+    pub address_manager: Arc<AddressManager>,
+    pub crawler_stats: Arc<Mutex<CrawlerPerformanceStats>>,
+    pub dns_stats: Arc<DnsQueryStats>,
 }
 
 /// Performance statistics
@@ -34,10 +53,43 @@ impl ProfilingServer {
             port,
             stats: Arc::new(Mutex::new(ProfilingStats::default())),
             is_running: Arc::new(Mutex::new(false)),
+            metrics: None,
+            status_page: false,
+        }
+    }
+
+    /// Serve Prometheus metrics for the given seeder state on `/metrics`
+    pub fn with_metrics(mut self, metrics: MetricsSources) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Serve a compact plain-text summary on `/status` for `curl` health
+    /// checks and terminal monitoring
+    pub fn with_status_page(mut self, enabled: bool) -> Self {
+        self.status_page = enabled;
+        self
+    }
+
+    /// Build the request router: `/metrics` when metrics are configured,
+    /// `/status` when enabled, the dashboard everywhere else
+    pub fn router(&self) -> Router {
+        let state = ProfilingState {
+            stats: self.stats.clone(),
+            metrics: self.metrics.clone(),
+        };
+
+        let mut router = Router::new().route("/metrics", get(metrics_handler));
+        if self.status_page {
+            router = router.route("/status", get(status_handler));
         }
+        router
+            .fallback(dashboard_handler)
+            .layer(middleware::from_fn_with_state(state.clone(), track_request))
+            .with_state(state)
     }
 
-    /// Start the performance profiling server
+    /// Start the performance profiling server on `127.0.0.1:<port>`
     pub async fn start(&self) -> Result<()> {
         let mut is_running = self.is_running.lock().await;
         if *is_running {
@@ -45,120 +97,378 @@ impl ProfilingServer {
             return Ok(());
         }
 
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, self.port));
+        let server = axum::Server::try_bind(&addr)
+            .map_err(|e| KaseederError::Service(format!("Profiling server error: {}", e)))?;
         *is_running = true;
         drop(is_running);
 
-        let port = self.port;
-        let stats = self.stats.clone();
-        let is_running = self.is_running.clone();
-
-        // Start the performance profiling server
-        tokio::spawn(async move {
-            if let Err(e) = Self::run_server(port, stats, is_running).await {
-                error!("Profiling server error: {}", e);
-            }
-        });
-
-        info!("Profiling server started on port {}", self.port);
-        Ok(())
-    }
-
-    /// Run the performance profiling server
-    async fn run_server(
-        port: u16,
-        stats: Arc<Mutex<ProfilingStats>>,
-        is_running: Arc<Mutex<bool>>,
-    ) -> Result<()> {
-        let addr = format!("0.0.0.0:{}", port).parse::<SocketAddr>()?;
-        let listener = TcpListener::bind(addr).await?;
-
-        info!("Profiling server listening on {}", addr);
-
         // Initialize statistics
-        {
-            let mut stats_guard = stats.lock().await;
-            stats_guard.start_time = Some(Instant::now());
-        }
+        self.stats.lock().await.start_time = Some(Instant::now());
 
         // Start statistics update task
-        let stats_clone = stats.clone();
+        let stats = self.stats.clone();
         tokio::spawn(async move {
-            Self::update_stats_periodically(stats_clone).await;
+            Self::update_stats_periodically(stats).await;
         });
 
-        loop {
-            // Check if should stop
-            {
-                let running = is_running.lock().await;
-                if !*running {
-                    break;
-                }
-            }
-
-            tokio::select! {
-                accept_result = listener.accept() => {
-                    match accept_result {
-                        Ok((socket, addr)) => {
-                            let stats = stats.clone();
-                            tokio::spawn(async move {
-                                if let Err(e) = Self::handle_connection(socket, addr, stats).await {
-                                    error!("Connection handling error: {}", e);
-                                }
-                            });
-                        }
-                        Err(e) => {
-                            error!("Accept error: {}", e);
-                        }
-                    }
-                }
-                _ = tokio::time::sleep(Duration::from_millis(100)) => {
-                    // Periodically check stop signal
+        let router = self.router();
+        let is_running = self.is_running.clone();
+        tokio::spawn(async move {
+            let shutdown = async move {
+                // Periodically check stop signal
+                while *is_running.lock().await {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
                 }
+            };
+            if let Err(e) = server
+                .serve(router.into_make_service())
+                .with_graceful_shutdown(shutdown)
+                .await
+            {
+                error!("Profiling server error: {}", e);
             }
-        }
+            info!("Profiling server stopped");
+        });
 
-        info!("Profiling server stopped");
+        info!("Profiling server listening on {}", addr);
         Ok(())
     }
 
-    /// Handle connection
-    async fn handle_connection(
-        mut socket: tokio::net::TcpStream,
-        addr: SocketAddr,
-        stats: Arc<Mutex<ProfilingStats>>,
-    ) -> Result<()> {
-        // Update active connection count
-        {
-            let mut stats_guard = stats.lock().await;
-            stats_guard.active_connections += 1;
-            stats_guard.request_count += 1;
+    /// Render peer, crawler and DNS counters in Prometheus text format
+    async fn render_metrics(metrics: &MetricsSources) -> String {
+        let summary = metrics.address_manager.get_address_summary();
+        let (successful_polls, failed_polls, addresses_found, poll_time_ms, batch_size) = {
+            let crawler = metrics.crawler_stats.lock().await;
+            (
+                crawler.successful_polls,
+                crawler.failed_polls,
+                crawler.total_addresses_found,
+                crawler.average_poll_time_ms,
+                crawler.last_poll_batch_size,
+            )
+        };
+        let dns = &metrics.dns_stats;
+        let mut queries: Vec<(String, u64)> = dns
+            .queries
+            .iter()
+            .map(|entry| (entry.key().to_string(), *entry.value()))
+            .collect();
+        queries.sort();
+
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, f64)]| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{}{} {}", name, labels, value);
+            }
+        };
+
+        metric(
+            "kaseeder_peers",
+            "gauge",
+            "Known peers by state",
+            &[
+                ("{state=\"good\"}".to_string(), summary.good as f64),
+                ("{state=\"stale\"}".to_string(), summary.stale as f64),
+                ("{state=\"bad\"}".to_string(), summary.bad as f64),
+            ],
+        );
+        metric(
+            "kaseeder_peers_by_family",
+            "gauge",
+            "Known peers by IP family",
+            &[
+                ("{family=\"ipv4\"}".to_string(), summary.ipv4 as f64),
+                ("{family=\"ipv6\"}".to_string(), summary.ipv6 as f64),
+            ],
+        );
+        metric(
+            "kaseeder_crawler_polls_total",
+            "counter",
+            "Peer polls by result",
+            &[
+                ("{result=\"success\"}".to_string(), successful_polls as f64),
+                ("{result=\"failure\"}".to_string(), failed_polls as f64),
+            ],
+        );
+        metric(
+            "kaseeder_crawler_addresses_found_total",
+            "counter",
+            "Addresses received from polled peers",
+            &[(String::new(), addresses_found as f64)],
+        );
+        metric(
+            "kaseeder_crawler_average_poll_time_ms",
+            "gauge",
+            "Average peer poll duration in milliseconds",
+            &[(String::new(), poll_time_ms)],
+        );
+        metric(
+            "kaseeder_crawler_last_poll_batch_size",
+            "gauge",
+            "Number of peers polled in the last batch",
+            &[(String::new(), batch_size as f64)],
+        );
+        metric(
+            "kaseeder_dns_queries_total",
+            "counter",
+            "DNS queries received by record type",
+            &queries
+                .into_iter()
+                .map(|(qtype, count)| (format!("{{qtype=\"{}\"}}", qtype), count as f64))
+                .collect::<Vec<_>>(),
+        );
+        metric(
+            "kaseeder_dns_rate_limited_total",
+            "counter",
+            "DNS queries dropped by the rate limit",
+            &[(
+                String::new(),
+                dns.rate_limited.load(Ordering::Relaxed) as f64,
+            )],
+        );
+        metric(
+            "kaseeder_dns_refused_total",
+            "counter",
+            "DNS queries refused for a disabled record type",
+            &[(String::new(), dns.refused.load(Ordering::Relaxed) as f64)],
+        );
+        metric(
+            "kaseeder_dns_nxdomain_total",
+            "counter",
+            "DNS queries for names outside the zone",
+            &[(String::new(), dns.nxdomain.load(Ordering::Relaxed) as f64)],
+        );
+        metric(
+            "kaseeder_dns_aaaa_placeholders_total",
+            "counter",
+            "AAAA answers that carried only the placeholder address",
+            &[(
+                String::new(),
+                dns.aaaa_placeholders.load(Ordering::Relaxed) as f64,
+            )],
+        );
+        metric(
+            "kaseeder_dns_self_check_divergences_total",
+            "counter",
+            "Self-queries whose answer did not match the address pool",
+            &[(
+                String::new(),
+                dns.self_check_divergences.load(Ordering::Relaxed) as f64,
+            )],
+        );
+        metric(
+            "kaseeder_dns_answer_cache_hits_total",
+            "counter",
+            "Address answers reused from the answer cache",
+            &[(
+                String::new(),
+                dns.answer_cache_hits.load(Ordering::Relaxed) as f64,
+            )],
+        );
+        metric(
+            "kaseeder_dns_answer_cache_misses_total",
+            "counter",
+            "Address answers selected from the address pool",
+            &[(
+                String::new(),
+                dns.answer_cache_misses.load(Ordering::Relaxed) as f64,
+            )],
+        );
+        metric(
+            "kaseeder_dns_drained_total",
+            "counter",
+            "Queries answered with SERVFAIL while draining for maintenance",
+            &[(String::new(), dns.drained.load(Ordering::Relaxed) as f64)],
+        );
+        if let Some(counts) = metrics.address_manager.country_counts() {
+            let samples: Vec<(String, f64)> = counts
+                .iter()
+                .map(|(country, count)| (format!("{{country=\"{}\"}}", country), *count as f64))
+                .collect();
+            metric(
+                "kaseeder_good_peers_by_country",
+                "gauge",
+                "Good peers per country",
+                &samples,
+            );
         }
-
-        // Simple HTTP response
-        let response = Self::generate_profiling_response(&stats).await;
-
-        if let Err(e) = tokio::io::AsyncWriteExt::write_all(&mut socket, response.as_bytes()).await
-        {
-            error!("Failed to write response to {}: {}", addr, e);
-
-            // Update error count
-            let mut stats_guard = stats.lock().await;
-            stats_guard.error_count += 1;
+        if let Some(counts) = metrics.address_manager.source_counts() {
+            let samples: Vec<(String, f64)> = counts
+                .iter()
+                .map(|(source, count)| (format!("{{source=\"{}\"}}", source), *count as f64))
+                .collect();
+            metric(
+                "kaseeder_good_peers_by_source",
+                "gauge",
+                "Good peers per discovery source",
+                &samples,
+            );
         }
+        if let Some(report) = metrics.address_manager.discovery_bias_report() {
+            let samples: Vec<(String, f64)> = report
+                .by_region
+                .iter()
+                .flat_map(|(region, reachability)| {
+                    [
+                        (
+                            format!("{{region=\"{}\",reachable=\"true\"}}", region),
+                            reachability.reachable as f64,
+                        ),
+                        (
+                            format!("{{region=\"{}\",reachable=\"false\"}}", region),
+                            reachability.unreachable as f64,
+                        ),
+                    ]
+                })
+                .collect();
+            metric(
+                "kaseeder_tried_peers_by_region",
+                "gauge",
+                "Tried peers per region by whether they were reached",
+                &samples,
+            );
+        }
+        metric(
+            "kaseeder_dns_degraded_answers_total",
+            "counter",
+            "Answers padded with stale peers because the good pool was small",
+            &[(
+                String::new(),
+                metrics.address_manager.get_stats().degraded_answers() as f64,
+            )],
+        );
 
-        // Update active connection count
-        {
-            let mut stats_guard = stats.lock().await;
-            stats_guard.active_connections = stats_guard.active_connections.saturating_sub(1);
+        out
+    }
+
+    /// Render the plain-text status page, one `key: value` per line
+    async fn render_status(
+        stats: &Arc<Mutex<ProfilingStats>>,
+        metrics: Option<&MetricsSources>,
+    ) -> String {
+        let uptime = stats.lock().await.start_time.map(|start| start.elapsed());
+
+        let mut out = String::new();
+        let _ = writeln!(out, "kaseeder {}", env!("CARGO_PKG_VERSION"));
+        match uptime {
+            Some(uptime) => {
+                let _ = writeln!(out, "uptime: {}s", uptime.as_secs());
+            }
+            None => {
+                let _ = writeln!(out, "uptime: unknown");
+            }
         }
 
-        Ok(())
+        let Some(metrics) = metrics else {
+            let _ = writeln!(out, "health: unknown");
+            return out;
+        };
+
+        let summary = metrics.address_manager.get_address_summary();
+        let (successful_polls, failed_polls) = {
+            let crawler = metrics.crawler_stats.lock().await;
+            (crawler.successful_polls, crawler.failed_polls)
+        };
+        let polls = successful_polls + failed_polls;
+        let polls_per_minute = uptime
+            .filter(|uptime| !uptime.is_zero())
+            .map(|uptime| polls as f64 * 60.0 / uptime.as_secs_f64())
+            .unwrap_or(0.0);
+        let success_rate = if polls > 0 {
+            successful_polls as f64 / polls as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let _ = writeln!(
+            out,
+            "peers: {} good / {} total",
+            summary.good, summary.nodes
+        );
+        let _ = writeln!(
+            out,
+            "crawler: {:.1} polls/min, {} ok / {} failed ({:.1}% success)",
+            polls_per_minute, successful_polls, failed_polls, success_rate
+        );
+        let _ = writeln!(
+            out,
+            "health: {}",
+            if summary.good > 0 {
+                "ok"
+            } else {
+                "degraded (no good peers)"
+            }
+        );
+        out
     }
 
-    /// Generate performance profiling response
-    async fn generate_profiling_response(stats: &Arc<Mutex<ProfilingStats>>) -> String {
+    /// Render the HTML dashboard
+    async fn render_dashboard(
+        stats: &Arc<Mutex<ProfilingStats>>,
+        metrics: Option<&MetricsSources>,
+    ) -> String {
         let stats_guard = stats.lock().await;
 
+        // Geographic spread of the good peers, when exposed
+        let countries = metrics
+            .and_then(|metrics| metrics.address_manager.country_counts())
+            .map(|counts| {
+                let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+                counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                let counts: Vec<String> = counts
+                    .iter()
+                    .map(|(country, count)| format!("{}: {}", country, count))
+                    .collect();
+                format!(
+                    r#"
+    <div class="metric">
+        <h3>Good Peers by Country</h3>
+        <div class="value">{}</div>
+        <div class="description">Geographic spread of the peers served in DNS answers</div>
+    </div>
+    "#,
+                    if counts.is_empty() {
+                        "none".to_string()
+                    } else {
+                        counts.join(", ")
+                    }
+                )
+            })
+            .unwrap_or_default();
+
+        // Where the good peers were discovered, when exposed
+        let sources = metrics
+            .and_then(|metrics| metrics.address_manager.source_counts())
+            .map(|counts| {
+                let total: usize = counts.values().sum();
+                let counts: Vec<String> = counts
+                    .iter()
+                    .rev()
+                    .map(|(source, count)| {
+                        let share = if total > 0 {
+                            *count as f64 / total as f64 * 100.0
+                        } else {
+                            0.0
+                        };
+                        format!("{}: {} ({:.0}%)", source, count, share)
+                    })
+                    .collect();
+                format!(
+                    r#"
+    <div class="metric">
+        <h3>Good Peers by Source</h3>
+        <div class="value">{}</div>
+        <div class="description">How the good peers were discovered; a pool dominated by seeder peers suggests weak gossip discovery</div>
+    </div>
+    "#,
+                    counts.join(", ")
+                )
+            })
+            .unwrap_or_default();
+
         let uptime = stats_guard
             .start_time
             .map(|start| {
@@ -167,7 +477,7 @@ impl ProfilingServer {
             })
             .unwrap_or_else(|| "unknown".to_string());
 
-        let html = format!(
+        format!(
             r#"
 <!DOCTYPE html>
 <html>
@@ -219,7 +529,8 @@ impl ProfilingServer {
         <div class="value">{}</div>
         <div class="description">Number of custom metrics tracked</div>
     </div>
-    
+    {}
+    {}
     <p><em>Last updated: {}</em></p>
 </body>
 </html>
@@ -234,13 +545,9 @@ impl ProfilingServer {
                 0.0
             },
             stats_guard.custom_metrics.len(),
+            countries,
+            sources,
             chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
-        );
-
-        format!(
-            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
-            html.len(),
-            html
         )
     }
 
@@ -285,12 +592,69 @@ impl ProfilingServer {
     }
 }
 
+/// Shared state of the profiling routes
+#[derive(Clone)]
+struct ProfilingState {
+    stats: Arc<Mutex<ProfilingStats>>,
+    metrics: Option<Arc<MetricsSources>>,
+}
+
+/// Count requests, in-flight requests and server errors
+async fn track_request<B>(
+    State(state): State<ProfilingState>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    {
+        let mut stats = state.stats.lock().await;
+        stats.active_connections += 1;
+        stats.request_count += 1;
+    }
+
+    let response = next.run(request).await;
+
+    let mut stats = state.stats.lock().await;
+    stats.active_connections = stats.active_connections.saturating_sub(1);
+    if response.status().is_server_error() {
+        stats.error_count += 1;
+    }
+    response
+}
+
+/// `GET /metrics`
+async fn metrics_handler(State(state): State<ProfilingState>) -> Response {
+    match state.metrics {
+        Some(ref metrics) => (
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            ProfilingServer::render_metrics(metrics).await,
+        )
+            .into_response(),
+        None => dashboard_handler(State(state)).await.into_response(),
+    }
+}
+
+/// `GET /status`
+async fn status_handler(State(state): State<ProfilingState>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        ProfilingServer::render_status(&state.stats, state.metrics.as_deref()).await,
+    )
+        .into_response()
+}
+
+/// Dashboard, served for every path but `/metrics` and `/status`
+async fn dashboard_handler(State(state): State<ProfilingState>) -> Html<String> {
+    Html(ProfilingServer::render_dashboard(&state.stats, state.metrics.as_deref()).await)
+}
+
 impl Clone for ProfilingServer {
     fn clone(&self) -> Self {
         Self {
             port: self.port,
             stats: self.stats.clone(),
             is_running: self.is_running.clone(),
+            metrics: self.metrics.clone(),
+            status_page: self.status_page,
         }
     }
 }
@@ -313,6 +677,28 @@ impl ProfilingStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::body::{Body, HttpBody};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tower::ServiceExt;
+
+    /// Content type and body of a request routed through the server
+    async fn get(server: &ProfilingServer, uri: &str) -> (String, String) {
+        let response = server
+            .router()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let mut body = response.into_body();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.data().await {
+            bytes.extend_from_slice(&chunk.unwrap());
+        }
+        (content_type, String::from_utf8(bytes).unwrap())
+    }
 
     #[tokio::test]
     async fn test_profiling_server_creation() {
@@ -330,4 +716,136 @@ mod tests {
         let stats = server.get_stats().await;
         assert_eq!(stats.custom_metrics.get("test_metric"), Some(&42.0));
     }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_renders_prometheus_text() {
+        use crate::types::NetAddress;
+        use tempfile::TempDir;
+        use trust_dns_proto::rr::RecordType;
+
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        let good = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        address_manager.add_addresses(
+            vec![
+                good.clone(),
+                NetAddress::new("2001:4860::1".parse().unwrap(), 16111),
+            ],
+            16111,
+            false,
+        );
+        address_manager.good(&good, None, None);
+
+        let crawler_stats = Arc::new(Mutex::new(CrawlerPerformanceStats {
+            successful_polls: 7,
+            failed_polls: 3,
+            ..Default::default()
+        }));
+        let dns_stats = Arc::new(DnsQueryStats::default());
+        dns_stats.queries.insert(RecordType::A, 12);
+        dns_stats.refused.store(2, Ordering::Relaxed);
+
+        let server = ProfilingServer::new(0).with_metrics(MetricsSources {
+            address_manager,
+            crawler_stats,
+            dns_stats,
+        });
+        let body = ProfilingServer::render_metrics(server.metrics.as_ref().unwrap()).await;
+
+        assert!(body.contains("# TYPE kaseeder_peers gauge\n"));
+        assert!(body.contains("kaseeder_peers{state=\"good\"} 1\n"));
+        assert!(body.contains("kaseeder_peers_by_family{family=\"ipv4\"} 1\n"));
+        assert!(body.contains("kaseeder_peers_by_family{family=\"ipv6\"} 1\n"));
+        assert!(body.contains("kaseeder_crawler_polls_total{result=\"success\"} 7\n"));
+        assert!(body.contains("kaseeder_crawler_polls_total{result=\"failure\"} 3\n"));
+        assert!(body.contains("kaseeder_dns_queries_total{qtype=\"A\"} 12\n"));
+        assert!(body.contains("kaseeder_dns_refused_total 2\n"));
+
+        // Served over HTTP, next to the dashboard
+        let (content_type, body) = get(&server, "/metrics?x=1").await;
+        assert_eq!(content_type, "text/plain; version=0.0.4");
+        assert!(body.contains("kaseeder_peers{state=\"good\"} 1\n"));
+        let (content_type, body) = get(&server, "/").await;
+        assert_eq!(content_type, "text/html; charset=utf-8");
+        assert!(body.contains("kaseeder Performance Metrics"));
+        assert_eq!(server.get_stats().await.request_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_start_serves_dashboard_on_localhost() {
+        // Reserve a free port, then hand it to the server
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = ProfilingServer::new(port);
+        server.start().await.unwrap();
+
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("kaseeder Performance Metrics"));
+
+        server.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_status_page_is_compact_plain_text() {
+        use crate::types::NetAddress;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let address_manager = Arc::new(AddressManager::new(&app_dir, 16111).unwrap());
+        let good = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        address_manager.add_addresses(
+            vec![
+                good.clone(),
+                NetAddress::new("45.0.0.2".parse().unwrap(), 16111),
+            ],
+            16111,
+            false,
+        );
+        address_manager.good(&good, None, None);
+
+        let server = ProfilingServer::new(0)
+            .with_metrics(MetricsSources {
+                address_manager,
+                crawler_stats: Arc::new(Mutex::new(CrawlerPerformanceStats {
+                    successful_polls: 3,
+                    failed_polls: 1,
+                    ..Default::default()
+                })),
+                dns_stats: Arc::new(DnsQueryStats::default()),
+            })
+            .with_status_page(true);
+        server.stats.lock().await.start_time = Some(Instant::now() - Duration::from_secs(120));
+
+        let (content_type, body) = get(&server, "/status").await;
+        assert_eq!(content_type, "text/plain; charset=utf-8");
+        assert!(!body.contains('<'));
+        assert!(body.starts_with("kaseeder "));
+        assert!(body.contains("\nuptime: 120s\n"), "{}", body);
+        assert!(body.contains("\npeers: 1 good / 2 total\n"), "{}", body);
+        assert!(
+            body.contains("\ncrawler: 2.0 polls/min, 3 ok / 1 failed (75.0% success)\n"),
+            "{}",
+            body
+        );
+        assert!(body.ends_with("health: ok\n"), "{}", body);
+
+        // Without the option the dashboard keeps answering every path
+        let server = ProfilingServer::new(0);
+        let (content_type, _) = get(&server, "/status").await;
+        assert_eq!(content_type, "text/html; charset=utf-8");
+    }
 }
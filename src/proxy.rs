@@ -0,0 +1,355 @@
+use crate::errors::{KaseederError, Result};
+use dashmap::DashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::debug;
+
+const SOCKS_VERSION: u8 = 0x05;
+const AUTH_NONE: u8 = 0x00;
+const CMD_CONNECT: u8 = 0x01;
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+const REPLY_SUCCEEDED: u8 = 0x00;
+const REPLY_CONNECTION_REFUSED: u8 = 0x05;
+const REPLY_TTL_EXPIRED: u8 = 0x06;
+
+/// How long a tunnel waits for the P2P client to connect to it
+const TUNNEL_ACCEPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// SOCKS5 proxy that outbound peer connections are routed through.
+///
+/// The kaspa P2P adaptor dials peers itself, so each proxied connection goes
+/// through a one-shot loopback tunnel: the adaptor connects to the tunnel and
+/// the tunnel relays the stream to the peer over the proxy.
+#[derive(Debug)]
+pub struct Socks5Proxy {
+    proxy_addr: String,
+    /// Peer each open tunnel relays to, keyed by the tunnel's local address
+    tunnels: DashMap<SocketAddr, SocketAddr>,
+}
+
+impl Socks5Proxy {
+    /// Parse a `socks5://host:port` proxy URL
+    pub fn parse(url: &str) -> std::result::Result<Self, String> {
+        let proxy_addr = url
+            .strip_prefix("socks5://")
+            .or_else(|| url.strip_prefix("socks5h://"))
+            .ok_or_else(|| format!("proxy must be a socks5://host:port URL, got '{}'", url))?
+            .trim_end_matches('/');
+
+        match proxy_addr.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {}
+            _ => return Err(format!("proxy must include a host and port, got '{}'", url)),
+        }
+
+        Ok(Self {
+            proxy_addr: proxy_addr.to_string(),
+            tunnels: DashMap::new(),
+        })
+    }
+
+    /// Open a TCP connection to the target through the proxy
+    pub async fn connect(&self, target: SocketAddr) -> std::io::Result<TcpStream> {
+        let mut stream = TcpStream::connect(&self.proxy_addr).await?;
+
+        // Greeting: offer only the no-authentication method
+        stream.write_all(&[SOCKS_VERSION, 1, AUTH_NONE]).await?;
+        let mut reply = [0u8; 2];
+        stream.read_exact(&mut reply).await?;
+        if reply != [SOCKS_VERSION, AUTH_NONE] {
+            return Err(proxy_error(
+                "proxy requires an unsupported authentication method",
+            ));
+        }
+
+        // CONNECT request
+        let mut request = vec![SOCKS_VERSION, CMD_CONNECT, 0x00];
+        match target.ip() {
+            IpAddr::V4(ip) => {
+                request.push(ATYP_IPV4);
+                request.extend_from_slice(&ip.octets());
+            }
+            IpAddr::V6(ip) => {
+                request.push(ATYP_IPV6);
+                request.extend_from_slice(&ip.octets());
+            }
+        }
+        request.extend_from_slice(&target.port().to_be_bytes());
+        stream.write_all(&request).await?;
+
+        let mut header = [0u8; 4];
+        stream.read_exact(&mut header).await?;
+        if header[0] != SOCKS_VERSION {
+            return Err(proxy_error("proxy replied with an invalid SOCKS version"));
+        }
+        if header[1] != REPLY_SUCCEEDED {
+            let message = format!(
+                "proxy could not connect to {} (reply code {})",
+                target, header[1]
+            );
+            return Err(match header[1] {
+                REPLY_CONNECTION_REFUSED => {
+                    std::io::Error::new(std::io::ErrorKind::ConnectionRefused, message)
+                }
+                REPLY_TTL_EXPIRED => std::io::Error::new(std::io::ErrorKind::TimedOut, message),
+                _ => proxy_error(&message),
+            });
+        }
+
+        // Skip the bound address, which we don't need
+        let bound_len = match header[3] {
+            ATYP_IPV4 => 4,
+            ATYP_IPV6 => 16,
+            ATYP_DOMAIN => stream.read_u8().await? as usize,
+            _ => return Err(proxy_error("proxy replied with an invalid address type")),
+        };
+        let mut bound = vec![0u8; bound_len + 2];
+        stream.read_exact(&mut bound).await?;
+
+        Ok(stream)
+    }
+
+    /// Connect to the target through the proxy, then open a loopback tunnel
+    /// to that connection and return the `ip:port` address to dial instead of
+    /// the target. The tunnel serves a single connection.
+    ///
+    /// The proxy connection and the SOCKS5 handshake must finish within
+    /// `connect_timeout`, and their failures are reported like a direct dial's
+    /// would be: a refusal as `PeerUnavailable` and a timeout as
+    /// `NetworkTimeout`.
+    pub async fn open_tunnel(
+        self: &Arc<Self>,
+        target: &str,
+        connect_timeout: Duration,
+    ) -> Result<String> {
+        let target = parse_target(target).ok_or_else(|| {
+            KaseederError::InvalidAddress(format!("Invalid peer address: {}", target))
+        })?;
+
+        let outbound = match tokio::time::timeout(connect_timeout, self.connect(target)).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => return Err(connect_error(target, e)),
+            Err(_) => {
+                return Err(KaseederError::NetworkTimeout(format!(
+                    "Connection timeout to {} through proxy {}",
+                    target, self.proxy_addr
+                )));
+            }
+        };
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let local_addr = listener.local_addr()?;
+        self.tunnels.insert(local_addr, target);
+
+        let proxy = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = relay(listener, outbound).await {
+                debug!("Proxy tunnel to {} closed: {}", target, e);
+            }
+            proxy.tunnels.remove(&local_addr);
+        });
+
+        Ok(local_addr.to_string())
+    }
+
+    /// Peer an open tunnel relays to
+    pub fn tunnel_target(&self, local_addr: &SocketAddr) -> Option<SocketAddr> {
+        self.tunnels.get(local_addr).map(|target| *target)
+    }
+}
+
+/// Accept the adaptor's connection and relay it over the proxied connection
+async fn relay(listener: TcpListener, mut outbound: TcpStream) -> std::io::Result<()> {
+    let (mut inbound, _) = tokio::time::timeout(TUNNEL_ACCEPT_TIMEOUT, listener.accept())
+        .await
+        .map_err(|_| proxy_error("no connection to the tunnel"))??;
+    drop(listener);
+
+    tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await?;
+    Ok(())
+}
+
+/// Parse an `ip:port` peer address, with or without brackets around IPv6 IPs
+fn parse_target(address: &str) -> Option<SocketAddr> {
+    if let Ok(target) = address.parse() {
+        return Some(target);
+    }
+    let (ip, port) = address.rsplit_once(':')?;
+    let ip: IpAddr = ip
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()?;
+    Some(SocketAddr::new(ip, port.parse().ok()?))
+}
+
+/// Classify a failed proxied connection the way a direct dial's is
+fn connect_error(target: SocketAddr, error: std::io::Error) -> KaseederError {
+    match error.kind() {
+        std::io::ErrorKind::ConnectionRefused => {
+            KaseederError::PeerUnavailable(format!("Peer {} refused connection: {}", target, error))
+        }
+        std::io::ErrorKind::TimedOut => {
+            KaseederError::NetworkTimeout(format!("Connection timeout to {}: {}", target, error))
+        }
+        _ => KaseederError::ConnectionFailed(format!(
+            "Connection failed to {} through proxy: {}",
+            target, error
+        )),
+    }
+}
+
+fn proxy_error(message: &str) -> std::io::Error {
+    std::io::Error::other(format!("SOCKS5: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal no-auth SOCKS5 server that records the requested target and
+    /// relays to a fixed upstream
+    async fn mock_socks5_server(
+        upstream: SocketAddr,
+    ) -> (SocketAddr, tokio::task::JoinHandle<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+
+            let mut greeting = [0u8; 3];
+            client.read_exact(&mut greeting).await.unwrap();
+            client.write_all(&[SOCKS_VERSION, AUTH_NONE]).await.unwrap();
+
+            let mut request = [0u8; 4 + 4 + 2];
+            client.read_exact(&mut request).await.unwrap();
+            // Succeeded, bound to 0.0.0.0:0
+            let mut reply = vec![SOCKS_VERSION, REPLY_SUCCEEDED, 0, ATYP_IPV4];
+            reply.extend_from_slice(&[0; 6]);
+            client.write_all(&reply).await.unwrap();
+
+            let mut upstream = TcpStream::connect(upstream).await.unwrap();
+            let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+            request.to_vec()
+        });
+
+        (addr, handle)
+    }
+
+    #[tokio::test]
+    async fn test_tunnel_relays_through_socks5_proxy() {
+        // Echo server standing in for the peer
+        let peer = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let peer_addr = peer.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = peer.accept().await.unwrap();
+            let mut buf = [0u8; 4];
+            stream.read_exact(&mut buf).await.unwrap();
+            stream.write_all(&buf).await.unwrap();
+        });
+
+        let (proxy_addr, proxy_handle) = mock_socks5_server(peer_addr).await;
+        let proxy = Arc::new(Socks5Proxy::parse(&format!("socks5://{}", proxy_addr)).unwrap());
+
+        let tunnel = proxy
+            .open_tunnel("45.0.0.1:16111", Duration::from_secs(5))
+            .await
+            .unwrap();
+        let tunnel_addr: SocketAddr = tunnel.parse().unwrap();
+        assert_eq!(
+            proxy.tunnel_target(&tunnel_addr),
+            Some("45.0.0.1:16111".parse().unwrap())
+        );
+
+        let mut stream = TcpStream::connect(tunnel_addr).await.unwrap();
+        stream.write_all(b"ping").await.unwrap();
+        let mut buf = [0u8; 4];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+        drop(stream);
+
+        // The proxy was asked to CONNECT to the peer, not the tunnel
+        let request = proxy_handle.await.unwrap();
+        assert_eq!(
+            request,
+            vec![
+                SOCKS_VERSION,
+                CMD_CONNECT,
+                0,
+                ATYP_IPV4,
+                45,
+                0,
+                0,
+                1,
+                0x3e,
+                0xef
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tunnel_reports_target_refused_by_proxy() {
+        // Proxy that answers every CONNECT with "connection refused"
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            client.read_exact(&mut greeting).await.unwrap();
+            client.write_all(&[SOCKS_VERSION, AUTH_NONE]).await.unwrap();
+            let mut request = [0u8; 4 + 4 + 2];
+            client.read_exact(&mut request).await.unwrap();
+            let mut reply = vec![SOCKS_VERSION, REPLY_CONNECTION_REFUSED, 0, ATYP_IPV4];
+            reply.extend_from_slice(&[0; 6]);
+            client.write_all(&reply).await.unwrap();
+        });
+
+        let proxy = Arc::new(Socks5Proxy::parse(&format!("socks5://{}", proxy_addr)).unwrap());
+        let result = proxy
+            .open_tunnel("45.0.0.1:16111", Duration::from_secs(5))
+            .await;
+        assert!(
+            matches!(result, Err(KaseederError::PeerUnavailable(_))),
+            "{:?}",
+            result
+        );
+        assert!(proxy.tunnels.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tunnel_times_out_on_stalled_proxy() {
+        // Bound but never accepted, so the greeting is never answered
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+
+        let proxy = Arc::new(Socks5Proxy::parse(&format!("socks5://{}", proxy_addr)).unwrap());
+        let result = proxy
+            .open_tunnel("45.0.0.1:16111", Duration::from_millis(200))
+            .await;
+        assert!(
+            matches!(result, Err(KaseederError::NetworkTimeout(_))),
+            "{:?}",
+            result
+        );
+        assert!(proxy.tunnels.is_empty());
+        drop(listener);
+    }
+
+    #[test]
+    fn test_proxy_url_parsing() {
+        assert!(Socks5Proxy::parse("socks5://127.0.0.1:9050").is_ok());
+        assert!(Socks5Proxy::parse("socks5h://proxy.internal:1080/").is_ok());
+        assert!(Socks5Proxy::parse("http://127.0.0.1:9050").is_err());
+        assert!(Socks5Proxy::parse("socks5://127.0.0.1").is_err());
+
+        assert_eq!(
+            parse_target("2001:4860::1:16111"),
+            Some("[2001:4860::1]:16111".parse().unwrap())
+        );
+    }
+}
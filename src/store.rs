@@ -0,0 +1,564 @@
+use crate::errors::{KaseederError, Result};
+use crate::manager::{Node, PeerSource};
+use crate::types::NetAddress;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::error;
+
+const PEERS_FILENAME: &str = "peers.json";
+const PEERS_DB_FILENAME: &str = "peers.sqlite";
+
+/// Where the address manager persists its nodes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// `peers.json`, rewritten in full on every dump
+    #[default]
+    Json,
+    /// `peers.sqlite`, updated incrementally as nodes change
+    Sqlite,
+}
+
+impl StorageBackend {
+    /// Name used in the configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageBackend::Json => "json",
+            StorageBackend::Sqlite => "sqlite",
+        }
+    }
+
+    /// Open the store for this backend in the application directory
+    pub fn open(&self, app_dir: &str) -> Result<Box<dyn PeerStore>> {
+        match self {
+            StorageBackend::Json => Ok(Box::new(JsonPeerStore::new(
+                Path::new(app_dir).join(PEERS_FILENAME),
+            ))),
+            StorageBackend::Sqlite => Ok(Box::new(SqlitePeerStore::open(
+                Path::new(app_dir).join(PEERS_DB_FILENAME),
+            )?)),
+        }
+    }
+}
+
+impl fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        [StorageBackend::Json, StorageBackend::Sqlite]
+            .into_iter()
+            .find(|backend| backend.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("unknown storage backend '{}'", s))
+    }
+}
+
+/// One peer in the portable export format, stored one JSON object per line
+/// so files from different seeders can be concatenated or filtered with
+/// line-oriented tools
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    pub ip: IpAddr,
+    pub port: u16,
+    /// Unix seconds of the last successful connection, 0 if never reached
+    pub last_success: u64,
+    pub user_agent: Option<String>,
+    /// Where the exporting seeder learned the peer
+    #[serde(default)]
+    pub source: PeerSource,
+    /// Unix seconds the peer was last seen and last tried, 0 if not exported
+    #[serde(default)]
+    pub last_seen: u64,
+    #[serde(default)]
+    pub last_attempt: u64,
+    /// Connection history, which places the peer in its serving tier
+    #[serde(default)]
+    pub connection_attempts: u32,
+    #[serde(default)]
+    pub successful_connections: u32,
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    #[serde(default)]
+    pub subnetwork_id: Option<String>,
+    #[serde(default)]
+    pub services: u64,
+    #[serde(default)]
+    pub protocol_version: u32,
+}
+
+impl From<&Node> for PeerRecord {
+    fn from(node: &Node) -> Self {
+        Self {
+            ip: node.address.ip,
+            port: node.address.port,
+            last_success: unix_secs(node.last_success),
+            user_agent: node.user_agent.clone(),
+            source: node.source,
+            last_seen: unix_secs(node.last_seen),
+            last_attempt: unix_secs(node.last_attempt),
+            connection_attempts: node.connection_attempts,
+            successful_connections: node.successful_connections,
+            consecutive_failures: node.consecutive_failures,
+            subnetwork_id: node.subnetwork_id.clone(),
+            services: node.services,
+            protocol_version: node.protocol_version,
+        }
+    }
+}
+
+/// The node as exported. Timestamps missing from older exports are left as
+/// a new node has them.
+impl From<&PeerRecord> for Node {
+    fn from(record: &PeerRecord) -> Self {
+        let mut node = Node::new(NetAddress::new(record.ip, record.port));
+        node.last_success = UNIX_EPOCH + Duration::from_secs(record.last_success);
+        if record.last_seen > 0 {
+            node.last_seen = UNIX_EPOCH + Duration::from_secs(record.last_seen);
+        }
+        if record.last_attempt > 0 {
+            node.last_attempt = UNIX_EPOCH + Duration::from_secs(record.last_attempt);
+        }
+        node.user_agent = record.user_agent.clone();
+        node.source = record.source;
+        node.connection_attempts = record.connection_attempts;
+        node.successful_connections = record.successful_connections;
+        node.consecutive_failures = record.consecutive_failures;
+        node.subnetwork_id = record.subnetwork_id.clone();
+        node.services = record.services;
+        node.protocol_version = record.protocol_version;
+        node
+    }
+}
+
+/// Unix seconds of `time`, 0 for times before the epoch
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Write peer records as JSON lines
+pub fn write_peer_records(path: &Path, records: &[PeerRecord]) -> Result<()> {
+    let mut content = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut content, record)?;
+        content.write_all(b"\n")?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Read peer records written by [`write_peer_records`], rejecting the whole
+/// file if any line is malformed
+pub fn read_peer_records(path: &Path) -> Result<Vec<PeerRecord>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut records = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let record: PeerRecord = serde_json::from_str(line).map_err(|e| {
+            KaseederError::Validation(format!(
+                "{} line {}: invalid peer record: {}",
+                path.display(),
+                i + 1,
+                e
+            ))
+        })?;
+        if record.port == 0 {
+            return Err(KaseederError::Validation(format!(
+                "{} line {}: invalid peer record: port 0",
+                path.display(),
+                i + 1
+            )));
+        }
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Persistence for the address manager's nodes, keyed by `ip:port`
+pub trait PeerStore: Send + Sync {
+    /// All stored nodes
+    fn load(&self) -> Result<Vec<(String, Node)>>;
+
+    /// Persist the full node set, replacing what was stored
+    fn save_all(&self, nodes: &[(String, Node)]) -> Result<()>;
+
+    /// Whether changed nodes are written as they change rather than only on
+    /// [`save_all`](PeerStore::save_all)
+    fn is_incremental(&self) -> bool {
+        false
+    }
+
+    /// Insert or update the given nodes
+    fn upsert(&self, _nodes: &[(String, Node)]) -> Result<()> {
+        Ok(())
+    }
+
+    /// Delete the nodes with the given keys
+    fn remove(&self, _keys: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    /// Wait for earlier [`upsert`](PeerStore::upsert) and
+    /// [`remove`](PeerStore::remove) calls to reach the store, reporting the
+    /// first of them that failed
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Location of the store, for logging
+    fn location(&self) -> &Path;
+}
+
+/// Stores nodes in a JSON file, written atomically through a temporary file
+pub struct JsonPeerStore {
+    path: PathBuf,
+}
+
+impl JsonPeerStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl PeerStore for JsonPeerStore {
+    fn load(&self) -> Result<Vec<(String, Node)>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn save_all(&self, nodes: &[(String, Node)]) -> Result<()> {
+        // Ensure the directory exists before writing files
+        if let Some(parent_dir) = self.path.parent()
+            && let Err(e) = std::fs::create_dir_all(parent_dir)
+        {
+            error!("Failed to create directory {}: {}", parent_dir.display(), e);
+            return Err(KaseederError::Io(e));
+        }
+
+        let serialized_nodes = serde_json::to_string(nodes).map_err(|e| {
+            KaseederError::Serialization(format!("Failed to serialize nodes: {}", e))
+        })?;
+
+        let tmp_file = PathBuf::from(format!("{}.new", self.path.display()));
+        if let Err(e) = std::fs::write(&tmp_file, serialized_nodes) {
+            error!(
+                "Failed to write temporary file {}: {}",
+                tmp_file.display(),
+                e
+            );
+            return Err(KaseederError::Io(e));
+        }
+
+        // Atomically rename file
+        if let Err(e) = std::fs::rename(&tmp_file, &self.path) {
+            error!(
+                "Failed to rename {} to {}: {}",
+                tmp_file.display(),
+                self.path.display(),
+                e
+            );
+            // Try to clean up temporary file, but don't fail if cleanup fails
+            if let Err(cleanup_e) = std::fs::remove_file(&tmp_file) {
+                error!(
+                    "Failed to remove temporary file {}: {}",
+                    tmp_file.display(),
+                    cleanup_e
+                );
+            }
+            return Err(KaseederError::Io(e));
+        }
+
+        Ok(())
+    }
+
+    fn location(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Commands the SQLite writer thread may have queued before callers block
+const SQLITE_QUEUE_CAPACITY: usize = 256;
+
+/// Stores nodes in a SQLite database, one row per node, so changes are
+/// written as they happen instead of rewriting the whole peer set. The
+/// connection lives on its own thread, so incremental updates are only
+/// queued instead of blocking the async runtime on disk I/O; their failures
+/// surface on the next [`flush`](PeerStore::flush).
+pub struct SqlitePeerStore {
+    path: PathBuf,
+    commands: Option<mpsc::SyncSender<SqliteCommand>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+/// Work queued for the SQLite writer thread
+enum SqliteCommand {
+    Upsert(Vec<(String, Node)>),
+    Remove(Vec<String>),
+    Replace(Vec<(String, Node)>, mpsc::SyncSender<Result<()>>),
+    Load(mpsc::SyncSender<Result<Vec<(String, Node)>>>),
+    Flush(mpsc::SyncSender<Result<()>>),
+}
+
+impl SqlitePeerStore {
+    pub fn open(path: PathBuf) -> Result<Self> {
+        if let Some(parent_dir) = path.parent() {
+            std::fs::create_dir_all(parent_dir)?;
+        }
+
+        let connection = Connection::open(&path)?;
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.pragma_update(None, "synchronous", "NORMAL")?;
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS peers (key TEXT PRIMARY KEY, node TEXT NOT NULL)",
+            [],
+        )?;
+
+        let (commands, queue) = mpsc::sync_channel(SQLITE_QUEUE_CAPACITY);
+        let writer = std::thread::Builder::new()
+            .name("peer-store".to_string())
+            .spawn(move || run_sqlite_writer(connection, queue))?;
+
+        Ok(Self {
+            path,
+            commands: Some(commands),
+            writer: Some(writer),
+        })
+    }
+
+    fn send(&self, command: SqliteCommand) -> Result<()> {
+        self.commands
+            .as_ref()
+            .and_then(|commands| commands.send(command).ok())
+            .ok_or_else(writer_stopped)
+    }
+
+    /// Queue a command carrying a reply channel and wait for its result
+    fn request<T>(
+        &self,
+        command: impl FnOnce(mpsc::SyncSender<Result<T>>) -> SqliteCommand,
+    ) -> Result<T> {
+        let (reply, response) = mpsc::sync_channel(1);
+        self.send(command(reply))?;
+        response.recv().map_err(|_| writer_stopped())?
+    }
+}
+
+fn writer_stopped() -> KaseederError {
+    KaseederError::Service("peer store writer has stopped".to_string())
+}
+
+/// Apply queued commands in order until the store is dropped. Queued
+/// updates have no caller waiting, so the first of them to fail is held
+/// until the next flush.
+fn run_sqlite_writer(mut connection: Connection, queue: mpsc::Receiver<SqliteCommand>) {
+    let mut failed: Option<KaseederError> = None;
+    for command in queue {
+        // Reply sends are ignored: the caller may have given up waiting
+        let result = match command {
+            SqliteCommand::Upsert(nodes) => write_nodes(&mut connection, &nodes, false),
+            SqliteCommand::Remove(keys) => remove_nodes(&mut connection, &keys),
+            SqliteCommand::Replace(nodes, reply) => {
+                let _ = reply.send(write_nodes(&mut connection, &nodes, true));
+                continue;
+            }
+            SqliteCommand::Load(reply) => {
+                let _ = reply.send(load_nodes(&connection));
+                continue;
+            }
+            SqliteCommand::Flush(reply) => {
+                let _ = reply.send(failed.take().map_or(Ok(()), Err));
+                continue;
+            }
+        };
+        if let Err(e) = result {
+            error!("Failed to update the peer store: {}", e);
+            failed.get_or_insert(e);
+        }
+    }
+    if let Some(e) = failed {
+        error!("Peer store closed with unflushed write failures: {}", e);
+    }
+}
+
+fn load_nodes(connection: &Connection) -> Result<Vec<(String, Node)>> {
+    let mut statement = connection.prepare("SELECT key, node FROM peers")?;
+    let rows = statement.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut nodes = Vec::new();
+    for row in rows {
+        let (key, node) = row?;
+        nodes.push((key, serde_json::from_str(&node)?));
+    }
+    Ok(nodes)
+}
+
+fn write_nodes(connection: &mut Connection, nodes: &[(String, Node)], replace: bool) -> Result<()> {
+    let transaction = connection.transaction()?;
+    if replace {
+        transaction.execute("DELETE FROM peers", [])?;
+    }
+    {
+        let mut statement = transaction.prepare_cached(
+            "INSERT INTO peers (key, node) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET node = excluded.node",
+        )?;
+        for (key, node) in nodes {
+            statement.execute(params![key, serde_json::to_string(node)?])?;
+        }
+    }
+    transaction.commit()?;
+    Ok(())
+}
+
+fn remove_nodes(connection: &mut Connection, keys: &[String]) -> Result<()> {
+    let transaction = connection.transaction()?;
+    {
+        let mut statement = transaction.prepare_cached("DELETE FROM peers WHERE key = ?1")?;
+        for key in keys {
+            statement.execute(params![key])?;
+        }
+    }
+    transaction.commit()?;
+    Ok(())
+}
+
+impl PeerStore for SqlitePeerStore {
+    fn load(&self) -> Result<Vec<(String, Node)>> {
+        self.request(SqliteCommand::Load)
+    }
+
+    fn save_all(&self, nodes: &[(String, Node)]) -> Result<()> {
+        let nodes = nodes.to_vec();
+        self.request(|reply| SqliteCommand::Replace(nodes, reply))
+    }
+
+    fn is_incremental(&self) -> bool {
+        true
+    }
+
+    fn upsert(&self, nodes: &[(String, Node)]) -> Result<()> {
+        if nodes.is_empty() {
+            return Ok(());
+        }
+        self.send(SqliteCommand::Upsert(nodes.to_vec()))
+    }
+
+    fn remove(&self, keys: &[String]) -> Result<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        self.send(SqliteCommand::Remove(keys.to_vec()))
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.request(SqliteCommand::Flush)
+    }
+
+    fn location(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for SqlitePeerStore {
+    fn drop(&mut self) {
+        // Closing the queue lets the writer finish pending writes and exit
+        self.commands.take();
+        if let Some(writer) = self.writer.take()
+            && writer.join().is_err()
+        {
+            error!("Peer store writer thread panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NetAddress;
+    use tempfile::TempDir;
+
+    fn node(ip: &str) -> (String, Node) {
+        let node = Node::new(NetAddress::new(ip.parse().unwrap(), 16111));
+        (node.key(), node)
+    }
+
+    #[test]
+    fn test_sqlite_store_upserts_and_removes_incrementally() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().to_string_lossy().to_string();
+        let backend: StorageBackend = "SQLite".parse().unwrap();
+        assert_eq!(backend, StorageBackend::Sqlite);
+        assert!("redis".parse::<StorageBackend>().is_err());
+
+        {
+            let store = backend.open(&app_dir).unwrap();
+            assert!(store.is_incremental());
+            store.upsert(&[node("45.0.0.1"), node("45.0.0.2")]).unwrap();
+
+            let (key, mut updated) = node("45.0.0.1");
+            updated.services = 1;
+            store.upsert(&[(key, updated)]).unwrap();
+            store.remove(&["45.0.0.2:16111".to_string()]).unwrap();
+        }
+
+        // Changes survive reopening without a full save
+        let store = backend.open(&app_dir).unwrap();
+        let nodes = store.load().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].0, "45.0.0.1:16111");
+        assert_eq!(nodes[0].1.services, 1);
+
+        store.save_all(&[node("45.0.0.3")]).unwrap();
+        let nodes = store.load().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].0, "45.0.0.3:16111");
+    }
+
+    #[test]
+    fn test_sqlite_store_reports_failed_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(PEERS_DB_FILENAME);
+        let store = SqlitePeerStore::open(path.clone()).unwrap();
+
+        store.upsert(&[node("45.0.0.1")]).unwrap();
+        store.flush().unwrap();
+
+        // Pull the table out from under the writer so every write fails
+        Connection::open(&path)
+            .unwrap()
+            .execute("DROP TABLE peers", [])
+            .unwrap();
+
+        assert!(store.save_all(&[node("45.0.0.2")]).is_err());
+
+        // A queued update only reports its failure on the next flush
+        store.upsert(&[node("45.0.0.3")]).unwrap();
+        assert!(store.flush().is_err());
+        store.flush().unwrap();
+    }
+}
@@ -1,8 +1,8 @@
 use kaspa_utils::networking::{IpAddress as KaspaIpAddress, NetAddress as KaspaNetAddress};
 use serde::{Deserialize, Serialize};
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 /// Network address, wrapping rusty-kaspa's NetAddress
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -29,6 +29,15 @@ impl NetAddress {
             port: self.port,
         }
     }
+
+    pub fn socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.ip, self.port)
+    }
+
+    /// Address to dial the peer on, with IPv6 hosts bracketed as in `[::1]:16111`
+    pub fn dial_string(&self) -> String {
+        self.socket_addr().to_string()
+    }
 }
 
 /// Network address extension traits
@@ -107,6 +116,12 @@ pub struct CrawlerStats {
     pub failed_connections: AtomicU64,
     pub successful_connections: AtomicU64,
     pub last_update: SystemTime,
+    /// Milliseconds from startup until the first non-empty DNS answer (0 until reached)
+    #[serde(default)]
+    pub time_to_first_good_peer_ms: AtomicU64,
+    /// DNS answers padded with stale peers because the good pool was small
+    #[serde(default)]
+    pub degraded_answers: AtomicU64,
 }
 
 impl Default for CrawlerStats {
@@ -117,6 +132,8 @@ impl Default for CrawlerStats {
             failed_connections: AtomicU64::new(0),
             successful_connections: AtomicU64::new(0),
             last_update: SystemTime::now(),
+            time_to_first_good_peer_ms: AtomicU64::new(0),
+            degraded_answers: AtomicU64::new(0),
         }
     }
 }
@@ -145,6 +162,31 @@ impl CrawlerStats {
     pub fn update_last_update(&mut self) {
         self.last_update = SystemTime::now();
     }
+
+    /// Record the time to the first good peer, returning true only the first time
+    pub fn record_first_good_peer(&self, elapsed: Duration) -> bool {
+        // 0 means "not reached yet", so never store it as a measurement
+        let elapsed_ms = (elapsed.as_millis() as u64).max(1);
+        self.time_to_first_good_peer_ms
+            .compare_exchange(0, elapsed_ms, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    pub fn record_degraded_answer(&self) {
+        self.degraded_answers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn degraded_answers(&self) -> u64 {
+        self.degraded_answers.load(Ordering::Relaxed)
+    }
+
+    /// Time from startup until the first good peer was available, if reached
+    pub fn time_to_first_good_peer(&self) -> Option<Duration> {
+        match self.time_to_first_good_peer_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
 }
 
 /// DNS record type
@@ -161,3 +203,23 @@ pub type AddressEntry = NetAddress;
 
 /// Node information (for backward compatibility)
 pub type NodeInfo = NetAddress;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dial_string_brackets_ipv6() {
+        let ipv4 = NetAddress::new("45.0.0.1".parse().unwrap(), 16111);
+        assert_eq!(ipv4.dial_string(), "45.0.0.1:16111");
+
+        let ipv6 = NetAddress::new("2001:db8::1".parse().unwrap(), 16111);
+        assert_eq!(ipv6.dial_string(), "[2001:db8::1]:16111");
+        // Round-trips through the parser the adaptor and proxy dial with
+        let parsed: SocketAddr = ipv6.dial_string().parse().unwrap();
+        assert_eq!(parsed, ipv6.socket_addr());
+
+        let loopback = NetAddress::new("::1".parse().unwrap(), 16111);
+        assert_eq!(loopback.dial_string(), "[::1]:16111");
+    }
+}
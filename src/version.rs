@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// Commit the binary was built from, embedded by the build script
+pub const GIT_COMMIT: &str = env!("KASEEDER_GIT_COMMIT");
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionInfo {
     pub version: String,
@@ -12,9 +15,9 @@ impl VersionInfo {
     pub fn new() -> Self {
         Self {
             version: env!("CARGO_PKG_VERSION").to_string(),
-            git_commit: "unknown".to_string(), // Remove dependency on VERGEN_GIT_SHA
+            git_commit: GIT_COMMIT.to_string(),
             build_date: chrono::Utc::now().to_rfc3339(), // Use current time
-            rust_version: "unknown".to_string(), // Remove dependency on VERGEN_RUSTC_SEMVER
+            rust_version: "unknown".to_string(),         // Remove dependency on VERGEN_RUSTC_SEMVER
         }
     }
 
@@ -46,7 +49,7 @@ impl Default for VersionInfo {
     fn default() -> Self {
         Self {
             version: env!("CARGO_PKG_VERSION").to_string(),
-            git_commit: "unknown".to_string(),
+            git_commit: GIT_COMMIT.to_string(),
             build_date: "unknown".to_string(),
             rust_version: "unknown".to_string(),
         }
@@ -61,6 +64,16 @@ pub fn version_info() -> VersionInfo {
     VersionInfo::new()
 }
 
+/// Build version, commit and network as served in the version TXT record
+pub fn build_info_txt(network: &str) -> String {
+    format!(
+        "kaseeder version={} commit={} network={}",
+        version(),
+        GIT_COMMIT,
+        network
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;